@@ -0,0 +1,210 @@
+//! Command-line batch mode: load a log and/or DBC and write exports without
+//! opening a window, for CI pipelines, fleets of logs and machines with no display.
+//! With no arguments (or just a file to open) the GUI opens as usual.
+//!
+//! Exports go through the same loaders and writers as the GUI's File > Export.
+
+use crate::core::{CanMessage, DbcFile};
+use crate::decode::signal_export::{all_signal_keys, select_signal_keys, write_signals_csv};
+use crate::input::{self, TimePrecision};
+use anyhow::{bail, Context, Result};
+use clap::Parser;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "shit",
+    version,
+    about = "Signal Harvesting & Interpretation Toolkit",
+    after_help = "Without any options the GUI opens, loading FILE if given.\n\
+        Examples:\n  \
+        shit --input drive.csv --export-csv frames.csv\n  \
+        shit --input drive.rlog --dbc car.dbc --signals Rpm,Speed@bus1 --export-csv signals.csv\n  \
+        shit --dbc car.dbc --signals Rpm,Speed --export-dbc subset.dbc"
+)]
+pub struct Cli {
+    /// Log or DBC file to open in the GUI
+    #[arg(value_name = "FILE", conflicts_with_all = ["input", "dbc"])]
+    pub open: Option<PathBuf>,
+
+    /// Log file to load (CSV, rlog or Cabana export)
+    #[arg(long, value_name = "FILE")]
+    pub input: Option<PathBuf>,
+
+    /// DBC file to decode signals with
+    #[arg(long, value_name = "FILE")]
+    pub dbc: Option<PathBuf>,
+
+    /// Write raw frames as CSV, or decoded signals when --signals is given
+    #[arg(long, value_name = "OUT")]
+    pub export_csv: Option<PathBuf>,
+
+    /// Write the DBC back out, keeping only the --signals if given
+    #[arg(long, value_name = "OUT")]
+    pub export_dbc: Option<PathBuf>,
+
+    /// Write decoded signals (all of them, or the --signals) as Parquet
+    #[cfg(feature = "parquet")]
+    #[arg(long, value_name = "OUT")]
+    pub export_parquet: Option<PathBuf>,
+
+    /// Comma-separated signals to extract, as names, "name@busN", "name@0xID" or "name@0xID@busN" keys
+    #[arg(long, value_name = "NAMES", value_delimiter = ',')]
+    pub signals: Vec<String>,
+
+    /// Decimal places of exported times: ms (3), us (6), or auto (us if the log has them)
+    #[arg(long, value_name = "P", default_value = "auto", value_parser = parse_time_precision)]
+    pub time_precision: TimePrecision,
+}
+
+fn parse_time_precision(value: &str) -> std::result::Result<TimePrecision, String> {
+    match value {
+        "auto" => Ok(TimePrecision::Auto),
+        "ms" | "3" => Ok(TimePrecision::Millis),
+        "us" | "6" => Ok(TimePrecision::Micros),
+        _ => Err("expected auto, ms or us".to_string()),
+    }
+}
+
+impl Cli {
+    /// Anything given beyond the program name: run headless instead of opening the GUI
+    pub fn is_batch(&self) -> bool {
+        self.input.is_some() || self.dbc.is_some() || !self.signals.is_empty()
+            || self.export_csv.is_some() || self.export_dbc.is_some() || self.parquet_output().is_some()
+    }
+
+    #[cfg(feature = "parquet")]
+    fn parquet_output(&self) -> Option<&Path> {
+        self.export_parquet.as_deref()
+    }
+
+    #[cfg(not(feature = "parquet"))]
+    fn parquet_output(&self) -> Option<&Path> {
+        None
+    }
+}
+
+/// Loaded inputs of a batch run
+struct Inputs {
+    messages: Option<Vec<CanMessage>>,
+    dbc: Option<DbcFile>,
+}
+
+impl Inputs {
+    fn dbc(&self, export: &str) -> Result<&DbcFile> {
+        self.dbc.as_ref().with_context(|| format!("{} needs --dbc", export))
+    }
+
+    fn messages(&self, export: &str) -> Result<&[CanMessage]> {
+        self.messages.as_deref().with_context(|| format!("{} needs --input", export))
+    }
+
+    /// Signal columns for a decoded export: the --signals, or every signal in the log
+    fn signal_keys(&self, cli: &Cli, messages: &[CanMessage], dbc: &DbcFile) -> Result<Vec<String>> {
+        let keys = all_signal_keys(messages, dbc);
+        if cli.signals.is_empty() {
+            Ok(keys)
+        } else {
+            select_signal_keys(&keys, &cli.signals)
+        }
+    }
+}
+
+/// Run every requested export; returns a summary line per output
+pub fn run_batch(cli: &Cli) -> Result<Vec<String>> {
+    let messages = match &cli.input {
+        Some(path) => {
+            let path = path.to_string_lossy();
+            Some(input::load_file(&path).with_context(|| format!("Failed to load {}", path))?)
+        }
+        None => None,
+    };
+    let dbc = match &cli.dbc {
+        Some(path) => Some(DbcFile::load(path).with_context(|| format!("Failed to load DBC {}", path.display()))?),
+        None => None,
+    };
+    let inputs = Inputs { messages, dbc };
+    let mut summaries = Vec::new();
+
+    if let Some(out) = &cli.export_csv {
+        let messages = inputs.messages("--export-csv")?;
+        let writer = std::io::BufWriter::new(create(out)?);
+        if cli.signals.is_empty() {
+            let omitted = input::write_csv(writer, messages, cli.time_precision).with_context(|| format!("Failed to write {}", out.display()))?;
+            summaries.push(format!("Exported {} messages to {}{}", messages.len() - omitted, out.display(), input::omitted_note(omitted)));
+        } else {
+            let dbc = inputs.dbc("--signals")?;
+            let keys = inputs.signal_keys(cli, messages, dbc)?;
+            let rows = write_signals_csv(writer, messages, dbc, &keys, cli.time_precision)
+                .with_context(|| format!("Failed to write {}", out.display()))?;
+            summaries.push(format!("Exported {} signals ({} rows) to {}", keys.len(), rows, out.display()));
+        }
+    }
+
+    if let Some(out) = &cli.export_dbc {
+        let dbc = inputs.dbc("--export-dbc")?;
+        let subset = if cli.signals.is_empty() {
+            dbc.clone()
+        } else {
+            for name in &cli.signals {
+                if !dbc.messages.iter().any(|m| m.get_signal(name).is_some()) {
+                    bail!("Signal {} is not in the DBC", name);
+                }
+            }
+            dbc.filtered(|_, signal| cli.signals.contains(&signal.name))
+        };
+        subset.save(out).with_context(|| format!("Failed to write {}", out.display()))?;
+        summaries.push(format!("Exported {} messages to {}", subset.messages.len(), out.display()));
+    }
+
+    #[cfg(feature = "parquet")]
+    if let Some(out) = &cli.export_parquet {
+        let messages = inputs.messages("--export-parquet")?;
+        let dbc = inputs.dbc("--export-parquet")?;
+        let keys = inputs.signal_keys(cli, messages, dbc)?;
+        let rows = crate::decode::parquet_export::export_parquet(out, messages, dbc, &keys, cli.time_precision, None)?;
+        summaries.push(format!("Exported {} signals ({} rows) to {}", keys.len(), rows, out.display()));
+    }
+
+    if summaries.is_empty() {
+        bail!("Nothing to export: add --export-csv or --export-dbc (see --help)");
+    }
+    Ok(summaries)
+}
+
+fn create(path: &Path) -> Result<std::fs::File> {
+    std::fs::File::create(path).with_context(|| format!("Failed to create {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_args() {
+        let cli = Cli::try_parse_from(["shit"]).unwrap();
+        assert!(!cli.is_batch());
+
+        // A bare file opens in the GUI
+        let cli = Cli::try_parse_from(["shit", "drive.csv"]).unwrap();
+        assert!(!cli.is_batch());
+        assert_eq!(cli.open, Some(PathBuf::from("drive.csv")));
+        assert!(Cli::try_parse_from(["shit", "drive.csv", "--input", "other.csv"]).is_err());
+
+        let cli = Cli::try_parse_from([
+            "shit", "--input", "drive.csv", "--dbc", "car.dbc", "--signals", "Rpm,Speed@bus1", "--export-csv", "out.csv",
+        ]).unwrap();
+        assert!(cli.is_batch());
+        assert_eq!(cli.input, Some(PathBuf::from("drive.csv")));
+        assert_eq!(cli.signals, vec!["Rpm", "Speed@bus1"]);
+        assert_eq!(cli.export_csv, Some(PathBuf::from("out.csv")));
+        assert_eq!(cli.time_precision, TimePrecision::Auto);
+
+        let cli = Cli::try_parse_from(["shit", "--input", "drive.csv", "--time-precision", "us", "--export-csv", "out.csv"]).unwrap();
+        assert_eq!(cli.time_precision, TimePrecision::Micros);
+        assert!(Cli::try_parse_from(["shit", "--time-precision", "ns"]).is_err());
+
+        assert!(Cli::try_parse_from(["shit", "--export-csv"]).is_err());
+        assert!(Cli::try_parse_from(["shit", "--bogus"]).is_err());
+    }
+}
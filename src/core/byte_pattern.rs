@@ -0,0 +1,87 @@
+//! Data-byte search patterns like `?? FF ?? 12`, where `?` is a wildcard nibble.
+//!
+//! Each pattern byte is a (value, mask) pair, so `F?` matches 0xF0..=0xFF and `??`
+//! matches anything. A pattern matches a frame if it lines up with the payload at
+//! any byte offset.
+
+use std::ops::Range;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BytePattern {
+    /// (value, mask) per byte; only the bits set in mask are compared
+    bytes: Vec<(u8, u8)>,
+}
+
+impl BytePattern {
+    /// Parse hex bytes separated by spaces ("?? FF 1?"), or runs of digit pairs
+    /// ("??FF1?"). A single-digit byte is its low nibble, as in the sender.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut bytes = Vec::new();
+        for token in text.split_whitespace() {
+            if !token.chars().all(|c| c.is_ascii_hexdigit() || c == '?') {
+                return Err(format!("'{}' is not hex or ?", token));
+            }
+            if token.len() > 2 && token.len() % 2 != 0 {
+                return Err(format!("'{}' has an odd digit count", token));
+            }
+            // All ASCII, so every slice is on a char boundary
+            for start in (0..token.len()).step_by(2) {
+                let pair = &token[start..(start + 2).min(token.len())];
+                let pair = match pair {
+                    "?" => "??".to_string(),
+                    _ => format!("{:0>2}", pair),
+                };
+                bytes.push(parse_masked_byte(&pair));
+            }
+        }
+        if bytes.is_empty() {
+            return Err("Enter a byte pattern".to_string());
+        }
+        Ok(Self { bytes })
+    }
+
+    /// Byte range of the first place the pattern lines up with `data`
+    pub fn find(&self, data: &[u8]) -> Option<Range<usize>> {
+        data.windows(self.bytes.len())
+            .position(|window| {
+                window.iter().zip(&self.bytes).all(|(byte, (value, mask))| byte & mask == *value)
+            })
+            .map(|start| start..start + self.bytes.len())
+    }
+}
+
+/// (value, mask) of two pattern characters, each a hex digit or `?`
+fn parse_masked_byte(pair: &str) -> (u8, u8) {
+    pair.chars().fold((0, 0), |(value, mask), c| match c.to_digit(16) {
+        Some(digit) => ((value << 4) | digit as u8, (mask << 4) | 0xF),
+        None => (value << 4, mask << 4),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_byte_pattern_find() {
+        let pattern = BytePattern::parse("?? FF ?? ??").unwrap();
+        assert_eq!(pattern.find(&[0x01, 0xFF, 0x02, 0x03]), Some(0..4));
+        // Lines up anywhere in the payload
+        assert_eq!(pattern.find(&[0x00, 0x00, 0x07, 0xFF, 0x01, 0x02]), Some(2..6));
+        assert_eq!(pattern.find(&[0x01, 0xFE, 0x02, 0x03]), None);
+        // Longer than the frame
+        assert_eq!(pattern.find(&[0x01, 0xFF]), None);
+
+        let nibble = BytePattern::parse("1?").unwrap();
+        assert_eq!(nibble.find(&[0x21, 0x1A]), Some(1..2));
+        assert_eq!(nibble.find(&[0x21, 0xA1]), None);
+
+        // Packed pairs, and a lone digit or ? is one byte
+        assert_eq!(BytePattern::parse("??FF").unwrap(), BytePattern::parse("? FF").unwrap());
+        assert_eq!(BytePattern::parse("7").unwrap().find(&[0x70, 0x07]), Some(1..2));
+
+        assert!(BytePattern::parse("").is_err());
+        assert!(BytePattern::parse("FG").is_err());
+        assert!(BytePattern::parse("FFF").is_err());
+    }
+}
@@ -3,15 +3,27 @@ use std::collections::HashMap;
 use std::path::Path;
 use anyhow::{Context, Result};
 
+/// Placeholder node name used by DBC tools when no transmitter/receiver is known
+pub const DEFAULT_NODE: &str = "Vector__XXX";
+
+/// Message attribute holding the nominal transmit period in ms
+const CYCLE_TIME_ATTRIBUTE: &str = "GenMsgCycleTime";
+
+/// Observed periods further than this fraction from the cycle time are flagged
+pub const CYCLE_TIME_TOLERANCE: f64 = 0.2;
+
 /// Represents a loaded DBC file
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DbcFile {
     /// Version string
     pub version: String,
+    /// Network nodes (ECUs) declared in `BU_:`
+    #[serde(default)]
+    pub nodes: Vec<String>,
     /// All messages in the DBC
     pub messages: Vec<DbcMessage>,
-    /// Quick lookup by CAN ID
-    pub message_lookup: HashMap<u32, DbcMessage>,
+    /// Index into `messages` by CAN ID (the last definition wins on duplicates)
+    message_index: HashMap<u32, usize>,
     /// All value tables (enums)
     pub value_tables: HashMap<String, Vec<ValueDescription>>,
     /// File path (if loaded from file)
@@ -23,8 +35,9 @@ impl DbcFile {
     pub fn new() -> Self {
         Self {
             version: String::new(),
+            nodes: Vec::new(),
             messages: Vec::new(),
-            message_lookup: HashMap::new(),
+            message_index: HashMap::new(),
             value_tables: HashMap::new(),
             file_path: None,
         }
@@ -32,33 +45,73 @@ impl DbcFile {
 
     /// Load a DBC file from disk
     pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Self::load_with_progress(path, |_, _| {})
+    }
+
+    /// Load a DBC file, calling `progress(lines_parsed, total_lines)` as parsing goes
+    pub fn load_with_progress<P: AsRef<Path>>(path: P, progress: impl FnMut(usize, usize)) -> Result<Self> {
         let path = path.as_ref();
         let content = std::fs::read_to_string(path)
             .with_context(|| format!("Failed to read DBC file: {:?}", path))?;
 
-        let mut dbc = Self::parse(&content)?;
+        let mut dbc = Self::parse_with_progress(&content, progress)?;
         dbc.file_path = Some(path.to_string_lossy().to_string());
         Ok(dbc)
     }
 
     /// Parse DBC file content
     pub fn parse(content: &str) -> Result<Self> {
+        Self::parse_with_progress(content, |_, _| {})
+    }
+
+    /// Parse DBC file content, calling `progress(lines_parsed, total_lines)` every
+    /// `PROGRESS_LINES` lines and once at the end
+    pub fn parse_with_progress(content: &str, mut progress: impl FnMut(usize, usize)) -> Result<Self> {
+        const PROGRESS_LINES: usize = 5000;
         let mut dbc = Self::new();
+        // Windows editors often prepend a UTF-8 BOM; `lines()` already drops the CR of CRLF endings
+        let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+        let total_lines = content.lines().count();
 
         // Simple DBC parser - handles basic DBC format
         // For full DBC support, we would use the can-dbc crate
-        for line in content.lines() {
+        // `CM_` comments may span lines; collect them until the closing `";`
+        let mut pending_comment: Option<String> = None;
+        for (line_no, line) in content.lines().enumerate() {
+            if line_no % PROGRESS_LINES == 0 {
+                progress(line_no, total_lines);
+            }
+            if let Some(mut comment) = pending_comment.take() {
+                comment.push('\n');
+                comment.push_str(line);
+                if comment_complete(&comment) {
+                    dbc.apply_comment_line(&comment);
+                } else {
+                    pending_comment = Some(comment);
+                }
+                continue;
+            }
+
             let line = line.trim();
 
-            if line.starts_with("VERSION") {
+            if line.starts_with("CM_ ") {
+                if comment_complete(line) {
+                    dbc.apply_comment_line(line);
+                } else {
+                    pending_comment = Some(line.to_string());
+                }
+            }
+            else if line.starts_with("VERSION") {
                 dbc.version = line.strip_prefix("VERSION ")
                     .unwrap_or("")
                     .trim_matches('"')
                     .to_string();
             }
+            else if line.starts_with("BU_:") {
+                dbc.nodes = parse_nodes_line(line);
+            }
             else if line.starts_with("BO_ ") {
                 if let Some(msg) = parse_message_line(line) {
-                    dbc.message_lookup.insert(msg.id, msg.clone());
                     dbc.messages.push(msg);
                 }
             }
@@ -70,6 +123,13 @@ impl DbcFile {
                     }
                 }
             }
+            else if line.starts_with("BA_ ") {
+                if let Some((id, ms)) = parse_cycle_time_line(line) {
+                    if let Some(msg) = dbc.messages.iter_mut().find(|m| m.id == id) {
+                        msg.cycle_time_ms = Some(ms);
+                    }
+                }
+            }
             else if line.starts_with("VAL_ ") {
                 // Value description (enum)
                 if let Some((name, values)) = parse_val_line(line) {
@@ -78,14 +138,25 @@ impl DbcFile {
             }
         }
 
-        // Rebuild message lookup after parsing
-        dbc.message_lookup = dbc.messages.iter()
-            .map(|m| (m.id, m.clone()))
-            .collect();
+        dbc.rebuild_index();
 
+        progress(total_lines, total_lines);
         Ok(dbc)
     }
 
+    fn rebuild_index(&mut self) {
+        self.message_index = self.messages.iter().enumerate().map(|(i, m)| (m.id, i)).collect();
+    }
+
+    /// Attach a parsed `CM_ BO_` comment to its message (other comment kinds are ignored)
+    fn apply_comment_line(&mut self, line: &str) {
+        if let Some((id, text)) = parse_message_comment(line) {
+            if let Some(msg) = self.messages.iter_mut().find(|m| m.id == id) {
+                msg.comment = Some(text);
+            }
+        }
+    }
+
     /// Save DBC file to disk
     pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
         let path = path.as_ref();
@@ -137,41 +208,44 @@ impl DbcFile {
         // Bit timing
         output.push_str("BS_:\n\n");
 
-        // Nodes (placeholder)
-        output.push_str("BU_: Vector__XXX\n\n");
+        // Nodes
+        if self.nodes.is_empty() {
+            output.push_str(&format!("BU_: {}\n\n", DEFAULT_NODE));
+        } else {
+            output.push_str(&format!("BU_: {}\n\n", self.nodes.join(" ")));
+        }
 
         // Messages
         for msg in &self.messages {
             output.push_str(&format!(
-                "BO_ {} {}: {} Vector__XXX\n",
-                msg.id, msg.name, msg.size
+                "BO_ {} {}: {} {}\n",
+                msg.id, msg.name, msg.size, msg.transmitter
             ));
             for signal in &msg.signals {
-                let byte_order = match signal.byte_order {
-                    ByteOrder::Motorola => '0',
-                    ByteOrder::Intel => '1',
-                };
-                let value_type = match signal.value_type {
-                    ValueType::Signed => '-',
-                    ValueType::Unsigned => '+',
-                };
-                output.push_str(&format!(
-                    " SG_ {} : {}|{}@{}{} ({},{}) [{}|{}] \"{}\" Vector__XXX\n",
-                    signal.name,
-                    signal.start_bit,
-                    signal.bit_length,
-                    byte_order,
-                    value_type,
-                    signal.factor,
-                    signal.offset,
-                    signal.minimum.unwrap_or(0.0),
-                    signal.maximum.unwrap_or(0.0),
-                    signal.unit.as_deref().unwrap_or("")
-                ));
+                output.push_str(&format!(" {}\n", signal.to_dbc_line()));
             }
             output.push_str("\n");
         }
 
+        // Message comments
+        for msg in &self.messages {
+            if let Some(comment) = &msg.comment {
+                output.push_str(&format!("CM_ BO_ {} \"{}\";\n", msg.id, escape_dbc_string(comment)));
+            }
+        }
+
+        // Cycle time attributes
+        let cycle_times: Vec<(u32, u32)> = self.messages.iter()
+            .filter_map(|m| m.cycle_time_ms.map(|ms| (m.id, ms)))
+            .collect();
+        if !cycle_times.is_empty() {
+            output.push_str(&format!("BA_DEF_ BO_ \"{}\" INT 0 65535;\n", CYCLE_TIME_ATTRIBUTE));
+            output.push_str(&format!("BA_DEF_DEF_ \"{}\" 0;\n", CYCLE_TIME_ATTRIBUTE));
+            for (id, ms) in cycle_times {
+                output.push_str(&format!("BA_ \"{}\" BO_ {} {};\n", CYCLE_TIME_ATTRIBUTE, id, ms));
+            }
+        }
+
         // Value tables
         for (name, values) in &self.value_tables {
             output.push_str(&format!("VAL_ {} ", name));
@@ -186,27 +260,111 @@ impl DbcFile {
 
     /// Add a message to the DBC
     pub fn add_message(&mut self, message: DbcMessage) {
-        self.message_lookup.insert(message.id, message.clone());
+        self.message_index.insert(message.id, self.messages.len());
         self.messages.push(message);
     }
 
+    /// Copy of this DBC keeping only the signals `keep` accepts (and their value tables).
+    /// Messages left with no signals are dropped.
+    pub fn filtered(&self, mut keep: impl FnMut(&DbcMessage, &DbcSignal) -> bool) -> DbcFile {
+        let mut out = DbcFile::new();
+        out.version = self.version.clone();
+        for msg in &self.messages {
+            let signals: Vec<DbcSignal> = msg.signals.iter().filter(|s| keep(msg, s)).cloned().collect();
+            if signals.is_empty() {
+                continue;
+            }
+            for signal in &signals {
+                if let Some(values) = self.value_tables.get(&signal.name) {
+                    out.value_tables.insert(signal.name.clone(), values.clone());
+                }
+            }
+            if self.nodes.contains(&msg.transmitter) && !out.nodes.contains(&msg.transmitter) {
+                out.nodes.push(msg.transmitter.clone());
+            }
+            out.add_message(DbcMessage { signals, ..msg.clone() });
+        }
+        out
+    }
+
+    /// Set (or clear) a message's `CM_` comment. Returns false if the ID isn't defined.
+    pub fn set_message_comment(&mut self, id: u32, comment: Option<String>) -> bool {
+        let Some(msg) = self.get_message_mut(id) else {
+            return false;
+        };
+        msg.comment = comment;
+        true
+    }
+
+    /// Copy signal `name` of message `from` into message `to`. A name the target already
+    /// uses gets a numeric suffix ("Speed_2"), and the copy takes the value table along.
+    /// A multiplex role the target can't carry is dropped: a muxed signal into a message
+    /// without a selector, or a second selector.
+    pub fn copy_signal(&mut self, from: u32, name: &str, to: u32) -> std::result::Result<CopiedSignal, String> {
+        let mut signal = self.get_message(from)
+            .and_then(|m| m.get_signal(name))
+            .cloned()
+            .ok_or_else(|| format!("'{}' is not in 0x{:03X}", name, from))?;
+        let target = self.get_message(to).ok_or_else(|| format!("0x{:03X} is not in the DBC", to))?;
+
+        signal.name = (1..)
+            .map(|n| if n == 1 { name.to_string() } else { format!("{}_{}", name, n) })
+            .find(|candidate| target.get_signal(candidate).is_none())
+            .unwrap_or_default();
+        let has_selector = target.signals.iter().any(|s| matches!(s.multiplexor, Some(Multiplexor::Signal)));
+        signal.multiplexor = match signal.multiplexor {
+            Some(Multiplexor::Signal) if has_selector => None,
+            Some(Multiplexor::Value(_)) if !has_selector => None,
+            other => other,
+        };
+        let overlaps = target.signals.iter()
+            .filter(|s| !exclusive_mux(&s.multiplexor, &signal.multiplexor) && signals_overlap(s, &signal))
+            .map(|s| s.name.clone())
+            .collect();
+
+        if signal.name != name {
+            if let Some(values) = self.value_tables.get(name).cloned() {
+                self.value_tables.insert(signal.name.clone(), values);
+            }
+        }
+        let copied = CopiedSignal { name: signal.name.clone(), overlaps };
+        if let Some(msg) = self.get_message_mut(to) {
+            msg.add_signal(signal);
+        }
+        Ok(copied)
+    }
+
+    /// Signals whose name contains `query` (case-insensitive) across all messages, with their
+    /// owning message. Names starting with the query come first, otherwise file order.
+    pub fn find_signals(&self, query: &str) -> Vec<(&DbcMessage, &DbcSignal)> {
+        let query = query.trim().to_lowercase();
+        if query.is_empty() {
+            return Vec::new();
+        }
+        let mut matches: Vec<(&DbcMessage, &DbcSignal)> = self.messages.iter()
+            .flat_map(|msg| msg.signals.iter().map(move |signal| (msg, signal)))
+            .filter(|(_, signal)| signal.name.to_lowercase().contains(&query))
+            .collect();
+        matches.sort_by_key(|(_, signal)| !signal.name.to_lowercase().starts_with(&query));
+        matches
+    }
+
     /// Get a message by CAN ID
     pub fn get_message(&self, id: u32) -> Option<&DbcMessage> {
-        self.message_lookup.get(&id)
+        self.message_index.get(&id).map(|&i| &self.messages[i])
     }
 
     /// Get a mutable reference to a message by CAN ID
     pub fn get_message_mut(&mut self, id: u32) -> Option<&mut DbcMessage> {
-        self.message_lookup.get_mut(&id)
+        self.message_index.get(&id).map(|&i| &mut self.messages[i])
     }
 
     /// Remove a message by CAN ID
     pub fn remove_message(&mut self, id: u32) -> Option<DbcMessage> {
-        let msg = self.message_lookup.remove(&id);
-        if let Some(ref msg) = msg {
-            self.messages.retain(|m| m.id != id);
-        }
-        msg
+        let msg = self.get_message(id)?.clone();
+        self.messages.retain(|m| m.id != id);
+        self.rebuild_index();
+        Some(msg)
     }
 
     /// Get all message IDs
@@ -238,15 +396,88 @@ fn parse_message_line(line: &str) -> Option<DbcMessage> {
     let id = parts[1].parse::<u32>().ok()?;
     let name = parts[2].trim_end_matches(':').to_string();
     let size = parts[3].parse::<u8>().ok()?;
+    let transmitter = parts.get(4).unwrap_or(&DEFAULT_NODE).to_string();
 
     Some(DbcMessage {
         id,
         name,
         size,
+        transmitter,
         signals: Vec::new(),
+        comment: None,
+        cycle_time_ms: None,
     })
 }
 
+/// Parse a cycle time attribute; other attributes are ignored
+/// Format: BA_ "GenMsgCycleTime" BO_ <id> <ms>;
+fn parse_cycle_time_line(line: &str) -> Option<(u32, u32)> {
+    let parts: Vec<&str> = line.trim_end_matches(';').split_whitespace().collect();
+    if parts.len() != 5 || parts[1].trim_matches('"') != CYCLE_TIME_ATTRIBUTE || parts[2] != "BO_" {
+        return None;
+    }
+    let id = parts[3].parse::<u32>().ok()?;
+    let ms = parts[4].parse::<f64>().ok()?;
+    (ms >= 0.0).then_some((id, ms.round() as u32))
+}
+
+/// Whether a `CM_` statement has reached its closing `";` (outside the quoted text)
+fn comment_complete(text: &str) -> bool {
+    let mut in_string = false;
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if in_string => {
+                chars.next();
+            }
+            '"' => in_string = !in_string,
+            ';' if !in_string => return true,
+            _ => {}
+        }
+    }
+    false
+}
+
+/// Parse a message comment
+/// Format: CM_ BO_ <id> "<text>";
+fn parse_message_comment(text: &str) -> Option<(u32, String)> {
+    let rest = text.trim().strip_prefix("CM_")?.trim_start().strip_prefix("BO_")?;
+    let (id, rest) = rest.trim_start().split_once(char::is_whitespace)?;
+    let id = id.parse::<u32>().ok()?;
+    let quoted = rest.trim().strip_suffix(';')?.trim_end().strip_prefix('"')?.strip_suffix('"')?;
+    Some((id, unescape_dbc_string(quoted)))
+}
+
+fn escape_dbc_string(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn unescape_dbc_string(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            if let Some(next) = chars.next() {
+                out.push(next);
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// Parse a node list line from DBC format
+/// Format: BU_: <node1> <node2> ...
+fn parse_nodes_line(line: &str) -> Vec<String> {
+    line.strip_prefix("BU_:")
+        .unwrap_or("")
+        .split_whitespace()
+        .filter(|n| *n != DEFAULT_NODE)
+        .map(|n| n.to_string())
+        .collect()
+}
+
 /// Parse a signal line from DBC format
 /// Format: SG_ <name> [M|m<val>] : <start_bit>|<bit_length>@<byte_order><value_type> (<factor>,<offset>) [<min>|<max>] "<unit>" <receiver>
 fn parse_signal_line(line: &str) -> Option<DbcSignal> {
@@ -260,7 +491,13 @@ fn parse_signal_line(line: &str) -> Option<DbcSignal> {
     let rest = &line[colon_pos + 1..];
 
     // Extract signal name (first token before any multiplexer indicator)
-    let name = name_part.split_whitespace().next()?.to_string();
+    let mut name_tokens = name_part.split_whitespace();
+    let name = name_tokens.next()?.to_string();
+    let multiplexor = match name_tokens.next() {
+        Some("M") => Some(Multiplexor::Signal),
+        Some(m) => m.strip_prefix('m').and_then(|v| v.parse().ok()).map(Multiplexor::Value),
+        None => None,
+    };
 
     // Parse the rest: start|len@order+ (factor,offset) [min|max] "unit" receiver
     let rest = rest.trim_start();
@@ -324,7 +561,7 @@ fn parse_signal_line(line: &str) -> Option<DbcSignal> {
         minimum,
         maximum,
         unit,
-        multiplexor: None,
+        multiplexor,
     })
 }
 
@@ -417,8 +654,37 @@ pub struct DbcMessage {
     pub name: String,
     /// Data Length Code (DLC), 0-8
     pub size: u8,
+    /// Transmitting node (`Vector__XXX` when unknown)
+    #[serde(default = "default_node")]
+    pub transmitter: String,
     /// Signals contained in this message
     pub signals: Vec<DbcSignal>,
+    /// `CM_ BO_` comment, if any
+    #[serde(default)]
+    pub comment: Option<String>,
+    /// Nominal transmit period from the `GenMsgCycleTime` attribute
+    #[serde(default)]
+    pub cycle_time_ms: Option<u32>,
+}
+
+/// How an observed frame period compares with the DBC cycle time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CycleTiming {
+    OnTime,
+    /// Frames arrive less often than specified
+    Slow,
+    /// Frames arrive more often than specified
+    Fast,
+}
+
+impl CycleTiming {
+    pub fn label(self) -> &'static str {
+        match self {
+            CycleTiming::OnTime => "on time",
+            CycleTiming::Slow => "slow",
+            CycleTiming::Fast => "fast",
+        }
+    }
 }
 
 impl DbcMessage {
@@ -428,10 +694,31 @@ impl DbcMessage {
             id,
             name: name.to_string(),
             size,
+            transmitter: DEFAULT_NODE.to_string(),
             signals: Vec::new(),
+            comment: None,
+            cycle_time_ms: None,
         }
     }
 
+    /// Compare an observed mean frame period with the cycle time.
+    /// None for event-driven messages (no cycle time, or 0).
+    pub fn check_cycle(&self, observed_period_ms: f64) -> Option<CycleTiming> {
+        let expected = self.cycle_time_ms.filter(|&ms| ms > 0)? as f64;
+        Some(if observed_period_ms > expected * (1.0 + CYCLE_TIME_TOLERANCE) {
+            CycleTiming::Slow
+        } else if observed_period_ms < expected * (1.0 - CYCLE_TIME_TOLERANCE) {
+            CycleTiming::Fast
+        } else {
+            CycleTiming::OnTime
+        })
+    }
+
+    /// Whether the transmitting node is known (not the `Vector__XXX` placeholder)
+    pub fn has_transmitter(&self) -> bool {
+        !self.transmitter.is_empty() && self.transmitter != DEFAULT_NODE
+    }
+
     /// Add a signal to this message
     pub fn add_signal(&mut self, signal: DbcSignal) {
         self.signals.push(signal);
@@ -470,7 +757,7 @@ impl DbcMessage {
 
         // Check each signal fits within the message
         for signal in &self.signals {
-            let end_bit = signal.start_bit as usize + signal.bit_length as usize;
+            let end_bit = signal.bits().max().map_or(0, |bit| bit + 1);
             let max_bits = self.size as usize * 8;
             if end_bit > max_bits {
                 errors.push(format!(
@@ -484,12 +771,18 @@ impl DbcMessage {
     }
 }
 
+fn default_node() -> String {
+    DEFAULT_NODE.to_string()
+}
+
 /// A signal defined in the DBC
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DbcSignal {
     /// Signal name
     pub name: String,
-    /// Starting bit position (in DBC notation)
+    /// Starting bit position in DBC notation: byte * 8 + bit within the byte,
+    /// bit 0 being the byte's LSB. Intel signals start at their LSB, Motorola
+    /// signals at their MSB.
     pub start_bit: u8,
     /// Number of bits
     pub bit_length: u8,
@@ -529,6 +822,11 @@ impl DbcSignal {
         }
     }
 
+    /// DBC bit numbers the signal occupies (see `signal_bits`)
+    pub fn bits(&self) -> impl Iterator<Item = usize> {
+        signal_bits(self.start_bit, self.bit_length, self.byte_order)
+    }
+
     /// Create a signal with full options
     pub fn with_options(
         name: &str,
@@ -567,6 +865,37 @@ impl DbcSignal {
         self
     }
 
+    /// Format as a DBC `SG_` line (without the leading indent)
+    pub fn to_dbc_line(&self) -> String {
+        let byte_order = match self.byte_order {
+            ByteOrder::Motorola => '0',
+            ByteOrder::Intel => '1',
+        };
+        let value_type = match self.value_type {
+            ValueType::Signed => '-',
+            ValueType::Unsigned => '+',
+        };
+        let mux = match self.multiplexor {
+            Some(Multiplexor::Signal) => " M".to_string(),
+            Some(Multiplexor::Value(v)) => format!(" m{}", v),
+            None => String::new(),
+        };
+        format!(
+            "SG_ {}{} : {}|{}@{}{} ({},{}) [{}|{}] \"{}\" Vector__XXX",
+            self.name,
+            mux,
+            self.start_bit,
+            self.bit_length,
+            byte_order,
+            value_type,
+            self.factor,
+            self.offset,
+            self.minimum.unwrap_or(0.0),
+            self.maximum.unwrap_or(0.0),
+            self.unit.as_deref().unwrap_or("")
+        )
+    }
+
     /// Get the raw value range (before factor/offset)
     pub fn raw_range(&self) -> (u64, u64) {
         let max_raw = (1u64 << self.bit_length) - 1;
@@ -580,6 +909,14 @@ impl DbcSignal {
         let physical_max = (raw_max as f64) * self.factor + self.offset;
         (physical_min, physical_max)
     }
+
+    /// Declared min/max when the DBC gives a usable range, otherwise the full physical range
+    pub fn value_range(&self) -> (f64, f64) {
+        match (self.minimum, self.maximum) {
+            (Some(min), Some(max)) if max > min => (min, max),
+            _ => self.physical_range(),
+        }
+    }
 }
 
 /// Byte order for signal encoding
@@ -591,6 +928,20 @@ pub enum ByteOrder {
     Intel,
 }
 
+/// DBC bit numbers a signal occupies, from its start bit on. Intel counts up from
+/// the LSB. Motorola counts down from the MSB to bit 0 of a byte, then carries on
+/// at bit 7 of the next byte (the DBC "sawtooth"), ending at the LSB.
+pub fn signal_bits(start_bit: u8, bit_length: u8, byte_order: ByteOrder) -> impl Iterator<Item = usize> {
+    std::iter::successors(Some(start_bit as usize), move |&bit| {
+        Some(match byte_order {
+            ByteOrder::Intel => bit + 1,
+            ByteOrder::Motorola if bit % 8 == 0 => bit + 15,
+            ByteOrder::Motorola => bit - 1,
+        })
+    })
+    .take(bit_length as usize)
+}
+
 impl Default for ByteOrder {
     fn default() -> Self {
         ByteOrder::Intel
@@ -630,15 +981,24 @@ pub struct ValueDescription {
     pub description: String,
 }
 
+/// Outcome of `DbcFile::copy_signal`
+#[derive(Debug, Clone, PartialEq)]
+pub struct CopiedSignal {
+    /// Name of the copy in the target message (suffixed on a collision)
+    pub name: String,
+    /// Target signals sharing bits with the copy
+    pub overlaps: Vec<String>,
+}
+
 /// Check if two signals overlap in bit positions
 fn signals_overlap(a: &DbcSignal, b: &DbcSignal) -> bool {
-    let a_start = a.start_bit as usize;
-    let a_end = a_start + a.bit_length as usize;
-    let b_start = b.start_bit as usize;
-    let b_end = b_start + b.bit_length as usize;
+    let a_bits: Vec<usize> = a.bits().collect();
+    b.bits().any(|bit| a_bits.contains(&bit))
+}
 
-    // Simple overlap check - doesn't account for byte order differences
-    a_start < b_end && b_start < a_end
+/// True if two signals are never in the same frame (different multiplexer values)
+fn exclusive_mux(a: &Option<Multiplexor>, b: &Option<Multiplexor>) -> bool {
+    matches!((a, b), (Some(Multiplexor::Value(x)), Some(Multiplexor::Value(y))) if x != y)
 }
 
 #[cfg(test)]
@@ -652,6 +1012,52 @@ mod tests {
         assert_eq!(msg.id, 256);
         assert_eq!(msg.name, "StatusMessage");
         assert_eq!(msg.size, 8);
+        assert_eq!(msg.transmitter, DEFAULT_NODE);
+        assert!(!msg.has_transmitter());
+
+        let msg = parse_message_line("BO_ 512 EngineData: 8 ECM").unwrap();
+        assert_eq!(msg.transmitter, "ECM");
+        assert!(msg.has_transmitter());
+    }
+
+    #[test]
+    fn test_parse_bom_and_crlf() {
+        let content = "\u{feff}VERSION \"1.0\"\r\n\r\nBU_: ECM\r\n\r\nBO_ 256 EngineData: 8 ECM\r\n SG_ Rpm : 0|16@1+ (1,0) [0|0] \"rpm\" Vector__XXX\r\n\r\nVAL_ 256 Rpm 0 \"Off\" ;\r\n";
+        let dbc = DbcFile::parse(content).unwrap();
+        assert_eq!(dbc.version, "1.0");
+        assert_eq!(dbc.nodes, vec!["ECM".to_string()]);
+        let signal = &dbc.get_message(256).unwrap().signals[0];
+        assert_eq!(signal.name, "Rpm");
+        assert_eq!(signal.unit.as_deref(), Some("rpm"));
+        assert_eq!(dbc.value_tables.len(), 1);
+    }
+
+    #[test]
+    fn test_parse_reports_line_progress() {
+        let mut content = String::new();
+        for id in 0..3000 {
+            content.push_str(&format!("BO_ {} Msg{}: 8 Vector__XXX\n SG_ Sig{} : 0|8@1+ (1,0) [0|0] \"\" Vector__XXX\n\n", id, id, id));
+        }
+        let mut reports = Vec::new();
+        let dbc = DbcFile::parse_with_progress(&content, |done, total| reports.push((done, total))).unwrap();
+        assert_eq!(dbc.messages.len(), 3000);
+        assert_eq!(reports, vec![(0, 9000), (5000, 9000), (9000, 9000)]);
+    }
+
+    #[test]
+    fn test_nodes_roundtrip() {
+        let content = "VERSION \"\"\n\nBU_: ECM TCM\n\nBO_ 256 EngineData: 8 ECM\n SG_ Rpm : 0|16@1+ (1,0) [0|0] \"\" TCM\n";
+        let dbc = DbcFile::parse(content).unwrap();
+        assert_eq!(dbc.nodes, vec!["ECM".to_string(), "TCM".to_string()]);
+        assert_eq!(dbc.messages[0].transmitter, "ECM");
+
+        let output = dbc.to_dbc_string();
+        assert!(output.contains("BU_: ECM TCM"));
+        assert!(output.contains("BO_ 256 EngineData: 8 ECM"));
+
+        let reparsed = DbcFile::parse(&output).unwrap();
+        assert_eq!(reparsed.nodes, dbc.nodes);
+        assert_eq!(reparsed.messages[0].transmitter, "ECM");
     }
 
     #[test]
@@ -668,6 +1074,51 @@ mod tests {
         assert_eq!(signal.unit, Some("km/h".to_string()));
     }
 
+    #[test]
+    fn test_signal_dbc_line_roundtrip() {
+        let mut signal = DbcSignal::with_options("Torque", 39, 12, ByteOrder::Motorola, ValueType::Signed, 0.25, -10.0)
+            .with_unit("Nm")
+            .with_range(-500.0, 500.0);
+        signal.multiplexor = Some(Multiplexor::Value(3));
+
+        let line = signal.to_dbc_line();
+        assert_eq!(line, "SG_ Torque m3 : 39|12@0- (0.25,-10) [-500|500] \"Nm\" Vector__XXX");
+
+        let parsed = parse_signal_line(&line).unwrap();
+        assert_eq!(parsed.start_bit, 39);
+        assert_eq!(parsed.byte_order, ByteOrder::Motorola);
+        assert_eq!(parsed.value_type, ValueType::Signed);
+        assert_eq!(parsed.maximum, Some(500.0));
+        assert!(matches!(parsed.multiplexor, Some(Multiplexor::Value(3))));
+    }
+
+    #[test]
+    fn test_filtered_keeps_only_selected_signals() {
+        let mut dbc = DbcFile::new();
+        let mut engine = DbcMessage::new(0x100, "Engine", 8);
+        engine.add_signal(DbcSignal::new("Rpm", 0, 16));
+        engine.add_signal(DbcSignal::new("Temp", 16, 8));
+        dbc.add_message(engine);
+        let mut body = DbcMessage::new(0x200, "Body", 8);
+        body.add_signal(DbcSignal::new("Door", 0, 1));
+        dbc.add_message(body);
+        dbc.value_tables.insert("Temp".to_string(), vec![ValueDescription { value: 0, description: "Cold".to_string() }]);
+
+        // Edits made through the lookup must reach the output too
+        dbc.get_message_mut(0x100).unwrap().add_signal(DbcSignal::new("NewSig", 24, 4));
+
+        let subset = dbc.filtered(|_, s| s.name == "Temp" || s.name == "NewSig");
+        assert_eq!(subset.messages.len(), 1);
+        let names: Vec<&str> = subset.messages[0].signals.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["Temp", "NewSig"]);
+        assert!(subset.value_tables.contains_key("Temp"));
+
+        let text = subset.to_dbc_string();
+        assert!(text.contains("SG_ NewSig"));
+        assert!(!text.contains("Rpm") && !text.contains("Body"));
+        assert!(dbc.to_dbc_string().contains("SG_ NewSig"));
+    }
+
     #[test]
     fn test_dbc_roundtrip() {
         let mut dbc = DbcFile::new();
@@ -688,6 +1139,92 @@ mod tests {
         assert_eq!(parsed.messages[0].signals.len(), 1);
     }
 
+    #[test]
+    fn test_cycle_time_attributes() {
+        let content = "BO_ 256 Engine: 8 ECU\nBO_ 512 Brake: 8 ECU\n\
+            BA_DEF_ BO_  \"GenMsgCycleTime\" INT 0 65535;\n\
+            BA_ \"GenMsgCycleTime\" BO_ 256 100;\n\
+            BA_ \"GenMsgSendType\" BO_ 512 1;\n";
+        let dbc = DbcFile::parse(content).unwrap();
+        let engine = dbc.get_message(0x100).unwrap();
+        assert_eq!(engine.cycle_time_ms, Some(100));
+        assert_eq!(dbc.get_message(0x200).unwrap().cycle_time_ms, None);
+
+        assert_eq!(engine.check_cycle(105.0), Some(CycleTiming::OnTime));
+        assert_eq!(engine.check_cycle(250.0), Some(CycleTiming::Slow));
+        assert_eq!(engine.check_cycle(50.0), Some(CycleTiming::Fast));
+        assert_eq!(dbc.get_message(0x200).unwrap().check_cycle(50.0), None);
+
+        let reparsed = DbcFile::parse(&dbc.to_dbc_string()).unwrap();
+        assert_eq!(reparsed.get_message(0x100).unwrap().cycle_time_ms, Some(100));
+        assert_eq!(reparsed.get_message(0x200).unwrap().cycle_time_ms, None);
+    }
+
+    #[test]
+    fn test_find_signals_across_messages() {
+        let mut dbc = DbcFile::new();
+        let mut engine = DbcMessage::new(0x100, "Engine", 8);
+        engine.add_signal(DbcSignal::new("EngineSpeed", 0, 16));
+        engine.add_signal(DbcSignal::new("Coolant", 16, 8));
+        dbc.add_message(engine);
+        let mut wheels = DbcMessage::new(0x200, "Wheels", 8);
+        wheels.add_signal(DbcSignal::new("SpeedFL", 0, 16));
+        dbc.add_message(wheels);
+
+        let found: Vec<(u32, &str)> = dbc.find_signals("speed").iter()
+            .map(|(msg, signal)| (msg.id, signal.name.as_str()))
+            .collect();
+        assert_eq!(found, vec![(0x200, "SpeedFL"), (0x100, "EngineSpeed")]);
+        assert!(dbc.find_signals("  ").is_empty());
+        assert!(dbc.find_signals("brake").is_empty());
+    }
+
+    #[test]
+    fn test_message_comment_roundtrip() {
+        let mut dbc = DbcFile::new();
+        dbc.add_message(DbcMessage::new(0x100, "Engine", 8));
+        dbc.add_message(DbcMessage::new(0x200, "Brake", 8));
+        assert!(dbc.set_message_comment(0x100, Some("Says \"hi\"\nsecond line".to_string())));
+        assert!(!dbc.set_message_comment(0x300, Some("nope".to_string())));
+
+        let parsed = DbcFile::parse(&dbc.to_dbc_string()).unwrap();
+        assert_eq!(parsed.get_message(0x100).unwrap().comment.as_deref(), Some("Says \"hi\"\nsecond line"));
+        assert_eq!(parsed.get_message(0x200).unwrap().comment, None);
+
+        // Signal comments and trailing statements on the same file are left alone
+        let parsed = DbcFile::parse("BO_ 256 Engine: 8 ECU\nCM_ SG_ 256 Rpm \"speed\";\nCM_ BO_ 256 \"a; b\";").unwrap();
+        assert_eq!(parsed.get_message(0x100).unwrap().comment.as_deref(), Some("a; b"));
+    }
+
+    #[test]
+    fn test_copy_signal_between_messages() {
+        let mut dbc = DbcFile::parse(concat!(
+            "BO_ 256 Engine: 8 ECU\n",
+            " SG_ Gear : 0|4@1+ (1,0) [0|15] \"\" Vector__XXX\n",
+            " SG_ Mode m1 : 8|8@1+ (1,0) [0|255] \"\" Vector__XXX\n",
+            "BO_ 512 Gearbox: 8 TCU\n",
+            " SG_ Gear : 16|8@1+ (1,0) [0|255] \"\" Vector__XXX\n",
+            " SG_ Status : 2|4@1+ (1,0) [0|15] \"\" Vector__XXX\n",
+            "VAL_ 256 Gear 0 \"P\" 1 \"R\" ;\n",
+        )).unwrap();
+
+        // Name taken in the target: suffixed, keeps the value table, warns about Status
+        let copied = dbc.copy_signal(0x100, "Gear", 0x200).unwrap();
+        assert_eq!(copied, CopiedSignal { name: "Gear_2".to_string(), overlaps: vec!["Status".to_string()] });
+        let copy = dbc.get_message(0x200).unwrap().get_signal("Gear_2").unwrap();
+        assert_eq!((copy.start_bit, copy.bit_length), (0, 4));
+        assert_eq!(dbc.value_tables["Gear_2"].len(), 2);
+        assert_eq!(dbc.copy_signal(0x100, "Gear", 0x200).unwrap().name, "Gear_3");
+
+        // The target has no multiplexer, so the copy is always present
+        let copied = dbc.copy_signal(0x100, "Mode", 0x200).unwrap();
+        assert_eq!(copied.name, "Mode");
+        assert!(dbc.get_message(0x200).unwrap().get_signal("Mode").unwrap().multiplexor.is_none());
+
+        assert!(dbc.copy_signal(0x100, "Missing", 0x200).is_err());
+        assert!(dbc.copy_signal(0x100, "Gear", 0x300).is_err());
+    }
+
     #[test]
     fn test_message_validation() {
         let mut msg = DbcMessage::new(0x100, "Test", 8);
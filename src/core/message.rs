@@ -123,6 +123,26 @@ pub struct CanMessage {
 
     /// Raw data bytes (0-8 bytes, stack-allocated)
     pub data: CanData,
+
+    /// Remote transmission request frame. RTR frames carry no payload;
+    /// `data` is zero-filled and its length is the requested DLC.
+    #[serde(default)]
+    pub is_rtr: bool,
+
+    /// Error frame. `id` holds the error class bits (SocketCAN `CAN_ERR_*`,
+    /// 0 when the log doesn't say) and `data` the error details, if any.
+    #[serde(default)]
+    pub is_error: bool,
+}
+
+/// Error frame flag of a SocketCAN `can_id`, as written in candump logs
+pub const CAN_ERR_FLAG: u32 = 0x2000_0000;
+
+/// Parse a hex CAN ID typed by the user (optional 0x prefix), rejecting anything wider than 29 bits
+pub fn parse_can_id(input: &str) -> Option<u32> {
+    let input = input.trim();
+    let hex = input.strip_prefix("0x").or_else(|| input.strip_prefix("0X")).unwrap_or(input);
+    u32::from_str_radix(hex, 16).ok().filter(|&id| id <= 0x1FFF_FFFF)
 }
 
 impl CanMessage {
@@ -133,6 +153,32 @@ impl CanMessage {
             bus,
             id,
             data,
+            is_rtr: false,
+            is_error: false,
+        }
+    }
+
+    /// Create a remote transmission request (RTR) frame requesting `dlc` bytes
+    pub fn new_rtr(bus: u8, id: u32, dlc: u8) -> Self {
+        Self {
+            timestamp: Utc::now(),
+            bus,
+            id,
+            data: CanData::from_slice(&[0u8; 8][..dlc.min(8) as usize]),
+            is_rtr: true,
+            is_error: false,
+        }
+    }
+
+    /// Create an error frame with error class bits `class` and detail bytes `data`
+    pub fn new_error(bus: u8, class: u32, data: CanData) -> Self {
+        Self {
+            timestamp: Utc::now(),
+            bus,
+            id: class,
+            data,
+            is_rtr: false,
+            is_error: true,
         }
     }
 
@@ -150,6 +196,33 @@ impl CanMessage {
             .join(" ")
     }
 
+    /// Format as a `candump -L` log line, e.g. `(1436509052.249713) can0 123#DEADBEEF`
+    pub fn to_candump_line(&self) -> String {
+        let id = if self.is_error {
+            format!("{:08X}", self.id | CAN_ERR_FLAG)
+        } else if self.is_extended() {
+            format!("{:08X}", self.id)
+        } else {
+            format!("{:03X}", self.id)
+        };
+        let payload = if self.is_rtr {
+            match self.data.len() {
+                0 => "R".to_string(),
+                dlc => format!("R{}", dlc),
+            }
+        } else {
+            self.data.iter().map(|b| format!("{:02X}", b)).collect()
+        };
+        format!(
+            "({}.{:06}) can{} {}#{}",
+            self.timestamp.timestamp(),
+            self.timestamp.timestamp_subsec_micros(),
+            self.bus,
+            id,
+            payload
+        )
+    }
+
     /// Get timestamp as Unix timestamp in seconds
     pub fn timestamp_unix(&self) -> f64 {
         self.timestamp.timestamp_millis() as f64 / 1000.0
@@ -174,3 +247,33 @@ impl CanMessage {
         Ok(CanData::from_slice(&bytes))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_candump_line_uses_real_dlc() {
+        let mut msg = CanMessage::new(1, 0x123, vec![0xDE, 0xAD, 0x01].into());
+        msg.timestamp = Utc.timestamp_opt(1_436_509_052, 249_713_000).unwrap();
+        assert_eq!(msg.to_candump_line(), "(1436509052.249713) can1 123#DEAD01");
+
+        msg.id = 0x18DAF110;
+        msg.data = CanData::from_slice(&[]);
+        assert_eq!(msg.to_candump_line(), "(1436509052.249713) can1 18DAF110#");
+
+        let mut rtr = CanMessage::new_rtr(0, 0x7E0, 4);
+        rtr.timestamp = msg.timestamp;
+        assert_eq!(rtr.to_candump_line(), "(1436509052.249713) can0 7E0#R4");
+    }
+
+    #[test]
+    fn test_parse_can_id() {
+        assert_eq!(parse_can_id(" 0x1a3 "), Some(0x1A3));
+        assert_eq!(parse_can_id("7E8"), Some(0x7E8));
+        assert_eq!(parse_can_id("0x1FFFFFFF"), Some(0x1FFF_FFFF));
+        assert_eq!(parse_can_id("0x20000000"), None);
+        assert_eq!(parse_can_id("zz"), None);
+    }
+}
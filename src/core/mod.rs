@@ -1,6 +1,7 @@
 pub mod message;
 pub mod signal;
 pub mod dbc;
+pub mod byte_pattern;
 
 pub use message::{CanData, CanMessage};
 pub use signal::Signal;
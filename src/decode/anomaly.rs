@@ -0,0 +1,214 @@
+//! Anomalous-frame detection for log navigation: frames whose length doesn't
+//! match the DBC, and frames failing a per-message checksum check.
+//!
+//! Rows the loaders could not parse never become frames, so they can't be
+//! navigated to; the scan only covers frames that made it into the log.
+
+use crate::core::dbc::DbcFile;
+use crate::core::CanMessage;
+use crate::decode::decoder::{extract_bits, insert_bits};
+use std::collections::HashMap;
+
+/// Widest checksum signal the algorithms can fill: they all produce one byte
+pub const MAX_CHECKSUM_BITS: u8 = 8;
+
+/// Checksum algorithms a message's checksum signal can be validated with.
+/// Each runs over the whole payload with the checksum signal's bits zeroed,
+/// and the result is truncated to the signal's bit length (at most `MAX_CHECKSUM_BITS`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChecksumAlgorithm {
+    /// XOR of all bytes
+    Xor8,
+    /// Wrapping sum of all bytes
+    Sum8,
+    /// CRC-8 SAE J1850 (poly 0x1D, init 0xFF, final XOR 0xFF)
+    Crc8J1850,
+}
+
+impl ChecksumAlgorithm {
+    pub const ALL: [ChecksumAlgorithm; 3] = [ChecksumAlgorithm::Xor8, ChecksumAlgorithm::Sum8, ChecksumAlgorithm::Crc8J1850];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ChecksumAlgorithm::Xor8 => "XOR",
+            ChecksumAlgorithm::Sum8 => "Sum",
+            ChecksumAlgorithm::Crc8J1850 => "CRC-8 (J1850)",
+        }
+    }
+
+    pub fn compute(self, data: &[u8]) -> u8 {
+        match self {
+            ChecksumAlgorithm::Xor8 => data.iter().fold(0, |acc, b| acc ^ b),
+            ChecksumAlgorithm::Sum8 => data.iter().fold(0, |acc: u8, b| acc.wrapping_add(*b)),
+            ChecksumAlgorithm::Crc8J1850 => {
+                let mut crc = 0xFFu8;
+                for byte in data {
+                    crc ^= byte;
+                    for _ in 0..8 {
+                        crc = if crc & 0x80 != 0 { (crc << 1) ^ 0x1D } else { crc << 1 };
+                    }
+                }
+                crc ^ 0xFF
+            }
+        }
+    }
+}
+
+/// Checksum hook for one message: which signal holds the checksum and how it's computed
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChecksumCheck {
+    pub signal: String,
+    pub algorithm: ChecksumAlgorithm,
+}
+
+impl ChecksumCheck {
+    /// Whether a signal `bit_length` bits wide can hold a checksum (wider ones would never match)
+    pub fn fits(bit_length: u8) -> bool {
+        (1..=MAX_CHECKSUM_BITS).contains(&bit_length)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AnomalyKind {
+    /// Payload length differs from the DBC message size
+    DlcMismatch { expected: u8, actual: u8 },
+    /// Checksum signal doesn't match the value computed from the payload
+    ChecksumMismatch { signal: String, expected: u64, actual: u64 },
+}
+
+/// An anomalous frame, by index into the scanned log
+#[derive(Debug, Clone, PartialEq)]
+pub struct Anomaly {
+    pub index: usize,
+    pub kind: AnomalyKind,
+}
+
+impl Anomaly {
+    pub fn describe(&self) -> String {
+        match &self.kind {
+            AnomalyKind::DlcMismatch { expected, actual } => {
+                format!("DLC {} (DBC expects {})", actual, expected)
+            }
+            AnomalyKind::ChecksumMismatch { signal, expected, actual } => {
+                format!("{} = 0x{:X}, computed 0x{:X}", signal, actual, expected)
+            }
+        }
+    }
+}
+
+/// Check a single frame against its DBC definition and checksum hook
+pub fn check_frame(msg: &CanMessage, dbc: &DbcFile, checksum: Option<&ChecksumCheck>) -> Option<AnomalyKind> {
+    if msg.is_error {
+        return None;
+    }
+    let def = dbc.get_message(msg.id)?;
+    if msg.data.len() != def.size as usize {
+        return Some(AnomalyKind::DlcMismatch { expected: def.size, actual: msg.data.len() as u8 });
+    }
+    // RTR frames carry no payload to checksum
+    if msg.is_rtr {
+        return None;
+    }
+
+    let check = checksum?;
+    let signal = def.get_signal(&check.signal)?;
+    if !ChecksumCheck::fits(signal.bit_length) {
+        return None;
+    }
+    let actual = extract_bits(&msg.data, signal.start_bit, signal.bit_length, signal.byte_order)?;
+    let mut zeroed = msg.data.to_vec();
+    insert_bits(&mut zeroed, 0, signal.start_bit, signal.bit_length, signal.byte_order);
+    let mask = u64::MAX >> (64 - signal.bit_length as u32);
+    let expected = check.algorithm.compute(&zeroed) as u64 & mask;
+    (expected != actual).then(|| AnomalyKind::ChecksumMismatch { signal: check.signal.clone(), expected, actual })
+}
+
+/// Scan a log for anomalous frames; `checksums` is keyed by message ID
+pub fn scan_anomalies(messages: &[CanMessage], dbc: &DbcFile, checksums: &HashMap<u32, ChecksumCheck>) -> Vec<Anomaly> {
+    messages.iter()
+        .enumerate()
+        .filter_map(|(index, msg)| {
+            check_frame(msg, dbc, checksums.get(&msg.id)).map(|kind| Anomaly { index, kind })
+        })
+        .collect()
+}
+
+/// First anomaly after frame `position` (anomalies sorted by index)
+pub fn next_anomaly(anomalies: &[Anomaly], position: usize) -> Option<&Anomaly> {
+    let i = anomalies.partition_point(|a| a.index <= position);
+    anomalies.get(i)
+}
+
+/// Last anomaly before frame `position`
+pub fn prev_anomaly(anomalies: &[Anomaly], position: usize) -> Option<&Anomaly> {
+    let i = anomalies.partition_point(|a| a.index < position);
+    i.checked_sub(1).map(|i| &anomalies[i])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::dbc::{DbcMessage, DbcSignal};
+
+    #[test]
+    fn test_scan_and_navigate_anomalies() {
+        let mut def = DbcMessage::new(0x100, "Status", 4);
+        def.add_signal(DbcSignal::new("Value", 0, 16));
+        def.add_signal(DbcSignal::new("Checksum", 24, 8));
+        let mut dbc = DbcFile::new();
+        dbc.add_message(def);
+        let checksums = HashMap::from([(0x100, ChecksumCheck { signal: "Checksum".to_string(), algorithm: ChecksumAlgorithm::Xor8 })]);
+
+        let frame = |data: &[u8]| CanMessage::new(0, 0x100, data.into());
+        let messages = vec![
+            frame(&[0x12, 0x34, 0x00, 0x26]),  // 0x12 ^ 0x34 = 0x26
+            frame(&[0x12, 0x34, 0x00, 0x27]),  // bad checksum
+            frame(&[0x12, 0x34, 0x00]),         // short frame
+            CanMessage::new(0, 0x200, vec![0xFF].into()),  // not in DBC
+            frame(&[0x00, 0x01, 0x00, 0x01]),
+        ];
+
+        let anomalies = scan_anomalies(&messages, &dbc, &checksums);
+        assert_eq!(anomalies.len(), 2);
+        assert_eq!(anomalies[0].index, 1);
+        assert_eq!(anomalies[0].kind, AnomalyKind::ChecksumMismatch { signal: "Checksum".to_string(), expected: 0x26, actual: 0x27 });
+        assert_eq!(anomalies[1].kind, AnomalyKind::DlcMismatch { expected: 4, actual: 3 });
+
+        assert_eq!(next_anomaly(&anomalies, 0).map(|a| a.index), Some(1));
+        assert_eq!(next_anomaly(&anomalies, 1).map(|a| a.index), Some(2));
+        assert_eq!(next_anomaly(&anomalies, 2), None);
+        assert_eq!(prev_anomaly(&anomalies, 4).map(|a| a.index), Some(2));
+        assert_eq!(prev_anomaly(&anomalies, 1), None);
+    }
+
+    #[test]
+    fn test_checksum_width() {
+        let mut def = DbcMessage::new(0x100, "Status", 4);
+        def.add_signal(DbcSignal::new("Value", 0, 16));
+        def.add_signal(DbcSignal::new("Nibble", 16, 4));
+        def.add_signal(DbcSignal::new("Wide", 16, 16));
+        let mut dbc = DbcFile::new();
+        dbc.add_message(def);
+        let check = |signal: &str| ChecksumCheck { signal: signal.to_string(), algorithm: ChecksumAlgorithm::Xor8 };
+        let frame = CanMessage::new(0, 0x100, vec![0x12, 0x34, 0x06, 0xA0].into());
+
+        // Narrow checksums compare at their own width: 0x12 ^ 0x34 ^ 0xA0 = 0x86, low nibble 6
+        assert_eq!(check_frame(&frame, &dbc, Some(&check("Nibble"))), None);
+        let bad = CanMessage::new(0, 0x100, vec![0x12, 0x34, 0x07, 0xA0].into());
+        assert_eq!(
+            check_frame(&bad, &dbc, Some(&check("Nibble"))),
+            Some(AnomalyKind::ChecksumMismatch { signal: "Nibble".to_string(), expected: 0x6, actual: 0x7 })
+        );
+
+        // 16-bit signals can't hold an 8-bit checksum: rejected up front, never flagged
+        assert!(!ChecksumCheck::fits(16));
+        assert!(ChecksumCheck::fits(8));
+        assert_eq!(check_frame(&frame, &dbc, Some(&check("Wide"))), None);
+    }
+
+    #[test]
+    fn test_crc8_j1850() {
+        // Standard check value for "123456789"
+        assert_eq!(ChecksumAlgorithm::Crc8J1850.compute(b"123456789"), 0x4B);
+    }
+}
@@ -1,5 +1,6 @@
-use crate::core::dbc::{DbcFile, DbcMessage, DbcSignal, ByteOrder, ValueType};
+use crate::core::dbc::{signal_bits, DbcFile, DbcMessage, DbcSignal, ByteOrder, Multiplexor, ValueType};
 use crate::core::CanMessage;
+use crate::decode::encoder::encode_signal;
 use chrono::{DateTime, Utc};
 
 /// A decoded signal value from a CAN message
@@ -37,21 +38,56 @@ impl SignalDecoder {
         self.dbc = None;
     }
 
-    /// Decode all signals from a CAN message
+    /// Decode all signals from a CAN message.
+    ///
+    /// Multiplexed signals are only decoded from frames whose selector matches
+    /// their mux value; in other frames their bits belong to another signal.
     pub fn decode_message(&self, msg: &CanMessage) -> Vec<DecodedSignal> {
-        let dbc = match &self.dbc {
-            Some(dbc) => dbc,
-            None => return Vec::new(),
+        self.decode_message_filtered(msg, |_| true)
+    }
+
+    /// Like `decode_message`, but only decodes the signals `wanted` accepts
+    pub fn decode_message_filtered(&self, msg: &CanMessage, wanted: impl FnMut(&DbcSignal) -> bool) -> Vec<DecodedSignal> {
+        let mut decoded = Vec::new();
+        self.visit_signals(msg, wanted, |_, value| decoded.extend(value));
+        decoded
+    }
+
+    /// Call `f` for every signal `wanted` accepts in the frame's active mux
+    /// branch, with `None` for a signal the frame can't supply (its bits run
+    /// past the end of the data, e.g. a short DLC)
+    pub fn visit_signals(
+        &self,
+        msg: &CanMessage,
+        mut wanted: impl FnMut(&DbcSignal) -> bool,
+        mut f: impl FnMut(&DbcSignal, Option<DecodedSignal>),
+    ) {
+        let Some(dbc) = &self.dbc else {
+            return;
         };
+        // An error frame's `id` is its error class, not a DBC message, and an
+        // RTR frame carries no payload to decode
+        if msg.is_error || msg.is_rtr {
+            return;
+        }
 
-        let dbc_msg = match dbc.get_message(msg.id) {
-            Some(m) => m,
-            None => return Vec::new(),
+        let Some(dbc_msg) = dbc.get_message(msg.id) else {
+            return;
         };
 
-        dbc_msg.signals.iter()
-            .filter_map(|signal| self.decode_signal(msg, signal))
-            .collect()
+        let mux_value = dbc_msg.signals.iter()
+            .find(|s| matches!(s.multiplexor, Some(Multiplexor::Signal)))
+            .and_then(|selector| extract_bits(&msg.data, selector.start_bit, selector.bit_length, selector.byte_order));
+
+        let active = dbc_msg.signals.iter()
+            .filter(|signal| match signal.multiplexor {
+                Some(Multiplexor::Value(v)) => mux_value == Some(v as u64),
+                _ => true,
+            })
+            .filter(|signal| wanted(signal));
+        for signal in active {
+            f(signal, self.decode_signal(msg, signal));
+        }
     }
 
     /// Decode a single signal from a CAN message
@@ -65,8 +101,13 @@ impl SignalDecoder {
             raw_value
         };
 
-        // Apply factor and offset to get physical value
-        let physical_value = (raw_value as f64) * signal.factor + signal.offset;
+        // Apply factor and offset to get physical value (sign-extended raw must be read as i64)
+        let raw_numeric = if signal.value_type == ValueType::Signed {
+            raw_value as i64 as f64
+        } else {
+            raw_value as f64
+        };
+        let physical_value = raw_numeric * signal.factor + signal.offset;
 
         Some(DecodedSignal {
             name: signal.name.clone(),
@@ -80,19 +121,7 @@ impl SignalDecoder {
 
     /// Encode a signal value into CAN data bytes
     pub fn encode_signal(&self, data: &mut [u8], signal: &DbcSignal, physical_value: f64) -> bool {
-        // Convert physical value to raw value
-        let raw_value = ((physical_value - signal.offset) / signal.factor) as i64;
-
-        // Convert to unsigned for bit manipulation
-        let raw_unsigned = if raw_value < 0 {
-            // Handle negative values
-            let mask = (1u64 << signal.bit_length) - 1;
-            (raw_value as u64) & mask
-        } else {
-            raw_value as u64
-        };
-
-        insert_bits(data, raw_unsigned, signal.start_bit, signal.bit_length, signal.byte_order)
+        encode_signal(data, signal, physical_value).is_ok()
     }
 }
 
@@ -109,29 +138,27 @@ impl Default for SignalDecoder {
 /// * `start_bit` - Starting bit position (0-63, in DBC notation)
 /// * `bit_length` - Number of bits to extract
 /// * `byte_order` - Intel (little-endian) or Motorola (big-endian)
+///
+/// Intel `start_bit` is the LSB and the signal runs up through the bit numbers.
+/// Motorola `start_bit` is the MSB and the signal follows the DBC sawtooth (see
+/// `signal_bits`): e.g. `15|16@0` is byte 1 then byte 2, big-endian. A Motorola
+/// signal running past the end of `data` gives None; an Intel one reads what is there.
 pub fn extract_bits(data: &[u8], start_bit: u8, bit_length: u8, byte_order: ByteOrder) -> Option<u64> {
     if data.is_empty() || bit_length == 0 || bit_length > 64 {
         return None;
     }
 
+    if byte_order == ByteOrder::Motorola {
+        // MSB first, one bit at a time along the sawtooth
+        return signal_bits(start_bit, bit_length, byte_order).try_fold(0u64, |value, bit| {
+            let byte = *data.get(bit / 8)?;
+            Some((value << 1) | ((byte >> (bit % 8)) & 1) as u64)
+        });
+    }
+
     let start_bit = start_bit as usize;
     let bit_length = bit_length as usize;
-
-    // Convert DBC bit position to actual bit position
-    // Intel: start_bit = LSB, read forward
-    // Motorola: start_bit = MSB, so LSB is at (start_bit - length + 1); we read from LSB forward
-    let (byte_idx, bit_idx) = match byte_order {
-        ByteOrder::Intel => {
-            (start_bit / 8, start_bit % 8)
-        }
-        ByteOrder::Motorola => {
-            if start_bit + 1 < bit_length {
-                return None; // Invalid: MSB at start_bit requires start_bit >= bit_length - 1
-            }
-            let lsb = start_bit + 1 - bit_length;
-            dbc_motorola_to_position(lsb)
-        }
-    };
+    let (byte_idx, bit_idx) = (start_bit / 8, start_bit % 8);
 
     if byte_idx >= data.len() {
         return None;
@@ -163,34 +190,27 @@ pub fn extract_bits(data: &[u8], start_bit: u8, bit_length: u8, byte_order: Byte
     Some(result)
 }
 
-/// Convert DBC bit position to byte/bit position.
-/// DBC numbering: bit 0 = LSB of byte 0, bit 7 = MSB of byte 0 (same for Intel and Motorola).
-/// bit_in_byte 0 = LSB, 7 = MSB (for extraction: (data[byte] >> bit_in_byte) & 1).
-fn dbc_motorola_to_position(dbc_bit: usize) -> (usize, usize) {
-    let byte = dbc_bit / 8;
-    let bit_in_byte = dbc_bit % 8;
-    (byte, bit_in_byte)
-}
-
-/// Insert bits into a byte array
+/// Insert bits into a byte array (same bit layout as `extract_bits`)
 pub fn insert_bits(data: &mut [u8], value: u64, start_bit: u8, bit_length: u8, byte_order: ByteOrder) -> bool {
     if data.is_empty() || bit_length == 0 || bit_length > 64 {
         return false;
     }
 
+    if byte_order == ByteOrder::Motorola {
+        // Check the whole sawtooth fits first, so a failed insert leaves `data` untouched
+        if signal_bits(start_bit, bit_length, byte_order).any(|bit| bit / 8 >= data.len()) {
+            return false;
+        }
+        for (i, bit) in signal_bits(start_bit, bit_length, byte_order).enumerate() {
+            let value_bit = ((value >> (bit_length as usize - 1 - i)) & 1) as u8;
+            data[bit / 8] = (data[bit / 8] & !(1 << (bit % 8))) | (value_bit << (bit % 8));
+        }
+        return true;
+    }
+
     let start_bit = start_bit as usize;
     let bit_length = bit_length as usize;
-
-    let (byte_idx, bit_idx) = match byte_order {
-        ByteOrder::Intel => (start_bit / 8, start_bit % 8),
-        ByteOrder::Motorola => {
-            if start_bit + 1 < bit_length {
-                return false; // Invalid: MSB at start_bit requires start_bit >= bit_length - 1
-            }
-            let lsb = start_bit + 1 - bit_length;
-            dbc_motorola_to_position(lsb)
-        }
-    };
+    let (byte_idx, bit_idx) = (start_bit / 8, start_bit % 8);
 
     if byte_idx >= data.len() {
         return false;
@@ -241,6 +261,7 @@ fn sign_extend(value: u64, bit_length: u8) -> u64 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::HashMap;
 
     #[test]
     fn test_extract_bits_intel_single_byte() {
@@ -293,6 +314,7 @@ mod tests {
             id: 0x123,
             name: "TestMessage".to_string(),
             size: 8,
+            transmitter: crate::core::dbc::DEFAULT_NODE.to_string(),
             signals: vec![DbcSignal {
                 name: "TestSignal".to_string(),
                 start_bit: 0,
@@ -306,6 +328,8 @@ mod tests {
                 unit: Some("degC".to_string()),
                 multiplexor: None,
             }],
+            comment: None,
+            cycle_time_ms: None,
         });
 
         let decoder = SignalDecoder::new();
@@ -321,17 +345,99 @@ mod tests {
         assert_eq!(signals[0].physical_value, 10.0); // 100 * 0.5 - 40 = 10
     }
 
+    #[test]
+    fn test_decode_skips_unselected_mux_signals() {
+        let mut msg_def = DbcMessage::new(0x300, "Muxed", 8);
+        let mut selector = DbcSignal::new("Page", 0, 8);
+        selector.multiplexor = Some(Multiplexor::Signal);
+        msg_def.add_signal(selector);
+        let mut temp = DbcSignal::new("Temp", 8, 16);
+        temp.multiplexor = Some(Multiplexor::Value(0));
+        msg_def.add_signal(temp);
+        let mut pressure = DbcSignal::new("Pressure", 8, 16);
+        pressure.multiplexor = Some(Multiplexor::Value(1));
+        msg_def.add_signal(pressure);
+        let mut dbc = DbcFile::new();
+        dbc.add_message(msg_def);
+        let mut decoder = SignalDecoder::new();
+        decoder.set_dbc(dbc);
+
+        // Alternate pages 0 and 1, the way chart population walks a log
+        let mut points: HashMap<String, Vec<f64>> = HashMap::new();
+        for i in 0..10u8 {
+            let page = i % 2;
+            let msg = CanMessage::new(0, 0x300, vec![page, 100 + i, 0].into());
+            for signal in decoder.decode_message(&msg) {
+                points.entry(signal.name).or_default().push(signal.physical_value);
+            }
+        }
+
+        assert_eq!(points["Page"].len(), 10);
+        assert_eq!(points["Temp"], vec![100.0, 102.0, 104.0, 106.0, 108.0]);
+        assert_eq!(points["Pressure"], vec![101.0, 103.0, 105.0, 107.0, 109.0]);
+    }
+
+    #[test]
+    fn test_decode_skips_rtr_and_error_frames() {
+        let mut msg_def = DbcMessage::new(0x123, "Test", 8);
+        msg_def.add_signal(DbcSignal::new("Byte0", 0, 8));
+        let mut dbc = DbcFile::new();
+        dbc.add_message(msg_def);
+        let mut decoder = SignalDecoder::new();
+        decoder.set_dbc(dbc);
+
+        let mut rtr = CanMessage::new(0, 0x123, vec![0; 8].into());
+        rtr.is_rtr = true;
+        let mut error = CanMessage::new(0, 0x123, vec![0; 8].into());
+        error.is_error = true;
+        for msg in [&rtr, &error] {
+            let mut visited = 0;
+            decoder.visit_signals(msg, |_| true, |_, _| visited += 1);
+            assert_eq!(visited, 0);
+        }
+
+        let mut visited = 0;
+        decoder.visit_signals(&CanMessage::new(0, 0x123, vec![0; 8].into()), |_| true, |_, _| visited += 1);
+        assert_eq!(visited, 1);
+    }
+
     #[test]
     fn test_extract_bits_motorola() {
-        // Motorola: start_bit 51 = MSB, 4 bits = DBC bits 48,49,50,51
-        // Byte 6: bits 48-55. So we extract from LSB (bit 48) at byte 6.
-        // Example: data with 0b1011 at bits 48-51 (LSB=1, MSB=1) -> value 0b1101 = 13
-        let mut data = [0u8; 8];
-        data[6] = 0b0000_1011; // bits 0-3 of byte 6 = DBC bits 48-51 (48 at pos 0, 51 at pos 3 in typical layout)
-        // DBC bit 48 = LSB of byte 6. In dbc_motorola_to_position, bit 48 -> (6, 7) if 7-(48%8)=7.
-        // So we read from byte 6, bit 7. That's the LSB of the byte (rightmost). Good.
-        let result = extract_bits(&data, 51, 4, ByteOrder::Motorola);
-        assert_eq!(result, Some(0b1011)); // 4 bits: 1,1,0,1 from LSB to MSB
+        // Known Motorola layouts (start bit = MSB, DBC sawtooth) over a fixed frame
+        let data = [0x12, 0x34, 0x56, 0x78, 0x9A, 0xBC, 0xDE, 0xF0];
+        let cases: [(u8, u8, Option<u64>); 12] = [
+            (7, 8, Some(0x12)),                  // whole byte 0
+            (15, 16, Some(0x3456)),              // bytes 1-2
+            (7, 16, Some(0x1234)),               // bytes 0-1
+            (7, 64, Some(0x1234_5678_9ABC_DEF0)), // whole frame
+            (3, 8, Some(0x23)),                  // low nibble of byte 0, high nibble of byte 1
+            (51, 4, Some(0xE)),                  // inside byte 6
+            (4, 12, Some(0x91A)),                // 5 bits of byte 0, 7 of byte 1
+            (55, 12, Some(0xDEF)),               // byte 6 and the top of byte 7
+            (39, 20, Some(0x9ABCD)),             // three bytes
+            (0, 1, Some(0)),
+            (63, 8, Some(0xF0)),
+            (60, 8, None),                       // runs past byte 7
+        ];
+        for (start, len, expected) in cases {
+            assert_eq!(extract_bits(&data, start, len, ByteOrder::Motorola), expected, "{}|{}@0", start, len);
+
+            // Inserting the value into an empty frame puts back exactly those bits
+            if let Some(value) = expected {
+                let mut frame = [0u8; 8];
+                assert!(insert_bits(&mut frame, value, start, len, ByteOrder::Motorola));
+                assert_eq!(extract_bits(&frame, start, len, ByteOrder::Motorola), Some(value));
+                let touched: Vec<usize> = signal_bits(start, len, ByteOrder::Motorola).collect();
+                for bit in 0..64 {
+                    if !touched.contains(&bit) {
+                        assert_eq!((frame[bit / 8] >> (bit % 8)) & 1, 0, "{}|{}@0 wrote bit {}", start, len, bit);
+                    }
+                }
+            } else {
+                assert!(!insert_bits(&mut [0u8; 8], 0, start, len, ByteOrder::Motorola));
+            }
+        }
+        assert_eq!(signal_bits(3, 8, ByteOrder::Motorola).collect::<Vec<_>>(), vec![3, 2, 1, 0, 15, 14, 13, 12]);
     }
 
     #[test]
@@ -0,0 +1,184 @@
+//! Signal encoding: the inverse of `decoder::extract_bits` plus factor/offset
+
+use crate::core::dbc::{DbcMessage, DbcSignal, Multiplexor, ValueType};
+use crate::decode::decoder::insert_bits;
+use anyhow::{bail, Result};
+use std::collections::HashMap;
+
+/// Convert a physical value to the signal's raw bit pattern (inverse of factor/offset + sign extension)
+///
+/// Fails if the value doesn't fit in the signal's bit width.
+pub fn physical_to_raw(signal: &DbcSignal, physical_value: f64) -> Result<u64> {
+    if signal.bit_length == 0 || signal.bit_length > 64 {
+        bail!("{}: invalid bit length {}", signal.name, signal.bit_length);
+    }
+    if signal.factor == 0.0 {
+        bail!("{}: factor is zero", signal.name);
+    }
+
+    let raw = ((physical_value - signal.offset) / signal.factor).round();
+    let bits = signal.bit_length as u32;
+    let (raw_min, raw_max) = raw_limits(signal);
+    if !raw.is_finite() || raw < raw_min as f64 || raw > raw_max as f64 {
+        bail!(
+            "{}: {} is out of range for {} {}-bit ({} to {})",
+            signal.name,
+            physical_value,
+            if signal.value_type == ValueType::Signed { "signed" } else { "unsigned" },
+            bits,
+            raw_min as f64 * signal.factor + signal.offset,
+            raw_max as f64 * signal.factor + signal.offset,
+        );
+    }
+
+    let mask = if bits == 64 { u64::MAX } else { (1u64 << bits) - 1 };
+    Ok((raw as i128 as u64) & mask)
+}
+
+/// Smallest and largest raw integer the signal's bit width can hold
+fn raw_limits(signal: &DbcSignal) -> (i128, i128) {
+    let bits = signal.bit_length.clamp(1, 64) as u32;
+    match signal.value_type {
+        ValueType::Signed => (-(1i128 << (bits - 1)), (1i128 << (bits - 1)) - 1),
+        ValueType::Unsigned => (0, (1i128 << bits) - 1),
+    }
+}
+
+/// Clamp a physical value to what the signal can represent
+fn saturate_physical(signal: &DbcSignal, physical_value: f64) -> f64 {
+    let (raw_min, raw_max) = raw_limits(signal);
+    let a = raw_min as f64 * signal.factor + signal.offset;
+    let b = raw_max as f64 * signal.factor + signal.offset;
+    physical_value.clamp(a.min(b), a.max(b))
+}
+
+/// Encode one signal's physical value into `data` (inverse of `extract_bits`)
+pub fn encode_signal(data: &mut [u8], signal: &DbcSignal, physical_value: f64) -> Result<()> {
+    let raw = physical_to_raw(signal, physical_value)?;
+    if !insert_bits(data, raw, signal.start_bit, signal.bit_length, signal.byte_order) {
+        bail!("{}: bits {}+{} don't fit in a {}-byte payload", signal.name, signal.start_bit, signal.bit_length, data.len());
+    }
+    Ok(())
+}
+
+/// Signals of `message` to encode for the given values: everything except
+/// multiplexed signals whose mux value isn't the one the selector is set to
+fn active_signals<'a>(message: &'a DbcMessage, values: &HashMap<String, f64>) -> Result<Vec<&'a DbcSignal>> {
+    let mux_value = message.signals.iter()
+        .find(|s| matches!(s.multiplexor, Some(Multiplexor::Signal)))
+        .and_then(|selector| values.get(&selector.name).map(|v| physical_to_raw(selector, *v)))
+        .transpose()?;
+
+    Ok(message.signals.iter()
+        .filter(|signal| match signal.multiplexor {
+            Some(Multiplexor::Value(v)) => mux_value == Some(v as u64),
+            _ => true,
+        })
+        .collect())
+}
+
+/// Build an 8-byte frame from physical signal values keyed by signal name
+///
+/// Signals without a value are left zero. Out-of-range values saturate to the
+/// signal's limits; use `try_encode_message` to reject them instead.
+pub fn encode_message(msg_def: &DbcMessage, values: &HashMap<String, f64>) -> [u8; 8] {
+    let mut buf = [0u8; 8];
+    let clamped: HashMap<String, f64> = msg_def.signals.iter()
+        .filter_map(|s| values.get(&s.name).map(|v| (s.name.clone(), saturate_physical(s, *v))))
+        .collect();
+    if let Ok(signals) = active_signals(msg_def, &clamped) {
+        for signal in signals {
+            if let Some(&value) = clamped.get(&signal.name) {
+                let _ = encode_signal(&mut buf, signal, value);
+            }
+        }
+    }
+    buf
+}
+
+/// Like `encode_message`, but fails on the first value that doesn't fit its signal
+pub fn try_encode_message(msg_def: &DbcMessage, values: &HashMap<String, f64>) -> Result<[u8; 8]> {
+    let mut buf = [0u8; 8];
+    for signal in active_signals(msg_def, values)? {
+        if let Some(&value) = values.get(&signal.name) {
+            encode_signal(&mut buf, signal, value)?;
+        }
+    }
+    Ok(buf)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::dbc::ByteOrder;
+    use crate::core::{CanMessage, DbcFile};
+    use crate::decode::SignalDecoder;
+
+    #[test]
+    fn test_encode_message_roundtrip_and_range() {
+        let mut msg = DbcMessage::new(0x123, "Test", 8);
+        let mut temp = DbcSignal::new("Temp", 0, 8);
+        temp.value_type = ValueType::Signed;
+        temp.factor = 0.5;
+        msg.add_signal(temp);
+        msg.add_signal(DbcSignal::new("Rpm", 8, 16));
+
+        let mut values = HashMap::new();
+        values.insert("Temp".to_string(), -20.5);
+        values.insert("Rpm".to_string(), 3000.0);
+        let data = try_encode_message(&msg, &values).unwrap();
+
+        let mut dbc = DbcFile::new();
+        dbc.add_message(msg.clone());
+        let mut decoder = SignalDecoder::new();
+        decoder.set_dbc(dbc);
+        let decoded = decoder.decode_message(&CanMessage::new(0, 0x123, data.to_vec().into()));
+        assert_eq!(decoded[0].physical_value, -20.5);
+        assert_eq!(decoded[1].physical_value, 3000.0);
+
+        // Signed 8-bit with factor 0.5 tops out at 63.5
+        values.insert("Temp".to_string(), 64.0);
+        let err = try_encode_message(&msg, &values).unwrap_err();
+        assert!(err.to_string().contains("out of range"));
+
+        // The infallible variant saturates instead
+        let data = encode_message(&msg, &values);
+        let decoded = decoder.decode_message(&CanMessage::new(0, 0x123, data.to_vec().into()));
+        assert_eq!(decoded[0].physical_value, 63.5);
+    }
+
+    #[test]
+    fn test_encode_roundtrip_motorola_and_signed() {
+        let mut msg = DbcMessage::new(0x200, "Mixed", 8);
+        let mut speed = DbcSignal::new("Speed", 15, 16);
+        speed.byte_order = ByteOrder::Motorola;
+        speed.factor = 0.01;
+        msg.add_signal(speed);
+        let mut torque = DbcSignal::new("Torque", 48, 12);
+        torque.value_type = ValueType::Signed;
+        torque.factor = 0.25;
+        torque.offset = -10.0;
+        msg.add_signal(torque);
+        let mut angle = DbcSignal::new("Angle", 39, 10);
+        angle.byte_order = ByteOrder::Motorola;
+        angle.value_type = ValueType::Signed;
+        angle.factor = 0.1;
+        msg.add_signal(angle);
+
+        let mut values = HashMap::new();
+        values.insert("Speed".to_string(), 123.45);
+        values.insert("Torque".to_string(), -135.75);
+        values.insert("Angle".to_string(), -42.3);
+        let data = encode_message(&msg, &values);
+
+        let mut dbc = DbcFile::new();
+        dbc.add_message(msg.clone());
+        let mut decoder = SignalDecoder::new();
+        decoder.set_dbc(dbc);
+        for decoded in decoder.decode_message(&CanMessage::new(0, 0x200, data.to_vec().into())) {
+            let expected = values[&decoded.name];
+            assert!((decoded.physical_value - expected).abs() < 1e-6,
+                "{}: {} != {}", decoded.name, decoded.physical_value, expected);
+        }
+    }
+}
@@ -0,0 +1,488 @@
+//! Virtual signals: arithmetic over decoded signals, e.g. `Power = Voltage * Current`.
+//!
+//! Expressions take `+ - * / ^`, parentheses, numbers and the functions `abs`,
+//! `sqrt`, `min` and `max`. Variables are DBC signal names, read on the virtual
+//! signal's bus; a name several messages carry must be written `Temperature@0x100`.
+//! Over a log, a virtual signal has a sample wherever any of its inputs does,
+//! computed from each input's sample nearest that time; live, from each input's
+//! latest sample.
+//!
+//! The parser is a small recursive descent one rather than an expression crate
+//! (evalexpr, meval): the grammar is a handful of rules, and the build has no
+//! network access to fetch new dependencies.
+
+use crate::core::{CanMessage, DbcFile};
+use crate::decode::router::{parse_signal_key, signal_key, SignalRouter};
+use crate::decode::SignalDecoder;
+use anyhow::{bail, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Message ID of virtual signals' chart keys: above the 29-bit range, so no frame has it
+pub const VIRTUAL_MSG_ID: u32 = u32::MAX;
+
+/// A user-defined signal, persisted with the app settings
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct VirtualSignal {
+    pub name: String,
+    pub expression: String,
+    /// Bus the inputs are read from
+    #[serde(default)]
+    pub bus: u8,
+}
+
+impl VirtualSignal {
+    /// Chart key of this signal
+    pub fn key(&self) -> String {
+        signal_key(&self.name, VIRTUAL_MSG_ID, self.bus)
+    }
+}
+
+/// Chart key of each formula input on `bus`. A bare name must belong to exactly
+/// one DBC message; a `name@0xID` one must be on that message.
+fn input_keys(formula: &Formula, dbc: &DbcFile, bus: u8) -> Result<Vec<String>> {
+    formula.inputs().iter().map(|input| {
+        let (name, msg_id, _) = parse_signal_key(input);
+        let owners: Vec<u32> = dbc.messages.iter()
+            .filter(|m| msg_id.is_none_or(|id| m.id == id) && m.get_signal(name).is_some())
+            .map(|m| m.id)
+            .collect();
+        match owners.as_slice() {
+            [id] => Ok(signal_key(name, *id, bus)),
+            [] => match msg_id {
+                Some(id) => bail!("0x{:X} has no signal {}", id, name),
+                None => bail!("{} is not in the DBC", name),
+            },
+            ids => {
+                let ids: Vec<String> = ids.iter().map(|id| format!("0x{:X}", id)).collect();
+                bail!("{} is on several messages ({}), write it as {}@0xID", name, ids.join(", "), name)
+            }
+        }
+    }).collect()
+}
+
+/// A virtual signal being evaluated, fed one frame at a time (so a long log can
+/// be spread over several UI frames, and live frames can be charted as they come)
+pub struct VirtualEval {
+    formula: Formula,
+    /// Chart key of each input, in `Formula::inputs` order
+    keys: Vec<String>,
+    router: SignalRouter,
+    bus: u8,
+    /// Every sample of each input fed so far, for `finish`
+    samples: Vec<Vec<(f64, DateTime<Utc>)>>,
+    /// Latest value of each input, for `feed_live`
+    latest: Vec<Option<f64>>,
+}
+
+impl VirtualEval {
+    pub fn new(signal: &VirtualSignal, dbc: &DbcFile) -> Result<Self> {
+        let formula = Formula::parse(&signal.expression)?;
+        let keys = input_keys(&formula, dbc, signal.bus)?;
+        let router = SignalRouter::new(dbc, keys.iter().map(String::as_str));
+        let inputs = keys.len();
+        Ok(Self { formula, keys, router, bus: signal.bus, samples: vec![Vec::new(); inputs], latest: vec![None; inputs] })
+    }
+
+    /// Record the input samples `msg` carries (frames in time order)
+    pub fn feed(&mut self, decoder: &SignalDecoder, msg: &CanMessage) {
+        let (keys, samples) = (&self.keys, &mut self.samples);
+        self.router.route(decoder, msg, |key, value| {
+            if let Some(idx) = keys.iter().position(|k| k == key) {
+                samples[idx].push((value, msg.timestamp));
+            }
+        });
+    }
+
+    /// Evaluate at every time any input was fed, from each input's nearest sample
+    pub fn finish(self) -> Result<Vec<(f64, DateTime<Utc>)>> {
+        if let Some(idx) = self.samples.iter().position(Vec::is_empty) {
+            bail!("{} has no samples on bus {}", self.formula.inputs()[idx], self.bus);
+        }
+
+        let mut times: Vec<DateTime<Utc>> = self.samples.iter().flatten().map(|(_, ts)| *ts).collect();
+        times.sort();
+        times.dedup();
+        let mut values = vec![0.0; self.keys.len()];
+        Ok(times.into_iter()
+            .filter_map(|t| {
+                for (value, input) in values.iter_mut().zip(&self.samples) {
+                    *value = nearest_value(input, t);
+                }
+                let result = self.formula.eval(&values);
+                result.is_finite().then_some((result, t))
+            })
+            .collect())
+    }
+
+    /// Take a live frame's inputs; when it carried one and every input has been
+    /// seen, the value from each input's latest sample
+    pub fn feed_live(&mut self, decoder: &SignalDecoder, msg: &CanMessage) -> Option<f64> {
+        let (keys, latest) = (&self.keys, &mut self.latest);
+        let mut updated = false;
+        self.router.route(decoder, msg, |key, value| {
+            if let Some(idx) = keys.iter().position(|k| k == key) {
+                latest[idx] = Some(value);
+                updated = true;
+            }
+        });
+        if !updated {
+            return None;
+        }
+        let values: Option<Vec<f64>> = self.latest.iter().copied().collect();
+        let result = self.formula.eval(&values?);
+        result.is_finite().then_some(result)
+    }
+}
+
+/// Value of the sample closest in time to `t` (`samples` is time-sorted and not empty)
+fn nearest_value(samples: &[(f64, DateTime<Utc>)], t: DateTime<Utc>) -> f64 {
+    let idx = samples.partition_point(|(_, ts)| *ts < t);
+    let before = idx.checked_sub(1).map(|i| samples[i]);
+    let after = samples.get(idx).copied();
+    match (before, after) {
+        (Some(b), Some(a)) if (a.1 - t) < (t - b.1) => a.0,
+        (Some(b), _) => b.0,
+        (None, Some(a)) => a.0,
+        (None, None) => f64::NAN,
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Op {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Pow,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Func {
+    Abs,
+    Sqrt,
+    Min,
+    Max,
+}
+
+impl Func {
+    fn from_name(name: &str) -> Option<(Self, usize)> {
+        match name {
+            "abs" => Some((Func::Abs, 1)),
+            "sqrt" => Some((Func::Sqrt, 1)),
+            "min" => Some((Func::Min, 2)),
+            "max" => Some((Func::Max, 2)),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Expr {
+    Num(f64),
+    /// Index into `Formula::inputs`
+    Var(usize),
+    Neg(Box<Expr>),
+    Bin(Op, Box<Expr>, Box<Expr>),
+    Call(Func, Vec<Expr>),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Num(f64),
+    Ident(String),
+    Op(char),
+    Open,
+    Close,
+    Comma,
+}
+
+/// A parsed expression
+#[derive(Debug)]
+pub struct Formula {
+    expr: Expr,
+    inputs: Vec<String>,
+}
+
+impl Formula {
+    pub fn parse(text: &str) -> Result<Self> {
+        let tokens = tokenize(text)?;
+        let mut parser = Parser { tokens: &tokens, pos: 0, inputs: Vec::new() };
+        let expr = parser.expr()?;
+        if let Some(token) = parser.tokens.get(parser.pos) {
+            bail!("Unexpected {:?}", token);
+        }
+        Ok(Self { expr, inputs: parser.inputs })
+    }
+
+    /// Signal names the expression reads, in order of first use
+    pub fn inputs(&self) -> &[String] {
+        &self.inputs
+    }
+
+    /// Evaluate with `values[i]` for `inputs()[i]`
+    pub fn eval(&self, values: &[f64]) -> f64 {
+        eval(&self.expr, values)
+    }
+}
+
+fn eval(expr: &Expr, values: &[f64]) -> f64 {
+    match expr {
+        Expr::Num(n) => *n,
+        Expr::Var(idx) => values.get(*idx).copied().unwrap_or(f64::NAN),
+        Expr::Neg(inner) => -eval(inner, values),
+        Expr::Bin(op, lhs, rhs) => {
+            let (a, b) = (eval(lhs, values), eval(rhs, values));
+            match op {
+                Op::Add => a + b,
+                Op::Sub => a - b,
+                Op::Mul => a * b,
+                Op::Div => a / b,
+                Op::Pow => a.powf(b),
+            }
+        }
+        Expr::Call(func, args) => {
+            let arg = |i: usize| eval(&args[i], values);
+            match func {
+                Func::Abs => arg(0).abs(),
+                Func::Sqrt => arg(0).sqrt(),
+                Func::Min => arg(0).min(arg(1)),
+                Func::Max => arg(0).max(arg(1)),
+            }
+        }
+    }
+}
+
+fn tokenize(text: &str) -> Result<Vec<Token>> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let start = i;
+        i += 1;
+        match c {
+            c if c.is_whitespace() => {}
+            '+' | '-' | '*' | '/' | '^' => tokens.push(Token::Op(c)),
+            '(' => tokens.push(Token::Open),
+            ')' => tokens.push(Token::Close),
+            ',' => tokens.push(Token::Comma),
+            c if c.is_ascii_digit() || c == '.' => {
+                while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                    i += 1;
+                }
+                let number: String = chars[start..i].iter().collect();
+                match number.parse() {
+                    Ok(n) => tokens.push(Token::Num(n)),
+                    Err(_) => bail!("Bad number {}", number),
+                }
+            }
+            // Signal names, optionally qualified with a message ID: `Temperature@0x100`
+            c if c.is_alphabetic() || c == '_' => {
+                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '@') {
+                    i += 1;
+                }
+                tokens.push(Token::Ident(chars[start..i].iter().collect()));
+            }
+            _ => bail!("Unexpected '{}'", c),
+        }
+    }
+    Ok(tokens)
+}
+
+/// Recursive descent, loosest binding first: `+ -`, then `* /`, unary minus, then right-associative `^`
+struct Parser<'a> {
+    tokens: &'a [Token],
+    pos: usize,
+    inputs: Vec<String>,
+}
+
+impl Parser<'_> {
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn peek_op(&self, ops: &[char]) -> Option<char> {
+        match self.tokens.get(self.pos) {
+            Some(Token::Op(c)) if ops.contains(c) => Some(*c),
+            _ => None,
+        }
+    }
+
+    fn expr(&mut self) -> Result<Expr> {
+        let mut lhs = self.term()?;
+        while let Some(c) = self.peek_op(&['+', '-']) {
+            self.pos += 1;
+            let op = if c == '+' { Op::Add } else { Op::Sub };
+            lhs = Expr::Bin(op, Box::new(lhs), Box::new(self.term()?));
+        }
+        Ok(lhs)
+    }
+
+    fn term(&mut self) -> Result<Expr> {
+        let mut lhs = self.unary()?;
+        while let Some(c) = self.peek_op(&['*', '/']) {
+            self.pos += 1;
+            let op = if c == '*' { Op::Mul } else { Op::Div };
+            lhs = Expr::Bin(op, Box::new(lhs), Box::new(self.unary()?));
+        }
+        Ok(lhs)
+    }
+
+    fn unary(&mut self) -> Result<Expr> {
+        if self.peek_op(&['-']).is_some() {
+            self.pos += 1;
+            return Ok(Expr::Neg(Box::new(self.unary()?)));
+        }
+        let base = self.atom()?;
+        if self.peek_op(&['^']).is_some() {
+            self.pos += 1;
+            return Ok(Expr::Bin(Op::Pow, Box::new(base), Box::new(self.unary()?)));
+        }
+        Ok(base)
+    }
+
+    fn atom(&mut self) -> Result<Expr> {
+        match self.next() {
+            Some(Token::Num(n)) => Ok(Expr::Num(n)),
+            Some(Token::Open) => {
+                let inner = self.expr()?;
+                match self.next() {
+                    Some(Token::Close) => Ok(inner),
+                    _ => bail!("Missing ')'"),
+                }
+            }
+            Some(Token::Ident(name)) if self.tokens.get(self.pos) == Some(&Token::Open) => {
+                let Some((func, arity)) = Func::from_name(&name) else {
+                    bail!("Unknown function {}", name);
+                };
+                self.pos += 1;
+                let mut args = vec![self.expr()?];
+                while self.tokens.get(self.pos) == Some(&Token::Comma) {
+                    self.pos += 1;
+                    args.push(self.expr()?);
+                }
+                if self.next() != Some(Token::Close) {
+                    bail!("Missing ')' after {} arguments", name);
+                }
+                if args.len() != arity {
+                    bail!("{} takes {} argument{}", name, arity, if arity == 1 { "" } else { "s" });
+                }
+                Ok(Expr::Call(func, args))
+            }
+            Some(Token::Ident(name)) => {
+                let idx = match self.inputs.iter().position(|n| *n == name) {
+                    Some(idx) => idx,
+                    None => {
+                        self.inputs.push(name);
+                        self.inputs.len() - 1
+                    }
+                };
+                Ok(Expr::Var(idx))
+            }
+            Some(token) => bail!("Unexpected {:?}", token),
+            None => bail!("Expression ends early"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::dbc::{DbcMessage, DbcSignal};
+    use chrono::Duration;
+
+    /// Evaluate over a whole log in one go
+    fn compute(signal: &VirtualSignal, messages: &[CanMessage], decoder: &SignalDecoder, dbc: &DbcFile) -> Result<Vec<(f64, DateTime<Utc>)>> {
+        let mut eval = VirtualEval::new(signal, dbc)?;
+        for msg in messages {
+            eval.feed(decoder, msg);
+        }
+        eval.finish()
+    }
+
+    #[test]
+    fn test_formula_parse_and_eval() {
+        let formula = Formula::parse("Voltage * Current - 2 ^ 3 ^ 0 / (1 + 1)").unwrap();
+        assert_eq!(formula.inputs(), ["Voltage", "Current"]);
+        assert_eq!(formula.eval(&[12.0, 2.5]), 29.0);
+        assert_eq!(Formula::parse("-abs(x) + max(x, 3) + sqrt(16)").unwrap().eval(&[-5.0]), 2.0);
+        assert_eq!(Formula::parse("Temp@0x100 - Temp@0x200").unwrap().inputs(), ["Temp@0x100", "Temp@0x200"]);
+
+        for bad in ["", "1 +", "(1", "max(1)", "foo(1)", "1 $ 2", "1 2"] {
+            assert!(Formula::parse(bad).is_err(), "{:?} should not parse", bad);
+        }
+    }
+
+    #[test]
+    fn test_virtual_signal_pairs_nearest_samples() {
+        let mut battery = DbcMessage::new(0x100, "Battery", 8);
+        battery.add_signal(DbcSignal::new("Voltage", 0, 8));
+        let mut motor = DbcMessage::new(0x200, "Motor", 8);
+        motor.add_signal(DbcSignal::new("Current", 0, 8));
+        let mut dbc = DbcFile::new();
+        dbc.add_message(battery);
+        dbc.add_message(motor);
+        let mut decoder = SignalDecoder::new();
+        decoder.set_dbc(dbc.clone());
+
+        let base = Utc::now();
+        let frame = |bus: u8, id: u32, byte: u8, ms: i64| {
+            let mut msg = CanMessage::new(bus, id, vec![byte].into());
+            msg.timestamp = base + Duration::milliseconds(ms);
+            msg
+        };
+        let messages = vec![
+            frame(0, 0x100, 12, 0),
+            frame(0, 0x200, 2, 10),
+            frame(1, 0x200, 100, 20),  // other bus: ignored
+            frame(0, 0x100, 14, 100),
+        ];
+        let power = VirtualSignal { name: "Power".to_string(), expression: "Voltage * Current".to_string(), bus: 0 };
+        let points = compute(&power, &messages, &decoder, &dbc).unwrap();
+        assert_eq!(points, vec![
+            (24.0, base),
+            (24.0, base + Duration::milliseconds(10)),
+            (28.0, base + Duration::milliseconds(100)),
+        ]);
+        assert_eq!(power.key(), "Power@0xFFFFFFFF@bus0");
+
+        let missing = VirtualSignal { bus: 2, ..power };
+        assert!(compute(&missing, &messages, &decoder, &dbc).is_err());
+    }
+
+    #[test]
+    fn test_virtual_signal_inputs_must_be_unambiguous() {
+        let mut front = DbcMessage::new(0x100, "Front", 8);
+        front.add_signal(DbcSignal::new("Temp", 0, 8));
+        let mut rear = DbcMessage::new(0x200, "Rear", 8);
+        rear.add_signal(DbcSignal::new("Temp", 0, 8));
+        let mut dbc = DbcFile::new();
+        dbc.add_message(front);
+        dbc.add_message(rear);
+        let mut decoder = SignalDecoder::new();
+        decoder.set_dbc(dbc.clone());
+        let signal = |expression: &str| VirtualSignal { name: "Delta".to_string(), expression: expression.to_string(), bus: 0 };
+
+        let err = VirtualEval::new(&signal("Temp * 2"), &dbc).err().unwrap().to_string();
+        assert!(err.contains("several messages"), "{}", err);
+        assert!(VirtualEval::new(&signal("Temp@0x300"), &dbc).is_err());
+        assert!(VirtualEval::new(&signal("Speed"), &dbc).is_err());
+
+        let messages = vec![
+            CanMessage::new(0, 0x100, vec![30].into()),
+            CanMessage::new(0, 0x200, vec![20].into()),
+        ];
+        let delta = signal("Temp@0x100 - Temp@0x200");
+        assert_eq!(compute(&delta, &messages, &decoder, &dbc).unwrap().iter().map(|p| p.0).collect::<Vec<_>>(), vec![10.0, 10.0]);
+
+        // Live: nothing until both inputs have a sample, then each frame re-evaluates
+        let mut live = VirtualEval::new(&delta, &dbc).unwrap();
+        assert_eq!(live.feed_live(&decoder, &messages[0]), None);
+        assert_eq!(live.feed_live(&decoder, &messages[1]), Some(10.0));
+        assert_eq!(live.feed_live(&decoder, &CanMessage::new(0, 0x100, vec![35].into())), Some(15.0));
+        assert_eq!(live.feed_live(&decoder, &CanMessage::new(0, 0x300, vec![0].into())), None);
+    }
+}
@@ -0,0 +1,131 @@
+//! Missing-frame detection: periodic messages that stop arriving for a while.
+//!
+//! Each (ID, bus) is expected every DBC `GenMsgCycleTime` when the DBC has one,
+//! otherwise every median observed interval. A silence longer than `multiplier`
+//! expected periods is a gap; one running to the end of the log means the
+//! message stopped altogether.
+
+use crate::core::dbc::DbcFile;
+use crate::core::CanMessage;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+
+/// Default silence, in expected periods, before a message counts as missing
+pub const DEFAULT_GAP_MULTIPLIER: f64 = 3.0;
+
+/// Without a DBC cycle time, an ID needs this many frames for its median interval to mean anything
+const MIN_FRAMES_FOR_OBSERVED_CYCLE: usize = 5;
+
+/// A time range in which an expected message was absent
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrameGap {
+    pub id: u32,
+    pub bus: u8,
+    /// Last frame before the gap
+    pub start: DateTime<Utc>,
+    /// First frame after the gap, or the end of the log
+    pub end: DateTime<Utc>,
+    /// Period the message was expected at
+    pub expected_ms: f64,
+    /// No frame came after the gap: the message stopped for good
+    pub to_end_of_log: bool,
+}
+
+impl FrameGap {
+    pub fn duration_ms(&self) -> f64 {
+        (self.end - self.start).num_microseconds().unwrap_or(0) as f64 / 1000.0
+    }
+
+    /// Frames that should have arrived during the gap
+    pub fn missed_frames(&self) -> usize {
+        ((self.duration_ms() / self.expected_ms).round() as usize).saturating_sub(1)
+    }
+}
+
+fn median_interval_ms(times: &[DateTime<Utc>]) -> Option<f64> {
+    if times.len() < MIN_FRAMES_FOR_OBSERVED_CYCLE {
+        return None;
+    }
+    let mut intervals: Vec<f64> = times.windows(2)
+        .map(|w| (w[1] - w[0]).num_microseconds().unwrap_or(0) as f64 / 1000.0)
+        .collect();
+    intervals.sort_by(|a, b| a.total_cmp(b));
+    Some(intervals[intervals.len() / 2])
+}
+
+/// Find gaps longer than `multiplier` expected periods in a time-sorted log, sorted by start time
+pub fn find_gaps(messages: &[CanMessage], dbc: Option<&DbcFile>, multiplier: f64) -> Vec<FrameGap> {
+    let Some(log_end) = messages.last().map(|m| m.timestamp) else {
+        return Vec::new();
+    };
+
+    let mut by_key: HashMap<(u32, u8), Vec<DateTime<Utc>>> = HashMap::new();
+    for msg in messages.iter().filter(|m| !m.is_error) {
+        by_key.entry((msg.id, msg.bus)).or_default().push(msg.timestamp);
+    }
+
+    let mut gaps = Vec::new();
+    for ((id, bus), times) in by_key {
+        let declared = dbc
+            .and_then(|dbc| dbc.get_message(id))
+            .and_then(|m| m.cycle_time_ms)
+            .filter(|&ms| ms > 0)
+            .map(|ms| ms as f64);
+        let Some(expected_ms) = declared.or_else(|| median_interval_ms(&times)).filter(|&ms| ms > 0.0) else {
+            continue;
+        };
+        let limit_ms = expected_ms * multiplier;
+
+        let last = *times.last().unwrap();
+        let following = times.iter().skip(1).copied().chain(std::iter::once(log_end));
+        for (start, end) in times.iter().copied().zip(following) {
+            let silence_ms = (end - start).num_microseconds().unwrap_or(0) as f64 / 1000.0;
+            if silence_ms > limit_ms {
+                gaps.push(FrameGap { id, bus, start, end, expected_ms, to_end_of_log: start == last });
+            }
+        }
+    }
+    gaps.sort_by(|a, b| a.start.cmp(&b.start).then(a.id.cmp(&b.id)));
+    gaps
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::dbc::DbcMessage;
+    use chrono::Duration;
+
+    #[test]
+    fn test_find_gaps() {
+        let base = Utc::now();
+        let frame = |id: u32, ms: i64| {
+            let mut msg = CanMessage::new(0, id, vec![0u8].into());
+            msg.timestamp = base + Duration::milliseconds(ms);
+            msg
+        };
+        // 0x100 every 10 ms with a 100 ms hole at 200-300; 0x200 (DBC: 20 ms) stops at 500 ms
+        let mut messages: Vec<CanMessage> = (0..=100)
+            .map(|i| i * 10)
+            .filter(|ms| !(210..300).contains(ms))
+            .map(|ms| frame(0x100, ms))
+            .chain((0..=25).map(|i| frame(0x200, i * 20)))
+            .collect();
+        messages.sort_by_key(|m| m.timestamp);
+
+        let mut dbc = DbcFile::new();
+        let mut def = DbcMessage::new(0x200, "Heartbeat", 8);
+        def.cycle_time_ms = Some(20);
+        dbc.add_message(def);
+
+        let gaps = find_gaps(&messages, Some(&dbc), DEFAULT_GAP_MULTIPLIER);
+        assert_eq!(gaps.len(), 2);
+        assert_eq!((gaps[0].id, gaps[0].duration_ms(), gaps[0].expected_ms), (0x100, 100.0, 10.0));
+        assert_eq!(gaps[0].missed_frames(), 9);
+        assert!(!gaps[0].to_end_of_log);
+        assert_eq!((gaps[1].id, gaps[1].duration_ms()), (0x200, 500.0));
+        assert!(gaps[1].to_end_of_log);
+
+        // A looser multiplier tolerates the 0x100 hole
+        assert_eq!(find_gaps(&messages, Some(&dbc), 20.0).len(), 1);
+    }
+}
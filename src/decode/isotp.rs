@@ -0,0 +1,403 @@
+//! ISO-TP (ISO 15765-2) reassembly of diagnostic traffic on a request/response ID pair.
+//!
+//! Single, first and consecutive frames are stitched back into PDUs, one
+//! reassembler per direction; flow control frames are reported as they are
+//! (block size and separation time are shown, not enforced). Reassembly is
+//! read-only: nothing is ever transmitted.
+
+use crate::core::CanMessage;
+use chrono::{DateTime, Utc};
+
+/// Common 11-bit diagnostic pair (engine ECU physical request/response)
+pub const DEFAULT_REQUEST_ID: u32 = 0x7E0;
+pub const DEFAULT_RESPONSE_ID: u32 = 0x7E8;
+
+/// Which side of the pair a frame was sent on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IsotpDirection {
+    Request,
+    Response,
+}
+
+impl IsotpDirection {
+    pub fn label(self) -> &'static str {
+        match self {
+            IsotpDirection::Request => "Req",
+            IsotpDirection::Response => "Resp",
+        }
+    }
+}
+
+/// Why a PDU was given up before all its bytes arrived
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IsotpError {
+    /// Consecutive frame with the wrong sequence number
+    Sequence { expected: u8, got: u8 },
+    /// A new single or first frame started before the last consecutive frame
+    Interrupted,
+    /// The log ended mid-PDU
+    Truncated,
+}
+
+impl IsotpError {
+    pub fn label(self) -> String {
+        match self {
+            IsotpError::Sequence { expected, got } => format!("sequence error (expected {:X}, got {:X})", expected, got),
+            IsotpError::Interrupted => "interrupted".to_string(),
+            IsotpError::Truncated => "truncated".to_string(),
+        }
+    }
+}
+
+/// Flow status of a flow control frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlowStatus {
+    ContinueToSend,
+    Wait,
+    Overflow,
+    Reserved(u8),
+}
+
+impl FlowStatus {
+    fn from_nibble(nibble: u8) -> Self {
+        match nibble {
+            0 => FlowStatus::ContinueToSend,
+            1 => FlowStatus::Wait,
+            2 => FlowStatus::Overflow,
+            n => FlowStatus::Reserved(n),
+        }
+    }
+
+    pub fn label(self) -> String {
+        match self {
+            FlowStatus::ContinueToSend => "CTS".to_string(),
+            FlowStatus::Wait => "Wait".to_string(),
+            FlowStatus::Overflow => "Overflow".to_string(),
+            FlowStatus::Reserved(n) => format!("reserved ({})", n),
+        }
+    }
+}
+
+/// A reassembled (or abandoned) payload
+#[derive(Debug, Clone, PartialEq)]
+pub struct IsotpPdu {
+    /// Time of the single or first frame
+    pub timestamp: DateTime<Utc>,
+    pub direction: IsotpDirection,
+    pub data: Vec<u8>,
+    /// Length announced by the first frame (== `data.len()` when complete)
+    pub expected_len: usize,
+    /// CAN frames the PDU was carried in
+    pub frames: usize,
+    /// None for a complete PDU
+    pub error: Option<IsotpError>,
+}
+
+/// A flow control frame, as sent by the receiving side
+#[derive(Debug, Clone, PartialEq)]
+pub struct IsotpFlowControl {
+    pub timestamp: DateTime<Utc>,
+    pub direction: IsotpDirection,
+    pub status: FlowStatus,
+    /// Consecutive frames allowed before the next flow control (0 = no limit)
+    pub block_size: u8,
+    /// Minimum separation time between consecutive frames, in microseconds
+    pub st_min_us: u32,
+}
+
+/// One row of the reassembly view
+#[derive(Debug, Clone, PartialEq)]
+pub enum IsotpEvent {
+    Pdu(IsotpPdu),
+    FlowControl(IsotpFlowControl),
+}
+
+impl IsotpEvent {
+    pub fn timestamp(&self) -> DateTime<Utc> {
+        match self {
+            IsotpEvent::Pdu(pdu) => pdu.timestamp,
+            IsotpEvent::FlowControl(fc) => fc.timestamp,
+        }
+    }
+}
+
+/// STmin byte to microseconds: 0x00-0x7F are milliseconds, 0xF1-0xF9 are 100-900 µs, the rest reserved (treated as the 127 ms maximum)
+fn st_min_us(byte: u8) -> u32 {
+    match byte {
+        0x00..=0x7F => byte as u32 * 1000,
+        0xF1..=0xF9 => (byte - 0xF0) as u32 * 100,
+        _ => 127_000,
+    }
+}
+
+/// Multi-frame PDU being collected
+struct Pending {
+    timestamp: DateTime<Utc>,
+    data: Vec<u8>,
+    expected_len: usize,
+    frames: usize,
+    next_seq: u8,
+}
+
+impl Pending {
+    /// The transfer so far, as a PDU cut off by the end of the log
+    fn snapshot(&self, direction: IsotpDirection) -> IsotpEvent {
+        IsotpEvent::Pdu(IsotpPdu {
+            timestamp: self.timestamp,
+            direction,
+            data: self.data.clone(),
+            expected_len: self.expected_len,
+            frames: self.frames,
+            error: Some(IsotpError::Truncated),
+        })
+    }
+
+    fn abandon(self, direction: IsotpDirection, error: IsotpError) -> IsotpEvent {
+        IsotpEvent::Pdu(IsotpPdu {
+            timestamp: self.timestamp,
+            direction,
+            data: self.data,
+            expected_len: self.expected_len,
+            frames: self.frames,
+            error: Some(error),
+        })
+    }
+}
+
+/// Reassembly state of one direction
+struct Reassembler {
+    direction: IsotpDirection,
+    pending: Option<Pending>,
+}
+
+impl Reassembler {
+    fn new(direction: IsotpDirection) -> Self {
+        Self { direction, pending: None }
+    }
+
+    fn interrupt(&mut self, out: &mut Vec<IsotpEvent>) {
+        if let Some(pending) = self.pending.take() {
+            out.push(pending.abandon(self.direction, IsotpError::Interrupted));
+        }
+    }
+
+    fn feed(&mut self, timestamp: DateTime<Utc>, data: &[u8], out: &mut Vec<IsotpEvent>) {
+        let Some(&pci) = data.first() else {
+            return;
+        };
+        match pci >> 4 {
+            0 => {
+                // Single frame (classic CAN: the length nibble is 1-7)
+                let len = (pci & 0x0F) as usize;
+                if len == 0 || 1 + len > data.len() {
+                    return;
+                }
+                self.interrupt(out);
+                out.push(IsotpEvent::Pdu(IsotpPdu {
+                    timestamp,
+                    direction: self.direction,
+                    data: data[1..1 + len].to_vec(),
+                    expected_len: len,
+                    frames: 1,
+                    error: None,
+                }));
+            }
+            1 => {
+                // First frame: 12-bit length, or 32-bit after a zero escape for PDUs over 4095 bytes
+                if data.len() < 2 {
+                    return;
+                }
+                let (len, offset) = match ((pci as usize & 0x0F) << 8) | data[1] as usize {
+                    0 if data.len() >= 6 => (u32::from_be_bytes([data[2], data[3], data[4], data[5]]) as usize, 6),
+                    0 => return,
+                    n => (n, 2),
+                };
+                self.interrupt(out);
+                let mut payload = data[offset..].to_vec();
+                payload.truncate(len);
+                self.pending = Some(Pending { timestamp, data: payload, expected_len: len, frames: 1, next_seq: 1 });
+                self.finish_if_complete(out);
+            }
+            2 => {
+                // Consecutive frames outside a transfer are padding or retransmissions we never saw start
+                let Some(pending) = self.pending.as_mut() else {
+                    return;
+                };
+                let seq = pci & 0x0F;
+                if seq != pending.next_seq {
+                    let expected = pending.next_seq;
+                    let pending = self.pending.take().unwrap();
+                    out.push(pending.abandon(self.direction, IsotpError::Sequence { expected, got: seq }));
+                    return;
+                }
+                let remaining = pending.expected_len - pending.data.len();
+                pending.data.extend(data[1..].iter().take(remaining));
+                pending.frames += 1;
+                pending.next_seq = (seq + 1) & 0x0F;
+                self.finish_if_complete(out);
+            }
+            3 => {
+                if data.len() < 3 {
+                    return;
+                }
+                out.push(IsotpEvent::FlowControl(IsotpFlowControl {
+                    timestamp,
+                    direction: self.direction,
+                    status: FlowStatus::from_nibble(pci & 0x0F),
+                    block_size: data[1],
+                    st_min_us: st_min_us(data[2]),
+                }));
+            }
+            _ => {}
+        }
+    }
+
+    fn finish_if_complete(&mut self, out: &mut Vec<IsotpEvent>) {
+        if self.pending.as_ref().is_some_and(|p| p.data.len() >= p.expected_len) {
+            let pending = self.pending.take().unwrap();
+            out.push(IsotpEvent::Pdu(IsotpPdu {
+                timestamp: pending.timestamp,
+                direction: self.direction,
+                data: pending.data,
+                expected_len: pending.expected_len,
+                frames: pending.frames,
+                error: None,
+            }));
+        }
+    }
+}
+
+/// Reassembly of the ISO-TP traffic between a request and response ID (any bus).
+/// Fed a growing log, it only looks at the frames added since the last call.
+pub struct IsotpStream {
+    request_id: u32,
+    response_id: u32,
+    request: Reassembler,
+    response: Reassembler,
+    /// Finished events, sorted by start time
+    events: Vec<IsotpEvent>,
+    /// Messages of the log fed so far
+    fed: usize,
+}
+
+impl IsotpStream {
+    pub fn new(request_id: u32, response_id: u32) -> Self {
+        Self {
+            request_id,
+            response_id,
+            request: Reassembler::new(IsotpDirection::Request),
+            response: Reassembler::new(IsotpDirection::Response),
+            events: Vec::new(),
+            fed: 0,
+        }
+    }
+
+    pub fn ids(&self) -> (u32, u32) {
+        (self.request_id, self.response_id)
+    }
+
+    /// Messages of the log fed so far
+    pub fn fed(&self) -> usize {
+        self.fed
+    }
+
+    /// Feed `messages[self.fed()..]`; `messages` must be the log fed before, grown at the end
+    pub fn feed(&mut self, messages: &[CanMessage]) {
+        let mut finished = Vec::new();
+        for msg in &messages[self.fed.min(messages.len())..] {
+            if msg.is_rtr || msg.is_error {
+                continue;
+            }
+            if msg.id == self.request_id {
+                self.request.feed(msg.timestamp, &msg.data, &mut finished);
+            } else if msg.id == self.response_id {
+                self.response.feed(msg.timestamp, &msg.data, &mut finished);
+            }
+        }
+        self.fed = messages.len();
+        // PDUs are emitted when they finish, so an interleaved flow control can come out ahead of the PDU it paced
+        for event in finished {
+            let at = self.events.partition_point(|e| e.timestamp() <= event.timestamp());
+            self.events.insert(at, event);
+        }
+    }
+
+    /// Finished events, sorted by start time
+    pub fn events(&self) -> &[IsotpEvent] {
+        &self.events
+    }
+
+    /// Transfers still being collected, reported as truncated PDUs
+    pub fn pending(&self) -> Vec<IsotpEvent> {
+        [&self.request, &self.response]
+            .into_iter()
+            .filter_map(|r| r.pending.as_ref().map(|p| p.snapshot(r.direction)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_reassemble_multi_frame_exchange() {
+        let base = Utc::now();
+        let frame = |id: u32, ms: i64, data: &[u8]| {
+            let mut msg = CanMessage::new(0, id, data.to_vec().into());
+            msg.timestamp = base + Duration::milliseconds(ms);
+            msg
+        };
+        let messages = vec![
+            // ReadDataByIdentifier F190 (VIN) as a single frame, padded
+            frame(0x7E0, 0, &[0x03, 0x22, 0xF1, 0x90, 0xAA, 0xAA, 0xAA, 0xAA]),
+            frame(0x123, 1, &[0x10, 0x00]),  // unrelated ID
+            // 19-byte response: first frame + 2 consecutive frames, paced by flow control
+            frame(0x7E8, 2, &[0x10, 0x13, 0x62, 0xF1, 0x90, 0x57, 0x30, 0x4C]),
+            frame(0x7E0, 3, &[0x30, 0x00, 0xF5, 0x00, 0x00, 0x00, 0x00, 0x00]),
+            frame(0x7E8, 4, &[0x21, 0x30, 0x30, 0x30, 0x30, 0x31, 0x32, 0x33]),
+            frame(0x7E8, 5, &[0x22, 0x34, 0x35, 0x36, 0x37, 0x38, 0x39, 0xAA]),
+            // A skipped sequence number abandons the next transfer
+            frame(0x7E8, 6, &[0x10, 0x0A, 1, 2, 3, 4, 5, 6]),
+            frame(0x7E8, 7, &[0x23, 7, 8, 9, 10]),
+            // And the log ends mid-request
+            frame(0x7E0, 8, &[0x10, 0x09, 1, 2, 3, 4, 5, 6]),
+        ];
+
+        let mut stream = IsotpStream::new(DEFAULT_REQUEST_ID, DEFAULT_RESPONSE_ID);
+        stream.feed(&messages);
+        let events = stream.events();
+        assert_eq!(events.len(), 4);
+
+        let IsotpEvent::Pdu(request) = &events[0] else { panic!("expected a PDU") };
+        assert_eq!((request.direction, request.data.as_slice(), request.frames), (IsotpDirection::Request, &[0x22, 0xF1, 0x90][..], 1));
+
+        let IsotpEvent::Pdu(response) = &events[1] else { panic!("expected a PDU") };
+        assert_eq!(response.direction, IsotpDirection::Response);
+        assert_eq!(response.data.len(), 19);
+        assert_eq!(&response.data[..3], &[0x62, 0xF1, 0x90]);
+        assert_eq!(&response.data[3..], b"W0L0000123456789".as_slice());
+        assert_eq!((response.frames, response.error), (3, None));
+
+        let IsotpEvent::FlowControl(fc) = &events[2] else { panic!("expected flow control") };
+        assert_eq!((fc.status, fc.block_size, fc.st_min_us), (FlowStatus::ContinueToSend, 0, 500));
+
+        let IsotpEvent::Pdu(broken) = &events[3] else { panic!("expected a PDU") };
+        assert_eq!(broken.error, Some(IsotpError::Sequence { expected: 1, got: 3 }));
+        assert_eq!(broken.data, vec![1, 2, 3, 4, 5, 6]);
+
+        let pending = stream.pending();
+        let [IsotpEvent::Pdu(truncated)] = pending.as_slice() else { panic!("expected one pending PDU") };
+        assert_eq!((truncated.direction, truncated.error), (IsotpDirection::Request, Some(IsotpError::Truncated)));
+
+        // Fed a frame at a time, the stream ends up with the same events
+        let mut incremental = IsotpStream::new(DEFAULT_REQUEST_ID, DEFAULT_RESPONSE_ID);
+        for len in 1..=messages.len() {
+            incremental.feed(&messages[..len]);
+        }
+        assert_eq!(incremental.fed(), messages.len());
+        assert_eq!(incremental.events(), stream.events());
+        assert_eq!(incremental.pending(), pending);
+    }
+}
@@ -1,3 +1,15 @@
+pub mod anomaly;
 pub mod decoder;
+pub mod encoder;
+pub mod formula;
+pub mod gaps;
+pub mod isotp;
+#[cfg(feature = "parquet")]
+pub mod parquet_export;
+pub mod router;
+pub mod signal_export;
+pub mod uds;
+pub mod units;
 
 pub use decoder::{SignalDecoder, DecodedSignal};
+pub use encoder::{encode_message, try_encode_message};
@@ -0,0 +1,162 @@
+//! Export of decoded signals as a Parquet time series (`parquet` feature).
+//!
+//! One row per frame carrying an exported signal: a UTC `timestamp` column plus
+//! one nullable Float64 column per signal, keyed "name@0xID@busN" like the charts.
+//! Frames are decoded once each through the `SignalRouter`, and rows are
+//! written a row group at a time so large logs never sit in memory as a table.
+
+use crate::core::dbc::DbcFile;
+use crate::core::CanMessage;
+use crate::decode::router::SignalRouter;
+use crate::decode::SignalDecoder;
+use crate::input::TimePrecision;
+use anyhow::{Context, Result};
+use arrow_array::{ArrayRef, Float64Array, RecordBatch, TimestampMicrosecondArray};
+use arrow_schema::{DataType, Field, Schema, TimeUnit};
+use chrono::{DateTime, Utc};
+use parquet::arrow::ArrowWriter;
+use parquet::basic::Compression;
+use parquet::file::properties::WriterProperties;
+use std::collections::HashMap;
+use std::fs::File;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Rows buffered before they're written out as a row group
+const ROW_GROUP_ROWS: usize = 65_536;
+
+/// Columns of the row group being filled
+struct RowGroup {
+    timestamps: Vec<i64>,
+    columns: Vec<Vec<Option<f64>>>,
+}
+
+impl RowGroup {
+    fn new(width: usize) -> Self {
+        Self {
+            timestamps: Vec::with_capacity(ROW_GROUP_ROWS),
+            columns: vec![Vec::with_capacity(ROW_GROUP_ROWS); width],
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.timestamps.len()
+    }
+
+    fn start_row(&mut self, micros: i64) {
+        self.timestamps.push(micros);
+        for column in &mut self.columns {
+            column.push(None);
+        }
+    }
+
+    fn take_batch(&mut self, schema: &Arc<Schema>) -> Result<RecordBatch> {
+        let mut arrays: Vec<ArrayRef> = Vec::with_capacity(self.columns.len() + 1);
+        arrays.push(Arc::new(TimestampMicrosecondArray::from(std::mem::take(&mut self.timestamps)).with_timezone("UTC")));
+        for column in &mut self.columns {
+            arrays.push(Arc::new(Float64Array::from(std::mem::take(column))));
+        }
+        Ok(RecordBatch::try_new(schema.clone(), arrays)?)
+    }
+}
+
+/// Write the signals in `keys` ("name@0xID@busN") to a Parquet file; returns the number of rows written.
+/// Timestamps are stored in microseconds either way, rounded to whole milliseconds at 3 digits.
+/// With an `origin`, they count from it instead (as an offset from the Unix epoch).
+pub fn export_parquet(
+    path: &Path,
+    messages: &[CanMessage],
+    dbc: &DbcFile,
+    keys: &[String],
+    precision: TimePrecision,
+    origin: Option<DateTime<Utc>>,
+) -> Result<usize> {
+    let mut fields = vec![Field::new("timestamp", DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into())), false)];
+    fields.extend(keys.iter().map(|key| Field::new(key, DataType::Float64, true)));
+    let schema = Arc::new(Schema::new(fields));
+
+    let file = File::create(path).with_context(|| format!("Failed to create {}", path.display()))?;
+    let props = WriterProperties::builder()
+        .set_compression(Compression::SNAPPY)
+        .set_max_row_group_size(ROW_GROUP_ROWS)
+        .build();
+    let mut writer = ArrowWriter::try_new(file, schema.clone(), Some(props))?;
+
+    let mut decoder = SignalDecoder::new();
+    decoder.set_dbc(dbc.clone());
+    let router = SignalRouter::new(dbc, keys.iter().map(String::as_str));
+    let column_of: HashMap<&str, usize> = keys.iter().enumerate().map(|(i, k)| (k.as_str(), i)).collect();
+
+    let millis_only = precision.digits(messages) == 3;
+    let mut group = RowGroup::new(keys.len());
+    let mut rows = 0;
+    for msg in messages {
+        let mut row_started = false;
+        router.route(&decoder, msg, |key, value| {
+            if let Some(&column) = column_of.get(key) {
+                if !row_started {
+                    let micros = match origin {
+                        Some(origin) => (msg.timestamp - origin).num_microseconds().unwrap_or(0),
+                        None => msg.timestamp.timestamp_micros(),
+                    };
+                    group.start_row(if millis_only { (micros as f64 / 1000.0).round() as i64 * 1000 } else { micros });
+                    row_started = true;
+                }
+                *group.columns[column].last_mut().unwrap() = Some(value);
+            }
+        });
+        if group.len() == ROW_GROUP_ROWS {
+            rows += group.len();
+            writer.write(&group.take_batch(&schema)?)?;
+        }
+    }
+    if group.len() > 0 {
+        rows += group.len();
+        writer.write(&group.take_batch(&schema)?)?;
+    }
+    writer.close()?;
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::dbc::{DbcMessage, DbcSignal};
+    use crate::decode::signal_export::all_signal_keys;
+    use arrow_array::Array;
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+    #[test]
+    fn test_export_parquet_rows_and_nulls() {
+        let mut engine = DbcMessage::new(0x100, "Engine", 8);
+        engine.add_signal(DbcSignal::new("Rpm", 0, 8));
+        let mut brake = DbcMessage::new(0x200, "Brake", 8);
+        brake.add_signal(DbcSignal::new("Pressure", 0, 8));
+        let mut dbc = DbcFile::new();
+        dbc.add_message(engine);
+        dbc.add_message(brake);
+
+        let messages = vec![
+            CanMessage::new(0, 0x100, vec![10].into()),
+            CanMessage::new(0, 0x300, vec![0].into()),  // not in the DBC: no row
+            CanMessage::new(0, 0x200, vec![20].into()),
+            CanMessage::new(1, 0x100, vec![30].into()),
+        ];
+        let keys = all_signal_keys(&messages, &dbc);
+        assert_eq!(keys, vec!["Pressure@0x200@bus0", "Rpm@0x100@bus0", "Rpm@0x100@bus1"]);
+
+        let path = std::env::temp_dir().join(format!("shit-export-test-{}.parquet", std::process::id()));
+        assert_eq!(export_parquet(&path, &messages, &dbc, &keys, TimePrecision::Auto, None).unwrap(), 3);
+
+        let reader = ParquetRecordBatchReaderBuilder::try_new(File::open(&path).unwrap()).unwrap().build().unwrap();
+        let batches: Vec<RecordBatch> = reader.map(|b| b.unwrap()).collect();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(batches.len(), 1);
+        let batch = &batches[0];
+        assert_eq!(batch.num_columns(), 4);
+        let rpm = batch.column_by_name("Rpm@0x100@bus0").unwrap().as_any().downcast_ref::<Float64Array>().unwrap();
+        assert_eq!(rpm.iter().collect::<Vec<_>>(), vec![Some(10.0), None, None]);
+        let pressure = batch.column_by_name("Pressure@0x200@bus0").unwrap().as_any().downcast_ref::<Float64Array>().unwrap();
+        assert_eq!(pressure.iter().collect::<Vec<_>>(), vec![None, Some(20.0), None]);
+    }
+}
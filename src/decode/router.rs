@@ -0,0 +1,203 @@
+//! Fan-out of decoded frames to charted signals.
+//!
+//! Charted signals are keyed "name@0xID@busN", so signals that share a name in
+//! different messages stay apart. Keys without the message ID ("name@busN", from
+//! older savestates and the CLI) mean that name in every message carrying it.
+//! Rather than decoding every frame once
+//! per charted signal, the router maps each (message ID, bus) to the charted
+//! signals it carries, so a frame is decoded at most once and only for the
+//! signals someone is looking at.
+
+use crate::core::dbc::DbcFile;
+use crate::core::CanMessage;
+use crate::decode::SignalDecoder;
+use std::collections::HashMap;
+
+/// Chart key of signal `name` in message `msg_id` on `bus`
+pub fn signal_key(name: &str, msg_id: u32, bus: u8) -> String {
+    format!("{}@0x{:03X}@bus{}", name, msg_id, bus)
+}
+
+/// Split a chart key "name@0xID@busN" into signal name, message ID (None if the
+/// key doesn't name one) and bus (bus 0 if absent)
+pub fn parse_signal_key(key: &str) -> (&str, Option<u32>, u8) {
+    let (rest, bus) = match key.find("@bus") {
+        Some(pos) => (&key[..pos], key[pos + 4..].parse::<u8>().unwrap_or(0)),
+        None => (key, 0),
+    };
+    match rest.split_once("@0x") {
+        Some((name, id)) => match u32::from_str_radix(id, 16) {
+            Ok(id) => (name, Some(id), bus),
+            Err(_) => (rest, None, bus),
+        },
+        None => (rest, None, bus),
+    }
+}
+
+/// Split a chart key into signal name and bus, dropping any message ID
+pub fn split_signal_key(key: &str) -> (&str, u8) {
+    let (name, _, bus) = parse_signal_key(key);
+    (name, bus)
+}
+
+/// Charted signals per (message ID, bus): (signal name, chart key)
+pub struct SignalRouter {
+    routes: HashMap<(u32, u8), Vec<(String, String)>>,
+}
+
+impl SignalRouter {
+    pub fn new<'a>(dbc: &DbcFile, keys: impl IntoIterator<Item = &'a str>) -> Self {
+        let mut routes: HashMap<(u32, u8), Vec<(String, String)>> = HashMap::new();
+        for key in keys {
+            let (name, msg_id, bus) = parse_signal_key(key);
+            let owners = dbc.messages.iter()
+                .filter(|m| msg_id.is_none_or(|id| m.id == id) && m.get_signal(name).is_some());
+            for msg in owners {
+                routes.entry((msg.id, bus)).or_default().push((name.to_string(), key.to_string()));
+            }
+        }
+        Self { routes }
+    }
+
+    /// (message ID, bus) pairs carrying at least one routed signal
+    pub fn sources(&self) -> impl Iterator<Item = (u32, u8)> + '_ {
+        self.routes.keys().copied()
+    }
+
+    /// Decode `msg` once and hand each charted signal's value to `sink(key, value)`
+    pub fn route(&self, decoder: &SignalDecoder, msg: &CanMessage, mut sink: impl FnMut(&str, f64)) {
+        self.route_with_misses(decoder, msg, |key, value| {
+            if let Some(value) = value {
+                sink(key, value);
+            }
+        });
+    }
+
+    /// Like `route`, but also reports `None` for a charted signal the frame
+    /// carries yet can't be decoded from (too short for its bits)
+    pub fn route_with_misses(&self, decoder: &SignalDecoder, msg: &CanMessage, mut sink: impl FnMut(&str, Option<f64>)) {
+        let Some(targets) = self.routes.get(&(msg.id, msg.bus)) else {
+            return;
+        };
+        decoder.visit_signals(
+            msg,
+            |signal| targets.iter().any(|(name, _)| *name == signal.name),
+            |signal, decoded| {
+                for (_, key) in targets.iter().filter(|(name, _)| *name == signal.name) {
+                    sink(key, decoded.as_ref().map(|d| d.physical_value));
+                }
+            },
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::dbc::{DbcMessage, DbcSignal};
+    use std::time::Instant;
+
+    fn test_dbc(signals_per_message: u8) -> DbcFile {
+        let mut dbc = DbcFile::new();
+        for id in 0..20u32 {
+            let mut msg = DbcMessage::new(0x100 + id, &format!("Msg{}", id), 8);
+            for s in 0..signals_per_message {
+                msg.add_signal(DbcSignal::new(&format!("Sig{}_{}", id, s), s * 8, 8));
+            }
+            dbc.add_message(msg);
+        }
+        dbc
+    }
+
+    fn test_log(frames: usize) -> Vec<CanMessage> {
+        (0..frames)
+            .map(|i| CanMessage::new((i % 2) as u8, 0x100 + (i % 20) as u32, vec![i as u8; 8].into()))
+            .collect()
+    }
+
+    #[test]
+    fn test_route_to_charted_keys() {
+        let dbc = test_dbc(4);
+        let mut decoder = SignalDecoder::new();
+        decoder.set_dbc(dbc.clone());
+        let router = SignalRouter::new(&dbc, ["Sig0_1@bus0", "Sig0_2@bus1", "Sig3_0@bus1", "Missing@bus0"]);
+
+        let mut points: HashMap<String, usize> = HashMap::new();
+        for msg in test_log(400) {
+            router.route(&decoder, &msg, |key, _| *points.entry(key.to_string()).or_default() += 1);
+        }
+        // ID 0x100 only appears on bus 0 (every 20th frame, even index); ID 0x103 only on bus 1
+        assert_eq!(points.get("Sig0_1@bus0"), Some(&20));
+        assert_eq!(points.get("Sig0_2@bus1"), None);
+        assert_eq!(points.get("Sig3_0@bus1"), Some(&20));
+        assert_eq!(points.len(), 2);
+        assert_eq!(split_signal_key("Speed@bus2"), ("Speed", 2));
+        assert_eq!(split_signal_key("Speed"), ("Speed", 0));
+        assert_eq!(parse_signal_key(&signal_key("Speed", 0x1A0, 2)), ("Speed", Some(0x1A0), 2));
+        assert_eq!(split_signal_key("Speed@0x1A0@bus2"), ("Speed", 2));
+    }
+
+    #[test]
+    fn test_route_signal_added_after_load() {
+        let mut dbc = test_dbc(1);
+        dbc.get_message_mut(0x105).unwrap().add_signal(DbcSignal::new("Added", 8, 8));
+        let mut decoder = SignalDecoder::new();
+        decoder.set_dbc(dbc.clone());
+        let router = SignalRouter::new(&dbc, [signal_key("Added", 0x105, 1).as_str()]);
+
+        let frame = CanMessage::new(1, 0x105, vec![0, 42].into());
+        let mut seen = Vec::new();
+        router.route(&decoder, &frame, |key, value| seen.push((key.to_string(), value)));
+        assert_eq!(seen, vec![(signal_key("Added", 0x105, 1), 42.0)]);
+    }
+
+    #[test]
+    fn test_route_reports_short_frames() {
+        let dbc = test_dbc(4);
+        let mut decoder = SignalDecoder::new();
+        decoder.set_dbc(dbc.clone());
+        let router = SignalRouter::new(&dbc, ["Sig0_0@bus0", "Sig0_3@bus0"]);
+
+        // Two data bytes: Sig0_0 (byte 0) decodes, Sig0_3 (byte 3) doesn't fit
+        let short = CanMessage::new(0, 0x100, vec![7, 9].into());
+        let mut seen = Vec::new();
+        router.route_with_misses(&decoder, &short, |key, value| seen.push((key.to_string(), value)));
+        assert_eq!(seen, vec![("Sig0_0@bus0".to_string(), Some(7.0)), ("Sig0_3@bus0".to_string(), None)]);
+
+        let mut routed = 0;
+        router.route(&decoder, &short, |_, _| routed += 1);
+        assert_eq!(routed, 1);
+    }
+
+    /// Compare against the old per-signal full-decode loop: `cargo test --release -- --ignored --nocapture`
+    #[test]
+    #[ignore]
+    fn bench_route_vs_decode_per_signal() {
+        let dbc = test_dbc(8);
+        let mut decoder = SignalDecoder::new();
+        decoder.set_dbc(dbc.clone());
+        let log = test_log(1_000_000);
+        let keys: Vec<String> = (0..6).map(|i| format!("Sig{}_3@bus{}", i * 3, i % 2)).collect();
+
+        let start = Instant::now();
+        let mut naive = 0usize;
+        for key in &keys {
+            let (name, bus) = split_signal_key(key);
+            for msg in log.iter().filter(|m| m.bus == bus) {
+                naive += decoder.decode_message(msg).iter().filter(|s| s.name == name).count();
+            }
+        }
+        let naive_time = start.elapsed();
+
+        let start = Instant::now();
+        let router = SignalRouter::new(&dbc, keys.iter().map(String::as_str));
+        let mut routed = 0usize;
+        for msg in &log {
+            router.route(&decoder, msg, |_, _| routed += 1);
+        }
+        let routed_time = start.elapsed();
+
+        assert_eq!(naive, routed);
+        println!("{} frames, {} signals: per-signal decode {:?}, routed {:?}", log.len(), keys.len(), naive_time, routed_time);
+    }
+}
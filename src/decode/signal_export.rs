@@ -0,0 +1,141 @@
+//! Decoded-signal exports shared by the GUI and the command line: choosing the
+//! signal columns and writing them as a wide CSV (one row per frame, one column
+//! per signal keyed "name@0xID@busN" like the charts, empty where a frame lacks it).
+
+use crate::core::dbc::DbcFile;
+use crate::core::CanMessage;
+use crate::decode::router::{parse_signal_key, signal_key, SignalRouter};
+use crate::decode::SignalDecoder;
+use crate::input::TimePrecision;
+use anyhow::{bail, Result};
+use std::collections::{BTreeSet, HashMap};
+use std::io::Write;
+
+/// Column keys for every DBC signal, on every bus its message appears on in the log
+pub fn all_signal_keys(messages: &[CanMessage], dbc: &DbcFile) -> Vec<String> {
+    let seen: BTreeSet<(u32, u8)> = messages.iter().map(|m| (m.id, m.bus)).collect();
+    let mut keys: Vec<String> = seen.iter()
+        .filter_map(|&(id, bus)| dbc.get_message(id).map(|msg| (msg, bus)))
+        .flat_map(|(msg, bus)| msg.signals.iter().map(move |s| signal_key(&s.name, msg.id, bus)))
+        .collect();
+    keys.sort();
+    keys.dedup();
+    keys
+}
+
+/// Keys among `keys` matching the requested signals, each given as a bare name
+/// (every message and bus it's on), "name@busN", "name@0xID" or a full
+/// "name@0xID@busN" key. Errors on a name matching nothing.
+pub fn select_signal_keys(keys: &[String], requested: &[String]) -> Result<Vec<String>> {
+    let mut selected = Vec::new();
+    for wanted in requested {
+        let (name, msg_id, bus) = parse_signal_key(wanted);
+        let any_bus = !wanted.contains("@bus");
+        let matches: Vec<&String> = keys.iter()
+            .filter(|key| {
+                let (key_name, key_id, key_bus) = parse_signal_key(key);
+                key_name == name
+                    && (msg_id.is_none() || key_id == msg_id)
+                    && (any_bus || key_bus == bus)
+            })
+            .collect();
+        if matches.is_empty() {
+            bail!("Signal {} is not in the DBC or never appears in the log", wanted);
+        }
+        for key in matches {
+            if !selected.contains(key) {
+                selected.push(key.clone());
+            }
+        }
+    }
+    Ok(selected)
+}
+
+/// Write the signals in `keys` as CSV, time in seconds relative to the first message;
+/// returns the number of rows written
+pub fn write_signals_csv<W: Write>(
+    mut writer: W,
+    messages: &[CanMessage],
+    dbc: &DbcFile,
+    keys: &[String],
+    precision: TimePrecision,
+) -> Result<usize> {
+    writeln!(writer, "time,{}", keys.join(","))?;
+    let digits = precision.digits(messages);
+
+    let mut decoder = SignalDecoder::new();
+    decoder.set_dbc(dbc.clone());
+    let router = SignalRouter::new(dbc, keys.iter().map(String::as_str));
+    let column_of: HashMap<&str, usize> = keys.iter().enumerate().map(|(i, k)| (k.as_str(), i)).collect();
+
+    let first_ts = messages.first().map(|m| m.timestamp);
+    let mut row: Vec<Option<f64>> = vec![None; keys.len()];
+    let mut rows = 0;
+    for msg in messages {
+        let mut any = false;
+        router.route(&decoder, msg, |key, value| {
+            if let Some(&column) = column_of.get(key) {
+                row[column] = Some(value);
+                any = true;
+            }
+        });
+        if !any {
+            continue;
+        }
+        let rel_time = first_ts
+            .map(|t| (msg.timestamp - t).num_microseconds().unwrap_or(0) as f64 / 1_000_000.0)
+            .unwrap_or(0.0);
+        write!(writer, "{:.*}", digits, rel_time)?;
+        for cell in &mut row {
+            match cell.take() {
+                Some(value) => write!(writer, ",{}", value)?,
+                None => write!(writer, ",")?,
+            }
+        }
+        writeln!(writer)?;
+        rows += 1;
+    }
+    writer.flush()?;
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::dbc::{DbcMessage, DbcSignal};
+
+    #[test]
+    fn test_select_and_write_signals_csv() {
+        let mut engine = DbcMessage::new(0x100, "Engine", 8);
+        engine.add_signal(DbcSignal::new("Rpm", 0, 8));
+        engine.add_signal(DbcSignal::new("Temp", 8, 8));
+        let mut dbc = DbcFile::new();
+        dbc.add_message(engine);
+
+        let base = chrono::Utc::now();
+        let frame = |bus: u8, id: u32, data: Vec<u8>, ms: i64| {
+            let mut msg = CanMessage::new(bus, id, data.into());
+            msg.timestamp = base + chrono::Duration::milliseconds(ms);
+            msg
+        };
+        let messages = vec![
+            frame(0, 0x100, vec![10, 1], 0),
+            frame(0, 0x300, vec![0], 5),  // not in the DBC: no row
+            frame(1, 0x100, vec![30, 3], 10),
+        ];
+        let keys = all_signal_keys(&messages, &dbc);
+        assert_eq!(keys, vec!["Rpm@0x100@bus0", "Rpm@0x100@bus1", "Temp@0x100@bus0", "Temp@0x100@bus1"]);
+
+        let names = |list: &[&str]| list.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        let selected = select_signal_keys(&keys, &names(&["Rpm", "Temp@bus1", "Rpm@0x100@bus0"])).unwrap();
+        assert_eq!(selected, vec!["Rpm@0x100@bus0", "Rpm@0x100@bus1", "Temp@0x100@bus1"]);
+        assert_eq!(select_signal_keys(&keys, &names(&["Temp@0x100"])).unwrap().len(), 2);
+        assert!(select_signal_keys(&keys, &names(&["Speed"])).is_err());
+        assert!(select_signal_keys(&keys, &names(&["Rpm@0x200"])).is_err());
+
+        let mut out = Vec::new();
+        assert_eq!(write_signals_csv(&mut out, &messages, &dbc, &selected, TimePrecision::Micros).unwrap(), 2);
+        assert_eq!(String::from_utf8(out).unwrap(),
+            "time,Rpm@0x100@bus0,Rpm@0x100@bus1,Temp@0x100@bus1\n0.000000,10,,\n0.010000,,30,3\n");
+    }
+}
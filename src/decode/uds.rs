@@ -0,0 +1,212 @@
+//! UDS (ISO 14229) service decoding of reassembled ISO-TP payloads.
+//!
+//! Each PDU becomes one readable line: the service name, whether it is a
+//! request, positive response or negative response, and the service's own
+//! parameters (DID, DTC records, ...). Services are looked up in `SERVICES`;
+//! ones without a parameter decoder there still get their name and raw bytes.
+
+/// Service ID of a negative response (`7F <request SID> <NRC>`)
+pub const NEGATIVE_RESPONSE_SID: u8 = 0x7F;
+
+/// Added to a request SID to form its positive response SID
+const POSITIVE_RESPONSE_OFFSET: u8 = 0x40;
+
+/// One entry of the service table
+pub struct UdsService {
+    pub sid: u8,
+    pub name: &'static str,
+    /// Parameter decoder: (is a response, bytes after the SID), None to show them as hex
+    describe: Option<fn(bool, &[u8]) -> String>,
+}
+
+/// Known services; add a row to decode another one
+pub const SERVICES: &[UdsService] = &[
+    UdsService { sid: 0x10, name: "DiagnosticSessionControl", describe: Some(describe_session_control) },
+    UdsService { sid: 0x11, name: "ECUReset", describe: None },
+    UdsService { sid: 0x14, name: "ClearDiagnosticInformation", describe: None },
+    UdsService { sid: 0x19, name: "ReadDTCInformation", describe: Some(describe_read_dtc) },
+    UdsService { sid: 0x22, name: "ReadDataByIdentifier", describe: Some(describe_read_did) },
+    UdsService { sid: 0x27, name: "SecurityAccess", describe: None },
+    UdsService { sid: 0x28, name: "CommunicationControl", describe: None },
+    UdsService { sid: 0x2E, name: "WriteDataByIdentifier", describe: Some(describe_write_did) },
+    UdsService { sid: 0x31, name: "RoutineControl", describe: None },
+    UdsService { sid: 0x34, name: "RequestDownload", describe: None },
+    UdsService { sid: 0x36, name: "TransferData", describe: None },
+    UdsService { sid: 0x37, name: "RequestTransferExit", describe: None },
+    UdsService { sid: 0x3E, name: "TesterPresent", describe: None },
+    UdsService { sid: 0x85, name: "ControlDTCSetting", describe: None },
+];
+
+/// Negative response codes
+const NEGATIVE_RESPONSE_CODES: &[(u8, &str)] = &[
+    (0x10, "generalReject"),
+    (0x11, "serviceNotSupported"),
+    (0x12, "subFunctionNotSupported"),
+    (0x13, "incorrectMessageLengthOrInvalidFormat"),
+    (0x14, "responseTooLong"),
+    (0x21, "busyRepeatRequest"),
+    (0x22, "conditionsNotCorrect"),
+    (0x24, "requestSequenceError"),
+    (0x31, "requestOutOfRange"),
+    (0x33, "securityAccessDenied"),
+    (0x35, "invalidKey"),
+    (0x36, "exceededNumberOfAttempts"),
+    (0x37, "requiredTimeDelayNotExpired"),
+    (0x70, "uploadDownloadNotAccepted"),
+    (0x72, "generalProgrammingFailure"),
+    (0x78, "requestCorrectlyReceived-ResponsePending"),
+    (0x7E, "subFunctionNotSupportedInActiveSession"),
+    (0x7F, "serviceNotSupportedInActiveSession"),
+];
+
+pub fn service(sid: u8) -> Option<&'static UdsService> {
+    SERVICES.iter().find(|s| s.sid == sid)
+}
+
+fn negative_response_name(nrc: u8) -> &'static str {
+    NEGATIVE_RESPONSE_CODES.iter()
+        .find(|(code, _)| *code == nrc)
+        .map(|(_, name)| *name)
+        .unwrap_or("unknown")
+}
+
+fn hex_bytes(data: &[u8]) -> String {
+    data.iter().map(|b| format!("{:02X}", b)).collect::<Vec<_>>().join(" ")
+}
+
+/// " <hex>" for a non-empty parameter tail, "" otherwise
+fn hex_tail(data: &[u8]) -> String {
+    if data.is_empty() { String::new() } else { format!(" {}", hex_bytes(data)) }
+}
+
+fn describe_session_control(_response: bool, params: &[u8]) -> String {
+    let Some(&session) = params.first() else {
+        return String::new();
+    };
+    let name = match session & 0x7F {
+        0x01 => "default",
+        0x02 => "programming",
+        0x03 => "extended",
+        0x04 => "safety system",
+        _ => "other",
+    };
+    format!("session {:02X} ({}){}", session, name, hex_tail(&params[1..]))
+}
+
+fn describe_read_did(response: bool, params: &[u8]) -> String {
+    if response {
+        // Only the first record can be split off: record lengths live in the ECU's DID table, not the frame
+        match params {
+            [hi, lo, data @ ..] => format!("DID {:02X}{:02X}:{}", hi, lo, hex_tail(data)),
+            _ => hex_bytes(params),
+        }
+    } else {
+        let dids: Vec<String> = params.chunks(2).map(|did| hex_bytes(did).replace(' ', "")).collect();
+        format!("DID {}", dids.join(", "))
+    }
+}
+
+fn describe_write_did(_response: bool, params: &[u8]) -> String {
+    match params {
+        [hi, lo, data @ ..] => format!("DID {:02X}{:02X}{}", hi, lo, hex_tail(data)),
+        _ => hex_bytes(params),
+    }
+}
+
+fn describe_read_dtc(response: bool, params: &[u8]) -> String {
+    let Some((&subfunction, rest)) = params.split_first() else {
+        return String::new();
+    };
+    let report = match subfunction {
+        0x01 => "reportNumberOfDTCByStatusMask",
+        0x02 => "reportDTCByStatusMask",
+        0x04 => "reportDTCSnapshotRecordByDTCNumber",
+        0x06 => "reportDTCExtDataRecordByDTCNumber",
+        0x0A => "reportSupportedDTC",
+        _ => "",
+    };
+    let head = if report.is_empty() { format!("sub {:02X}", subfunction) } else { report.to_string() };
+
+    if !response {
+        return match (subfunction, rest) {
+            (0x01 | 0x02, [mask]) => format!("{} mask {:02X}", head, mask),
+            _ => format!("{}{}", head, hex_tail(rest)),
+        };
+    }
+    match (subfunction, rest) {
+        (0x01, [mask, format, hi, lo]) => format!(
+            "{} availability {:02X} format {:02X}: {} DTCs",
+            head, mask, format, u16::from_be_bytes([*hi, *lo])
+        ),
+        (0x02 | 0x0A, [mask, records @ ..]) if records.len() % 4 == 0 => {
+            let dtcs: Vec<String> = records.chunks(4)
+                .map(|r| format!("{:02X}{:02X}{:02X} (status {:02X})", r[0], r[1], r[2], r[3]))
+                .collect();
+            if dtcs.is_empty() {
+                format!("{} availability {:02X}: no DTCs", head, mask)
+            } else {
+                format!("{} availability {:02X}: {}", head, mask, dtcs.join(", "))
+            }
+        }
+        _ => format!("{}{}", head, hex_tail(rest)),
+    }
+}
+
+/// One-line description of a UDS payload, None if it is empty or not a known service
+pub fn describe(data: &[u8]) -> Option<String> {
+    let (&sid, params) = data.split_first()?;
+
+    if sid == NEGATIVE_RESPONSE_SID {
+        let [request_sid, nrc, ..] = *params else {
+            return Some("Negative response".to_string());
+        };
+        let name = service(request_sid).map(|s| s.name).unwrap_or("unknown service");
+        return Some(format!(
+            "Negative response to {} ({:02X}): {} ({:02X})",
+            name, request_sid, negative_response_name(nrc), nrc
+        ));
+    }
+
+    let (service, response) = match service(sid) {
+        Some(service) => (service, false),
+        None => (service(sid.checked_sub(POSITIVE_RESPONSE_OFFSET)?)?, true),
+    };
+    let details = match service.describe {
+        Some(describe) => describe(response, params),
+        None => hex_bytes(params),
+    };
+    let kind = if response { " response" } else { "" };
+    Some(if details.is_empty() {
+        format!("{}{}", service.name, kind)
+    } else {
+        format!("{}{}: {}", service.name, kind, details)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_describe_uds_services() {
+        assert_eq!(describe(&[0x22, 0xF1, 0x90]).unwrap(), "ReadDataByIdentifier: DID F190");
+        assert_eq!(describe(&[0x22, 0xF1, 0x90, 0xF1, 0x8C]).unwrap(), "ReadDataByIdentifier: DID F190, F18C");
+        assert_eq!(describe(&[0x62, 0xF1, 0x90, 0x57, 0x30]).unwrap(), "ReadDataByIdentifier response: DID F190: 57 30");
+        assert_eq!(describe(&[0x2E, 0x01, 0x23, 0xAA]).unwrap(), "WriteDataByIdentifier: DID 0123 AA");
+        assert_eq!(describe(&[0x6E, 0x01, 0x23]).unwrap(), "WriteDataByIdentifier response: DID 0123");
+        assert_eq!(describe(&[0x19, 0x02, 0xFF]).unwrap(), "ReadDTCInformation: reportDTCByStatusMask mask FF");
+        assert_eq!(
+            describe(&[0x59, 0x02, 0xFF, 0x01, 0x23, 0x45, 0x2F, 0xC1, 0x00, 0x00, 0x08]).unwrap(),
+            "ReadDTCInformation response: reportDTCByStatusMask availability FF: 012345 (status 2F), C10000 (status 08)"
+        );
+        assert_eq!(
+            describe(&[0x7F, 0x22, 0x31]).unwrap(),
+            "Negative response to ReadDataByIdentifier (22): requestOutOfRange (31)"
+        );
+        // Known service without a parameter decoder, and an unknown SID
+        assert_eq!(describe(&[0x7E, 0x00]).unwrap(), "TesterPresent response: 00");
+        assert_eq!(describe(&[0x3E]).unwrap(), "TesterPresent");
+        assert_eq!(describe(&[0xA5, 0x01]), None);
+        assert_eq!(describe(&[]), None);
+    }
+}
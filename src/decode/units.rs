@@ -0,0 +1,99 @@
+//! Display-unit conversion presets (km/h to mph, degC to degF, ...)
+//!
+//! Conversions are linear and only ever applied when showing a value; decoded
+//! and stored values stay in the DBC unit.
+
+/// A linear conversion from one unit to another: `to = from * scale + offset`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UnitConversion {
+    pub from: &'static str,
+    pub to: &'static str,
+    pub scale: f64,
+    pub offset: f64,
+}
+
+impl UnitConversion {
+    pub fn apply(&self, value: f64) -> f64 {
+        value * self.scale + self.offset
+    }
+}
+
+const fn conv(from: &'static str, to: &'static str, scale: f64, offset: f64) -> UnitConversion {
+    UnitConversion { from, to, scale, offset }
+}
+
+/// Built-in conversions, keyed by the canonical source unit
+const CONVERSIONS: &[UnitConversion] = &[
+    conv("km/h", "mph", 0.621_371, 0.0),
+    conv("mph", "km/h", 1.609_344, 0.0),
+    conv("m/s", "km/h", 3.6, 0.0),
+    conv("m/s", "mph", 2.236_936, 0.0),
+    conv("°C", "°F", 1.8, 32.0),
+    conv("°F", "°C", 5.0 / 9.0, -160.0 / 9.0),
+    conv("°C", "K", 1.0, 273.15),
+    conv("kPa", "psi", 0.145_038, 0.0),
+    conv("kPa", "bar", 0.01, 0.0),
+    conv("bar", "psi", 14.503_77, 0.0),
+    conv("bar", "kPa", 100.0, 0.0),
+    conv("psi", "kPa", 6.894_757, 0.0),
+    conv("psi", "bar", 0.068_948, 0.0),
+    conv("km", "mi", 0.621_371, 0.0),
+    conv("mi", "km", 1.609_344, 0.0),
+    conv("Nm", "lb·ft", 0.737_562, 0.0),
+    conv("lb·ft", "Nm", 1.355_818, 0.0),
+    conv("kW", "hp", 1.341_022, 0.0),
+    conv("hp", "kW", 0.745_700, 0.0),
+    conv("L", "gal", 0.264_172, 0.0),
+    conv("kg", "lb", 2.204_623, 0.0),
+];
+
+/// Map the many spellings DBC files use onto the table's unit names
+fn canonical_unit(unit: &str) -> Option<&'static str> {
+    let unit = unit.trim().to_lowercase();
+    Some(match unit.as_str() {
+        "km/h" | "kmh" | "kph" | "kmph" | "km/hr" => "km/h",
+        "mph" | "mi/h" => "mph",
+        "m/s" | "mps" => "m/s",
+        "°c" | "degc" | "deg c" | "degreec" | "c" | "celsius" | "℃" => "°C",
+        "°f" | "degf" | "deg f" | "f" | "fahrenheit" | "℉" => "°F",
+        "kpa" => "kPa",
+        "bar" => "bar",
+        "psi" => "psi",
+        "km" => "km",
+        "mi" | "miles" => "mi",
+        "nm" | "n.m" | "n·m" | "n*m" => "Nm",
+        "lb·ft" | "lbft" | "lb-ft" | "lb.ft" | "ft·lb" | "ftlb" | "ft-lb" => "lb·ft",
+        "kw" => "kW",
+        "hp" => "hp",
+        "l" => "L",
+        "kg" => "kg",
+        _ => return None,
+    })
+}
+
+/// Conversions available for a signal whose DBC unit is `unit`
+pub fn conversions_for(unit: &str) -> Vec<&'static UnitConversion> {
+    match canonical_unit(unit) {
+        Some(from) => CONVERSIONS.iter().filter(|c| c.from == from).collect(),
+        None => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn find_conversion(unit: &str, to: &str) -> Option<&'static UnitConversion> {
+        conversions_for(unit).into_iter().find(|c| c.to == to)
+    }
+
+    #[test]
+    fn test_conversions_by_unit_spelling() {
+        let to_f = find_conversion("degC", "°F").unwrap();
+        assert_eq!(to_f.apply(100.0), 212.0);
+        assert!((find_conversion("deg F", "°C").unwrap().apply(212.0) - 100.0).abs() < 1e-9);
+        assert!((find_conversion("KPH", "mph").unwrap().apply(100.0) - 62.1371).abs() < 1e-4);
+        assert_eq!(conversions_for("kPa").len(), 2);
+        assert!(conversions_for("rpm").is_empty());
+    }
+}
@@ -3,10 +3,11 @@
 //! Each interface is assigned a unique bus ID (0, 1, 2, ...) and
 //! messages from all interfaces are aggregated with their bus IDs preserved.
 //!
-//! Bus IDs are reused when interfaces disconnect - the lowest available ID is always assigned.
+//! Bus IDs are reused when interfaces disconnect - the lowest available ID is assigned unless
+//! the connection asks for a specific one.
 
 use crate::hardware::can_manager::{CanManager, ConnectionStatus, ManagerMessage, ManagerStats};
-use crate::hardware::can_interface::{CanConfig, InterfaceType};
+use crate::hardware::can_interface::{BusErrorStatus, CanCapabilities, CanConfig, InterfaceType};
 use std::collections::{HashMap, BTreeSet};
 use std::sync::Arc;
 use tokio::sync::{Mutex, RwLock};
@@ -38,6 +39,16 @@ pub struct InterfaceStats {
     pub messages_sent: u64,
     /// Number of errors
     pub errors: u64,
+    /// Number of frames dropped because a buffer was full
+    pub dropped_frames: u64,
+    /// Adapter detected on connect, if the interface identifies itself
+    pub adapter: Option<String>,
+    /// Number of frames that failed to transmit (TX queue full or write failed)
+    pub tx_dropped_frames: u64,
+    /// Controller error state last reported by the interface
+    pub bus_status: BusErrorStatus,
+    /// Capabilities reported by the connected interface
+    pub capabilities: Option<CanCapabilities>,
 }
 
 /// Bus ID allocator that reuses freed IDs
@@ -70,6 +81,20 @@ impl BusIdAllocator {
         }
     }
 
+    /// Take a specific bus ID; false if it is already in use
+    fn claim(&mut self, bus_id: u8) -> bool {
+        if self.available.remove(&bus_id) {
+            return true;
+        }
+        if bus_id < self.next_id {
+            return false;
+        }
+        // IDs skipped over stay free for `allocate`
+        self.available.extend(self.next_id..bus_id);
+        self.next_id = bus_id.wrapping_add(1);
+        true
+    }
+
     /// Free a bus ID so it can be reused
     fn free(&mut self, bus_id: u8) {
         self.available.insert(bus_id);
@@ -109,7 +134,7 @@ impl CanManagerCollection {
         interfaces.values().any(|m| m.interface_name == interface)
     }
 
-    /// Connect to a new CAN interface, assigning it the lowest available bus ID
+    /// Connect to a new CAN interface as `bus_id`, or the lowest available bus ID if None
     ///
     /// Returns the assigned bus ID on success
     pub async fn connect(
@@ -117,16 +142,22 @@ impl CanManagerCollection {
         interface: &str,
         config: CanConfig,
         interface_type: InterfaceType,
+        bus_id: Option<u8>,
     ) -> Result<u8, String> {
         // Prevent duplicate connection to same interface
         if self.has_interface(interface).await {
             return Err(format!("Already connected or connecting to {}", interface));
         }
 
-        // Allocate the lowest available bus ID
         let bus_id = {
             let mut allocator = self.allocator.lock().await;
-            allocator.allocate()
+            match bus_id {
+                Some(bus_id) if !allocator.claim(bus_id) => {
+                    return Err(format!("Bus {} is already in use", bus_id));
+                }
+                Some(bus_id) => bus_id,
+                None => allocator.allocate(),
+            }
         };
 
         // Create new manager for this interface
@@ -227,6 +258,11 @@ impl CanManagerCollection {
                 messages_received: manager_stats.messages_received.load(std::sync::atomic::Ordering::SeqCst),
                 messages_sent: manager_stats.messages_sent.load(std::sync::atomic::Ordering::SeqCst),
                 errors: manager_stats.errors.load(std::sync::atomic::Ordering::SeqCst),
+                dropped_frames: manager_stats.dropped_frames.load(std::sync::atomic::Ordering::SeqCst),
+                adapter: managed.manager.adapter().await,
+                tx_dropped_frames: manager_stats.tx_dropped_frames.load(std::sync::atomic::Ordering::SeqCst),
+                bus_status: managed.manager.bus_status().await,
+                capabilities: managed.manager.capabilities().await,
             });
         }
 
@@ -267,3 +303,23 @@ impl Default for CanManagerCollection {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bus_id_claim() {
+        let mut allocator = BusIdAllocator::new();
+        assert!(allocator.claim(2));
+        assert!(!allocator.claim(2));
+        // IDs skipped by the claim are handed out first
+        assert_eq!(allocator.allocate(), 0);
+        assert_eq!(allocator.allocate(), 1);
+        assert_eq!(allocator.allocate(), 3);
+
+        allocator.free(1);
+        assert!(allocator.claim(1));
+        assert_eq!(allocator.allocate(), 4);
+    }
+}
@@ -3,6 +3,7 @@ use crate::core::CanMessage;
 use std::error::Error;
 use std::future::Future;
 use std::pin::Pin;
+use std::time::Duration;
 
 /// Boxed future type for async operations
 pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
@@ -10,6 +11,25 @@ pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
 /// Result type for CAN interface operations
 pub type CanResult<T> = Result<T, Box<dyn Error + Send + Sync>>;
 
+/// Default number of received frames an interface buffers before applying its overflow policy
+pub const DEFAULT_RX_BUFFER_SIZE: usize = 10_000;
+
+/// Default minimum spacing between transmitted frames (keeps SLCAN adapters' TX buffers from overrunning)
+pub const DEFAULT_TX_MIN_GAP: Duration = Duration::from_millis(1);
+
+/// Default interval between controller error-state polls (SLCAN `F` command)
+pub const DEFAULT_STATUS_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// What to do with received frames when the RX buffer is full
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum RxOverflowPolicy {
+    /// Keep the buffered frames and drop the newly received one
+    #[default]
+    DropNewest,
+    /// Drop the oldest buffered frame to make room for the new one
+    DropOldest,
+}
+
 /// Configuration for a CAN interface
 #[derive(Debug, Clone)]
 pub struct CanConfig {
@@ -19,6 +39,16 @@ pub struct CanConfig {
     pub fd_mode: bool,
     /// Enable listen-only mode
     pub listen_only: bool,
+    /// Maximum number of received frames buffered by the interface
+    pub rx_buffer_size: usize,
+    /// Behaviour when the RX buffer is full
+    pub rx_overflow: RxOverflowPolicy,
+    /// Minimum time between two transmitted frames
+    pub tx_min_gap: Duration,
+    /// Echo transmitted frames back as received (only the mock interface honours this)
+    pub loopback: bool,
+    /// How often to ask the adapter for its error state (None = never)
+    pub status_poll_interval: Option<Duration>,
 }
 
 impl Default for CanConfig {
@@ -27,8 +57,54 @@ impl Default for CanConfig {
             bitrate: 500_000,
             fd_mode: false,
             listen_only: false,
+            rx_buffer_size: DEFAULT_RX_BUFFER_SIZE,
+            rx_overflow: RxOverflowPolicy::default(),
+            tx_min_gap: DEFAULT_TX_MIN_GAP,
+            loopback: false,
+            status_poll_interval: Some(DEFAULT_STATUS_POLL_INTERVAL),
+        }
+    }
+}
+
+/// Bitrates every classic CAN controller can be configured for
+pub const STANDARD_BITRATES: [u32; 4] = [125_000, 250_000, 500_000, 1_000_000];
+
+/// What an interface can do, so the UI only offers settings it will honour
+#[derive(Debug, Clone, PartialEq)]
+pub struct CanCapabilities {
+    /// Nominal bitrates the interface can be set to
+    pub bitrates: Vec<u32>,
+    /// CAN FD frames (and `CanConfig::fd_mode`)
+    pub fd: bool,
+    /// Receive without acknowledging or transmitting
+    pub listen_only: bool,
+    /// Frames are stamped with the adapter's own clock
+    pub hardware_timestamps: bool,
+    /// Largest payload in bytes
+    pub max_payload: usize,
+}
+
+impl CanCapabilities {
+    /// A classic CAN interface at the standard bitrates, no FD, no hardware timestamps
+    pub fn classic() -> Self {
+        Self {
+            bitrates: STANDARD_BITRATES.to_vec(),
+            fd: false,
+            listen_only: true,
+            hardware_timestamps: false,
+            max_payload: 8,
         }
     }
+
+    pub fn supports_bitrate(&self, bitrate: u32) -> bool {
+        self.bitrates.contains(&bitrate)
+    }
+}
+
+impl Default for CanCapabilities {
+    fn default() -> Self {
+        Self::classic()
+    }
 }
 
 /// Status of a CAN interface
@@ -44,6 +120,54 @@ pub enum CanStatus {
     Error,
 }
 
+/// CAN controller error state (ISO 11898-1 fault confinement), worst last
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum ErrorState {
+    /// Error counters below 96: normal operation
+    #[default]
+    Active,
+    /// An error counter reached the warning limit (96)
+    Warning,
+    /// An error counter passed 127: the controller may only send passive error flags
+    Passive,
+    /// The transmit error counter passed 255: the controller has left the bus
+    BusOff,
+}
+
+impl ErrorState {
+    pub fn label(self) -> &'static str {
+        match self {
+            ErrorState::Active => "Error active",
+            ErrorState::Warning => "Error warning",
+            ErrorState::Passive => "Error passive",
+            ErrorState::BusOff => "Bus-off",
+        }
+    }
+
+    /// Bad enough that frames are likely being lost
+    pub fn is_fault(self) -> bool {
+        self >= ErrorState::Passive
+    }
+}
+
+/// Error state last reported by an interface's controller
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct BusErrorStatus {
+    pub state: ErrorState,
+    /// Receive and transmit error counters, if the adapter reports them
+    pub counters: Option<(u16, u16)>,
+}
+
+impl BusErrorStatus {
+    /// "Error passive (RX 130, TX 96)"
+    pub fn describe(&self) -> String {
+        match self.counters {
+            Some((rx, tx)) => format!("{} (RX {}, TX {})", self.state.label(), rx, tx),
+            None => self.state.label().to_string(),
+        }
+    }
+}
+
 /// Trait for CAN bus interface implementations
 ///
 /// This trait provides a common interface for different CAN hardware:
@@ -76,11 +200,37 @@ pub trait CanInterface: Send {
     /// Clear the receive buffer
     fn clear_rx_buffer(&mut self);
 
+    /// Number of received frames dropped because the RX buffer was full
+    fn dropped_frames(&self) -> u64 {
+        0
+    }
+
+    /// Controller error state, for interfaces that report it
+    fn bus_error_status(&self) -> BusErrorStatus {
+        BusErrorStatus::default()
+    }
+
+    /// Whether `receive` stamps frames with the adapter's own clock rather than host receive time
+    fn device_timestamps(&self) -> bool {
+        false
+    }
+
     /// Check if the interface supports CAN FD
     fn supports_fd(&self) -> bool {
         false
     }
 
+    /// Settings this interface supports
+    fn capabilities(&self) -> CanCapabilities {
+        let fd = self.supports_fd();
+        CanCapabilities {
+            fd,
+            hardware_timestamps: self.device_timestamps(),
+            max_payload: if fd { 64 } else { 8 },
+            ..CanCapabilities::classic()
+        }
+    }
+
     /// Get available CAN interfaces on the system
     fn list_interfaces() -> Vec<String> where Self: Sized {
         Vec::new()
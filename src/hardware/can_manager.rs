@@ -1,12 +1,15 @@
 use crate::core::CanMessage;
-use crate::hardware::can_interface::{CanConfig, CanInterface, InterfaceType};
+use crate::hardware::can_interface::{BusErrorStatus, CanCapabilities, CanConfig, CanInterface, InterfaceType};
 use crate::hardware::serial_can::SerialCanInterface;
 use crate::hardware::mock::MockCanInterface;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::collections::VecDeque;
+use std::time::{Duration, Instant};
 use tokio::sync::{mpsc, Mutex};
-use chrono::Utc;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::error;
 
 /// Maximum messages to keep in the live buffer
 const MAX_LIVE_MESSAGES: usize = 5000;
@@ -15,7 +18,85 @@ const MAX_LIVE_MESSAGES: usize = 5000;
 #[derive(Clone)]
 pub struct ManagerMessage {
     pub message: CanMessage,
+    /// Host time the manager received the frame
     pub timestamp: chrono::DateTime<Utc>,
+    /// Time from the adapter's own clock, when the interface provides one
+    pub device_timestamp: Option<chrono::DateTime<Utc>>,
+}
+
+/// Which clock recorded live frames are stamped with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum TimestampSource {
+    /// Adapter timestamps when the interface reports them, otherwise arrival time
+    #[default]
+    DeviceIfAvailable,
+    /// Host time the frame reached the CAN manager
+    Arrival,
+    /// Offset from the first recorded frame (first frame = 0 s), on the device clock if available
+    LogRelative,
+}
+
+impl TimestampSource {
+    pub const ALL: [TimestampSource; 3] = [
+        TimestampSource::DeviceIfAvailable,
+        TimestampSource::Arrival,
+        TimestampSource::LogRelative,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            TimestampSource::DeviceIfAvailable => "Device (if available)",
+            TimestampSource::Arrival => "Arrival",
+            TimestampSource::LogRelative => "Log-relative",
+        }
+    }
+
+    /// Timestamp to record for a frame. `origin` holds the first frame's time for
+    /// `LogRelative` and is set on the first call; reset it to start a new log.
+    pub fn resolve(self, msg: &ManagerMessage, origin: &mut Option<DateTime<Utc>>) -> DateTime<Utc> {
+        let best = msg.device_timestamp.unwrap_or(msg.timestamp);
+        match self {
+            TimestampSource::DeviceIfAvailable => best,
+            TimestampSource::Arrival => msg.timestamp,
+            TimestampSource::LogRelative => {
+                let origin = *origin.get_or_insert(best);
+                DateTime::UNIX_EPOCH + (best - origin)
+            }
+        }
+    }
+}
+
+/// Enforces a minimum gap between transmitted frames
+struct TxPacer {
+    min_gap: Duration,
+    last_sent: Option<Instant>,
+}
+
+impl TxPacer {
+    fn new(min_gap: Duration) -> Self {
+        Self { min_gap, last_sent: None }
+    }
+
+    /// How long to wait at `now` before the next frame may go out
+    fn wait_time(&self, now: Instant) -> Duration {
+        self.last_sent
+            .map_or(Duration::ZERO, |last| self.min_gap.saturating_sub(now.saturating_duration_since(last)))
+    }
+
+    fn mark_sent(&mut self, now: Instant) {
+        self.last_sent = Some(now);
+    }
+}
+
+/// Shared state a connection task reports into
+#[derive(Clone)]
+struct ConnectionHandles {
+    status: Arc<Mutex<ConnectionStatus>>,
+    adapter: Arc<Mutex<Option<String>>>,
+    bus_status: Arc<Mutex<BusErrorStatus>>,
+    capabilities: Arc<Mutex<Option<CanCapabilities>>>,
+    stats: Arc<ManagerStats>,
+    stop_signal: Arc<AtomicBool>,
 }
 
 /// CAN hardware manager that handles connections and message streaming
@@ -32,6 +113,12 @@ pub struct CanManager {
     tx_sender: Option<mpsc::Sender<CanMessage>>,
     /// Current interface name
     interface_name: Arc<Mutex<Option<String>>>,
+    /// Adapter detected on connect (SLCAN version probe), for display
+    adapter: Arc<Mutex<Option<String>>>,
+    /// Controller error state last reported by the interface
+    bus_status: Arc<Mutex<BusErrorStatus>>,
+    /// What the connected interface offers, once it has identified itself
+    capabilities: Arc<Mutex<Option<CanCapabilities>>>,
 }
 
 #[derive(Clone, Copy, PartialEq, Debug)]
@@ -47,6 +134,10 @@ pub struct ManagerStats {
     pub messages_received: AtomicU64,
     pub messages_sent: AtomicU64,
     pub errors: AtomicU64,
+    /// Frames lost because an RX buffer (interface or live buffer) was full
+    pub dropped_frames: AtomicU64,
+    /// Frames queued for transmit that never went out (TX queue full or write failed)
+    pub tx_dropped_frames: AtomicU64,
     pub start_time: Arc<Mutex<Option<chrono::DateTime<Utc>>>>,
 }
 
@@ -65,6 +156,9 @@ impl CanManager {
             stop_signal: Arc::new(AtomicBool::new(false)),
             tx_sender: None,
             interface_name: Arc::new(Mutex::new(None)),
+            adapter: Arc::new(Mutex::new(None)),
+            bus_status: Arc::new(Mutex::new(BusErrorStatus::default())),
+            capabilities: Arc::new(Mutex::new(None)),
         }
     }
 
@@ -78,6 +172,21 @@ impl CanManager {
         self.interface_name.lock().await.clone()
     }
 
+    /// Adapter detected on connect, e.g. "CANable (v1.0)"
+    pub async fn adapter(&self) -> Option<String> {
+        self.adapter.lock().await.clone()
+    }
+
+    /// Controller error state last reported by the interface
+    pub async fn bus_status(&self) -> BusErrorStatus {
+        *self.bus_status.lock().await
+    }
+
+    /// Capabilities of the connected interface (e.g. hardware timestamps on a Lawicel adapter)
+    pub async fn capabilities(&self) -> Option<CanCapabilities> {
+        self.capabilities.lock().await.clone()
+    }
+
     /// Connect to a CAN interface
     pub async fn connect(&mut self, interface: &str, config: CanConfig, interface_type: InterfaceType) -> Result<(), String> {
         self.connect_with_bus(interface, config, interface_type, 0).await
@@ -96,6 +205,9 @@ impl CanManager {
 
         // Store interface name
         *self.interface_name.lock().await = Some(interface.to_string());
+        *self.adapter.lock().await = None;
+        *self.bus_status.lock().await = BusErrorStatus::default();
+        *self.capabilities.lock().await = None;
 
         // Clear previous messages
         self.messages.lock().await.clear();
@@ -104,6 +216,8 @@ impl CanManager {
         self.stats.messages_received.store(0, Ordering::SeqCst);
         self.stats.messages_sent.store(0, Ordering::SeqCst);
         self.stats.errors.store(0, Ordering::SeqCst);
+        self.stats.dropped_frames.store(0, Ordering::SeqCst);
+        self.stats.tx_dropped_frames.store(0, Ordering::SeqCst);
         *self.stats.start_time.lock().await = Some(Utc::now());
 
         // Reset stop signal
@@ -111,45 +225,30 @@ impl CanManager {
 
         // Create channels for message passing
         let (tx_sender, tx_receiver) = mpsc::channel::<CanMessage>(100);
-        let (rx_sender, rx_receiver) = mpsc::channel::<CanMessage>(1000);
+        let (rx_sender, rx_receiver) = mpsc::channel::<(CanMessage, Option<DateTime<Utc>>)>(1000);
 
         self.tx_sender = Some(tx_sender);
 
         // Clone for async task
+        let handles = ConnectionHandles {
+            status: self.status.clone(),
+            adapter: self.adapter.clone(),
+            bus_status: self.bus_status.clone(),
+            capabilities: self.capabilities.clone(),
+            stats: self.stats.clone(),
+            stop_signal: self.stop_signal.clone(),
+        };
         let status = self.status.clone();
-        let messages = self.messages.clone();
-        let stats = self.stats.clone();
-        let stop_signal = self.stop_signal.clone();
         let interface_str = interface.to_string();
 
         // Spawn background task for CAN communication
         tokio::spawn(async move {
             let result = match interface_type {
                 InterfaceType::Serial => {
-                    Self::run_serial_connection(
-                        &interface_str,
-                        config,
-                        tx_receiver,
-                        rx_sender,
-                        status.clone(),
-                        messages.clone(),
-                        stats.clone(),
-                        stop_signal.clone(),
-                        bus_id,
-                    ).await
+                    Self::run_serial_connection(&interface_str, config, tx_receiver, rx_sender, handles, bus_id).await
                 }
                 InterfaceType::Virtual => {
-                    Self::run_mock_connection(
-                        &interface_str,
-                        config,
-                        tx_receiver,
-                        rx_sender,
-                        status.clone(),
-                        messages.clone(),
-                        stats.clone(),
-                        stop_signal.clone(),
-                        bus_id,
-                    ).await
+                    Self::run_mock_connection(&interface_str, config, tx_receiver, rx_sender, handles, bus_id).await
                 }
                 _ => Err("Unsupported interface type".to_string()),
             };
@@ -165,15 +264,18 @@ impl CanManager {
         let stats_clone = self.stats.clone();
         tokio::spawn(async move {
             let mut rx_receiver = rx_receiver;
-            while let Some(msg) = rx_receiver.recv().await {
+            while let Some((msg, device_timestamp)) = rx_receiver.recv().await {
                 let manager_msg = ManagerMessage {
                     message: msg,
                     timestamp: Utc::now(),
+                    device_timestamp,
                 };
 
                 let mut msgs = messages_clone.lock().await;
                 if msgs.len() >= MAX_LIVE_MESSAGES {
+                    // UI isn't polling fast enough
                     msgs.pop_front();
+                    stats_clone.dropped_frames.fetch_add(1, Ordering::SeqCst);
                 }
                 msgs.push_back(manager_msg);
                 stats_clone.messages_received.fetch_add(1, Ordering::SeqCst);
@@ -187,36 +289,73 @@ impl CanManager {
         interface: &str,
         config: CanConfig,
         mut tx_receiver: mpsc::Receiver<CanMessage>,
-        rx_sender: mpsc::Sender<CanMessage>,
-        status: Arc<Mutex<ConnectionStatus>>,
-        _messages: Arc<Mutex<VecDeque<ManagerMessage>>>,
-        stats: Arc<ManagerStats>,
-        stop_signal: Arc<AtomicBool>,
+        rx_sender: mpsc::Sender<(CanMessage, Option<DateTime<Utc>>)>,
+        handles: ConnectionHandles,
         bus_id: u8,
     ) -> Result<(), String> {
+        let ConnectionHandles { status, adapter, bus_status, capabilities, stats, stop_signal } = handles;
         let mut can_if = SerialCanInterface::new_with_bus(interface, bus_id);
+        let mut pacer = TxPacer::new(config.tx_min_gap);
+        let mut pending_tx: Option<CanMessage> = None;
 
         // Connect to the interface
         can_if.connect(config.clone())
             .await
             .map_err(|e| format!("Failed to connect: {}", e))?;
 
+        *adapter.lock().await = can_if.adapter_description();
+        let mut reported_capabilities = can_if.capabilities();
+        *capabilities.lock().await = Some(reported_capabilities.clone());
         *status.lock().await = ConnectionStatus::Connected;
         eprintln!("[CAN Manager] Bus {} connected, starting receive loop...", bus_id);
 
         // Small delay after connection to ensure device is ready
         tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
 
+        let mut reported_drops = 0u64;
+        let mut reported_bus_status = BusErrorStatus::default();
+
         // Main loop
         loop {
             if stop_signal.load(Ordering::SeqCst) {
                 break;
             }
 
-            // Try to receive messages
-            match can_if.receive().await {
+            // Receive, or write the next queued frame once the pacer allows it
+            let result = tokio::select! {
+                result = can_if.receive() => result,
+                tx = Self::next_tx(&mut tx_receiver, &mut pending_tx, &pacer) => {
+                    match tx {
+                        Some(msg) => {
+                            Self::transmit(&mut can_if, &msg, &mut pacer, &stats).await;
+                            continue;
+                        }
+                        None => break,
+                    }
+                }
+            };
+
+            // Forward any new interface-side drops to the shared stats
+            let drops = can_if.dropped_frames();
+            if drops > reported_drops {
+                stats.dropped_frames.fetch_add(drops - reported_drops, Ordering::SeqCst);
+                reported_drops = drops;
+            }
+            let current_bus_status = can_if.bus_error_status();
+            if current_bus_status != reported_bus_status {
+                *bus_status.lock().await = current_bus_status;
+                reported_bus_status = current_bus_status;
+            }
+            // Unrecognised adapters show timestamping only once their frames carry it
+            if can_if.device_timestamps() && !reported_capabilities.hardware_timestamps {
+                reported_capabilities = can_if.capabilities();
+                *capabilities.lock().await = Some(reported_capabilities.clone());
+            }
+
+            match result {
                 Ok(Some(msg)) => {
-                    if rx_sender.send(msg).await.is_err() {
+                    let device_timestamp = can_if.device_timestamps().then_some(msg.timestamp);
+                    if rx_sender.send((msg, device_timestamp)).await.is_err() {
                         break;
                     }
                 }
@@ -229,20 +368,6 @@ impl CanManager {
                     eprintln!("Receive error: {}", e);
                 }
             }
-
-            // Try to send pending messages
-            match tx_receiver.try_recv() {
-                Ok(msg) => {
-                    if let Err(e) = can_if.send(&msg).await {
-                        stats.errors.fetch_add(1, Ordering::SeqCst);
-                        eprintln!("Send error: {}", e);
-                    } else {
-                        stats.messages_sent.fetch_add(1, Ordering::SeqCst);
-                    }
-                }
-                Err(mpsc::error::TryRecvError::Empty) => {}
-                Err(mpsc::error::TryRecvError::Disconnected) => break,
-            }
         }
 
         // Disconnect
@@ -256,15 +381,17 @@ impl CanManager {
         interface: &str,
         config: CanConfig,
         mut tx_receiver: mpsc::Receiver<CanMessage>,
-        rx_sender: mpsc::Sender<CanMessage>,
-        status: Arc<Mutex<ConnectionStatus>>,
-        _messages: Arc<Mutex<VecDeque<ManagerMessage>>>,
-        stats: Arc<ManagerStats>,
-        stop_signal: Arc<AtomicBool>,
+        rx_sender: mpsc::Sender<(CanMessage, Option<DateTime<Utc>>)>,
+        handles: ConnectionHandles,
         bus_id: u8,
     ) -> Result<(), String> {
+        let ConnectionHandles { status, stats, stop_signal, .. } = handles;
         let mut can_if = MockCanInterface::new_with_bus(interface, bus_id);
-        can_if.set_auto_generate(true);
+        // In loopback the only traffic is what was sent, so generated frames would get in the way
+        can_if.set_auto_generate(!config.loopback);
+        can_if.set_loopback(config.loopback);
+        let mut pacer = TxPacer::new(config.tx_min_gap);
+        let mut pending_tx: Option<CanMessage> = None;
 
         can_if.connect(config)
             .await
@@ -277,10 +404,24 @@ impl CanManager {
                 break;
             }
 
-            // Receive from mock (generates random messages)
-            match can_if.receive().await {
+            // Receive from mock (generates random messages), or write the next queued frame
+            let result = tokio::select! {
+                result = can_if.receive() => result,
+                tx = Self::next_tx(&mut tx_receiver, &mut pending_tx, &pacer) => {
+                    match tx {
+                        Some(msg) => {
+                            Self::transmit(&mut can_if, &msg, &mut pacer, &stats).await;
+                            continue;
+                        }
+                        None => break,
+                    }
+                }
+            };
+
+            match result {
                 Ok(Some(msg)) => {
-                    if rx_sender.send(msg).await.is_err() {
+                    let device_timestamp = can_if.device_timestamps().then_some(msg.timestamp);
+                    if rx_sender.send((msg, device_timestamp)).await.is_err() {
                         break;
                     }
                 }
@@ -292,20 +433,6 @@ impl CanManager {
                     eprintln!("Mock receive error: {}", e);
                 }
             }
-
-            // Send pending messages
-            match tx_receiver.try_recv() {
-                Ok(msg) => {
-                    if let Err(e) = can_if.send(&msg).await {
-                        stats.errors.fetch_add(1, Ordering::SeqCst);
-                        eprintln!("Mock send error: {}", e);
-                    } else {
-                        stats.messages_sent.fetch_add(1, Ordering::SeqCst);
-                    }
-                }
-                Err(mpsc::error::TryRecvError::Empty) => {}
-                Err(mpsc::error::TryRecvError::Disconnected) => break,
-            }
         }
 
         let _ = can_if.disconnect().await;
@@ -314,6 +441,38 @@ impl CanManager {
         Ok(())
     }
 
+    /// Next frame to transmit, resolving once the pacer's gap has elapsed; `None` once the
+    /// TX channel has closed. The frame is parked in `pending` while waiting, so dropping
+    /// this future (when a receive wins the select) doesn't lose it.
+    async fn next_tx(
+        tx_receiver: &mut mpsc::Receiver<CanMessage>,
+        pending: &mut Option<CanMessage>,
+        pacer: &TxPacer,
+    ) -> Option<CanMessage> {
+        if pending.is_none() {
+            *pending = Some(tx_receiver.recv().await?);
+        }
+        let wait = pacer.wait_time(Instant::now());
+        if !wait.is_zero() {
+            tokio::time::sleep(wait).await;
+        }
+        pending.take()
+    }
+
+    /// Write one frame and record it in the stats
+    async fn transmit<I: CanInterface>(can_if: &mut I, msg: &CanMessage, pacer: &mut TxPacer, stats: &ManagerStats) {
+        let result = can_if.send(msg).await;
+        pacer.mark_sent(Instant::now());
+
+        if let Err(e) = result {
+            stats.errors.fetch_add(1, Ordering::SeqCst);
+            stats.tx_dropped_frames.fetch_add(1, Ordering::SeqCst);
+            error!("Send error: {}", e);
+        } else {
+            stats.messages_sent.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
     /// Disconnect from the CAN interface
     pub async fn disconnect(&mut self) {
         self.stop_signal.store(true, Ordering::SeqCst);
@@ -322,11 +481,13 @@ impl CanManager {
         *self.interface_name.lock().await = None;
     }
 
-    /// Send a CAN message
+    /// Queue a CAN message for transmit
+    ///
+    /// Waits for room when the TX queue is full, so a burst is paced out rather than dropped.
     pub async fn send(&self, message: CanMessage) -> Result<(), String> {
         if let Some(sender) = &self.tx_sender {
             sender.send(message).await
-                .map_err(|e| format!("Failed to send: {}", e))?;
+                .map_err(|_| "Failed to send: interface closed".to_string())?;
         }
         Ok(())
     }
@@ -351,3 +512,92 @@ impl CanManager {
         self.messages.lock().await.len()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_tx_pacer_enforces_min_gap() {
+        let start = Instant::now();
+        let mut pacer = TxPacer::new(Duration::from_millis(2));
+        assert_eq!(pacer.wait_time(start), Duration::ZERO);
+
+        pacer.mark_sent(start);
+        assert_eq!(pacer.wait_time(start), Duration::from_millis(2));
+        assert_eq!(pacer.wait_time(start + Duration::from_millis(1)), Duration::from_millis(1));
+        assert_eq!(pacer.wait_time(start + Duration::from_millis(5)), Duration::ZERO);
+    }
+
+    #[tokio::test]
+    async fn test_full_tx_queue_waits_instead_of_dropping() {
+        let mut manager = CanManager::new();
+        let (tx_sender, mut tx_receiver) = mpsc::channel(1);
+        manager.tx_sender = Some(tx_sender);
+
+        manager.send(CanMessage::new(0, 0x100, vec![1].into())).await.unwrap();
+        let drain = tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            let mut received = Vec::new();
+            while let Some(msg) = tx_receiver.recv().await {
+                received.push(msg.data[0]);
+            }
+            received
+        });
+        manager.send(CanMessage::new(0, 0x100, vec![2].into())).await.unwrap();
+        manager.tx_sender = None;
+
+        assert_eq!(drain.await.unwrap(), vec![1, 2]);
+        assert_eq!(manager.stats.tx_dropped_frames.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_next_tx_keeps_frame_when_cancelled() {
+        let (tx_sender, mut tx_receiver) = mpsc::channel(4);
+        let mut pacer = TxPacer::new(Duration::from_millis(50));
+        pacer.mark_sent(Instant::now());
+        let mut pending = None;
+        tx_sender.send(CanMessage::new(0, 0x100, vec![7].into())).await.unwrap();
+
+        // Cancelled while waiting out the gap: the frame stays parked
+        let early = tokio::time::timeout(
+            Duration::from_millis(5),
+            CanManager::next_tx(&mut tx_receiver, &mut pending, &pacer),
+        ).await;
+        assert!(early.is_err());
+        assert!(pending.is_some());
+
+        let msg = CanManager::next_tx(&mut tx_receiver, &mut pending, &pacer).await.unwrap();
+        assert_eq!(msg.data[0], 7);
+        assert!(pending.is_none());
+
+        drop(tx_sender);
+        assert!(CanManager::next_tx(&mut tx_receiver, &mut pending, &pacer).await.is_none());
+    }
+
+    #[test]
+    fn test_timestamp_source_resolve() {
+        use chrono::Duration;
+        let arrival = Utc.timestamp_opt(1_000, 0).unwrap();
+        let device = arrival - Duration::milliseconds(3);
+        let with_device = ManagerMessage {
+            message: CanMessage::new(0, 0x100, Default::default()),
+            timestamp: arrival,
+            device_timestamp: Some(device),
+        };
+        let without_device = ManagerMessage { device_timestamp: None, ..with_device.clone() };
+
+        let mut origin = None;
+        assert_eq!(TimestampSource::DeviceIfAvailable.resolve(&with_device, &mut origin), device);
+        assert_eq!(TimestampSource::DeviceIfAvailable.resolve(&without_device, &mut origin), arrival);
+        assert_eq!(TimestampSource::Arrival.resolve(&with_device, &mut origin), arrival);
+        assert!(origin.is_none());
+
+        // First frame defines t = 0; later frames keep their offset
+        assert_eq!(TimestampSource::LogRelative.resolve(&with_device, &mut origin), DateTime::UNIX_EPOCH);
+        let later = ManagerMessage { timestamp: arrival + Duration::seconds(2), ..without_device };
+        let t = TimestampSource::LogRelative.resolve(&later, &mut origin);
+        assert_eq!((t - DateTime::UNIX_EPOCH).num_milliseconds(), 2_003);
+    }
+}
@@ -1,6 +1,6 @@
 use async_trait::async_trait;
 use crate::core::CanMessage;
-use crate::hardware::can_interface::{CanInterface, CanConfig, CanStatus, CanResult, InterfaceType, InterfaceInfo};
+use crate::hardware::can_interface::{CanInterface, CanConfig, CanStatus, CanResult, CanCapabilities, InterfaceType, InterfaceInfo};
 use std::collections::VecDeque;
 use chrono::Utc;
 
@@ -16,6 +16,8 @@ pub struct MockCanInterface {
     tx_buffer: VecDeque<CanMessage>,
     message_counter: u32,
     auto_generate: bool,
+    /// Echo sent frames into the receive buffer
+    loopback: bool,
     bus_id: u8,
 }
 
@@ -30,6 +32,7 @@ impl MockCanInterface {
             tx_buffer: VecDeque::new(),
             message_counter: 0,
             auto_generate: false,
+            loopback: false,
             bus_id: 0,
         }
     }
@@ -44,15 +47,30 @@ impl MockCanInterface {
             tx_buffer: VecDeque::new(),
             message_counter: 0,
             auto_generate: false,
+            loopback: false,
             bus_id,
         }
     }
 
+    /// The mock takes FD payloads and stamps frames with host time
+    pub fn mock_capabilities() -> CanCapabilities {
+        CanCapabilities {
+            fd: true,
+            max_payload: 64,
+            ..CanCapabilities::classic()
+        }
+    }
+
     /// Enable automatic message generation
     pub fn set_auto_generate(&mut self, enabled: bool) {
         self.auto_generate = enabled;
     }
 
+    /// Echo every sent frame back as a received one, stamped with the send time
+    pub fn set_loopback(&mut self, enabled: bool) {
+        self.loopback = enabled;
+    }
+
     /// Add a message to the receive buffer (for testing)
     pub fn inject_message(&mut self, message: CanMessage) {
         self.rx_buffer.push_back(message);
@@ -121,6 +139,12 @@ impl CanInterface for MockCanInterface {
             return Err("Not connected".into());
         }
         self.tx_buffer.push_back(message.clone());
+        if self.loopback {
+            let mut echo = message.clone();
+            echo.bus = self.bus_id;
+            echo.timestamp = Utc::now();
+            self.rx_buffer.push_back(echo);
+        }
         Ok(())
     }
 
@@ -149,6 +173,10 @@ impl CanInterface for MockCanInterface {
     fn supports_fd(&self) -> bool {
         true
     }
+
+    fn capabilities(&self) -> CanCapabilities {
+        Self::mock_capabilities()
+    }
 }
 
 /// List available mock interfaces
@@ -197,6 +225,20 @@ mod tests {
         assert_eq!(iface.rx_buffer_size(), 0);
     }
 
+    #[tokio::test]
+    async fn test_mock_interface_loopback() {
+        let mut iface = MockCanInterface::new_with_bus("test", 2);
+        iface.set_loopback(true);
+        iface.connect(CanConfig::default()).await.unwrap();
+
+        let msg = CanMessage::new(0, 0x321, crate::core::CanData::from_slice(&[9, 8]));
+        iface.send(&msg).await.unwrap();
+        let echoed = iface.receive().await.unwrap().unwrap();
+        assert_eq!((echoed.bus, echoed.id), (2, 0x321));
+        assert_eq!(echoed.data, vec![9, 8]);
+        assert_eq!(iface.take_sent_messages().len(), 1);
+    }
+
     #[tokio::test]
     async fn test_mock_interface_auto_generate() {
         let mut iface = MockCanInterface::new("test");
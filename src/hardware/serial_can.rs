@@ -1,17 +1,102 @@
 use async_trait::async_trait;
 use crate::core::CanMessage;
-use crate::hardware::can_interface::{CanInterface, CanConfig, CanStatus, CanResult, InterfaceType, InterfaceInfo};
+use crate::hardware::can_interface::{BusErrorStatus, CanCapabilities, CanInterface, CanConfig, CanStatus, ErrorState, CanResult, InterfaceType, InterfaceInfo, RxOverflowPolicy, DEFAULT_RX_BUFFER_SIZE};
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use tokio_serial::SerialPortBuilderExt;
 use tokio::sync::mpsc;
 use std::collections::VecDeque;
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
+use chrono::{DateTime, Utc};
 use tracing::{debug, info, warn, error};
 
-/// Buffer size for received messages
-const RX_BUFFER_SIZE: usize = 10000;
+/// SLCAN timestamps are a millisecond counter that wraps every minute
+const SLCAN_TIMESTAMP_WRAP_MS: u32 = 60_000;
+
+/// Lawicel `F` status flag bits (the reply is `Fxx`, xx the flag byte in hex)
+const STATUS_FLAG_ERROR_WARNING: u8 = 0x04;
+const STATUS_FLAG_ERROR_PASSIVE: u8 = 0x20;
+
+/// Turns the adapter's wrapping millisecond counter into absolute times,
+/// anchored to the host time of the first stamped frame
+struct SlcanClock {
+    last_ms: u16,
+    time: DateTime<Utc>,
+}
+
+impl SlcanClock {
+    fn new(first_ms: u16, anchor: DateTime<Utc>) -> Self {
+        Self { last_ms: first_ms, time: anchor }
+    }
+
+    /// Advance to the next frame's counter value (assumes < 60 s between frames)
+    fn advance(&mut self, ms: u16) -> DateTime<Utc> {
+        let delta = (ms as u32 + SLCAN_TIMESTAMP_WRAP_MS - self.last_ms as u32) % SLCAN_TIMESTAMP_WRAP_MS;
+        self.last_ms = ms;
+        self.time += chrono::Duration::milliseconds(delta as i64);
+        self.time
+    }
+}
+
+/// SLCAN adapter family, classified from its reply to the `V` (version) command
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SlcanAdapter {
+    /// Lawicel CANUSB/CAN232: `V` plus hardware and software version digits
+    Lawicel,
+    /// CANable slcan firmware: names its repo, or answers lowercase `v` plus a hex build hash
+    Canable,
+    /// candleLight-derived firmware
+    CandleLight,
+    Cantact,
+    /// No or unrecognised reply
+    Unknown,
+}
+
+impl SlcanAdapter {
+    pub fn label(self) -> &'static str {
+        match self {
+            SlcanAdapter::Lawicel => "Lawicel",
+            SlcanAdapter::Canable => "CANable",
+            SlcanAdapter::CandleLight => "candleLight",
+            SlcanAdapter::Cantact => "CANtact",
+            SlcanAdapter::Unknown => "Unknown SLCAN",
+        }
+    }
+
+    /// Classify the adapter from whatever it sent on open and in reply to `V`
+    pub fn detect(response: &str) -> Self {
+        let text = response.trim_matches(|c: char| c.is_whitespace() || c == '\x07');
+        let lower = text.to_ascii_lowercase();
+        if lower.contains("candle") {
+            SlcanAdapter::CandleLight
+        } else if lower.contains("cantact") {
+            SlcanAdapter::Cantact
+        } else if lower.contains("canable") || Self::is_build_hash(text) {
+            SlcanAdapter::Canable
+        } else if text.len() == 5 && text.starts_with('V') && text[1..].chars().all(|c| c.is_ascii_hexdigit()) {
+            SlcanAdapter::Lawicel
+        } else {
+            SlcanAdapter::Unknown
+        }
+    }
+
+    /// CANable's `v` + git hash reply; other `v...` replies stay unrecognised
+    fn is_build_hash(text: &str) -> bool {
+        text.strip_prefix('v')
+            .is_some_and(|hash| (6..=40).contains(&hash.len()) && hash.chars().all(|c| c.is_ascii_hexdigit()))
+    }
+
+    /// Recognised adapters answer every setup command; a missing ACK is a real failure
+    fn acks_commands(self) -> bool {
+        self != SlcanAdapter::Unknown
+    }
+
+    /// Only Lawicel firmware implements the `Z1` timestamp command
+    fn supports_timestamps(self) -> bool {
+        self == SlcanAdapter::Lawicel
+    }
+}
 
 /// SLCAN/Lawicel protocol serial CAN interface
 ///
@@ -33,12 +118,29 @@ pub struct SerialCanInterface {
     rx_buffer: VecDeque<CanMessage>,
     /// RX buffer size counter for atomic access
     rx_count: Arc<AtomicUsize>,
+    /// Maximum number of buffered frames (from `CanConfig::rx_buffer_size`)
+    rx_capacity: usize,
+    /// Behaviour when the RX buffer is full
+    rx_overflow: RxOverflowPolicy,
+    /// Frames dropped because the RX buffer was full
+    dropped_frames: u64,
     /// TX channel for sending messages to the serial task
     tx_sender: Option<mpsc::Sender<Vec<u8>>>,
     /// Line buffer for accumulating partial SLCAN frames
     line_buffer: String,
     /// Bus ID for this interface
     bus_id: u8,
+    /// Adapter clock, once the adapter has sent a timestamped frame
+    device_clock: Option<SlcanClock>,
+    /// Adapter family and version reply, detected on connect
+    adapter: Option<(SlcanAdapter, String)>,
+    /// How often to poll the status flags (from `CanConfig::status_poll_interval`)
+    status_poll_interval: Option<Duration>,
+    last_status_poll: Option<Instant>,
+    /// An `F` poll was sent and its reply hasn't arrived yet
+    status_poll_pending: bool,
+    /// Error state from the last status reply
+    bus_error_status: BusErrorStatus,
 }
 
 impl SerialCanInterface {
@@ -50,11 +152,20 @@ impl SerialCanInterface {
             status: CanStatus::Disconnected,
             port: None,
             config: None,
-            rx_buffer: VecDeque::with_capacity(RX_BUFFER_SIZE),
+            rx_buffer: VecDeque::with_capacity(DEFAULT_RX_BUFFER_SIZE),
             rx_count: Arc::new(AtomicUsize::new(0)),
+            rx_capacity: DEFAULT_RX_BUFFER_SIZE,
+            rx_overflow: RxOverflowPolicy::default(),
+            dropped_frames: 0,
             tx_sender: None,
             line_buffer: String::new(),
             bus_id: 0,
+            device_clock: None,
+            adapter: None,
+            status_poll_interval: None,
+            last_status_poll: None,
+            status_poll_pending: false,
+            bus_error_status: BusErrorStatus::default(),
         }
     }
 
@@ -66,14 +177,31 @@ impl SerialCanInterface {
             status: CanStatus::Disconnected,
             port: None,
             config: None,
-            rx_buffer: VecDeque::with_capacity(RX_BUFFER_SIZE),
+            rx_buffer: VecDeque::with_capacity(DEFAULT_RX_BUFFER_SIZE),
             rx_count: Arc::new(AtomicUsize::new(0)),
+            rx_capacity: DEFAULT_RX_BUFFER_SIZE,
+            rx_overflow: RxOverflowPolicy::default(),
+            dropped_frames: 0,
             tx_sender: None,
             line_buffer: String::new(),
             bus_id,
+            device_clock: None,
+            adapter: None,
+            status_poll_interval: None,
+            last_status_poll: None,
+            status_poll_pending: false,
+            bus_error_status: BusErrorStatus::default(),
         }
     }
 
+    /// Detected adapter and its version reply, for display (e.g. "Lawicel (V1013)")
+    pub fn adapter_description(&self) -> Option<String> {
+        self.adapter.as_ref().map(|(adapter, version)| match version.is_empty() {
+            true => adapter.label().to_string(),
+            false => format!("{} ({})", adapter.label(), version),
+        })
+    }
+
     /// List available serial ports that might be CAN interfaces
     pub fn list_serial_ports() -> Vec<String> {
         let ports = tokio_serial::available_ports()
@@ -87,6 +215,15 @@ impl SerialCanInterface {
             .collect()
     }
 
+    /// What a basic SLCAN adapter offers before it has sent any frames:
+    /// the `S0`-`S8` bitrates, listen-only via `L`, classic frames only
+    pub fn slcan_capabilities() -> CanCapabilities {
+        CanCapabilities {
+            bitrates: vec![10_000, 20_000, 50_000, 100_000, 125_000, 250_000, 500_000, 800_000, 1_000_000],
+            ..CanCapabilities::classic()
+        }
+    }
+
     /// Build SLCAN command to set bitrate
     fn build_bitrate_command(bitrate: u32) -> Vec<u8> {
         // SLCAN bitrate codes
@@ -141,48 +278,160 @@ impl SerialCanInterface {
         }
     }
 
-    /// Parse a standard (11-bit ID) CAN frame
-    fn parse_standard_frame(data: &str, _is_rtr: bool, bus_id: u8) -> Option<CanMessage> {
-        // Format: TIIIDDDDDDDDDDD (ID = 3 hex chars, DLC = 1 hex char, Data = 0-16 hex chars)
-        if data.len() < 4 {
+    /// Parse an adapter status line: the `Fxx` reply to a status poll (Lawicel flag
+    /// byte, no counters) or an unsolicited `sSrrrttt` state report as the Linux slcan
+    /// driver reads it (state a/w/p/b, then 3-digit decimal RX and TX error counters)
+    fn parse_status_line(line: &str) -> Option<BusErrorStatus> {
+        if let Some(flags) = line.strip_prefix('F') {
+            if flags.len() != 2 {
+                return None;
+            }
+            let flags = u8::from_str_radix(flags, 16).ok()?;
+            let state = if flags & STATUS_FLAG_ERROR_PASSIVE != 0 {
+                ErrorState::Passive
+            } else if flags & STATUS_FLAG_ERROR_WARNING != 0 {
+                ErrorState::Warning
+            } else {
+                ErrorState::Active
+            };
+            return Some(BusErrorStatus { state, counters: None });
+        }
+
+        let report = line.strip_prefix('s')?;
+        if report.len() != 7 || !report.is_char_boundary(1) {
             return None;
         }
+        let state = match &report[..1] {
+            "a" => ErrorState::Active,
+            "w" => ErrorState::Warning,
+            "p" => ErrorState::Passive,
+            "b" => ErrorState::BusOff,
+            _ => return None,
+        };
+        let rx = report.get(1..4)?.parse().ok()?;
+        let tx = report.get(4..7)?.parse().ok()?;
+        Some(BusErrorStatus { state, counters: Some((rx, tx)) })
+    }
 
-        let id = u32::from_str_radix(&data[0..3], 16).ok()?;
-        let dlc = data[3..4].parse::<usize>().ok()?;
+    /// Record a status report, logging changes of error state
+    fn set_bus_error_status(&mut self, status: BusErrorStatus) {
+        if status.state != self.bus_error_status.state {
+            if status.state.is_fault() {
+                warn!("{}: {}", self.name, status.describe());
+            } else {
+                info!("{}: {}", self.name, status.describe());
+            }
+        }
+        self.bus_error_status = status;
+    }
 
-        let expected_len = 4 + dlc * 2;
-        if data.len() < expected_len {
-            return None;
+    /// Ask for the status flags (`F`) once per poll interval; the reply comes back
+    /// through `receive` like a frame
+    async fn poll_status(&mut self) {
+        let Some(interval) = self.status_poll_interval else {
+            return;
+        };
+        if self.last_status_poll.is_some_and(|last| last.elapsed() < interval) {
+            return;
         }
+        self.last_status_poll = Some(Instant::now());
+        if let Some(port) = self.port.as_mut() {
+            match port.write_all(b"F\r").await {
+                Ok(()) => self.status_poll_pending = true,
+                Err(e) => warn!("{}: status poll failed: {}", self.name, e),
+            }
+        }
+    }
 
-        let hex_data = &data[4..expected_len];
-        let msg_data = Self::parse_hex_data(hex_data)?;
+    /// Drop BEL (0x07) error replies from the line buffer. They come without a CR,
+    /// so left in place they'd be glued onto the next frame line. A BEL while a
+    /// poll is outstanding means the adapter doesn't implement `F`: stop polling.
+    fn strip_bel_replies(&mut self) {
+        if !self.line_buffer.contains('\x07') {
+            return;
+        }
+        self.line_buffer.retain(|c| c != '\x07');
+        if self.status_poll_pending {
+            self.status_poll_pending = false;
+            if self.status_poll_interval.take().is_some() {
+                info!("{}: adapter rejected the status command (F), no longer polling", self.name);
+            }
+        }
+    }
 
-        Some(CanMessage::new(bus_id, id, msg_data.into()))
+    /// Parse a standard (11-bit ID) CAN frame
+    fn parse_standard_frame(data: &str, is_rtr: bool, bus_id: u8) -> Option<CanMessage> {
+        // Format: tIIIDDDDDDDDDDD (ID = 3 hex chars, DLC = 1 hex char, Data = 0-16 hex chars)
+        // RTR:    rIIID (no data bytes)
+        Self::parse_frame_body(data, 3, is_rtr, bus_id)
     }
 
     /// Parse an extended (29-bit ID) CAN frame
-    fn parse_extended_frame(data: &str, _is_rtr: bool, bus_id: u8) -> Option<CanMessage> {
+    fn parse_extended_frame(data: &str, is_rtr: bool, bus_id: u8) -> Option<CanMessage> {
         // Format: TIIIIIIIIDDDDDDDDDDD (ID = 8 hex chars, DLC = 1 hex char, Data = 0-16 hex chars)
-        if data.len() < 9 {
+        // RTR:    RIIIIIIIID (no data bytes)
+        Self::parse_frame_body(data, 8, is_rtr, bus_id)
+    }
+
+    /// Parse the ID, DLC and (for data frames) payload following the frame type character
+    fn parse_frame_body(data: &str, id_len: usize, is_rtr: bool, bus_id: u8) -> Option<CanMessage> {
+        if data.len() < id_len + 1 {
             return None;
         }
 
-        let id = u32::from_str_radix(&data[0..8], 16).ok()?;
-        let dlc = data[8..9].parse::<usize>().ok()?;
+        let id = u32::from_str_radix(data.get(0..id_len)?, 16).ok()?;
+        let dlc = data.get(id_len..id_len + 1)?.parse::<usize>().ok()?;
+        if dlc > 8 {
+            return None;
+        }
 
-        let expected_len = 9 + dlc * 2;
+        if is_rtr {
+            return Some(CanMessage::new_rtr(bus_id, id, dlc as u8));
+        }
+
+        let expected_len = id_len + 1 + dlc * 2;
         if data.len() < expected_len {
             return None;
         }
 
-        let hex_data = &data[9..expected_len];
+        let hex_data = &data[id_len + 1..expected_len];
         let msg_data = Self::parse_hex_data(hex_data)?;
 
         Some(CanMessage::new(bus_id, id, msg_data.into()))
     }
 
+    /// Millisecond timestamp appended to a frame by adapters with timestamping on (`Z1`)
+    ///
+    /// Present when the line has exactly 4 hex digits after the payload.
+    fn frame_timestamp(line: &str) -> Option<u16> {
+        let (id_len, is_rtr) = match line.chars().next()? {
+            't' => (3, false),
+            'T' => (8, false),
+            'r' => (3, true),
+            'R' => (8, true),
+            _ => return None,
+        };
+        let dlc = line.get(1 + id_len..2 + id_len)?.parse::<usize>().ok()?;
+        let frame_len = 2 + id_len + if is_rtr { 0 } else { dlc * 2 };
+        if line.len() != frame_len + 4 {
+            return None;
+        }
+        let ms = u16::from_str_radix(line.get(frame_len..)?, 16).ok()?;
+        ((ms as u32) < SLCAN_TIMESTAMP_WRAP_MS).then_some(ms)
+    }
+
+    /// Replace the host timestamp with the adapter's, if the line carries one
+    fn apply_device_timestamp(&mut self, mut msg: CanMessage, line: &str) -> CanMessage {
+        if let Some(ms) = Self::frame_timestamp(line) {
+            let anchor = msg.timestamp;
+            msg.timestamp = match self.device_clock.as_mut() {
+                Some(clock) => clock.advance(ms),
+                None => self.device_clock.insert(SlcanClock::new(ms, anchor)).time,
+            };
+        }
+        msg
+    }
+
     /// Parse hex data string into bytes
     fn parse_hex_data(hex: &str) -> Option<Vec<u8>> {
         (0..hex.len())
@@ -191,23 +440,87 @@ impl SerialCanInterface {
             .collect()
     }
 
+    /// Encode a payload length as the single hex DLC nibble SLCAN expects
+    ///
+    /// Classic SLCAN (`t`/`T`/`r`/`R`) only carries DLC 0-8, so longer payloads are rejected.
+    fn encode_dlc(len: usize) -> CanResult<char> {
+        if len > 8 {
+            return Err(format!("SLCAN frame payload of {} bytes exceeds the 8-byte classic CAN limit", len).into());
+        }
+        Ok(std::char::from_digit(len as u32, 16)
+            .map(|c| c.to_ascii_uppercase())
+            .unwrap_or('0'))
+    }
+
     /// Build an SLCAN command to transmit a CAN frame
-    fn build_tx_command(message: &CanMessage) -> Vec<u8> {
-        let dlc = message.data.len();
-        let data_hex: String = message.data.iter()
-            .map(|b| format!("{:02X}", b))
-            .collect();
+    ///
+    /// RTR frames use the `r`/`R` prefix and carry only the requested DLC.
+    fn build_tx_command(message: &CanMessage) -> CanResult<Vec<u8>> {
+        let dlc = Self::encode_dlc(message.data.len())?;
+        let data_hex: String = if message.is_rtr {
+            String::new()
+        } else {
+            message.data.iter()
+                .map(|b| format!("{:02X}", b))
+                .collect()
+        };
+
+        let prefix = match (message.is_extended(), message.is_rtr) {
+            (false, false) => 't',
+            (true, false) => 'T',
+            (false, true) => 'r',
+            (true, true) => 'R',
+        };
 
         if message.is_extended() {
             // Extended frame: TIIIIIIIIDDDDDDDDDDD
-            format!("T{:08X}{}{}\r", message.id, dlc, data_hex).into_bytes()
+            Ok(format!("{}{:08X}{}{}\r", prefix, message.id, dlc, data_hex).into_bytes())
         } else {
             // Standard frame: tIIIDDDDDDDDDDD
-            format!("t{:03X}{}{}\r", message.id, dlc, data_hex).into_bytes()
+            Ok(format!("{}{:03X}{}{}\r", prefix, message.id, dlc, data_hex).into_bytes())
+        }
+    }
+
+    /// Buffer a received frame, applying the overflow policy when the buffer is full
+    fn buffer_frame(&mut self, msg: CanMessage) {
+        if self.rx_buffer.len() >= self.rx_capacity {
+            self.dropped_frames += 1;
+            if self.dropped_frames == 1 || self.dropped_frames.is_multiple_of(1000) {
+                warn!("{}: RX buffer full ({} frames), {} frames dropped", self.name, self.rx_capacity, self.dropped_frames);
+            }
+            match self.rx_overflow {
+                RxOverflowPolicy::DropNewest => return,
+                RxOverflowPolicy::DropOldest => {
+                    if self.rx_buffer.pop_front().is_some() {
+                        self.rx_count.fetch_sub(1, Ordering::SeqCst);
+                    }
+                }
+            }
+        }
+        self.rx_buffer.push_back(msg);
+        self.rx_count.fetch_add(1, Ordering::SeqCst);
+    }
+
+    /// Send a setup command: recognised adapters must ACK it, unrecognised ones
+    /// get a fire-and-forget retry since some clones never answer
+    async fn send_setup_command(port: &mut tokio_serial::SerialStream, cmd: &[u8], adapter: SlcanAdapter) -> CanResult<()> {
+        match Self::send_command_wait_ack(port, cmd).await {
+            Ok(()) => {
+                eprintln!("[CAN-Viz SerialCan] {:?} ACK received!", String::from_utf8_lossy(cmd));
+                Ok(())
+            }
+            Err(e) if adapter.acks_commands() => Err(e),
+            Err(_) => {
+                eprintln!("[CAN-Viz SerialCan] {:?} timed out waiting for ACK, trying fire-and-forget mode...", String::from_utf8_lossy(cmd));
+                let _ = port.write_all(cmd).await;
+                let _ = port.flush().await;
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                eprintln!("[CAN-Viz SerialCan] Command sent (no ACK expected)");
+                Ok(())
+            }
         }
     }
 
-    /// Send a command and wait for SLCAN acknowledgment (\r)
     async fn send_command_wait_ack(port: &mut tokio_serial::SerialStream, cmd: &[u8]) -> CanResult<()> {
         eprintln!("[CAN-Viz SLCAN] Sending command: {:?} ({})", cmd, String::from_utf8_lossy(cmd));
         debug!("Sending SLCAN command: {}", String::from_utf8_lossy(cmd));
@@ -338,15 +651,12 @@ impl CanInterface for SerialCanInterface {
 
         // Try to read any initial announcement with a short timeout
         let mut init_buf = [0u8; 256];
+        let mut announcement = String::new();
         match tokio::time::timeout(Duration::from_millis(100), port.read(&mut init_buf)).await {
             Ok(Ok(n)) if n > 0 => {
                 eprintln!("[CAN-Viz SerialCan] Device sent {} bytes on open: {:02X?}", n, &init_buf[..n]);
-                if let Ok(s) = std::str::from_utf8(&init_buf[..n]) {
-                    eprintln!("[CAN-Viz SerialCan] As string: {:?}", s);
-                    if s.contains("candle") || s.contains("CANDLE") || s.contains("slcan") || s.contains("SLCAN") {
-                        eprintln!("[CAN-Viz SerialCan] Detected device identification!");
-                    }
-                }
+                announcement = String::from_utf8_lossy(&init_buf[..n]).into_owned();
+                eprintln!("[CAN-Viz SerialCan] As string: {:?}", announcement);
             }
             _ => {
                 eprintln!("[CAN-Viz SerialCan] No initial data from device");
@@ -399,6 +709,12 @@ impl CanInterface for SerialCanInterface {
         } else {
             eprintln!("[CAN-Viz SerialCan] No response to version command");
         }
+        let version = String::from_utf8_lossy(&ver_data).trim_matches(|c: char| c.is_whitespace() || c == '\x07').to_string();
+        let adapter = match SlcanAdapter::detect(&version) {
+            SlcanAdapter::Unknown => SlcanAdapter::detect(&announcement),
+            detected => detected,
+        };
+        info!("Detected SLCAN adapter: {} (version reply {:?})", adapter.label(), version);
 
         // Clear any remaining data in the buffer before sending bitrate command
         eprintln!("[CAN-Viz SerialCan] Clearing any remaining buffer data...");
@@ -421,51 +737,27 @@ impl CanInterface for SerialCanInterface {
         let bitrate_cmd = Self::build_bitrate_command(config.bitrate);
         eprintln!("[CAN-Viz SerialCan] Sending bitrate command: {:?}", String::from_utf8_lossy(&bitrate_cmd));
 
-        // Try with ACK first, then try without if it times out
-        let mut bitrate_success = false;
-        match Self::send_command_wait_ack(&mut port, &bitrate_cmd).await {
-            Ok(()) => {
-                eprintln!("[CAN-Viz SerialCan] Bitrate command ACK received!");
-                bitrate_success = true;
-            }
-            Err(_) => {
-                eprintln!("[CAN-Viz SerialCan] Bitrate command timed out waiting for ACK, trying fire-and-forget mode...");
-                // Try fire-and-forget mode
-                let _ = port.write_all(&bitrate_cmd).await;
-                let _ = port.flush().await;
-                tokio::time::sleep(Duration::from_millis(50)).await;
-                bitrate_success = true;
-                eprintln!("[CAN-Viz SerialCan] Bitrate command sent (no ACK expected)");
-            }
-        }
-
-        if !bitrate_success {
-            return Err("Bitrate command failed".into());
-        }
+        Self::send_setup_command(&mut port, &bitrate_cmd, adapter).await
+            .map_err(|e| format!("{} rejected bitrate command: {}", adapter.label(), e))?;
 
         info!("Bitrate set to {} bps", config.bitrate);
 
         // Small delay after bitrate configuration
         tokio::time::sleep(Duration::from_millis(50)).await;
 
-        // Open CAN channel - also try fire-and-forget if ACK fails
-        let open_cmd = Self::build_open_command(config.listen_only);
-        eprintln!("[CAN-Viz SerialCan] Sending open command: {:?}", String::from_utf8_lossy(&open_cmd));
-
-        match Self::send_command_wait_ack(&mut port, &open_cmd).await {
-            Ok(()) => {
-                eprintln!("[CAN-Viz SerialCan] Open command ACK received!");
-            }
-            Err(_) => {
-                eprintln!("[CAN-Viz SerialCan] Open command timed out waiting for ACK, trying fire-and-forget mode...");
-                // Try fire-and-forget mode
-                let _ = port.write_all(&open_cmd).await;
-                let _ = port.flush().await;
-                tokio::time::sleep(Duration::from_millis(50)).await;
-                eprintln!("[CAN-Viz SerialCan] Open command sent (no ACK expected)");
+        // Adapter timestamps (must be set while the channel is closed); frames fall back to host time without them
+        if adapter.supports_timestamps() {
+            if let Err(e) = Self::send_command_wait_ack(&mut port, b"Z1\r").await {
+                warn!("{} did not enable timestamps: {}", adapter.label(), e);
             }
         }
 
+        // Open CAN channel
+        let open_cmd = Self::build_open_command(config.listen_only);
+        eprintln!("[CAN-Viz SerialCan] Sending open command: {:?}", String::from_utf8_lossy(&open_cmd));
+        Self::send_setup_command(&mut port, &open_cmd, adapter).await
+            .map_err(|e| format!("{} rejected open command: {}", adapter.label(), e))?;
+
         info!("CAN channel opened (listen_only: {})", config.listen_only);
 
         // Warm-up period: Give the device time to start receiving CAN messages
@@ -523,9 +815,21 @@ impl CanInterface for SerialCanInterface {
         }
 
         self.port = Some(port);
+        self.rx_capacity = config.rx_buffer_size.max(1);
+        self.rx_overflow = config.rx_overflow;
+        self.dropped_frames = 0;
         self.config = Some(config);
         self.status = CanStatus::Connected;
         self.line_buffer.clear();
+        self.device_clock = None;
+        self.adapter = Some((adapter, version));
+        // An adapter that didn't answer `V` isn't going to answer `F` either
+        self.status_poll_interval = self.config.as_ref()
+            .and_then(|c| c.status_poll_interval)
+            .filter(|_| adapter != SlcanAdapter::Unknown);
+        self.last_status_poll = None;
+        self.status_poll_pending = false;
+        self.bus_error_status = BusErrorStatus::default();
 
         info!("Successfully connected to {}", self.name);
         Ok(())
@@ -555,7 +859,7 @@ impl CanInterface for SerialCanInterface {
     async fn send(&mut self, message: &CanMessage) -> CanResult<()> {
         let port = self.port.as_mut().ok_or("Not connected")?;
 
-        let cmd = Self::build_tx_command(message);
+        let cmd = Self::build_tx_command(message)?;
         port.write_all(&cmd).await?;
         port.flush().await?;
 
@@ -569,6 +873,8 @@ impl CanInterface for SerialCanInterface {
             return Ok(Some(msg));
         }
 
+        self.poll_status().await;
+
         // Try to read more data from the port
         if let Some(port) = self.port.as_mut() {
             let mut buf = [0u8; 256];
@@ -589,6 +895,7 @@ impl CanInterface for SerialCanInterface {
                     // Accumulate data in line buffer
                     if let Ok(text) = std::str::from_utf8(data) {
                         self.line_buffer.push_str(text);
+                        self.strip_bel_replies();
 
                         // Process complete lines (SLCAN frames end with \r)
                         while let Some(cr_pos) = self.line_buffer.find('\r') {
@@ -596,15 +903,16 @@ impl CanInterface for SerialCanInterface {
                             // Remove the processed line including the \r
                             self.line_buffer = self.line_buffer[cr_pos + 1..].to_string();
 
-                            if !line.is_empty() {
+                            if let Some(status) = Self::parse_status_line(&line) {
+                                self.status_poll_pending = false;
+                                self.set_bus_error_status(status);
+                            } else if !line.is_empty() {
                                 debug!("Processing SLCAN line: {:?}", line);
                                 if let Some(msg) = self.parse_frame(&line) {
                                     debug!("Parsed CAN message: ID=0x{:03X}, len={}",
                                            msg.id, msg.data.len());
-                                    if self.rx_buffer.len() < RX_BUFFER_SIZE {
-                                        self.rx_buffer.push_back(msg);
-                                        self.rx_count.fetch_add(1, Ordering::SeqCst);
-                                    }
+                                    let msg = self.apply_device_timestamp(msg, &line);
+                                    self.buffer_frame(msg);
                                 } else {
                                     warn!("Failed to parse SLCAN frame: {:?}", line);
                                 }
@@ -616,15 +924,16 @@ impl CanInterface for SerialCanInterface {
                             let line = self.line_buffer[..lf_pos].trim().to_string();
                             self.line_buffer = self.line_buffer[lf_pos + 1..].to_string();
 
-                            if !line.is_empty() {
+                            if let Some(status) = Self::parse_status_line(&line) {
+                                self.status_poll_pending = false;
+                                self.set_bus_error_status(status);
+                            } else if !line.is_empty() {
                                 debug!("Processing SLCAN line (LF): {:?}", line);
                                 if let Some(msg) = self.parse_frame(&line) {
                                     debug!("Parsed CAN message: ID=0x{:03X}, len={}",
                                            msg.id, msg.data.len());
-                                    if self.rx_buffer.len() < RX_BUFFER_SIZE {
-                                        self.rx_buffer.push_back(msg);
-                                        self.rx_count.fetch_add(1, Ordering::SeqCst);
-                                    }
+                                    let msg = self.apply_device_timestamp(msg, &line);
+                                    self.buffer_frame(msg);
                                 }
                             }
                         }
@@ -659,9 +968,30 @@ impl CanInterface for SerialCanInterface {
         self.rx_count.store(0, Ordering::SeqCst);
     }
 
+    fn dropped_frames(&self) -> u64 {
+        self.dropped_frames
+    }
+
+    fn bus_error_status(&self) -> BusErrorStatus {
+        self.bus_error_status
+    }
+
+    fn device_timestamps(&self) -> bool {
+        self.device_clock.is_some()
+    }
+
     fn supports_fd(&self) -> bool {
         false  // Basic SLCAN doesn't support CAN FD
     }
+
+    fn capabilities(&self) -> CanCapabilities {
+        // Adapters we turn `Z1` on for timestamp from the start; others only once their frames show it
+        let timestamping_adapter = self.adapter.as_ref().is_some_and(|(adapter, _)| adapter.supports_timestamps());
+        CanCapabilities {
+            hardware_timestamps: timestamping_adapter || self.device_timestamps(),
+            ..Self::slcan_capabilities()
+        }
+    }
 }
 
 /// List all available serial CAN interfaces
@@ -676,3 +1006,178 @@ pub fn list_interfaces() -> Vec<InterfaceInfo> {
         })
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::CanData;
+
+    #[test]
+    fn test_rx_overflow_policy() {
+        let mut iface = SerialCanInterface::new("test");
+        iface.rx_capacity = 2;
+        for id in 1..=3 {
+            iface.buffer_frame(CanMessage::new(0, id, CanData::new()));
+        }
+        assert_eq!(iface.dropped_frames(), 1);
+        assert_eq!(iface.rx_buffer_size(), 2);
+        assert_eq!(iface.rx_buffer.back().unwrap().id, 2);
+
+        iface.rx_overflow = RxOverflowPolicy::DropOldest;
+        iface.buffer_frame(CanMessage::new(0, 4, CanData::new()));
+        assert_eq!(iface.dropped_frames(), 2);
+        assert_eq!(iface.rx_buffer_size(), 2);
+        assert_eq!(iface.rx_buffer.front().unwrap().id, 2);
+        assert_eq!(iface.rx_buffer.back().unwrap().id, 4);
+    }
+
+    #[test]
+    fn test_rtr_standard_roundtrip() {
+        let msg = CanMessage::new_rtr(0, 0x123, 4);
+        let cmd = SerialCanInterface::build_tx_command(&msg).unwrap();
+        assert_eq!(cmd, b"r1234\r");
+
+        let iface = SerialCanInterface::new("test");
+        let parsed = iface.parse_frame("r1234").unwrap();
+        assert!(parsed.is_rtr);
+        assert_eq!(parsed.id, 0x123);
+        assert_eq!(parsed.data.len(), 4);
+    }
+
+    #[test]
+    fn test_rtr_extended_roundtrip() {
+        let msg = CanMessage::new_rtr(0, 0x18DAF110, 8);
+        let cmd = SerialCanInterface::build_tx_command(&msg).unwrap();
+        assert_eq!(cmd, b"R18DAF1108\r");
+
+        let iface = SerialCanInterface::new("test");
+        let parsed = iface.parse_frame("R18DAF1108").unwrap();
+        assert!(parsed.is_rtr);
+        assert_eq!(parsed.id, 0x18DAF110);
+        assert_eq!(parsed.data.len(), 8);
+    }
+
+    #[test]
+    fn test_zero_length_data_frame_roundtrip() {
+        let msg = CanMessage::new(0, 0x7FF, CanData::new());
+        let cmd = SerialCanInterface::build_tx_command(&msg).unwrap();
+        assert_eq!(cmd, b"t7FF0\r");
+
+        let iface = SerialCanInterface::new("test");
+        let parsed = iface.parse_frame("t7FF0").unwrap();
+        assert!(!parsed.is_rtr);
+        assert_eq!(parsed.id, 0x7FF);
+        assert!(parsed.data.is_empty());
+    }
+
+    #[test]
+    fn test_tx_dlc_encoding() {
+        let msg = CanMessage::new(0, 0x100, CanData::new());
+        let cmd = SerialCanInterface::build_tx_command(&msg).unwrap();
+        assert_eq!(cmd, b"t1000\r");
+
+        let msg = CanMessage::new(0, 0x100, vec![0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88].into());
+        let cmd = SerialCanInterface::build_tx_command(&msg).unwrap();
+        assert_eq!(cmd, b"t10081122334455667788\r");
+
+        assert_eq!(SerialCanInterface::encode_dlc(8).unwrap(), '8');
+        let err = SerialCanInterface::encode_dlc(12).unwrap_err();
+        assert!(err.to_string().contains("12 bytes"));
+    }
+
+    #[test]
+    fn test_detect_adapter() {
+        assert_eq!(SlcanAdapter::detect("V1013\r"), SlcanAdapter::Lawicel);
+        assert_eq!(SlcanAdapter::detect("vf2d4a1c\r"), SlcanAdapter::Canable);
+        assert_eq!(SlcanAdapter::detect("b7c1d0a github.com/normaldotcom/canable-fw.git\r"), SlcanAdapter::Canable);
+        assert_eq!(SlcanAdapter::detect("v2.1 USB2CAN\r"), SlcanAdapter::Unknown);
+        assert_eq!(SlcanAdapter::detect("v1\r"), SlcanAdapter::Unknown);
+        assert_eq!(SlcanAdapter::detect("candleLight_fw slcan"), SlcanAdapter::CandleLight);
+        assert_eq!(SlcanAdapter::detect("CANtact 1.0"), SlcanAdapter::Cantact);
+        assert_eq!(SlcanAdapter::detect(""), SlcanAdapter::Unknown);
+        assert_eq!(SlcanAdapter::detect("\x07"), SlcanAdapter::Unknown);
+        assert!(SlcanAdapter::Lawicel.supports_timestamps() && !SlcanAdapter::Canable.supports_timestamps());
+        assert!(!SlcanAdapter::Unknown.acks_commands());
+
+        let mut iface = SerialCanInterface::new("test");
+        assert_eq!(iface.adapter_description(), None);
+        iface.adapter = Some((SlcanAdapter::Canable, "v1.0".to_string()));
+        assert!(!iface.capabilities().hardware_timestamps);
+        iface.adapter = Some((SlcanAdapter::Lawicel, "V1013".to_string()));
+        assert_eq!(iface.adapter_description().as_deref(), Some("Lawicel (V1013)"));
+        // Z1 goes on during connect, before any frame has arrived
+        assert!(iface.capabilities().hardware_timestamps);
+    }
+
+    #[test]
+    fn test_device_timestamps_unwrap() {
+        assert_eq!(SerialCanInterface::frame_timestamp("t1232AABB"), None);
+        assert_eq!(SerialCanInterface::frame_timestamp("t1232AABB1F40"), Some(8000));
+        assert_eq!(SerialCanInterface::frame_timestamp("r12341F40"), Some(8000));
+        // Out of the 0..60000 range: not a timestamp
+        assert_eq!(SerialCanInterface::frame_timestamp("t1232AABBFFFF"), None);
+
+        let mut iface = SerialCanInterface::new("test");
+        assert!(!iface.device_timestamps());
+        assert!(!iface.capabilities().hardware_timestamps);
+        let line = "t1232AABBEA00";
+        let first = iface.parse_frame(line).unwrap();
+        let anchor = first.timestamp;
+        let first = iface.apply_device_timestamp(first, line);
+        assert_eq!(first.timestamp, anchor);
+        assert_eq!(first.data.as_slice(), &[0xAA, 0xBB]);
+        assert!(iface.device_timestamps());
+        assert!(iface.capabilities().hardware_timestamps);
+        assert!(!iface.capabilities().fd);
+
+        // 0xEA00 = 59904 ms, next frame wraps round to 100 ms: 196 ms later
+        let line = "t1232AABB0064";
+        let second = iface.parse_frame(line).unwrap();
+        let second = iface.apply_device_timestamp(second, line);
+        assert_eq!((second.timestamp - anchor).num_milliseconds(), 196);
+    }
+
+    #[test]
+    fn test_parse_status_line() {
+        let status = |line| SerialCanInterface::parse_status_line(line);
+        assert_eq!(status("F00"), Some(BusErrorStatus::default()));
+        assert_eq!(status("F04").unwrap().state, ErrorState::Warning);
+        // Passive wins over warning; other flags (FIFO full, overrun) don't change the state
+        assert_eq!(status("F24").unwrap().state, ErrorState::Passive);
+        assert_eq!(status("F09").unwrap().state, ErrorState::Active);
+        assert_eq!(status("F0"), None);
+
+        assert_eq!(status("sp130096"), Some(BusErrorStatus { state: ErrorState::Passive, counters: Some((130, 96)) }));
+        assert_eq!(status("sb000256").unwrap().state, ErrorState::BusOff);
+        assert_eq!(status("sx000000"), None);
+        assert_eq!(status("sa00001"), None);
+        // Frames are not status lines
+        assert_eq!(status("t1232AABB"), None);
+
+        let mut iface = SerialCanInterface::new("test");
+        iface.set_bus_error_status(status("sb000256").unwrap());
+        assert!(iface.bus_error_status().state.is_fault());
+        assert_eq!(iface.bus_error_status().describe(), "Bus-off (RX 0, TX 256)");
+    }
+
+    #[test]
+    fn test_bel_reply_stops_status_polling() {
+        let mut iface = SerialCanInterface::new("test");
+        iface.status_poll_interval = Some(Duration::from_millis(500));
+
+        // A BEL for a rejected transmit doesn't turn polling off
+        iface.line_buffer.push_str("\x07t1232AABB\r");
+        iface.strip_bel_replies();
+        assert_eq!(iface.line_buffer, "t1232AABB\r");
+        assert!(iface.status_poll_interval.is_some());
+
+        // Unanswered `F`: the BEL is dropped and the frame after it stays intact
+        iface.line_buffer.clear();
+        iface.status_poll_pending = true;
+        iface.line_buffer.push_str("\x07t1232AABB\r");
+        iface.strip_bel_replies();
+        assert_eq!(iface.line_buffer, "t1232AABB\r");
+        assert_eq!(iface.status_poll_interval, None);
+        assert!(!iface.status_poll_pending);
+    }
+}
@@ -0,0 +1,142 @@
+use anyhow::Result;
+use chrono::{DateTime, Duration, NaiveDateTime, Utc};
+use crate::core::{CanData, CanMessage};
+
+/// Load a Vector ASC log
+///
+/// Classic CAN data and remote frames (`0.0123 1  123  Rx   d 2 01 02`,
+/// `... Rx   r 4`) and error frames (`0.0200 1  ErrorFrame`) are read; `x`
+/// marks an extended ID. Channels are 1-based in ASC and become bus
+/// `channel - 1`. Times are offsets from the `date` header, or from the load
+/// time when there is none. CAN FD and other event lines are skipped.
+pub fn load_asc(path: &str) -> Result<Vec<CanMessage>> {
+    parse_asc(&std::fs::read_to_string(path)?)
+}
+
+pub fn parse_asc(text: &str) -> Result<Vec<CanMessage>> {
+    let mut start = None;
+    let mut hex = true;
+    let mut relative = false;
+    let mut last_offset = 0.0;
+    let mut messages = Vec::new();
+
+    for line in text.lines() {
+        let line = line.trim();
+        if let Some(date) = line.strip_prefix("date ") {
+            start = start.or_else(|| parse_date(date));
+            continue;
+        }
+        if let Some(base) = line.strip_prefix("base ") {
+            let mut words = base.split_whitespace();
+            hex = words.next() != Some("dec");
+            relative = words.any(|w| w == "relative");
+            continue;
+        }
+
+        let mut fields = line.split_whitespace();
+        let Some(offset) = fields.next().and_then(|t| t.parse::<f64>().ok()) else {
+            continue;
+        };
+        // "timestamps relative": each line's time is the delta from the previous one
+        let offset = if relative { last_offset + offset } else { offset };
+        last_offset = offset;
+
+        let fields: Vec<&str> = fields.collect();
+        if let Some(mut msg) = parse_event(&fields, hex) {
+            let start = *start.get_or_insert_with(Utc::now);
+            msg.timestamp = start + Duration::microseconds((offset * 1e6).round() as i64);
+            messages.push(msg);
+        }
+    }
+    if messages.is_empty() && !is_asc_header(text) {
+        anyhow::bail!("No ASC frames found");
+    }
+    Ok(messages)
+}
+
+/// Does this look like the start of an ASC log?
+pub fn is_asc_header(text: &str) -> bool {
+    text.lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with("//"))
+        .take(3)
+        .any(|l| l.starts_with("date ") || l.starts_with("base "))
+}
+
+/// Frame from the fields after the time, None for lines that aren't classic CAN frames
+fn parse_event(fields: &[&str], hex: bool) -> Option<CanMessage> {
+    let channel = fields.first()?.parse::<u8>().ok()?;
+    let bus = channel.saturating_sub(1);
+    if fields.get(1) == Some(&"ErrorFrame") {
+        return Some(CanMessage::new_error(bus, 0, CanData::new()));
+    }
+
+    let (id_text, extended) = match fields.get(1)?.strip_suffix(['x', 'X']) {
+        Some(id) => (id, true),
+        None => (*fields.get(1)?, false),
+    };
+    let id = u32::from_str_radix(id_text, if hex { 16 } else { 10 }).ok()?;
+    if !extended && id > 0x7FF {
+        return None;
+    }
+    let dlc = fields.get(4).and_then(|d| d.parse::<u8>().ok());
+    match (*fields.get(2)?, *fields.get(3)?) {
+        ("Rx" | "Tx", "r") => Some(CanMessage::new_rtr(bus, id, dlc.unwrap_or(0).min(8))),
+        ("Rx" | "Tx", "d") => {
+            let dlc = dlc?.min(8) as usize;
+            let bytes = fields.get(5..5 + dlc)?
+                .iter()
+                .map(|b| u8::from_str_radix(b, if hex { 16 } else { 10 }).ok())
+                .collect::<Option<Vec<u8>>>()?;
+            Some(CanMessage::new(bus, id, CanData::from_slice(&bytes)))
+        }
+        _ => None,
+    }
+}
+
+/// `Wed Jun 5 10:30:00.000 am 2024` and the 24-hour variant without am/pm
+fn parse_date(text: &str) -> Option<DateTime<Utc>> {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    ["%a %b %d %I:%M:%S%.f %p %Y", "%a %b %d %H:%M:%S%.f %Y", "%a %b %d %I:%M:%S %p %Y", "%a %b %d %H:%M:%S %Y"]
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(&text, format).ok())
+        .map(|naive| naive.and_utc())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_asc_rtr_and_error_frames() {
+        let log = "\
+date Wed Jun 5 10:30:00.000 am 2024
+base hex  timestamps absolute
+internal events logged
+// version 9.0.0
+Begin Triggerblock Wed Jun 5 10:30:00.000 am 2024
+   0.000000 Start of measurement
+   0.010000 1  123             Rx   d 3 01 02 FF  Length = 0 BitCount = 64 ID = 291
+   0.020500 2  18DAF110x       Tx   d 2 AA BB
+   0.030000 1  7E0             Rx   r 4
+   0.040000 2  ErrorFrame
+   0.050000 CANFD   1 Rx        123                                   1 0 8  8 01 02 03 04 05 06 07 08
+End TriggerBlock
+";
+        let msgs = parse_asc(log).unwrap();
+        assert_eq!(msgs.len(), 4);
+        let start = parse_date("Wed Jun 5 10:30:00.000 am 2024").unwrap();
+        assert_eq!(msgs[0].timestamp, start + Duration::milliseconds(10));
+        assert_eq!((msgs[0].bus, msgs[0].id, msgs[0].hex_data()), (0, 0x123, "01 02 FF".to_string()));
+        assert_eq!((msgs[1].bus, msgs[1].id, msgs[1].data.len()), (1, 0x18DAF110, 2));
+        assert_eq!(msgs[1].timestamp, start + Duration::microseconds(20_500));
+
+        assert!(msgs[2].is_rtr);
+        assert_eq!((msgs[2].id, msgs[2].data.len()), (0x7E0, 4));
+        assert!(msgs[3].is_error && !msgs[3].is_rtr);
+        assert_eq!(msgs[3].bus, 1);
+
+        assert!(is_asc_header(log));
+        assert!(!is_asc_header("time,addr,bus,data\n"));
+    }
+}
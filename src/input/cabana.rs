@@ -420,6 +420,8 @@ fn decode_can_list(
                 bus: src,
                 id: address,
                 data: dat.into(),
+                is_rtr: false,
+                is_error: false,
             });
         }
     }
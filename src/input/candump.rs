@@ -0,0 +1,133 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use crate::core::message::CAN_ERR_FLAG;
+use crate::core::{CanData, CanMessage};
+
+/// SocketCAN `CAN_EFF_FLAG`: set on extended IDs in raw `can_id`s
+const CAN_EFF_FLAG: u32 = 0x8000_0000;
+const CAN_ID_MASK: u32 = 0x1FFF_FFFF;
+
+/// Load a `candump -L` log (`(1436509052.249713) can0 123#DEADBEEF`)
+///
+/// RTR frames (`123#R`, `123#R4`) and error frames (ID with `CAN_ERR_FLAG`) keep
+/// their flags. The bus is the interface's trailing number (`can1`, `vcan1` -> 1);
+/// interfaces without one are numbered in order of appearance. CAN FD frames
+/// (`123##1...`) don't fit a classic 8-byte frame and are skipped.
+pub fn load_candump(path: &str) -> Result<Vec<CanMessage>> {
+    parse_candump(&std::fs::read_to_string(path)?)
+}
+
+pub fn parse_candump(text: &str) -> Result<Vec<CanMessage>> {
+    let mut interfaces: Vec<String> = Vec::new();
+    let mut messages = Vec::new();
+    let mut skipped = 0usize;
+    for line in text.lines() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        match parse_line(line, &mut interfaces) {
+            Some(msg) => messages.push(msg),
+            None => skipped += 1,
+        }
+    }
+    if messages.is_empty() && skipped > 0 {
+        anyhow::bail!("No candump frames found");
+    }
+    if skipped > 0 {
+        tracing::warn!("Skipped {} unreadable or CAN FD candump lines", skipped);
+    }
+    Ok(messages)
+}
+
+/// Is this text a `candump -L` log line?
+pub fn is_candump_line(line: &str) -> bool {
+    let mut parts = line.split_whitespace();
+    matches!(
+        (parts.next(), parts.next(), parts.next()),
+        (Some(ts), Some(_), Some(frame)) if ts.starts_with('(') && ts.ends_with(')') && frame.contains('#')
+    )
+}
+
+fn parse_line(line: &str, interfaces: &mut Vec<String>) -> Option<CanMessage> {
+    let mut parts = line.split_whitespace();
+    let timestamp = parse_timestamp(parts.next()?.strip_prefix('(')?.strip_suffix(')')?)?;
+    let bus = bus_number(parts.next()?, interfaces)?;
+    let (id_text, payload) = parts.next()?.split_once('#')?;
+    if payload.starts_with('#') {
+        return None;
+    }
+    let raw_id = u32::from_str_radix(id_text, 16).ok()?;
+
+    let mut msg = if raw_id & CAN_ERR_FLAG != 0 && id_text.len() == 8 {
+        CanMessage::new_error(bus, raw_id & CAN_ID_MASK, CanMessage::parse_hex(payload).ok()?)
+    } else if let Some(dlc) = payload.strip_prefix('R') {
+        let dlc = if dlc.is_empty() { 0 } else { dlc.parse::<u8>().ok().filter(|d| *d <= 8)? };
+        CanMessage::new_rtr(bus, raw_id & !CAN_EFF_FLAG, dlc)
+    } else {
+        // `123#0102.0304` separators are allowed in candump payloads
+        let data: CanData = CanMessage::parse_hex(&payload.replace('.', "")).ok()?;
+        CanMessage::new(bus, raw_id & !CAN_EFF_FLAG, data)
+    };
+    msg.timestamp = timestamp;
+    Some(msg)
+}
+
+fn parse_timestamp(text: &str) -> Option<DateTime<Utc>> {
+    let (secs, frac) = text.split_once('.').unwrap_or((text, "0"));
+    let secs = secs.parse::<i64>().ok()?;
+    let digits = frac.len().min(9);
+    let nanos = frac[..digits].parse::<u32>().ok()? * 10u32.pow(9 - digits as u32);
+    DateTime::from_timestamp(secs, nanos)
+}
+
+fn bus_number(interface: &str, interfaces: &mut Vec<String>) -> Option<u8> {
+    let digits = interface.len() - interface.trim_end_matches(|c: char| c.is_ascii_digit()).len();
+    if digits > 0 {
+        return interface[interface.len() - digits..].parse().ok();
+    }
+    let idx = match interfaces.iter().position(|i| i == interface) {
+        Some(idx) => idx,
+        None => {
+            interfaces.push(interface.to_string());
+            interfaces.len() - 1
+        }
+    };
+    u8::try_from(idx).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_candump_rtr_and_error_frames() {
+        let log = "\
+(1436509052.249713) can0 123#DEADBEEF
+(1436509052.250000) vcan1 18DAF110#0102
+(1436509052.251000) can0 7E0#R4
+(1436509052.252000) can0 7E1#R
+(1436509052.253000) can1 20000004#0004000000000000
+(1436509052.254000) can0 123##1DEADBEEF
+";
+        let msgs = parse_candump(log).unwrap();
+        assert_eq!(msgs.len(), 5);
+        assert_eq!((msgs[0].bus, msgs[0].id, msgs[0].hex_data()), (0, 0x123, "DE AD BE EF".to_string()));
+        assert_eq!(msgs[0].timestamp.timestamp_subsec_micros(), 249_713);
+        assert_eq!((msgs[1].bus, msgs[1].id), (1, 0x18DAF110));
+
+        assert!(msgs[2].is_rtr && !msgs[2].is_error);
+        assert_eq!((msgs[2].id, msgs[2].data.len()), (0x7E0, 4));
+        assert_eq!(msgs[3].data.len(), 0);
+
+        let err = &msgs[4];
+        assert!(err.is_error && !err.is_rtr);
+        assert_eq!((err.bus, err.id, err.data.len()), (1, 0x004, 8));
+        // Error frames write back the way candump logged them
+        assert_eq!(err.to_candump_line(), "(1436509052.253000) can1 20000004#0004000000000000");
+
+        assert!(is_candump_line("(1436509052.249713) can0 123#DEADBEEF"));
+        assert!(!is_candump_line("time,addr,bus,data"));
+        assert!(parse_candump("not a log\n").is_err());
+    }
+}
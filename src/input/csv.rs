@@ -1,7 +1,8 @@
 use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
 use std::path::Path;
 use crate::core::{CanData, CanMessage};
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 
 /// Column layout for CSV parsing
 #[derive(Debug)]
@@ -18,12 +19,194 @@ pub type ProgressCallback = Box<dyn Fn(usize, usize) + Send>;
 /// Callback for streaming chunk: receives batch of messages
 pub type ChunkCallback = Box<dyn Fn(Vec<CanMessage>) + Send>;
 
+/// Whether the time column holds an offset from the first row or unix epoch time
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum CsvTimeBase {
+    /// Absolute if the first row's time looks like an epoch (see `EPOCH_THRESHOLD_SECS`)
+    #[default]
+    Auto,
+    /// Seconds relative to the first row, anchored to `CsvOptions::epoch`
+    Relative,
+    /// Unix epoch seconds
+    Absolute,
+}
+
+impl CsvTimeBase {
+    pub const ALL: [CsvTimeBase; 3] = [CsvTimeBase::Auto, CsvTimeBase::Relative, CsvTimeBase::Absolute];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            CsvTimeBase::Auto => "Auto-detect",
+            CsvTimeBase::Relative => "Relative to first row",
+            CsvTimeBase::Absolute => "Unix epoch",
+        }
+    }
+}
+
+/// Decimal places of exported relative times
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum TimePrecision {
+    /// Microseconds if the log has sub-millisecond timestamps, else milliseconds
+    #[default]
+    Auto,
+    Millis,
+    Micros,
+}
+
+impl TimePrecision {
+    pub const ALL: [TimePrecision; 3] = [TimePrecision::Auto, TimePrecision::Millis, TimePrecision::Micros];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            TimePrecision::Auto => "Auto (as recorded)",
+            TimePrecision::Millis => "Milliseconds (3 digits)",
+            TimePrecision::Micros => "Microseconds (6 digits)",
+        }
+    }
+
+    /// Decimal places to write `messages` with, resolving `Auto` against their timestamps
+    pub fn digits(self, messages: &[CanMessage]) -> usize {
+        match self {
+            TimePrecision::Millis => 3,
+            TimePrecision::Micros => 6,
+            TimePrecision::Auto => {
+                let sub_ms = messages.iter().any(|m| m.timestamp.timestamp_subsec_micros() % 1000 != 0);
+                if sub_ms { 6 } else { 3 }
+            }
+        }
+    }
+}
+
+/// First-row times at or above this (2001-09-09 as epoch seconds) are taken as absolute in `Auto`
+const EPOCH_THRESHOLD_SECS: f64 = 1e9;
+
+/// Options controlling how CSV rows are turned into messages
+#[derive(Debug, Clone, Default)]
+pub struct CsvOptions {
+    /// Absolute time the first row is anchored to for relative times (defaults to the time of loading)
+    pub epoch: Option<DateTime<Utc>>,
+    pub time_base: CsvTimeBase,
+}
+
+/// Format of an epoch typed into the CSV load options (UTC)
+pub const EPOCH_INPUT_FORMAT: &str = "%Y-%m-%d %H:%M:%S%.f";
+
+/// Parse a typed CSV epoch: `EPOCH_INPUT_FORMAT` (taken as UTC) or RFC 3339 with an offset
+pub fn parse_epoch(text: &str) -> Option<DateTime<Utc>> {
+    let text = text.trim();
+    if let Ok(time) = DateTime::parse_from_rfc3339(text) {
+        return Some(time.with_timezone(&Utc));
+    }
+    chrono::NaiveDateTime::parse_from_str(text, EPOCH_INPUT_FORMAT).ok().map(|t| t.and_utc())
+}
+
+/// Maps relative log times (seconds, may be negative or slightly out of order) to absolute timestamps.
+///
+/// The first row is anchored to the epoch and later rows keep their offset from it, so reordered
+/// rows stay reordered and can be sorted afterwards. A large backward jump is treated as a new
+/// session appended right after the previous row.
+///
+/// Absolute (epoch) times are converted as-is, with no anchoring or session handling.
+struct TimeMapper {
+    base_time: DateTime<Utc>,
+    time_base: CsvTimeBase,
+    first_time: Option<f64>,
+    session_offset: f64,
+    last_seen_time: f64,
+}
+
+impl TimeMapper {
+    /// Backward jumps larger than this (seconds) start a new session
+    const SESSION_RESET_SECS: f64 = 0.1;
+
+    fn new(options: &CsvOptions) -> Self {
+        Self {
+            base_time: options.epoch.unwrap_or_else(Utc::now),
+            time_base: options.time_base,
+            first_time: None,
+            session_offset: 0.0,
+            last_seen_time: 0.0,
+        }
+    }
+
+    fn map(&mut self, time_secs: f64) -> DateTime<Utc> {
+        let first_time = match self.first_time {
+            Some(t) => t,
+            None => {
+                if self.time_base == CsvTimeBase::Auto {
+                    self.time_base = if time_secs >= EPOCH_THRESHOLD_SECS {
+                        CsvTimeBase::Absolute
+                    } else {
+                        CsvTimeBase::Relative
+                    };
+                }
+                self.first_time = Some(time_secs);
+                self.last_seen_time = time_secs;
+                time_secs
+            }
+        };
+        if self.time_base == CsvTimeBase::Absolute {
+            return DateTime::UNIX_EPOCH + chrono::Duration::microseconds((time_secs * 1_000_000.0).round() as i64);
+        }
+        if time_secs < self.last_seen_time - Self::SESSION_RESET_SECS {
+            // Time jumped back significantly - this is likely a new session
+            self.session_offset += self.last_seen_time - time_secs + 0.000001;
+        }
+        self.last_seen_time = time_secs;
+
+        // Microsecond precision
+        let us = ((self.session_offset + time_secs - first_time) * 1_000_000.0).round() as i64;
+        self.base_time + chrono::Duration::microseconds(us)
+    }
+}
+
+/// Parse one CSV record into (relative time in seconds, bus, id, data)
+fn parse_record(layout: &CsvLayout, record: &csv::StringRecord) -> Result<(f64, u8, u32, CanData)> {
+    let (time_relative, bus, id, data) = match layout {
+        CsvLayout::SingleData { time_idx, bus_idx, id_idx, data_idx } => {
+            let time_val = record.get(*time_idx).and_then(|s| s.trim().parse::<f64>().ok()).unwrap_or(0.0);
+            let bus = record.get(*bus_idx).and_then(|s| s.parse::<u8>().ok()).unwrap_or(0);
+            let id = parse_can_id(record.get(*id_idx).context("Missing ID column")?)?;
+            let hex_data = record.get(*data_idx).context("Missing data column")?;
+            let data = CanMessage::parse_hex(hex_data)?;
+            (time_val, bus, id, data)
+        }
+        CsvLayout::DriveSav { time_idx, bus_idx, id_idx, len_idx, d_indices } => {
+            let time_val = record.get(*time_idx).and_then(|s| s.trim().parse::<f64>().ok()).unwrap_or(0.0);
+            let bus = record.get(*bus_idx).and_then(|s| s.parse::<u8>().ok()).unwrap_or(0);
+            let id = parse_can_id(record.get(*id_idx).context("Missing ID column")?)?;
+            let len: usize = record.get(*len_idx).and_then(|s| s.parse().ok()).unwrap_or(8).min(8);
+            let mut data = Vec::with_capacity(len);
+            for i in 0..len {
+                if let Some(&di) = d_indices.get(i) {
+                    if let Some(hex_byte) = record.get(di) {
+                        if let Ok(b) = u8::from_str_radix(hex_byte.trim(), 16) {
+                            data.push(b);
+                        }
+                    }
+                }
+            }
+            (time_val, bus, id, CanData::from_slice(&data))
+        }
+    };
+
+    // Normalize time to seconds (driveSAV uses microseconds)
+    let time_secs = if matches!(layout, CsvLayout::DriveSav { .. }) {
+        time_relative / 1_000_000.0
+    } else {
+        time_relative
+    };
+
+    Ok((time_secs, bus, id, data))
+}
+
 /// Load CSV in chunks, calling chunk_cb with each batch. Also calls progress_cb for progress.
 /// Chunk size is ~5000 messages.
 pub fn load_csv_streaming(
     path: &str,
     chunk_cb: ChunkCallback,
     progress_cb: Option<ProgressCallback>,
+    options: &CsvOptions,
 ) -> Result<()> {
     const CHUNK_SIZE: usize = 5000;
 
@@ -42,10 +225,7 @@ pub fn load_csv_streaming(
     let layout = detect_columns(headers)?;
 
     let mut batch = Vec::with_capacity(CHUNK_SIZE);
-    let mut accumulated_time_secs = 0.0;
-    let mut last_seen_time = 0.0;
-    let time_is_microseconds = matches!(&layout, CsvLayout::DriveSav { .. });
-    let base_time = Utc::now();
+    let mut time_mapper = TimeMapper::new(options);
     let mut record_count = 0usize;
 
     for result in rdr.records() {
@@ -59,51 +239,10 @@ pub fn load_csv_streaming(
             }
         }
 
-        let (time_relative, bus, id, data) = match &layout {
-            CsvLayout::SingleData { time_idx, bus_idx, id_idx, data_idx } => {
-                let time_val = record.get(*time_idx).and_then(|s| s.parse::<f64>().ok()).unwrap_or(0.0);
-                let bus = record.get(*bus_idx).and_then(|s| s.parse::<u8>().ok()).unwrap_or(0);
-                let id = parse_can_id(record.get(*id_idx).context("Missing ID column")?)?;
-                let hex_data = record.get(*data_idx).context("Missing data column")?;
-                let data = CanMessage::parse_hex(hex_data)?;
-                (time_val, bus, id, data)
-            }
-            CsvLayout::DriveSav { time_idx, bus_idx, id_idx, len_idx, d_indices } => {
-                let time_val = record.get(*time_idx).and_then(|s| s.parse::<f64>().ok()).unwrap_or(0.0);
-                let bus = record.get(*bus_idx).and_then(|s| s.parse::<u8>().ok()).unwrap_or(0);
-                let id = parse_can_id(record.get(*id_idx).context("Missing ID column")?)?;
-                let len: usize = record.get(*len_idx).and_then(|s| s.parse().ok()).unwrap_or(8).min(8);
-                let mut data = Vec::with_capacity(len);
-                for i in 0..len {
-                    if let Some(&di) = d_indices.get(i) {
-                        if let Some(hex_byte) = record.get(di) {
-                            if let Ok(b) = u8::from_str_radix(hex_byte.trim(), 16) {
-                                data.push(b);
-                            }
-                        }
-                    }
-                }
-                (time_val, bus, id, CanData::from_slice(&data))
-            }
-        };
+        let (time_secs, bus, id, data) = parse_record(&layout, &record)?;
+        let timestamp = time_mapper.map(time_secs);
 
-        let time_relative_secs = if time_is_microseconds {
-            time_relative / 1_000_000.0
-        } else {
-            time_relative
-        };
-
-        if time_relative_secs < last_seen_time - 0.1 {
-            accumulated_time_secs += 0.000001;
-        } else if time_relative_secs > last_seen_time {
-            accumulated_time_secs += time_relative_secs - last_seen_time;
-        }
-        last_seen_time = time_relative_secs;
-
-        let us = (accumulated_time_secs * 1_000_000.0) as i64;
-        let timestamp = base_time + chrono::Duration::microseconds(us);
-
-        batch.push(CanMessage { timestamp, bus, id, data });
+        batch.push(CanMessage { timestamp, bus, id, data, is_rtr: false, is_error: false });
 
         if batch.len() >= CHUNK_SIZE {
             chunk_cb(std::mem::take(&mut batch));
@@ -141,10 +280,7 @@ pub fn load_csv_with_progress(
 
     // Pre-allocate based on file size (~50 bytes per CSV record on average)
     let mut messages = Vec::with_capacity(total_bytes / 50);
-    let mut accumulated_time_secs = 0.0;
-    let mut last_seen_time = 0.0;
-    let time_is_microseconds = matches!(&layout, CsvLayout::DriveSav { .. });
-    let base_time = Utc::now();
+    let mut time_mapper = TimeMapper::new(&CsvOptions::default());
     let mut record_count = 0usize;
 
     for result in rdr.records() {
@@ -159,51 +295,10 @@ pub fn load_csv_with_progress(
             }
         }
 
-        let (time_relative, bus, id, data) = match &layout {
-            CsvLayout::SingleData { time_idx, bus_idx, id_idx, data_idx } => {
-                let time_val = record.get(*time_idx).and_then(|s| s.parse::<f64>().ok()).unwrap_or(0.0);
-                let bus = record.get(*bus_idx).and_then(|s| s.parse::<u8>().ok()).unwrap_or(0);
-                let id = parse_can_id(record.get(*id_idx).context("Missing ID column")?)?;
-                let hex_data = record.get(*data_idx).context("Missing data column")?;
-                let data = CanMessage::parse_hex(hex_data)?;
-                (time_val, bus, id, data)
-            }
-            CsvLayout::DriveSav { time_idx, bus_idx, id_idx, len_idx, d_indices } => {
-                let time_val = record.get(*time_idx).and_then(|s| s.parse::<f64>().ok()).unwrap_or(0.0);
-                let bus = record.get(*bus_idx).and_then(|s| s.parse::<u8>().ok()).unwrap_or(0);
-                let id = parse_can_id(record.get(*id_idx).context("Missing ID column")?)?;
-                let len: usize = record.get(*len_idx).and_then(|s| s.parse().ok()).unwrap_or(8).min(8);
-                let mut data = Vec::with_capacity(len);
-                for i in 0..len {
-                    if let Some(&di) = d_indices.get(i) {
-                        if let Some(hex_byte) = record.get(di) {
-                            if let Ok(b) = u8::from_str_radix(hex_byte.trim(), 16) {
-                                data.push(b);
-                            }
-                        }
-                    }
-                }
-                (time_val, bus, id, CanData::from_slice(&data))
-            }
-        };
-
-        let time_relative_secs = if time_is_microseconds {
-            time_relative / 1_000_000.0
-        } else {
-            time_relative
-        };
-
-        if time_relative_secs < last_seen_time - 0.1 {
-            accumulated_time_secs += 0.000001;
-        } else if time_relative_secs > last_seen_time {
-            accumulated_time_secs += time_relative_secs - last_seen_time;
-        }
-        last_seen_time = time_relative_secs;
-
-        let us = (accumulated_time_secs * 1_000_000.0) as i64;
-        let timestamp = base_time + chrono::Duration::microseconds(us);
+        let (time_secs, bus, id, data) = parse_record(&layout, &record)?;
+        let timestamp = time_mapper.map(time_secs);
 
-        messages.push(CanMessage { timestamp, bus, id, data });
+        messages.push(CanMessage { timestamp, bus, id, data, is_rtr: false, is_error: false });
     }
 
     Ok(messages)
@@ -217,84 +312,56 @@ pub fn load_csv_with_progress(
 /// - time,id,hex_data
 /// - driveSAV: Time Stamp,ID,Extended,Dir,Bus,LEN,D1,D2,D3,D4,D5,D6,D7,D8
 ///
-/// Timestamps are treated as relative seconds (or microseconds for driveSAV) from the start of the log
+/// Timestamps are seconds (or microseconds for driveSAV), either relative to the first row of the
+/// log (may be negative, rows may be out of order) or unix epoch time, detected from the first row.
 pub fn load_csv(path: &str) -> Result<Vec<CanMessage>> {
-    let file_path = Path::new(path);
-    let total_bytes = std::fs::metadata(file_path).map(|m| m.len() as usize).unwrap_or(0);
-    let mut rdr = csv::ReaderBuilder::new()
-        .flexible(true)
-        .from_path(file_path)?;
-
-    let headers = rdr.headers()?;
-    let layout = detect_columns(headers)?;
-
-    // Pre-allocate based on file size (~50 bytes per CSV record on average)
-    let mut messages = Vec::with_capacity(total_bytes / 50);
-
-    // Use the first message's actual time as base, and accumulate for subsequent messages
-    let mut accumulated_time_secs = 0.0;
-    let mut last_seen_time = 0.0;
-    let time_is_microseconds = matches!(&layout, CsvLayout::DriveSav { .. });
-
-    // Get base time as NOW for absolute timestamps
-    let base_time = Utc::now();
-
-    for result in rdr.records() {
-        let record = result.context("Failed to read CSV row")?;
+    load_csv_with_progress(path, None)
+}
 
-        let (time_relative, bus, id, data) = match &layout {
-            CsvLayout::SingleData { time_idx, bus_idx, id_idx, data_idx } => {
-                let time_val = record.get(*time_idx).and_then(|s| s.parse::<f64>().ok()).unwrap_or(0.0);
-                let bus = record.get(*bus_idx).and_then(|s| s.parse::<u8>().ok()).unwrap_or(0);
-                let id = parse_can_id(record.get(*id_idx).context("Missing ID column")?)?;
-                let hex_data = record.get(*data_idx).context("Missing data column")?;
-                let data = CanMessage::parse_hex(hex_data)?;
-                (time_val, bus, id, data)
-            }
-            CsvLayout::DriveSav { time_idx, bus_idx, id_idx, len_idx, d_indices } => {
-                let time_val = record.get(*time_idx).and_then(|s| s.parse::<f64>().ok()).unwrap_or(0.0);
-                let bus = record.get(*bus_idx).and_then(|s| s.parse::<u8>().ok()).unwrap_or(0);
-                let id = parse_can_id(record.get(*id_idx).context("Missing ID column")?)?;
-                let len: usize = record.get(*len_idx).and_then(|s| s.parse().ok()).unwrap_or(8).min(8);
-                let mut data = Vec::with_capacity(len);
-                for i in 0..len {
-                    if let Some(&di) = d_indices.get(i) {
-                        if let Some(hex_byte) = record.get(di) {
-                            if let Ok(b) = u8::from_str_radix(hex_byte.trim(), 16) {
-                                data.push(b);
-                            }
-                        }
-                    }
-                }
-                (time_val, bus, id, CanData::from_slice(&data))
-            }
-        };
+/// Write messages as `time,addr,bus,data` CSV, time in seconds relative to the first message
+///
+/// Error frames are left out: the format has no flag for them, and read back
+/// their error class would come in as a data frame ID. Returns how many were left out.
+pub fn write_csv<W: std::io::Write>(writer: W, messages: &[CanMessage], precision: TimePrecision) -> std::io::Result<usize> {
+    write_csv_from(writer, messages, precision, messages.first().map(|m| m.timestamp))
+}
 
-        // Normalize time to seconds (driveSAV uses microseconds)
-        let time_relative_secs = if time_is_microseconds {
-            time_relative / 1_000_000.0
+/// Like `write_csv`, with times relative to `origin` (e.g. the start of an exported time range)
+pub fn write_csv_from<W: std::io::Write>(
+    mut writer: W,
+    messages: &[CanMessage],
+    precision: TimePrecision,
+    origin: Option<DateTime<Utc>>,
+) -> std::io::Result<usize> {
+    writeln!(writer, "time,addr,bus,data")?;
+    let digits = precision.digits(messages);
+    let mut omitted = 0;
+    for msg in messages {
+        if msg.is_error {
+            omitted += 1;
+            continue;
+        }
+        let rel_time = origin
+            .map(|t| (msg.timestamp - t).num_microseconds().unwrap_or(0) as f64 / 1_000_000.0)
+            .unwrap_or(0.0);
+        let data_hex = if msg.data.is_empty() {
+            "0x".to_string()
         } else {
-            time_relative
+            format!("0x{}", msg.data.iter().map(|b| format!("{:02X}", b)).collect::<String>())
         };
-
-        // Track accumulated time - handle both forward time and resets
-        if time_relative_secs < last_seen_time - 0.1 {
-            // Time jumped back significantly - this is likely a new session
-            accumulated_time_secs += 0.000001;  // Small increment for the new message
-        } else if time_relative_secs > last_seen_time {
-            // Time moved forward - add the difference
-            accumulated_time_secs += time_relative_secs - last_seen_time;
-        }
-        last_seen_time = time_relative_secs;
-
-        // Calculate actual timestamp (microsecond precision)
-        let us = (accumulated_time_secs * 1_000_000.0) as i64;
-        let timestamp = base_time + chrono::Duration::microseconds(us);
-
-        messages.push(CanMessage { timestamp, bus, id, data });
+        writeln!(writer, "{:.*},0x{:03X},{},{}", digits, rel_time, msg.id, msg.bus, data_hex)?;
     }
+    writer.flush()?;
+    Ok(omitted)
+}
 
-    Ok(messages)
+/// Status suffix for the error frames `write_csv` left out, e.g. " (3 error frames omitted)"
+pub fn omitted_note(omitted: usize) -> String {
+    match omitted {
+        0 => String::new(),
+        1 => " (1 error frame omitted)".to_string(),
+        n => format!(" ({} error frames omitted)", n),
+    }
 }
 
 /// Parse CAN ID - supports decimal, 0x-prefixed hex, and bare hex (e.g. 00000197)
@@ -353,7 +420,8 @@ fn detect_drivesav_layout(headers: &csv::StringRecord) -> Result<CsvLayout> {
 /// Find a column by checking possible names
 fn find_column(headers: &csv::StringRecord, names: &[&str]) -> Result<usize> {
     for (idx, header) in headers.iter().enumerate() {
-        let header_lower = header.to_lowercase();
+        // Strip a UTF-8 BOM left on the first header and any padding around names
+        let header_lower = header.trim_start_matches('\u{feff}').trim().to_lowercase();
         if names.iter().any(|&name| header_lower == name) {
             return Ok(idx);
         }
@@ -406,4 +474,143 @@ mod tests {
 
         let _ = std::fs::remove_file(&path);
     }
+
+    #[test]
+    fn test_parse_epoch() {
+        use chrono::TimeZone;
+        let noon = Utc.with_ymd_and_hms(2024, 1, 31, 12, 0, 0).unwrap();
+        assert_eq!(parse_epoch("2024-01-31 12:00:00"), Some(noon));
+        assert_eq!(parse_epoch(" 2024-01-31 12:00:00.250 "), Some(noon + chrono::Duration::milliseconds(250)));
+        assert_eq!(parse_epoch("2024-01-31T13:00:00+01:00"), Some(noon));
+        assert_eq!(parse_epoch(&noon.format(EPOCH_INPUT_FORMAT).to_string()), Some(noon));
+        assert_eq!(parse_epoch("yesterday"), None);
+    }
+
+    #[test]
+    fn test_time_mapper_anchors_first_row() {
+        let epoch = Utc::now();
+        let mut mapper = TimeMapper::new(&CsvOptions { epoch: Some(epoch), time_base: CsvTimeBase::Auto });
+
+        // Negative, slightly out-of-order relative times keep their offsets from the first row
+        assert_eq!(mapper.map(-2.5), epoch);
+        assert_eq!(mapper.map(-2.0), epoch + chrono::Duration::milliseconds(500));
+        assert_eq!(mapper.map(-2.05), epoch + chrono::Duration::milliseconds(450));
+
+        // A large backward jump starts a new session right after the previous row
+        let reset = mapper.map(0.0 - 10.0);
+        assert!(reset > epoch + chrono::Duration::milliseconds(450));
+        assert!(reset < epoch + chrono::Duration::milliseconds(451));
+    }
+
+    #[test]
+    fn test_time_mapper_time_base() {
+        let epoch = Utc::now();
+        let options = |time_base| CsvOptions { epoch: Some(epoch), time_base };
+        let unix = |secs: f64| DateTime::UNIX_EPOCH + chrono::Duration::microseconds((secs * 1e6) as i64);
+
+        // Auto: small first times are relative, epoch-sized ones absolute
+        let mut mapper = TimeMapper::new(&options(CsvTimeBase::Auto));
+        assert_eq!(mapper.map(1.5), epoch);
+        assert_eq!(mapper.map(2.0), epoch + chrono::Duration::milliseconds(500));
+        let mut mapper = TimeMapper::new(&options(CsvTimeBase::Auto));
+        assert_eq!(mapper.map(1_700_000_000.25), unix(1_700_000_000.25));
+        assert_eq!(mapper.map(1_700_000_000.5), unix(1_700_000_000.5));
+
+        // Manual override wins over the guess in both directions
+        let mut mapper = TimeMapper::new(&options(CsvTimeBase::Relative));
+        assert_eq!(mapper.map(1_700_000_000.0), epoch);
+        assert_eq!(mapper.map(1_700_000_001.0), epoch + chrono::Duration::seconds(1));
+        let mut mapper = TimeMapper::new(&options(CsvTimeBase::Absolute));
+        assert_eq!(mapper.map(12.0), unix(12.0));
+        // Absolute times are never treated as a session reset
+        assert_eq!(mapper.map(2.0), unix(2.0));
+    }
+
+    #[test]
+    fn test_write_csv_round_trip() {
+        let messages = vec![
+            CanMessage::new(0, 0x197, vec![0x83, 0x0C].into()),
+            CanMessage::new(1, 0x7DF, Vec::<u8>::new().into()),
+        ];
+        let path = std::env::temp_dir().join("test_write_csv.csv");
+        write_csv(std::fs::File::create(&path).unwrap(), &messages, TimePrecision::Auto).unwrap();
+        let loaded = load_csv(path.to_str().unwrap()).unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(loaded.len(), 2);
+        assert_eq!((loaded[0].id, loaded[0].bus, loaded[0].data.to_vec()), (0x197, 0, vec![0x83, 0x0C]));
+        assert_eq!((loaded[1].id, loaded[1].bus, loaded[1].data.len()), (0x7DF, 1, 0));
+    }
+
+    #[test]
+    fn test_write_csv_from_range_start() {
+        let base = DateTime::UNIX_EPOCH + chrono::Duration::seconds(1_700_000_000);
+        let messages: Vec<CanMessage> = [2_500, 3_000]
+            .into_iter()
+            .map(|ms| {
+                let mut msg = CanMessage::new(0, 0x100, vec![0x01].into());
+                msg.timestamp = base + chrono::Duration::milliseconds(ms);
+                msg
+            })
+            .collect();
+
+        // A slice starting at 2 s: times count from the range start, not its first frame
+        let mut csv = Vec::new();
+        write_csv_from(&mut csv, &messages, TimePrecision::Millis, Some(base + chrono::Duration::seconds(2))).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        assert!(csv.contains("\n0.500,0x100,"));
+        assert!(csv.contains("\n1.000,0x100,"));
+    }
+
+    #[test]
+    fn test_write_csv_skips_error_frames() {
+        let mut error = CanMessage::new(0, 0x4, vec![0; 8].into());
+        error.is_error = true;
+        let messages = [CanMessage::new(0, 0x100, vec![0x01].into()), error];
+
+        let mut csv = Vec::new();
+        assert_eq!(write_csv(&mut csv, &messages, TimePrecision::Millis).unwrap(), 1);
+        let csv = String::from_utf8(csv).unwrap();
+        assert_eq!(csv.lines().count(), 2);
+        assert!(!csv.contains(",0x004,"));
+    }
+
+    #[test]
+    fn test_sub_millisecond_times_survive_export() {
+        let base = DateTime::UNIX_EPOCH + chrono::Duration::seconds(1_700_000_000);
+        let messages: Vec<CanMessage> = [0, 250, 1_000, 1_375]
+            .into_iter()
+            .map(|us| {
+                let mut msg = CanMessage::new(0, 0x100, vec![0x01].into());
+                msg.timestamp = base + chrono::Duration::microseconds(us);
+                msg
+            })
+            .collect();
+        let offsets = |msgs: &[CanMessage]| -> Vec<i64> {
+            msgs.iter().map(|m| (m.timestamp - msgs[0].timestamp).num_microseconds().unwrap()).collect()
+        };
+        let round_trip = |precision: TimePrecision| {
+            let mut csv = Vec::new();
+            write_csv(&mut csv, &messages, precision).unwrap();
+            let path = std::env::temp_dir().join(format!("test_precision_{:?}.csv", precision));
+            std::fs::write(&path, &csv).unwrap();
+            let loaded = load_csv(path.to_str().unwrap()).unwrap();
+            let _ = std::fs::remove_file(&path);
+            (String::from_utf8(csv).unwrap(), offsets(&loaded))
+        };
+
+        // Auto keeps the microseconds the log has
+        let (csv, loaded) = round_trip(TimePrecision::Auto);
+        assert!(csv.contains("\n0.000250,0x100,"));
+        assert_eq!(loaded, vec![0, 250, 1_000, 1_375]);
+
+        // Forced milliseconds quantizes them
+        let (csv, loaded) = round_trip(TimePrecision::Millis);
+        assert!(csv.contains("\n0.000,0x100,"));
+        assert_eq!(loaded, vec![0, 0, 1_000, 1_000]);
+
+        // A millisecond-resolution log exports with 3 digits under Auto
+        assert_eq!(TimePrecision::Auto.digits(&messages[..1]), 3);
+        assert_eq!(TimePrecision::Auto.digits(&messages), 6);
+    }
 }
@@ -0,0 +1,86 @@
+//! Notice when a file changes on disk, by polling its modification time and size.
+//!
+//! Polling keeps this dependency-free and copes with editors that save by
+//! writing a new file and renaming it over the old one.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+/// How often `poll` looks at the file
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+pub struct FileWatch {
+    path: PathBuf,
+    /// (modified time, size) last seen; None while the file is missing
+    stamp: Option<(SystemTime, u64)>,
+    last_poll: Instant,
+}
+
+impl FileWatch {
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        let path = path.as_ref().to_path_buf();
+        Self {
+            stamp: file_stamp(&path),
+            path,
+            last_poll: Instant::now(),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// True once per change since the last poll. Checks the disk at most every
+    /// `POLL_INTERVAL`; a missing file (mid-save) is not a change.
+    pub fn poll(&mut self) -> bool {
+        if self.last_poll.elapsed() < POLL_INTERVAL {
+            return false;
+        }
+        self.last_poll = Instant::now();
+        self.check()
+    }
+
+    fn check(&mut self) -> bool {
+        let Some(stamp) = file_stamp(&self.path) else {
+            return false;
+        };
+        let changed = self.stamp.is_some_and(|old| old != stamp);
+        self.stamp = Some(stamp);
+        changed
+    }
+}
+
+fn file_stamp(path: &Path) -> Option<(SystemTime, u64)> {
+    let meta = fs::metadata(path).ok()?;
+    Some((meta.modified().ok()?, meta.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_watch_sees_each_change_once() {
+        let path = std::env::temp_dir().join(format!("file_watch_test_{}.dbc", std::process::id()));
+        fs::write(&path, "VERSION \"\"\n").unwrap();
+        let mut watch = FileWatch::new(&path);
+        assert!(!watch.check());
+
+        let file = fs::File::options().write(true).open(&path).unwrap();
+        file.set_modified(SystemTime::now() + Duration::from_secs(5)).unwrap();
+        drop(file);
+        assert!(watch.check());
+        assert!(!watch.check());
+
+        // Gone mid-save, then back with new contents
+        fs::remove_file(&path).unwrap();
+        assert!(!watch.check());
+        fs::write(&path, "VERSION \"2\"\n").unwrap();
+        assert!(watch.check());
+
+        // Throttled: a poll right after creation doesn't touch the disk
+        assert!(!FileWatch::new(&path).poll());
+        fs::remove_file(&path).unwrap();
+    }
+}
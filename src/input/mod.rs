@@ -1,9 +1,16 @@
+pub mod asc;
 pub mod cabana;
+pub mod candump;
 pub mod csv;
+pub mod file_watch;
+pub mod generator;
 pub mod rlog;
 
+pub use asc::load_asc;
 pub use cabana::{load_cabana_rlog, load_cabana_rlog_with_progress, load_cabana_session};
-pub use csv::{load_csv, load_csv_with_progress, load_csv_streaming, ProgressCallback, ChunkCallback};
+pub use csv::{load_csv, write_csv, write_csv_from, omitted_note, load_csv_with_progress, load_csv_streaming, CsvOptions, CsvTimeBase, TimePrecision, parse_epoch, EPOCH_INPUT_FORMAT, ProgressCallback, ChunkCallback};
+pub use candump::load_candump;
+pub use generator::{generate_from_dbc, GeneratorOptions, SignalPattern};
 pub use rlog::load_rlog;
 
 use anyhow::Result;
@@ -15,6 +22,8 @@ pub enum InputFormat {
     Csv,
     Rlog,
     CabanaRlog,
+    Candump,
+    Asc,
     Unknown,
 }
 
@@ -25,6 +34,16 @@ pub fn detect_format(data: &[u8]) -> InputFormat {
         return InputFormat::Rlog;
     }
 
+    // Text logs with their own line syntax, before the lenient binary check below
+    if let Some(text) = text_sample(data) {
+        if text.lines().next().is_some_and(candump::is_candump_line) {
+            return InputFormat::Candump;
+        }
+        if asc::is_asc_header(text) {
+            return InputFormat::Asc;
+        }
+    }
+
     // Cabana/uncompressed rlog: Cap'n Proto stream (segment table)
     if is_cabana_rlog(data) {
         return InputFormat::CabanaRlog;
@@ -38,6 +57,16 @@ pub fn detect_format(data: &[u8]) -> InputFormat {
     InputFormat::Unknown
 }
 
+/// Detect the format of a file on disk from its first 1KB
+pub fn detect_file_format(path: &str) -> Result<InputFormat> {
+    // Only read the header to avoid loading large files twice
+    let mut f = std::fs::File::open(path)?;
+    let mut header = vec![0u8; 1024];
+    let n = std::io::Read::read(&mut f, &mut header)?;
+    header.truncate(n);
+    Ok(detect_format(&header))
+}
+
 fn is_rlog_bz2(data: &[u8]) -> bool {
     data.len() >= 2 && data[0] == b'B' && data[1] == b'Z'
 }
@@ -52,23 +81,27 @@ fn is_cabana_rlog(data: &[u8]) -> bool {
     seg_count >= 1 && seg_count <= 64
 }
 
-fn is_csv(data: &[u8]) -> bool {
-    // Check if the data looks like CSV (text with commas)
-    // Look for a line with commas in the first 500 bytes
+/// The first 500 bytes as text (BOM stripped), None if they aren't UTF-8
+fn text_sample(data: &[u8]) -> Option<&str> {
     if data.len() < 10 {
-        return false;
+        return None;
     }
 
-    let sample = std::str::from_utf8(&data[..data.len().min(500)]);
-    match sample {
-        Ok(text) => {
-            // Check for CSV-like patterns (multiple commas on a line)
-            text.lines().take(5).any(|line| line.chars().filter(|&c| c == ',').count() >= 2)
-        }
-        Err(_) => false,
+    let sample = &data[..data.len().min(500)];
+    let sample = sample.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(sample);
+    // The cut may land inside a multi-byte character; keep the valid prefix
+    match std::str::from_utf8(sample) {
+        Err(e) if e.error_len().is_none() => std::str::from_utf8(&sample[..e.valid_up_to()]).ok(),
+        other => other.ok(),
     }
 }
 
+fn is_csv(data: &[u8]) -> bool {
+    // Check for CSV-like patterns (multiple commas on a line)
+    text_sample(data)
+        .is_some_and(|text| text.lines().take(5).any(|line| line.chars().filter(|&c| c == ',').count() >= 2))
+}
+
 /// Load CAN data from a file, auto-detecting format
 pub fn load_file(path: &str) -> Result<Vec<CanMessage>> {
     load_file_with_progress(path, None)
@@ -79,40 +112,131 @@ pub fn load_file_with_progress(
     path: &str,
     progress_cb: Option<ProgressCallback>,
 ) -> Result<Vec<CanMessage>> {
-    // Only read first 1KB for format detection to avoid loading large files twice
-    let mut f = std::fs::File::open(path)?;
-    let mut header = vec![0u8; 1024];
-    let n = std::io::Read::read(&mut f, &mut header)?;
-    header.truncate(n);
-
-    match detect_format(&header) {
-        InputFormat::Csv => load_csv_with_progress(path, progress_cb),
+    let mut messages = match detect_file_format(path)? {
+        InputFormat::Csv => load_csv_with_progress(path, progress_cb)?,
         InputFormat::Rlog | InputFormat::CabanaRlog => {
-            load_cabana_rlog_with_progress(path, progress_cb)
+            load_cabana_rlog_with_progress(path, progress_cb)?
         }
+        InputFormat::Candump => load_candump(path)?,
+        InputFormat::Asc => load_asc(path)?,
         InputFormat::Unknown => anyhow::bail!("Unknown input format"),
+    };
+
+    sort_by_timestamp(&mut messages);
+    Ok(messages)
+}
+
+/// Stable-sort messages by timestamp. Seeking and windowing use binary search and
+/// assume monotonic order; logs with out-of-order rows are repaired here.
+/// Returns true if the messages were out of order.
+pub fn sort_by_timestamp(messages: &mut [CanMessage]) -> bool {
+    let sorted = messages.windows(2).all(|w| w[0].timestamp <= w[1].timestamp);
+    if !sorted {
+        messages.sort_by_key(|m| m.timestamp);
     }
+    !sorted
+}
+
+/// Frames of a time-sorted log between two offsets (seconds, inclusive) from its first frame
+pub fn messages_in_range(messages: &[CanMessage], (start, end): (f64, f64)) -> &[CanMessage] {
+    let Some(first) = messages.first().map(|m| m.timestamp) else {
+        return &[];
+    };
+    let at = |secs: f64| first + chrono::Duration::microseconds((secs * 1_000_000.0).round() as i64);
+    let from = messages.partition_point(|m| m.timestamp < at(start));
+    let to = messages.partition_point(|m| m.timestamp <= at(end));
+    &messages[from..to.max(from)]
 }
 
 /// Stream load CSV: calls chunk_cb with each batch, progress_cb for progress. Returns Ok(()) when done.
+/// Chunks are emitted in file order; callers should sort once the load completes.
 pub fn load_file_streaming(
     path: &str,
     chunk_cb: ChunkCallback,
     progress_cb: Option<ProgressCallback>,
+    csv_options: &CsvOptions,
 ) -> Result<()> {
-    let mut f = std::fs::File::open(path)?;
-    let mut header = vec![0u8; 1024];
-    let n = std::io::Read::read(&mut f, &mut header)?;
-    header.truncate(n);
-
-    match detect_format(&header) {
-        InputFormat::Csv => load_csv_streaming(path, chunk_cb, progress_cb),
+    match detect_file_format(path)? {
+        InputFormat::Csv => load_csv_streaming(path, chunk_cb, progress_cb, csv_options),
         InputFormat::Rlog | InputFormat::CabanaRlog => {
             // rlog/cabana don't support streaming - fall back to full load
             let messages = load_cabana_rlog(path)?;
             chunk_cb(messages);
             Ok(())
         }
+        InputFormat::Candump => {
+            chunk_cb(load_candump(path)?);
+            Ok(())
+        }
+        InputFormat::Asc => {
+            chunk_cb(load_asc(path)?);
+            Ok(())
+        }
         InputFormat::Unknown => anyhow::bail!("Unknown input format"),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn test_load_file_sorts_shuffled_rows() {
+        let dir = std::env::temp_dir();
+        let path = dir.join("test_shuffled_rows.csv");
+        let mut f = std::fs::File::create(&path).unwrap();
+        writeln!(f, "time,bus,addr,data").unwrap();
+        // Relative (negative) timestamps with rows deliberately out of order
+        writeln!(f, "-0.020,0,0x100,01").unwrap();
+        writeln!(f, "-0.050,0,0x101,02").unwrap();
+        writeln!(f, "0.010,0,0x102,03").unwrap();
+        writeln!(f, "-0.030,0,0x103,04").unwrap();
+        writeln!(f, "0.000,0,0x104,05").unwrap();
+        drop(f);
+
+        let msgs = load_file(path.to_str().unwrap()).unwrap();
+        assert_eq!(msgs.len(), 5);
+        assert!(msgs.windows(2).all(|w| w[0].timestamp <= w[1].timestamp));
+        let ids: Vec<u32> = msgs.iter().map(|m| m.id).collect();
+        assert_eq!(ids, vec![0x101, 0x103, 0x100, 0x104, 0x102]);
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_messages_in_range() {
+        let base = chrono::Utc::now();
+        let messages: Vec<CanMessage> = (0..10)
+            .map(|i| {
+                let mut msg = CanMessage::new(0, 0x100 + i, vec![i as u8].into());
+                msg.timestamp = base + chrono::Duration::milliseconds(i as i64 * 100);
+                msg
+            })
+            .collect();
+
+        let ids = |range| messages_in_range(&messages, range).iter().map(|m| m.id).collect::<Vec<_>>();
+        assert_eq!(ids((0.2, 0.4)), vec![0x102, 0x103, 0x104]);
+        assert_eq!(ids((0.25, 0.35)), vec![0x103]);
+        assert!(ids((2.0, 3.0)).is_empty());
+        assert!(messages_in_range(&[], (0.0, 1.0)).is_empty());
+    }
+
+    #[test]
+    fn test_load_csv_with_bom_and_crlf() {
+        let path = std::env::temp_dir().join("test_bom_crlf.csv");
+        let mut f = std::fs::File::create(&path).unwrap();
+        f.write_all(b"\xEF\xBB\xBFtime,bus,addr,data\r\n0.000,0,0x100,01 02\r\n0.010,1,0x200,FF\r\n").unwrap();
+        drop(f);
+
+        assert!(matches!(detect_format(&std::fs::read(&path).unwrap()), InputFormat::Csv));
+        let msgs = load_file(path.to_str().unwrap()).unwrap();
+        assert_eq!(msgs.len(), 2);
+        assert_eq!(msgs[0].id, 0x100);
+        assert_eq!(msgs[0].data, vec![0x01, 0x02]);
+        assert_eq!(msgs[1].bus, 1);
+        assert_eq!(msgs[1].data, vec![0xFF]);
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -1,22 +1,25 @@
 //! Logging setup: console (stderr), file, and in-app buffer for the Log window.
 
+use std::collections::VecDeque;
 use std::sync::{Arc, Mutex};
 use tracing_subscriber::{
     fmt::{self, format::FmtSpan},
-    layer::SubscriberExt,
+    layer::{Layer, SubscriberExt},
     util::SubscriberInitExt,
     EnvFilter,
 };
 
-/// In-memory buffer for the Log window (last N lines).
+/// In-memory ring buffer for the Log window (last N lines).
 const LOG_BUFFER_MAX: usize = 2000;
 
-static LOG_BUFFER: std::sync::OnceLock<Arc<Mutex<Vec<String>>>> = std::sync::OnceLock::new();
+type LogBuffer = Arc<Mutex<VecDeque<String>>>;
+
+static LOG_BUFFER: std::sync::OnceLock<LogBuffer> = std::sync::OnceLock::new();
 
 /// Returns the shared log buffer for the UI.
-pub fn log_buffer() -> Arc<Mutex<Vec<String>>> {
+pub fn log_buffer() -> LogBuffer {
     LOG_BUFFER
-        .get_or_init(|| Arc::new(Mutex::new(Vec::with_capacity(LOG_BUFFER_MAX))))
+        .get_or_init(|| Arc::new(Mutex::new(VecDeque::with_capacity(LOG_BUFFER_MAX))))
         .clone()
 }
 
@@ -63,34 +66,40 @@ pub fn init() {
         .with_thread_ids(false)
         .with_writer(move || BufferWriter::new(buffer_clone.clone()));
 
-    let filter = EnvFilter::try_from_default_env()
-        .unwrap_or_else(|_| EnvFilter::new("info"));
-
-    let registry = tracing_subscriber::registry()
-        .with(filter)
-        .with(fmt::layer().with_writer(std::io::stderr).with_span_events(FmtSpan::NONE))
-        .with(buffer_layer);
+    // RUST_LOG overrides both; by default the Log window also shows this crate's debug events
+    let env_filter = |default: &str| EnvFilter::try_from_default_env()
+        .unwrap_or_else(|_| EnvFilter::new(default));
 
-    if let Some(fl) = file_layer {
-        registry.with(fl).init();
-    } else {
-        registry.init();
-    }
+    tracing_subscriber::registry()
+        .with(fmt::layer().with_writer(std::io::stderr).with_span_events(FmtSpan::NONE).with_filter(env_filter("info")))
+        .with(buffer_layer.with_filter(env_filter("info,shit=debug")))
+        .with(file_layer.with_filter(env_filter("info")))
+        .init();
 }
 
 /// Writer that appends formatted lines to the log buffer.
 struct BufferWriter {
-    buffer: Arc<Mutex<Vec<String>>>,
+    buffer: LogBuffer,
     line: String,
 }
 
 impl BufferWriter {
-    fn new(buffer: Arc<Mutex<Vec<String>>>) -> Self {
+    fn new(buffer: LogBuffer) -> Self {
         Self {
             buffer,
             line: String::new(),
         }
     }
+
+    /// Append a finished line, dropping the oldest once the buffer is full
+    fn push_line(&mut self, line: String) {
+        if let Ok(mut v) = self.buffer.lock() {
+            if v.len() >= LOG_BUFFER_MAX {
+                v.pop_front();
+            }
+            v.push_back(line);
+        }
+    }
 }
 
 impl std::io::Write for BufferWriter {
@@ -100,13 +109,7 @@ impl std::io::Write for BufferWriter {
             if ch == '\n' {
                 let line = std::mem::take(&mut self.line);
                 if !line.is_empty() {
-                    if let Ok(mut v) = self.buffer.lock() {
-                        v.push(line);
-                        if v.len() > LOG_BUFFER_MAX {
-                            let excess = v.len() - LOG_BUFFER_MAX;
-                            v.drain(0..excess);
-                        }
-                    }
+                    self.push_line(line);
                 }
             } else {
                 self.line.push(ch);
@@ -117,14 +120,32 @@ impl std::io::Write for BufferWriter {
 
     fn flush(&mut self) -> std::io::Result<()> {
         if !self.line.is_empty() {
-            if let Ok(mut v) = self.buffer.lock() {
-                v.push(std::mem::take(&mut self.line));
-                if v.len() > LOG_BUFFER_MAX {
-                    let excess = v.len() - LOG_BUFFER_MAX;
-                    v.drain(0..excess);
-                }
-            }
+            let line = std::mem::take(&mut self.line);
+            self.push_line(line);
         }
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn test_buffer_writer_drops_oldest_lines() {
+        let buffer: LogBuffer = Arc::new(Mutex::new(VecDeque::new()));
+        let mut writer = BufferWriter::new(buffer.clone());
+        for i in 0..LOG_BUFFER_MAX + 5 {
+            write!(writer, "line {}\npartial", i).unwrap();
+            writer.line.clear();
+        }
+        writer.write_all(b"tail").unwrap();
+        writer.flush().unwrap();
+
+        let lines = buffer.lock().unwrap();
+        assert_eq!(lines.len(), LOG_BUFFER_MAX);
+        assert_eq!(lines.front().map(String::as_str), Some("line 6"));
+        assert_eq!(lines.back().map(String::as_str), Some("tail"));
+    }
+}
@@ -1,5 +1,6 @@
 #![cfg_attr(target_os = "windows", windows_subsystem = "console")]
 
+mod cli;
 mod core;
 mod decode;
 mod hardware;
@@ -11,13 +12,20 @@ mod ui;
 
 use core::{CanMessage, DbcFile};
 use decode::SignalDecoder;
-use playback::PlaybackEngine;
+use decode::router::{signal_key, split_signal_key, SignalRouter};
+use decode::anomaly::{next_anomaly, prev_anomaly, scan_anomalies, Anomaly};
+use decode::formula::VirtualEval;
+use decode::gaps::{find_gaps, FrameGap};
+use input::file_watch::FileWatch;
+use playback::{parse_seek_target, PlaybackEngine, DEFAULT_MAX_GAP_SECS, MAX_SPEED, MIN_SPEED};
 use hardware::CanManagerCollection;
-use hardware::can_manager::ManagerMessage;
+use hardware::can_manager::{ManagerMessage, TimestampSource};
 use hardware::can_interface::InterfaceType;
 use plugins::{PluginContext, PluginRegistry};
-use ui::{MessageListWindow, FileDialogs, MultiSignalGraph, HardwareManagerWindow, LiveModeAction, LiveMessageWindow, MessageSenderWindow, MessageStatsWindow, PatternAnalyzerWindow, ShortcutManager, ExportDialog, AboutDialog, BitVisualizerWindow, SignalInfo, LogWindow};
-use ui::statistics::{MessageStatistics, PatternAnalyzer};
+use ui::{MessageListWindow, FileDialogs, MissingValuePolicy, MultiSignalGraph, HardwareManagerWindow, LiveModeAction, LiveMessageWindow, MessageSenderWindow, MessageStatsWindow, PatternAnalyzerWindow, ShortcutManager, ExportDialog, AboutDialog, GenerateDialog, BitVisualizerWindow, SignalInfo, LogWindow, CoverageWindow, RangeCheckWindow, MessageRateWindow, DiagnosticsWindow, WatchWindow, TimelineWindow, Theme, WorkspaceAction, WorkspaceStore};
+use ui::statistics::{analyze_log, MessageStatistics, PatternAnalyzer};
+use ui::range_check::{scan_signal_ranges, SignalRange};
+use ui::live_mode::RecordingMode;
 use chrono::{DateTime, Duration, Utc};
 use imgui::{Context, FontConfig, FontSource, Condition};
 use imgui_winit_support::{HiDpiMode, WinitPlatform};
@@ -33,11 +41,12 @@ use glow::HasContext;
 
 use std::time::Instant;
 use std::sync::{Arc, Mutex};
-use tracing::{info, error};
+use tracing::{debug, info, error};
 use std::sync::mpsc::{channel, Receiver, Sender};
 use std::fs;
 use std::path::PathBuf;
 use serde::{Deserialize, Serialize};
+use anyhow::Context as _;
 
 struct AppState {
     messages: Vec<CanMessage>,
@@ -50,20 +59,54 @@ struct AppState {
     initial_data_populated: bool,  // Track if we've done initial population
     /// When true, update_graphs runs even when paused (e.g. after timeline scrub)
     seek_triggered_ui_update: bool,
+    /// Text of the Playback menu's "Go to time" box
+    goto_time_input: String,
+    /// Play idle gaps longer than `playback_gap_cap_secs` at that length
+    cap_playback_gaps: bool,
+    playback_gap_cap_secs: f64,
+    /// Anomalous frames in the loaded log (DLC mismatches, failed checksums), by index
+    anomalies: Vec<Anomaly>,
     // Phase 6 components
     message_stats: MessageStatsWindow,
     pattern_analyzer: PatternAnalyzerWindow,
     shortcut_manager: ShortcutManager,
     export_dialog: ExportDialog,
     about_dialog: AboutDialog,
+    generate_dialog: GenerateDialog,
     // Bit visualizer
     bit_visualizer: BitVisualizerWindow,
     // Log window
     log_window: LogWindow,
+    // DBC coverage of the loaded log
+    coverage_window: CoverageWindow,
+    // Observed signal ranges vs the DBC
+    range_check_window: RangeCheckWindow,
+    // Message rate over the loaded log
+    rate_window: MessageRateWindow,
+    // ISO-TP traffic on a diagnostic request/response pair
+    diagnostics_window: DiagnosticsWindow,
+    // Large readouts of watched signals
+    watch_window: WatchWindow,
+    // Message density scrubber over the loaded log
+    timeline_window: TimelineWindow,
     dbc_file: DbcFile,
+    /// File the DBC was loaded from or last saved to (None for a new DBC)
+    dbc_path: Option<String>,
     signal_decoder: SignalDecoder,
     file_loaded: bool,
     dbc_loaded: bool,
+    /// The DBC was edited since it was loaded or saved
+    dbc_dirty: bool,
+    /// Action that drops the DBC, waiting on the user to save or discard its edits
+    dbc_discard_pending: Option<DbcDiscard>,
+    /// Quit once this frame is done (set after the DBC save prompt)
+    exit_requested: bool,
+    /// Re-parse the DBC when its file changes on disk
+    auto_reload_dbc: bool,
+    /// Watch on `dbc_path` while `auto_reload_dbc` is on
+    dbc_watch: Option<FileWatch>,
+    /// The DBC parse in flight is a reload of the current file (keeps the charted signals)
+    dbc_reloading: bool,
     show_file_open_pending: bool,
     show_cabana_folder_pending: bool,
     show_dbc_open_pending: bool,
@@ -72,6 +115,10 @@ struct AppState {
     status_message: Option<String>,
     // Incremental chart data loading
     pending_signal_loads: std::collections::HashMap<String, usize>,  // signal_name -> current message index
+    // Virtual signals being recomputed over the log: evaluator and next message index
+    pending_virtual_loads: std::collections::HashMap<String, (VirtualEval, usize)>,
+    // Charted virtual signals' evaluators for live frames (dropped when the DBC changes)
+    live_virtuals: std::collections::HashMap<String, VirtualEval>,
     // Window visibility
     show_messages: bool,
     show_charts: bool,
@@ -86,14 +133,34 @@ struct AppState {
     show_bit_visualizer: bool,
     // Log window
     show_log: bool,
+    show_coverage: bool,
+    show_range_check: bool,
+    show_rate_graph: bool,
+    show_diagnostics: bool,
+    show_watch: bool,
+    show_timeline: bool,
     // Recently opened files (paths)
     recent_can_files: Vec<String>,
     recent_dbc_files: Vec<String>,
     recent_savestates: Vec<String>,
+    // Absolute time the first row of relative-time CSV logs is anchored to (None = load time)
+    csv_epoch: Option<DateTime<Utc>>,
+    // Whether CSV time columns are relative or epoch times (Auto = guess from the first row)
+    csv_time_base: input::CsvTimeBase,
+    // Text of the epoch input in the CSV timestamp options
+    csv_epoch_text: String,
     // Savestate loading: apply when CAN load completes
     pending_savestate: Option<Savestate>,
     // Layout to apply next frame (needs imgui context)
     pending_layout_apply: Option<String>,
+    // UI color theme, and a pending style update (needs imgui context)
+    theme: Theme,
+    pending_theme_apply: Option<Theme>,
+    // Palette signal, series and changed-byte colors come from (handed to each view)
+    color_palette: ui::colors::ColorPalette,
+    // Named layout + open-window presets, and a save waiting for the imgui context
+    workspaces: WorkspaceStore,
+    pending_workspace_save: Option<String>,
     // CAN hardware manager
     can_collection: CanManagerCollection,
     // Plugins
@@ -106,9 +173,26 @@ struct AppState {
     loading_progress: f32,
     loading_total: usize,
     loading_receiver: Option<Receiver<LoadingUpdate>>,
+    /// Whether the file being loaded goes into the recent files list (not for spilled recordings)
+    loading_remember_file: bool,
     pending_messages: Option<Arc<Mutex<Vec<CanMessage>>>>,
+    /// Background DBC parse in flight, and its progress in percent
+    dbc_loading_receiver: Option<Receiver<DbcLoadingUpdate>>,
+    dbc_loading_progress: f32,
     /// Receiver for background stats/analyzer results
-    analysis_receiver: Option<Receiver<(MessageStatistics, PatternAnalyzer)>>,
+    analysis_receiver: Option<Receiver<(MessageStatistics, PatternAnalyzer, Vec<SignalRange>)>>,
+    /// Background anomaly scan of the loaded log; a newer scan replaces (and so discards) an older one
+    anomaly_receiver: Option<Receiver<Vec<Anomaly>>>,
+    /// Report the anomaly count in the status bar when the pending scan finishes
+    announce_anomalies: bool,
+    /// Background gap scan of the loaded log, replaced like `anomaly_receiver`
+    gap_receiver: Option<Receiver<Vec<FrameGap>>>,
+    /// Loopback self-test running on the Tokio runtime
+    self_test_receiver: Option<Receiver<ui::self_test::SelfTestReport>>,
+    /// Settings JSON last written to (or loaded from) disk
+    saved_settings: String,
+    /// Changed settings not yet written, and when they last changed
+    pending_settings: Option<(String, Instant)>,
 }
 
 /// Messages for async loading
@@ -122,6 +206,33 @@ enum LoadingUpdate {
     Error(String),
 }
 
+/// Ways to drop the current DBC from the File menu
+#[derive(Clone, Copy, PartialEq)]
+enum DbcReset {
+    /// Start an empty DBC to define signals into
+    New,
+    /// Go back to no DBC: nothing is decoded
+    Close,
+}
+
+/// Actions that drop the current DBC, so unsaved edits to it need a save prompt first
+#[derive(Clone, PartialEq)]
+enum DbcDiscard {
+    Reset(DbcReset),
+    /// Load another DBC, from a file dialog when no path is given
+    Load(Option<String>),
+    Exit,
+}
+
+/// Messages for async DBC loading
+enum DbcLoadingUpdate {
+    /// Progress(lines_parsed, total_lines)
+    Progress(usize, usize),
+    /// Parsed DBC and the path it came from
+    Complete(String, Box<DbcFile>),
+    Error(String),
+}
+
 /// Savestate: snapshot of window layout, chart signals, bit visualizer quadrants, and file paths
 #[derive(Serialize, Deserialize, Default, Clone)]
 struct Savestate {
@@ -131,6 +242,9 @@ struct Savestate {
     dbc_file_path: Option<String>,
     #[serde(default)]
     chart_signals: Vec<String>,
+    /// Watch window signals, in display order
+    #[serde(default)]
+    watch_signals: Vec<String>,
     /// Each quadrant: (msg_id, bus) or None for empty
     #[serde(default)]
     bit_visualizer_quadrants: Vec<(u32, u8)>,
@@ -155,6 +269,18 @@ struct Savestate {
     show_pattern_analyzer: bool,
     #[serde(default)]
     show_log: bool,
+    #[serde(default)]
+    show_coverage: bool,
+    #[serde(default)]
+    show_range_check: bool,
+    #[serde(default)]
+    show_rate_graph: bool,
+    #[serde(default)]
+    show_diagnostics: bool,
+    #[serde(default)]
+    show_watch: bool,
+    #[serde(default)]
+    show_timeline: bool,
     /// ImGui layout INI content
     #[serde(default)]
     layout_ini: String,
@@ -174,15 +300,86 @@ struct AppSettings {
     show_bit_visualizer: bool,
     show_log: bool,
     #[serde(default)]
+    show_coverage: bool,
+    #[serde(default)]
+    show_range_check: bool,
+    #[serde(default)]
+    show_rate_graph: bool,
+    #[serde(default)]
+    show_diagnostics: bool,
+    #[serde(default)]
+    show_watch: bool,
+    #[serde(default)]
+    show_timeline: bool,
+    #[serde(default)]
     recent_can_files: Vec<String>,
     #[serde(default)]
     recent_dbc_files: Vec<String>,
     #[serde(default)]
     recent_savestates: Vec<String>,
+    #[serde(default)]
+    csv_epoch: Option<DateTime<Utc>>,
+    #[serde(default)]
+    csv_time_base: input::CsvTimeBase,
+    // Total chart points across all series (None = default budget)
+    #[serde(default)]
+    chart_points_budget: Option<usize>,
+    // Live chart points per second per signal (None = default cap, 0 = every frame)
+    #[serde(default)]
+    live_chart_rate_hz: Option<u32>,
+    // What charts record for a frame too short to decode a charted signal from
+    #[serde(default)]
+    chart_missing_policy: MissingValuePolicy,
+    // Chart colors picked in the legend, by "signal_name@0xID@busN"
+    #[serde(default)]
+    chart_colors: std::collections::BTreeMap<String, [f32; 4]>,
+    #[serde(default)]
+    theme: Theme,
+    #[serde(default)]
+    timestamp_source: TimestampSource,
+    #[serde(default)]
+    recording_mode: RecordingMode,
+    // Missing-frame threshold in cycle times (None = default)
+    #[serde(default)]
+    gap_multiplier: Option<f64>,
+    #[serde(default)]
+    cap_playback_gaps: bool,
+    // Longest idle gap played back, in seconds (None = default)
+    #[serde(default)]
+    playback_gap_cap_secs: Option<f64>,
+    // Bit visualizer grid zoom (None = default)
+    #[serde(default)]
+    bit_grid_zoom: Option<f32>,
+    #[serde(default)]
+    message_groups: ui::windows::MessageGroupSettings,
+    // Last-used connection settings by interface name
+    #[serde(default)]
+    adapter_configs: ui::live_mode::AdapterConfigs,
+    #[serde(default)]
+    color_palette: ui::colors::ColorPalette,
+    // Formula signals defined in the chart picker
+    #[serde(default)]
+    virtual_signals: Vec<decode::formula::VirtualSignal>,
+    // Re-parse the DBC when its file changes on disk
+    #[serde(default)]
+    auto_reload_dbc: bool,
 }
 
 const MAX_RECENT_FILES: usize = 10;
 
+/// How often the settings are checked for changes
+const SETTINGS_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// Changed settings are written once they've been stable this long, so bursts of edits cost one write
+const SETTINGS_DEBOUNCE: std::time::Duration = std::time::Duration::from_secs(3);
+
+/// Window title; gets a `*` while the DBC has unsaved edits
+const WINDOW_TITLE: &str = "S.H.I.T - Signal Harvesting & Interpretation Toolkit";
+
+/// Shown when the window can't be created, which usually means there's no display to put it on
+const NO_DISPLAY_HINT: &str = "Could not open a window. Is a display available (DISPLAY / WAYLAND_DISPLAY set)? \
+    To export without a window, run headless: shit --input <log> --export-csv <out> (see --help)";
+
 impl AppSettings {
     fn config_path() -> Option<PathBuf> {
         dirs::config_dir().map(|p| p.join("can-viz").join("settings.json"))
@@ -207,14 +404,16 @@ impl AppSettings {
         }
     }
 
-    fn save(&self) {
+    fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+
+    fn write_json(json: &str) {
         if let Some(path) = Self::config_path() {
             if let Some(parent) = path.parent() {
                 let _ = fs::create_dir_all(parent);
             }
-            if let Ok(json) = serde_json::to_string_pretty(self) {
-                let _ = fs::write(&path, json);
-            }
+            let _ = fs::write(&path, json);
         }
     }
 }
@@ -224,30 +423,84 @@ impl AppState {
         // Load persisted settings
         let settings = AppSettings::load();
 
-        Self {
+        let mut state = Self {
             messages: Vec::new(),
             playback: PlaybackEngine::new(Vec::new()),
-            message_list: MessageListWindow::new(),
-            charts: MultiSignalGraph::new(),
-            hardware_manager: HardwareManagerWindow::new(),
+            message_list: {
+                let mut message_list = MessageListWindow::new();
+                message_list.set_group_settings(settings.message_groups.clone());
+                message_list
+            },
+            charts: {
+                let mut charts = MultiSignalGraph::new();
+                if let Some(budget) = settings.chart_points_budget {
+                    charts.set_points_budget(budget);
+                }
+                if let Some(hz) = settings.live_chart_rate_hz {
+                    charts.set_live_rate_cap_hz(hz);
+                }
+                charts.set_missing_policy(settings.chart_missing_policy);
+                charts.set_theme(settings.theme);
+                charts.set_custom_colors(settings.chart_colors.clone());
+                charts.set_virtual_signals(settings.virtual_signals.clone());
+                charts
+            },
+            hardware_manager: {
+                let mut hardware_manager = HardwareManagerWindow::new();
+                hardware_manager.state_mut().timestamp_source = settings.timestamp_source;
+                hardware_manager.state_mut().recording_mode = settings.recording_mode;
+                hardware_manager.state_mut().adapter_configs = settings.adapter_configs.clone();
+                hardware_manager
+            },
             live_message_window: LiveMessageWindow::new(),
             message_sender: MessageSenderWindow::new(),
             initial_data_populated: false,
             seek_triggered_ui_update: false,
+            goto_time_input: String::new(),
+            cap_playback_gaps: settings.cap_playback_gaps,
+            playback_gap_cap_secs: settings.playback_gap_cap_secs.unwrap_or(DEFAULT_MAX_GAP_SECS),
+            anomalies: Vec::new(),
             // Phase 6 components
-            message_stats: MessageStatsWindow::new(),
+            message_stats: {
+                let mut message_stats = MessageStatsWindow::new();
+                if let Some(multiplier) = settings.gap_multiplier {
+                    message_stats.set_gap_multiplier(multiplier);
+                }
+                message_stats
+            },
             pattern_analyzer: PatternAnalyzerWindow::new(),
             shortcut_manager: ShortcutManager::new(),
             export_dialog: ExportDialog::new(),
             about_dialog: AboutDialog::new(),
+            generate_dialog: GenerateDialog::new(),
             // Bit visualizer
-            bit_visualizer: BitVisualizerWindow::new(),
+            bit_visualizer: {
+                let mut bit_visualizer = BitVisualizerWindow::new();
+                if let Some(zoom) = settings.bit_grid_zoom {
+                    bit_visualizer.set_grid_zoom(zoom);
+                }
+                bit_visualizer.set_signal_notes(ui::signal_notes::SignalNotes::load());
+                bit_visualizer
+            },
             // Log window
             log_window: LogWindow::new(),
+            coverage_window: CoverageWindow::new(),
+            range_check_window: RangeCheckWindow::new(),
+            rate_window: MessageRateWindow::new(),
+            diagnostics_window: DiagnosticsWindow::new(),
+            watch_window: WatchWindow::new(),
+            timeline_window: TimelineWindow::new(),
             dbc_file: DbcFile::new(),
+            dbc_path: None,
             signal_decoder: SignalDecoder::new(),
             file_loaded: false,
             dbc_loaded: false,
+            dbc_dirty: false,
+            dbc_discard_pending: None,
+            exit_requested: false,
+            auto_reload_dbc: settings.auto_reload_dbc,
+            dbc_watch: None,
+            dbc_reloading: false,
             show_file_open_pending: false,
             show_cabana_folder_pending: false,
             show_dbc_open_pending: false,
@@ -255,6 +508,8 @@ impl AppState {
             show_load_savestate_pending: false,
             status_message: None,
             pending_signal_loads: std::collections::HashMap::new(),
+            pending_virtual_loads: std::collections::HashMap::new(),
+            live_virtuals: std::collections::HashMap::new(),
             // Window visibility from settings
             show_messages: settings.show_messages,
             show_charts: settings.show_charts,
@@ -269,12 +524,26 @@ impl AppState {
             show_bit_visualizer: settings.show_bit_visualizer,
             // Log window
             show_log: settings.show_log,
+            show_coverage: settings.show_coverage,
+            show_range_check: settings.show_range_check,
+            show_rate_graph: settings.show_rate_graph,
+            show_diagnostics: settings.show_diagnostics,
+            show_watch: settings.show_watch,
+            show_timeline: settings.show_timeline,
             // Recently opened files
             recent_can_files: settings.recent_can_files,
             recent_dbc_files: settings.recent_dbc_files,
             recent_savestates: settings.recent_savestates,
+            csv_epoch: settings.csv_epoch,
+            csv_time_base: settings.csv_time_base,
+            csv_epoch_text: settings.csv_epoch.map(|t| t.format(input::EPOCH_INPUT_FORMAT).to_string()).unwrap_or_default(),
             pending_savestate: None,
             pending_layout_apply: None,
+            theme: settings.theme,
+            pending_theme_apply: Some(settings.theme),
+            color_palette: settings.color_palette,
+            workspaces: WorkspaceStore::load(),
+            pending_workspace_save: None,
             // CAN hardware manager
             can_collection: CanManagerCollection::new(),
             // Plugins
@@ -286,13 +555,55 @@ impl AppState {
             loading_progress: 0.0,
             loading_total: 0,
             loading_receiver: None,
+            loading_remember_file: true,
             pending_messages: None,
+            dbc_loading_receiver: None,
+            dbc_loading_progress: 0.0,
             analysis_receiver: None,
+            anomaly_receiver: None,
+            announce_anomalies: false,
+            gap_receiver: None,
+            self_test_receiver: None,
+            saved_settings: String::new(),
+            pending_settings: None,
+        };
+        state.set_color_palette(settings.color_palette);
+        // What's on disk now; only differences from it get written
+        state.saved_settings = state.current_settings().to_json();
+        state
+    }
+
+    /// Write settings now if they differ from what's on disk (on close)
+    fn save_settings(&mut self) {
+        let json = self.current_settings().to_json();
+        self.pending_settings = None;
+        if json != self.saved_settings {
+            AppSettings::write_json(&json);
+            self.saved_settings = json;
+        }
+    }
+
+    /// Write changed settings once they've stopped changing for `SETTINGS_DEBOUNCE`
+    fn poll_settings_save(&mut self) {
+        let json = self.current_settings().to_json();
+        if json == self.saved_settings {
+            self.pending_settings = None;
+            return;
+        }
+        match &self.pending_settings {
+            Some((pending, since)) if *pending == json => {
+                if since.elapsed() >= SETTINGS_DEBOUNCE {
+                    self.save_settings();
+                }
+            }
+            // Newly changed, or changed again: restart the debounce
+            _ => self.pending_settings = Some((json, Instant::now())),
         }
     }
 
-    fn save_settings(&self) {
-        let settings = AppSettings {
+    /// Snapshot of everything persisted in the settings file
+    fn current_settings(&self) -> AppSettings {
+        AppSettings {
             show_messages: self.show_messages,
             show_charts: self.show_charts,
             show_hardware_manager: self.show_hardware_manager,
@@ -303,11 +614,188 @@ impl AppState {
             show_shortcuts: self.show_shortcuts,
             show_bit_visualizer: self.show_bit_visualizer,
             show_log: self.show_log,
+            show_coverage: self.show_coverage,
+            show_range_check: self.show_range_check,
+            show_rate_graph: self.show_rate_graph,
+            show_diagnostics: self.show_diagnostics,
+            show_watch: self.show_watch,
+            show_timeline: self.show_timeline,
             recent_can_files: self.recent_can_files.clone(),
             recent_dbc_files: self.recent_dbc_files.clone(),
             recent_savestates: self.recent_savestates.clone(),
+            csv_epoch: self.csv_epoch,
+            csv_time_base: self.csv_time_base,
+            chart_points_budget: Some(self.charts.points_budget()),
+            live_chart_rate_hz: Some(self.charts.live_rate_cap_hz()),
+            chart_missing_policy: self.charts.missing_policy(),
+            chart_colors: self.charts.custom_colors().clone(),
+            theme: self.theme,
+            timestamp_source: self.hardware_manager.state().timestamp_source,
+            recording_mode: self.hardware_manager.state().recording_mode,
+            gap_multiplier: Some(self.message_stats.gap_multiplier()),
+            cap_playback_gaps: self.cap_playback_gaps,
+            playback_gap_cap_secs: Some(self.playback_gap_cap_secs),
+            bit_grid_zoom: Some(self.bit_visualizer.grid_zoom()),
+            message_groups: self.message_list.group_settings().clone(),
+            adapter_configs: self.hardware_manager.state().adapter_configs.clone(),
+            virtual_signals: self.charts.virtual_signals().to_vec(),
+            color_palette: self.color_palette,
+            auto_reload_dbc: self.auto_reload_dbc,
+        }
+    }
+
+    fn set_theme(&mut self, theme: Theme) {
+        self.theme = theme;
+        self.pending_theme_apply = Some(theme);
+        self.charts.set_theme(theme);
+    }
+
+    fn set_color_palette(&mut self, palette: ui::colors::ColorPalette) {
+        self.color_palette = palette;
+        self.charts.set_color_palette(palette);
+        self.bit_visualizer.set_color_palette(palette);
+        self.rate_window.set_color_palette(palette);
+        self.message_list.set_color_palette(palette);
+        self.live_message_window.set_color_palette(palette);
+    }
+
+    fn handle_export(&mut self, request: &ui::ExportRequest) {
+        #[cfg(feature = "parquet")]
+        if request.export_type == ui::ExportType::Parquet {
+            self.export_parquet(request.charted_only, request.time_precision, request.time_range, request.rebase_to_range);
+            return;
+        }
+
+        if let Some(path) = FileDialogs::export_csv_file() {
+            if let Ok(file) = std::fs::File::create(&path) {
+                let (messages, mut origin) = match request.time_range {
+                    Some(range) => {
+                        let origin = if request.rebase_to_range {
+                            self.range_origin(range)
+                        } else {
+                            self.messages.first().map(|m| m.timestamp)
+                        };
+                        (input::messages_in_range(&self.messages, range), origin)
+                    }
+                    None => (self.messages.as_slice(), self.messages.first().map(|m| m.timestamp)),
+                };
+                if request.epoch_times {
+                    origin = Some(DateTime::UNIX_EPOCH);
+                }
+                match input::write_csv_from(std::io::BufWriter::new(file), messages, request.time_precision, origin) {
+                    Ok(omitted) => {
+                        let written = messages.len() - omitted;
+                        let note = input::omitted_note(omitted);
+                        self.status_message = Some(format!("Exported {} messages to {}{}", written, path.display(), note));
+                        info!("Exported {} messages to {}{}", written, path.display(), note);
+                    }
+                    Err(e) => {
+                        self.status_message = Some(format!("CSV export failed: {}", e));
+                        error!("Failed to write {}: {}", path.display(), e);
+                    }
+                }
+            } else {
+                self.status_message = Some("Failed to create export file".to_string());
+            }
+        }
+    }
+
+    /// Absolute time of a range's start, for exports timed from it
+    fn range_origin(&self, range: (f64, f64)) -> Option<DateTime<Utc>> {
+        self.messages.first().map(|m| m.timestamp + Duration::microseconds((range.0 * 1_000_000.0) as i64))
+    }
+
+    /// Export decoded signals (every DBC signal, or just the charted ones) to Parquet
+    #[cfg(feature = "parquet")]
+    fn export_parquet(
+        &mut self,
+        charted_only: bool,
+        precision: input::TimePrecision,
+        time_range: Option<(f64, f64)>,
+        rebase_to_range: bool,
+    ) {
+        if !self.dbc_loaded {
+            self.status_message = Some("Load a DBC to export decoded signals".to_string());
+            return;
+        }
+        let messages = match time_range {
+            Some(range) => input::messages_in_range(&self.messages, range),
+            None => self.messages.as_slice(),
+        };
+        let keys = if charted_only {
+            self.charts.get_charted_signals()
+        } else {
+            decode::signal_export::all_signal_keys(messages, &self.dbc_file)
         };
-        settings.save();
+        let Some(path) = FileDialogs::export_parquet_file() else {
+            return;
+        };
+        let origin = time_range.filter(|_| rebase_to_range).and_then(|range| self.range_origin(range));
+        match decode::parquet_export::export_parquet(&path, messages, &self.dbc_file, &keys, precision, origin) {
+            Ok(rows) => {
+                self.status_message = Some(format!("Exported {} signals ({} rows) to {}", keys.len(), rows, path.display()));
+                info!("Exported {} signals ({} rows) to {}", keys.len(), rows, path.display());
+            }
+            Err(e) => self.status_message = Some(format!("Parquet export failed: {:#}", e)),
+        }
+    }
+
+    /// Visibility flag of every window a workspace can open or close, by name
+    fn window_flags(&mut self) -> [(&'static str, &mut bool); 15] {
+        [
+            ("Messages", &mut self.show_messages),
+            ("Charts", &mut self.show_charts),
+            ("Hardware Manager", &mut self.show_hardware_manager),
+            ("Live Messages", &mut self.show_live_messages),
+            ("Message Sender", &mut self.show_message_sender),
+            ("Message Statistics", &mut self.show_message_stats),
+            ("Pattern Analyzer", &mut self.show_pattern_analyzer),
+            ("DBC Coverage", &mut self.show_coverage),
+            ("Signal Range Check", &mut self.show_range_check),
+            ("Message Rate", &mut self.show_rate_graph),
+            ("Diagnostics", &mut self.show_diagnostics),
+            ("Watch", &mut self.show_watch),
+            ("Timeline", &mut self.show_timeline),
+            ("Bit Visualizer", &mut self.show_bit_visualizer),
+            ("Log", &mut self.show_log),
+        ]
+    }
+
+    fn handle_workspace_action(&mut self, action: WorkspaceAction) {
+        match action {
+            WorkspaceAction::None => {}
+            WorkspaceAction::Apply(workspace) => {
+                // Windows switch now and the layout loads before the next frame is drawn
+                for (name, open) in self.window_flags() {
+                    *open = workspace.open_windows.iter().any(|w| w == name);
+                }
+                self.pending_layout_apply = Some(workspace.layout_ini.clone());
+                self.workspaces.set_active(&workspace.name);
+                self.workspaces.save();
+                self.status_message = Some(format!("Workspace \"{}\"", workspace.name));
+            }
+            WorkspaceAction::SaveCurrent(name) => self.pending_workspace_save = Some(name),
+            WorkspaceAction::Delete(name) => {
+                self.workspaces.remove(&name);
+                self.workspaces.save();
+            }
+        }
+    }
+
+    fn process_workspace_save(&mut self, imgui: &mut imgui::Context) {
+        let Some(name) = self.pending_workspace_save.take() else {
+            return;
+        };
+        let mut layout_ini = String::new();
+        imgui.save_ini_settings(&mut layout_ini);
+        let open_windows = self.window_flags()
+            .into_iter()
+            .filter(|(_, open)| **open)
+            .map(|(name, _)| name.to_string())
+            .collect();
+        self.workspaces.upsert(ui::workspace::Workspace { name: name.clone(), layout_ini, open_windows });
+        self.workspaces.save();
+        self.status_message = Some(format!("Workspace \"{}\" saved", name));
     }
 
     fn add_recent_can_file(&mut self, path: &str) {
@@ -321,7 +809,6 @@ impl AppState {
         if self.recent_can_files.len() > MAX_RECENT_FILES {
             self.recent_can_files.truncate(MAX_RECENT_FILES);
         }
-        self.save_settings();
     }
 
     fn add_recent_dbc_file(&mut self, path: &str) {
@@ -335,18 +822,52 @@ impl AppState {
         if self.recent_dbc_files.len() > MAX_RECENT_FILES {
             self.recent_dbc_files.truncate(MAX_RECENT_FILES);
         }
-        self.save_settings();
     }
 
     fn load_file(&mut self, path: &str) {
+        let csv_options = input::CsvOptions { epoch: self.csv_epoch, time_base: self.csv_time_base };
+        self.loading_remember_file = true;
+        self.start_streaming_load(path, csv_options);
+    }
+
+    /// Open a file from the command line or dropped on the window: a DBC by its
+    /// extension, anything else as a log if its contents look like one
+    fn open_path(&mut self, path: &std::path::Path) {
+        let path_str = path.to_string_lossy().to_string();
+        if path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("dbc")) {
+            self.request_dbc_discard(DbcDiscard::Load(Some(path_str)));
+            return;
+        }
+        match input::detect_file_format(&path_str) {
+            Ok(input::InputFormat::Unknown) => {
+                self.status_message = Some(format!("Not a CAN log or DBC: {}", path.display()));
+            }
+            Ok(_) => self.load_file(&path_str),
+            Err(e) => {
+                error!("Failed to open {}: {}", path.display(), e);
+                self.status_message = Some(format!("Failed to open {}: {}", path.display(), e));
+            }
+        }
+    }
+
+    /// Load a recording spilled to disk back for playback, anchored at its first frame's time
+    fn load_recording(&mut self, path: &str, first_timestamp: DateTime<Utc>) {
+        let csv_options = input::CsvOptions { epoch: Some(first_timestamp), time_base: input::CsvTimeBase::Relative };
+        self.loading_remember_file = false;
+        self.start_streaming_load(path, csv_options);
+    }
+
+    fn start_streaming_load(&mut self, path: &str, csv_options: input::CsvOptions) {
         // Clear previous state before streaming load
         self.messages.clear();
         self.playback = PlaybackEngine::new(Vec::new());
         self.message_list.set_messages(Vec::new());
         self.file_loaded = false;
         self.pending_signal_loads.clear();
+        self.pending_virtual_loads.clear();
         self.charts.clear_data();
         self.charts.clear_time_range();
+        self.rebuild_timeline_density();
         self.message_stats.clear();
         self.pattern_analyzer.clear();
 
@@ -373,7 +894,7 @@ impl AppState {
                 let _ = tx_progress.send(LoadingUpdate::Progress(current, total));
             }));
 
-            match input::load_file_streaming(&path, chunk_cb, progress_cb) {
+            match input::load_file_streaming(&path, chunk_cb, progress_cb, &csv_options) {
                 Ok(()) => {
                     let _ = tx_complete.send(LoadingUpdate::Complete(path));
                 }
@@ -390,8 +911,10 @@ impl AppState {
         self.message_list.set_messages(Vec::new());
         self.file_loaded = false;
         self.pending_signal_loads.clear();
+        self.pending_virtual_loads.clear();
         self.charts.clear_data();
         self.charts.clear_time_range();
+        self.rebuild_timeline_density();
         self.message_stats.clear();
         self.pattern_analyzer.clear();
 
@@ -453,16 +976,15 @@ impl AppState {
                 }
                 LoadingUpdate::Complete(path) => {
                     self.finish_streaming_load(&path);
-                    if let Some(savestate) = self.pending_savestate.take() {
-                        self.apply_savestate(&savestate);
-                    }
                     self.loading = false;
+                    self.apply_pending_savestate();
                     done = true;
                     should_restore = false;
                 }
                 LoadingUpdate::Error(e) => {
                     self.status_message = Some(format!("Failed to load file: {}", e));
                     self.loading = false;
+                    self.apply_pending_savestate();
                     done = true;
                     should_restore = false;
                 }
@@ -505,33 +1027,156 @@ impl AppState {
 
     /// Finish streaming load (all chunks received)
     fn finish_streaming_load(&mut self, path: &str) {
-        self.add_recent_can_file(path);
+        if self.loading_remember_file {
+            self.add_recent_can_file(path);
+        }
         let msg_count = self.messages.len();
 
+        // Chunks arrive in file order; repair out-of-order logs before seeking/analysis rely on it
+        if input::sort_by_timestamp(&mut self.messages) {
+            info!("Log rows were out of order, sorted {} messages by timestamp", msg_count);
+            self.playback = PlaybackEngine::new(self.messages.clone());
+            self.message_list.set_messages(self.messages.clone());
+            if let (Some(first), Some(last)) = (self.messages.first(), self.messages.last()) {
+                self.charts.set_data_time_range(first.timestamp, last.timestamp);
+            }
+            if self.dbc_loaded {
+                self.charts.clear_data();
+                for key in self.charts.charted_signals() {
+                    self.pending_signal_loads.insert(key.to_string(), 0);
+                }
+            }
+        }
+
+        self.coverage_window.set_messages(&self.messages);
+        self.rate_window.set_messages(&self.messages);
+        self.rebuild_timeline_density();
+        self.diagnostics_window.clear();
+        self.watch_window.clear_values();
+        if self.dbc_loaded {
+            for key in self.charts.get_charted_signals() {
+                self.compute_virtual_signal(&key);
+            }
+        }
+
+        self.start_analysis();
+
+        self.status_message = Some(format!("Loaded {} messages", msg_count));
+        info!("Loaded {} messages", msg_count);
+    }
+
+    /// Run anomaly/gap scans and stats/pattern analysis of the loaded log on a background thread - prevents main thread freeze
+    fn start_analysis(&mut self) {
+        self.charts.set_error_frame_times(self.messages.iter().filter(|m| m.is_error).map(|m| m.timestamp).collect());
         let messages = self.messages.clone();
+        let dbc = self.dbc_loaded.then(|| self.dbc_file.clone());
+        let checksums = self.bit_visualizer.checksums().clone();
+        let gap_multiplier = self.message_stats.gap_multiplier();
+        let (anomaly_tx, rx) = channel();
+        self.anomaly_receiver = Some(rx);
+        self.announce_anomalies = false;
+        let (gap_tx, rx) = channel();
+        self.gap_receiver = Some(rx);
         let (tx, rx) = channel();
         self.analysis_receiver = Some(rx);
         std::thread::spawn(move || {
-            let mut stats = MessageStatistics::new();
-            stats.analyze(&messages);
-            let mut analyzer = PatternAnalyzer::new();
-            analyzer.analyze(&messages);
-            let _ = tx.send((stats, analyzer));
+            // The quick scans go first so their chart overlays show up before the stats
+            let anomalies = dbc.as_ref().map(|dbc| scan_anomalies(&messages, dbc, &checksums)).unwrap_or_default();
+            let _ = anomaly_tx.send(anomalies);
+            let _ = gap_tx.send(find_gaps(&messages, dbc.as_ref(), gap_multiplier));
+            let (stats, analyzer) = analyze_log(&messages, dbc.as_ref());
+            let ranges = dbc.as_ref().map(|dbc| scan_signal_ranges(&messages, dbc)).unwrap_or_default();
+            let _ = tx.send((stats, analyzer, ranges));
         });
+    }
 
-        self.status_message = Some(format!("Loaded {} messages", msg_count));
-        info!("Loaded {} messages", msg_count);
+    /// Re-check the loaded log for anomalous frames against the DBC and checksum hooks, in the background
+    fn rescan_anomalies(&mut self) {
+        let messages = self.messages.clone();
+        let dbc = self.dbc_loaded.then(|| self.dbc_file.clone());
+        let checksums = self.bit_visualizer.checksums().clone();
+        let (tx, rx) = channel();
+        self.anomaly_receiver = Some(rx);
+        std::thread::spawn(move || {
+            let _ = tx.send(dbc.map(|dbc| scan_anomalies(&messages, &dbc, &checksums)).unwrap_or_default());
+        });
+    }
+
+    /// Re-check the loaded log for messages that went missing, in the background
+    fn rescan_gaps(&mut self) {
+        let messages = self.messages.clone();
+        let dbc = self.dbc_loaded.then(|| self.dbc_file.clone());
+        let multiplier = self.message_stats.gap_multiplier();
+        let (tx, rx) = channel();
+        self.gap_receiver = Some(rx);
+        std::thread::spawn(move || {
+            let _ = tx.send(find_gaps(&messages, dbc.as_ref(), multiplier));
+        });
     }
 
-    /// Process background analysis results (stats + pattern analyzer)
+    /// Move the playhead to the first (or last) frame of the log
+    fn seek_to_edge(&mut self, start: bool) {
+        let target = if start { self.playback.start_time() } else { self.playback.end_time() };
+        if target.is_some() {
+            self.playback.seek_to_time(target);
+            self.seek_triggered_ui_update = true;
+        }
+    }
+
+    /// Seek to the next (or previous) anomalous frame after the playhead
+    fn jump_to_anomaly(&mut self, forward: bool) {
+        let position = self.playback.position();
+        let target = if forward {
+            next_anomaly(&self.anomalies, position)
+        } else {
+            prev_anomaly(&self.anomalies, position)
+        };
+        match target {
+            Some(anomaly) => {
+                let msg = &self.messages[anomaly.index];
+                self.status_message = Some(format!(
+                    "Frame {}: 0x{:X} on bus {}: {}",
+                    anomaly.index, msg.id, msg.bus, anomaly.describe()
+                ));
+                self.playback.pause();
+                self.playback.seek_to_position(anomaly.index);
+                self.seek_triggered_ui_update = true;
+            }
+            None => {
+                self.status_message = Some(format!(
+                    "No {} anomaly ({} in log)",
+                    if forward { "later" } else { "earlier" },
+                    self.anomalies.len()
+                ));
+            }
+        }
+    }
+
+    /// Process background analysis results (anomalies, gaps, stats, pattern analyzer, signal ranges)
     fn process_analysis_results(&mut self) {
+        if let Some(anomalies) = self.anomaly_receiver.as_ref().and_then(|r| r.try_recv().ok()) {
+            self.anomaly_receiver = None;
+            let times = anomalies.iter().filter_map(|a| self.messages.get(a.index)).map(|m| m.timestamp).collect();
+            self.charts.set_anomaly_times(times);
+            self.anomalies = anomalies;
+            if std::mem::take(&mut self.announce_anomalies) {
+                self.status_message = Some(format!("{} anomalous frames", self.anomalies.len()));
+            }
+        }
+        if let Some(gaps) = self.gap_receiver.as_ref().and_then(|r| r.try_recv().ok()) {
+            self.gap_receiver = None;
+            self.charts.set_gap_ranges(gaps.iter().map(|g| (g.start, g.end)).collect());
+            self.message_stats.set_gaps(gaps);
+        }
+
         let receiver = match self.analysis_receiver.take() {
             Some(r) => r,
             None => return,
         };
-        if let Ok((stats, analyzer)) = receiver.try_recv() {
+        if let Ok((stats, analyzer, ranges)) = receiver.try_recv() {
             self.message_stats.set_stats(stats);
             self.pattern_analyzer.set_analyzer(analyzer);
+            self.range_check_window.set_ranges(ranges);
         } else {
             self.analysis_receiver = Some(receiver);
         }
@@ -540,10 +1185,21 @@ impl AppState {
     /// Finish loading after background thread completes
     fn finish_loading(&mut self, messages: Vec<CanMessage>, path: &str) {
         self.add_recent_can_file(path);
+        self.set_loaded_messages(messages);
+    }
+
+    /// Replace the loaded log with an in-memory capture (no file involved)
+    fn set_loaded_messages(&mut self, messages: Vec<CanMessage>) {
         let msg_count = messages.len();
+        self.pending_signal_loads.clear();
+        self.pending_virtual_loads.clear();
         self.messages = messages.clone();
         self.playback = PlaybackEngine::new(messages.clone());
         self.message_list.set_messages(messages.clone());
+        self.coverage_window.set_messages(&messages);
+        self.rate_window.set_messages(&messages);
+        self.diagnostics_window.clear();
+        self.watch_window.clear_values();
         self.file_loaded = true;
         self.initial_data_populated = false;  // Reset for initial population
 
@@ -552,6 +1208,8 @@ impl AppState {
             self.charts.set_data_time_range(first.timestamp, last.timestamp);
         }
 
+        self.rebuild_timeline_density();
+
         // Clear chart data but keep selected signals
         self.charts.clear_data();
 
@@ -562,22 +1220,23 @@ impl AppState {
             }
         }
 
-        // Defer stats/analyzer to background thread - prevents main thread freeze
-        let messages_for_analysis = messages.clone();
-        let (tx, rx) = channel();
-        self.analysis_receiver = Some(rx);
-        std::thread::spawn(move || {
-            let mut stats = MessageStatistics::new();
-            stats.analyze(&messages_for_analysis);
-            let mut analyzer = PatternAnalyzer::new();
-            analyzer.analyze(&messages_for_analysis);
-            let _ = tx.send((stats, analyzer));
-        });
+        // Defer stats/analyzer to background thread
+        self.start_analysis();
 
         self.status_message = Some(format!("Loaded {} messages", msg_count));
         info!("Loaded {} messages", msg_count);
     }
 
+    /// Re-bin the timeline density over the loaded log, keeping its bin count and mode
+    fn rebuild_timeline_density(&mut self) {
+        let timestamps: Vec<DateTime<Utc>> = self.messages.iter().map(|m| m.timestamp).collect();
+        let timeline = self.timeline_window.timeline();
+        let (bins, mode) = (timeline.data().density_bins, timeline.data().density_mode());
+        timeline.build_density(&timestamps, bins, mode);
+        let errors: Vec<DateTime<Utc>> = self.messages.iter().filter(|m| m.is_error).map(|m| m.timestamp).collect();
+        timeline.data_mut().set_error_timestamps(&errors);
+    }
+
     /// Unload the currently loaded file
     fn unload_file(&mut self) {
         self.messages.clear();
@@ -589,11 +1248,24 @@ impl AppState {
         // Clear chart data and timeline
         self.charts.clear_data();
         self.charts.clear_time_range();
+        self.rebuild_timeline_density();
 
         // Clear message stats and pattern analyzer
         self.message_stats.clear();
         self.pattern_analyzer.clear();
-
+        self.coverage_window.clear();
+        self.range_check_window.clear();
+        self.rate_window.clear();
+        self.diagnostics_window.clear();
+        self.watch_window.clear_values();
+
+        self.analysis_receiver = None;
+        self.anomaly_receiver = None;
+        self.gap_receiver = None;
+        self.anomalies.clear();
+        self.charts.set_anomaly_times(Vec::new());
+        self.charts.set_gap_ranges(Vec::new());
+        self.charts.set_error_frame_times(Vec::new());
         self.status_message = Some("File unloaded".to_string());
     }
 
@@ -604,125 +1276,344 @@ impl AppState {
             return;
         }
 
+        let router = SignalRouter::new(&self.dbc_file, charted.iter().map(String::as_str));
         for msg in &self.messages {
-            let signals = self.signal_decoder.decode_message(&msg);
-            for signal in &signals {
-                let key = format!("{}@bus{}", signal.name, msg.bus);
-                if charted.contains(&key) {
-                    self.charts.add_point(&key, signal.physical_value, msg.timestamp);
+            router.route_with_misses(&self.signal_decoder, msg, |key, value| {
+                self.charts.add_decoded_point(key, value, msg.timestamp);
+            });
+        }
+        for key in &charted {
+            self.compute_virtual_signal(key);
+        }
+    }
+
+    /// Start recomputing a charted virtual signal over the whole log, a batch per
+    /// frame (see `process_pending_virtual_loads`); false if `key` isn't one
+    fn compute_virtual_signal(&mut self, key: &str) -> bool {
+        let Some(signal) = self.charts.virtual_signal(key).cloned() else {
+            return false;
+        };
+        self.charts.clear_points(key);
+        match VirtualEval::new(&signal, &self.dbc_file) {
+            Ok(eval) => {
+                self.pending_virtual_loads.insert(key.to_string(), (eval, 0));
+            }
+            Err(e) => self.status_message = Some(format!("{}: {}", signal.name, e)),
+        }
+        true
+    }
+
+    // Feed each pending virtual signal a batch of the log; chart it once it has seen every frame
+    fn process_pending_virtual_loads(&mut self) {
+        const BATCH_SIZE: usize = 10000;
+
+        let total = self.messages.len();
+        let mut finished = Vec::new();
+        for (key, (eval, next_idx)) in &mut self.pending_virtual_loads {
+            let start = (*next_idx).min(total);
+            let end = (start + BATCH_SIZE).min(total);
+            for msg in &self.messages[start..end] {
+                eval.feed(&self.signal_decoder, msg);
+            }
+            *next_idx = end;
+            if end == total {
+                finished.push(key.clone());
+            }
+        }
+
+        for key in finished {
+            let Some((eval, _)) = self.pending_virtual_loads.remove(&key) else {
+                continue;
+            };
+            match eval.finish() {
+                Ok(points) => {
+                    for (value, timestamp) in points {
+                        self.charts.add_point(&key, value, timestamp);
+                    }
                 }
+                Err(e) => self.status_message = Some(format!("{}: {}", split_signal_key(&key).0, e)),
             }
         }
     }
 
-    /// Populate chart data for a specific signal (bus-aware key: "name@busN")
+    /// Keep a live evaluator for each charted virtual signal
+    fn sync_live_virtuals(&mut self) {
+        let charts = &self.charts;
+        self.live_virtuals.retain(|key, _| charts.has_signal(key) && charts.virtual_signal(key).is_some());
+        for signal in self.charts.virtual_signals() {
+            let key = signal.key();
+            if self.charts.has_signal(&key) && !self.live_virtuals.contains_key(&key) {
+                if let Ok(eval) = VirtualEval::new(signal, &self.dbc_file) {
+                    self.live_virtuals.insert(key, eval);
+                }
+            }
+        }
+    }
+
+    /// Populate chart data for a specific signal (bus-aware key: "name@0xID@busN")
     fn populate_chart_data_for_signal(&mut self, signal_key: &str) {
-        // Parse the bus-aware signal key
-        let (signal_name, bus) = if let Some(pos) = signal_key.find("@bus") {
-            (&signal_key[..pos], signal_key[pos + 4..].parse::<u8>().unwrap_or(0))
-        } else {
-            (signal_key, 0)
-        };
+        let (signal_name, bus) = split_signal_key(signal_key);
 
-        use std::io::Write;
-        let mut f = std::fs::OpenOptions::new()
-            .create(true)
-            .append(true)
-            .open("/tmp/can-viz-chart-debug.txt")
-            .ok();
-        if let Some(ref mut f) = f {
-            let _ = writeln!(f, "populate_chart_data_for_signal: key={}, name={}, bus={}", signal_key, signal_name, bus);
-            let _ = writeln!(f, "  file_loaded: {}, dbc_loaded: {}", self.file_loaded, self.dbc_loaded);
-        }
+        debug!(signal_key, signal_name, bus, file_loaded = self.file_loaded, dbc_loaded = self.dbc_loaded,
+            "populate_chart_data_for_signal");
 
         if !self.file_loaded || !self.dbc_loaded {
-            if let Some(ref mut f) = f { let _ = writeln!(f, "  returning early - files not loaded"); }
+            debug!("Skipping chart population for {}: log or DBC not loaded", signal_key);
+            return;
+        }
+        if self.compute_virtual_signal(signal_key) {
             return;
         }
 
         // Start incremental loading - begin at message index 0
         self.pending_signal_loads.insert(signal_key.to_string(), 0);
-        if let Some(ref mut f) = f { let _ = writeln!(f, "  started incremental loading for {}", signal_key); }
+        debug!("Started incremental chart loading for {}", signal_key);
     }
 
     // Process a batch of pending signal data loading (call this each frame)
     fn process_pending_signal_loads(&mut self) {
-        const BATCH_SIZE: usize = 10000; // Process up to 10k messages per frame per signal
+        const BATCH_SIZE: usize = 10000; // Process up to 10k messages per frame
 
-        let mut completed = Vec::new();
+        // One pass from the furthest-behind load: each frame is decoded once and fanned out
+        // to every pending signal that hasn't seen it yet
+        let Some(&start_idx) = self.pending_signal_loads.values().min() else {
+            return;
+        };
+        let end_idx = (start_idx + BATCH_SIZE).min(self.messages.len());
+        let router = SignalRouter::new(&self.dbc_file, self.pending_signal_loads.keys().map(String::as_str));
+
+        for (msg_idx, msg) in self.messages[start_idx..end_idx].iter().enumerate() {
+            let msg_idx = start_idx + msg_idx;
+            router.route_with_misses(&self.signal_decoder, msg, |key, value| {
+                if self.pending_signal_loads.get(key).is_some_and(|&from| from <= msg_idx) {
+                    self.charts.add_decoded_point(key, value, msg.timestamp);
+                }
+            });
+        }
 
-        for (signal_key, start_idx) in self.pending_signal_loads.iter_mut() {
-            // Parse the bus-aware signal key
-            let (signal_name, bus) = if let Some(pos) = signal_key.find("@bus") {
-                (&signal_key[..pos], signal_key[pos + 4..].parse::<u8>().unwrap_or(0))
-            } else {
-                (signal_key.as_str(), 0)
+        let total = self.messages.len();
+        self.pending_signal_loads.retain(|_, next_idx| {
+            *next_idx = (*next_idx).max(end_idx);
+            *next_idx < total
+        });
+    }
+
+    /// Parse a DBC on a background thread; `process_dbc_loading` applies it when done.
+    /// A new load replaces one still in flight.
+    fn load_dbc(&mut self, path: &str) {
+        self.dbc_reloading = false;
+        self.dbc_loading_progress = 0.0;
+        self.status_message = Some(format!("Loading DBC {}...", path));
+
+        let path = path.to_string();
+        let (tx, rx) = channel();
+        self.dbc_loading_receiver = Some(rx);
+
+        std::thread::spawn(move || {
+            let tx_progress = tx.clone();
+            let progress = move |lines, total| {
+                let _ = tx_progress.send(DbcLoadingUpdate::Progress(lines, total));
             };
+            match DbcFile::load_with_progress(&path, progress) {
+                Ok(dbc) => {
+                    let _ = tx.send(DbcLoadingUpdate::Complete(path, Box::new(dbc)));
+                }
+                Err(e) => {
+                    let _ = tx.send(DbcLoadingUpdate::Error(e.to_string()));
+                }
+            }
+        });
+    }
 
-            let end_idx = (*start_idx + BATCH_SIZE).min(self.messages.len());
+    /// Process DBC loading updates from the background thread
+    fn process_dbc_loading(&mut self) {
+        let receiver = match self.dbc_loading_receiver.take() {
+            Some(r) => r,
+            None => return,
+        };
 
-            for msg_idx in *start_idx..end_idx {
-                if let Some(msg) = self.messages.get(msg_idx) {
-                    // Only add data from messages on the correct bus
-                    if msg.bus == bus {
-                        let signals = self.signal_decoder.decode_message(&msg);
-                        for signal in &signals {
-                            if signal.name == signal_name {
-                                self.charts.add_point(signal_key, signal.physical_value, msg.timestamp);
-                            }
-                        }
-                    }
+        let mut done = false;
+        while let Ok(update) = receiver.try_recv() {
+            match update {
+                DbcLoadingUpdate::Progress(lines, total) => {
+                    self.dbc_loading_progress = if total > 0 {
+                        (lines as f32 / total as f32) * 100.0
+                    } else {
+                        0.0
+                    };
+                    self.status_message = Some(format!(
+                        "Loading DBC... {:.0}% ({}/{} lines)",
+                        self.dbc_loading_progress, lines, total
+                    ));
+                }
+                DbcLoadingUpdate::Complete(path, dbc) => {
+                    self.apply_dbc(&path, *dbc);
+                    done = true;
+                }
+                DbcLoadingUpdate::Error(e) => {
+                    self.status_message = Some(format!("Failed to load DBC: {}", e));
+                    error!("Failed to load DBC: {}", e);
+                    self.dbc_reloading = false;
+                    done = true;
                 }
             }
+            if done {
+                break;
+            }
+        }
 
-            *start_idx = end_idx;
+        if done {
+            self.apply_pending_savestate();
+        } else {
+            self.dbc_loading_receiver = Some(receiver);
+        }
+    }
 
-            if end_idx >= self.messages.len() {
-                completed.push(signal_key.clone());
+    /// Install a parsed DBC: decoders, message names, chart signal list and analysis
+    fn apply_dbc(&mut self, path: &str, dbc: DbcFile) {
+        self.add_recent_dbc_file(path);
+        self.dbc_path = Some(path.to_string());
+        self.dbc_dirty = false;
+        self.signal_decoder.set_dbc(dbc.clone());
+        self.watch_window.dbc_changed();
+        self.live_virtuals.clear();
+        self.dbc_file = dbc.clone();
+        self.message_list.set_dbc(dbc.clone());
+        self.message_sender.set_dbc(&dbc);
+        self.dbc_loaded = true;
+
+        // Populate available signals for charts
+        let mut signals = Vec::new();
+        for msg in &dbc.messages {
+            for sig in &msg.signals {
+                signals.push(SignalInfo {
+                    name: sig.name.clone(),
+                    msg_id: msg.id,
+                    bus: 0,  // TODO: support per-bus DBC definitions in the future
+                    msg_name: msg.name.clone(),
+                    unit: sig.unit.clone().unwrap_or_default(),
+                    range: Some(sig.value_range()),
+                });
             }
         }
+        if std::mem::take(&mut self.dbc_reloading) {
+            // Same file, new contents: recompute the charted signals it still defines
+            let charted = self.charts.get_charted_signals();
+            self.charts.clear();
+            self.pending_signal_loads.clear();
+            self.pending_virtual_loads.clear();
+            self.charts.set_available_signals(signals);
+            self.charts.restore_signals(&charted);
+        } else {
+            self.charts.set_available_signals(signals);
+        }
 
-        // Remove completed loads
-        for key in completed {
-            self.pending_signal_loads.remove(&key);
+        // Pre-populate chart with all data if log file is already loaded
+        if self.file_loaded {
+            self.populate_chart_data();
+            // Re-run stats so multiplexer breakdowns reflect the new DBC
+            self.start_analysis();
         }
+
+        self.status_message = Some(format!("Loaded DBC: {} messages defined", self.dbc_file.messages.len()));
+        info!("Loaded DBC with {} messages", self.dbc_file.messages.len());
     }
 
-    fn load_dbc(&mut self, path: &str) {
-        match DbcFile::load(path) {
-            Ok(dbc) => {
-                self.add_recent_dbc_file(path);
-                self.signal_decoder.set_dbc(dbc.clone());
-                self.dbc_file = dbc.clone();
-                self.message_list.set_dbc(dbc.clone());
-                self.dbc_loaded = true;
-
-                // Populate available signals for charts
-                let mut signals = Vec::new();
-                for msg in &dbc.messages {
-                    for sig in &msg.signals {
-                        signals.push(SignalInfo {
-                            name: sig.name.clone(),
-                            msg_id: msg.id,
-                            bus: 0,  // TODO: support per-bus DBC definitions in the future
-                            msg_name: msg.name.clone(),
-                            unit: sig.unit.clone().unwrap_or_default(),
-                        });
-                    }
-                }
-                self.charts.set_available_signals(signals);
+    /// With auto-reload on, re-parse the DBC once its file changes on disk, unless
+    /// that would throw away unsaved edits
+    fn check_dbc_file_changed(&mut self) {
+        let path = match &self.dbc_path {
+            Some(path) if self.auto_reload_dbc => path.clone(),
+            _ => {
+                self.dbc_watch = None;
+                return;
+            }
+        };
+        let watch = match &mut self.dbc_watch {
+            Some(watch) if watch.path() == std::path::Path::new(&path) => watch,
+            _ => self.dbc_watch.insert(FileWatch::new(&path)),
+        };
+        if !watch.poll() {
+            return;
+        }
+        if self.dbc_dirty {
+            self.status_message = Some("DBC changed on disk; not reloaded over unsaved edits".to_string());
+            return;
+        }
+        info!("DBC {} changed on disk, reloading", path);
+        self.load_dbc(&path);
+        self.dbc_reloading = true;
+    }
 
-                // Pre-populate chart with all data if log file is already loaded
-                if self.file_loaded {
-                    self.populate_chart_data();
-                }
+    /// Drop the current DBC for an empty one, clearing everything decoded from it
+    fn reset_dbc(&mut self, reset: DbcReset) {
+        // A parse still in flight would otherwise replace the reset DBC when it lands
+        self.dbc_loading_receiver = None;
+        self.dbc_reloading = false;
+        self.dbc_path = None;
+        self.dbc_dirty = false;
+        self.dbc_file = DbcFile::new();
+        self.signal_decoder.set_dbc(DbcFile::new());
+        self.watch_window.dbc_changed();
+        self.live_virtuals.clear();
+        self.message_list.set_dbc(DbcFile::new());
+        self.message_sender.set_dbc(&self.dbc_file);
+        self.bit_visualizer.clear_session_signals();
+        self.dbc_loaded = reset == DbcReset::New;
+
+        self.charts.clear();
+        self.charts.set_available_signals(Vec::new());
+        self.pending_signal_loads.clear();
+        self.pending_virtual_loads.clear();
+        self.range_check_window.clear();
+        if self.file_loaded {
+            self.start_analysis();
+        }
+
+        self.status_message = Some(match reset {
+            DbcReset::New => "Started a new DBC".to_string(),
+            DbcReset::Close => "Closed DBC".to_string(),
+        });
+        info!("DBC reset ({})", if self.dbc_loaded { "new" } else { "closed" });
+    }
+
+    /// Run an action that drops the DBC now, or after the save prompt if it has unsaved edits
+    fn request_dbc_discard(&mut self, action: DbcDiscard) {
+        if self.dbc_dirty {
+            self.dbc_discard_pending = Some(action);
+        } else {
+            self.run_dbc_discard(action);
+        }
+    }
+
+    fn run_dbc_discard(&mut self, action: DbcDiscard) {
+        match action {
+            DbcDiscard::Reset(reset) => self.reset_dbc(reset),
+            DbcDiscard::Load(Some(path)) => self.load_dbc(&path),
+            DbcDiscard::Load(None) => self.show_dbc_open_pending = true,
+            DbcDiscard::Exit => self.exit_requested = true,
+        }
+    }
 
-                self.status_message = Some(format!("Loaded DBC: {} messages defined", self.dbc_file.messages.len()));
-                info!("Loaded DBC with {} messages", self.dbc_file.messages.len());
+    /// Ask where to save the DBC and write it there; false if cancelled or the write failed
+    fn save_dbc_as(&mut self) -> bool {
+        let Some(path) = FileDialogs::save_dbc_file() else {
+            return false;
+        };
+        let path_str = path.to_string_lossy().to_string();
+        match self.dbc_file.save(&path) {
+            Ok(()) => {
+                self.add_recent_dbc_file(&path_str);
+                self.dbc_path = Some(path_str.clone());
+                self.dbc_dirty = false;
+                // Our own write isn't an outside change to reload
+                self.dbc_watch = None;
+                self.status_message = Some(format!("Saved DBC to {}", path_str));
+                true
             }
             Err(e) => {
-                self.status_message = Some(format!("Failed to load DBC: {}", e));
-                error!("Failed to load DBC: {}", e);
+                self.status_message = Some(format!("Failed to save DBC: {}", e));
+                false
             }
         }
     }
@@ -775,7 +1666,7 @@ impl AppState {
                 None
             };
             let dbc_path = if self.dbc_loaded {
-                self.recent_dbc_files.first().cloned()
+                self.dbc_path.clone()
             } else {
                 None
             };
@@ -798,6 +1689,7 @@ impl AppState {
                 can_file_path: can_path,
                 dbc_file_path: dbc_path,
                 chart_signals: self.charts.get_charted_signals(),
+                watch_signals: self.watch_window.watched_keys(),
                 bit_visualizer_quadrants: self.bit_visualizer.get_quadrant_selections(),
                 playback_position: playback_pos,
                 show_messages: self.show_messages,
@@ -809,6 +1701,12 @@ impl AppState {
                 show_message_stats: self.show_message_stats,
                 show_pattern_analyzer: self.show_pattern_analyzer,
                 show_log: self.show_log,
+                show_coverage: self.show_coverage,
+                show_range_check: self.show_range_check,
+                show_rate_graph: self.show_rate_graph,
+                show_diagnostics: self.show_diagnostics,
+                show_watch: self.show_watch,
+                show_timeline: self.show_timeline,
                 layout_ini,
             };
 
@@ -836,7 +1734,6 @@ impl AppState {
         if self.recent_savestates.len() > MAX_RECENT_FILES {
             self.recent_savestates.truncate(MAX_RECENT_FILES);
         }
-        self.save_settings();
     }
 
     fn load_savestate(&mut self, path: &str) {
@@ -865,17 +1762,24 @@ impl AppState {
         }
 
         // Load CAN file if present (async)
-        if let Some(ref can_path) = savestate.can_file_path {
-            if std::path::Path::new(can_path).exists() {
-                let path = can_path.clone();
-                self.pending_savestate = Some(savestate);
-                self.load_file(&path);
-                return;
-            }
+        let can_path = savestate.can_file_path.clone().filter(|p| std::path::Path::new(p).exists());
+        self.pending_savestate = Some(savestate);
+        if let Some(path) = can_path {
+            self.load_file(&path);
         }
 
-        // No CAN file or path missing - apply savestate immediately
-        self.apply_savestate(&savestate);
+        // Applied once the DBC and log loads have finished (right away if neither is running)
+        self.apply_pending_savestate();
+    }
+
+    /// Apply the savestate waiting on background loads, once none are running
+    fn apply_pending_savestate(&mut self) {
+        if self.loading || self.dbc_loading_receiver.is_some() {
+            return;
+        }
+        if let Some(savestate) = self.pending_savestate.take() {
+            self.apply_savestate(&savestate);
+        }
     }
 
     fn apply_savestate(&mut self, savestate: &Savestate) {
@@ -889,6 +1793,14 @@ impl AppState {
         self.show_message_stats = savestate.show_message_stats;
         self.show_pattern_analyzer = savestate.show_pattern_analyzer;
         self.show_log = savestate.show_log;
+        self.show_coverage = savestate.show_coverage;
+        self.show_range_check = savestate.show_range_check;
+        self.show_rate_graph = savestate.show_rate_graph;
+        self.show_diagnostics = savestate.show_diagnostics;
+        self.show_watch = savestate.show_watch;
+        self.show_timeline = savestate.show_timeline;
+
+        self.watch_window.restore(&savestate.watch_signals, &self.dbc_file);
 
         // Chart signals (requires DBC to be loaded)
         if self.dbc_loaded {
@@ -968,26 +1880,41 @@ fn main() {
 
     info!("S.H.I.T v{} starting", env!("CARGO_PKG_VERSION"));
 
+    let args = <cli::Cli as clap::Parser>::parse();
+    let result = if args.is_batch() {
+        cli::run_batch(&args).map(|summaries| summaries.iter().for_each(|s| println!("{}", s)))
+    } else {
+        run_gui(args.open)
+    };
+    if let Err(e) = result {
+        error!("{:#}", e);
+        std::process::exit(1);
+    }
+}
+
+/// Open the window and run the event loop until it closes, loading `open` first if given
+fn run_gui(open: Option<std::path::PathBuf>) -> anyhow::Result<()> {
     // Create tokio runtime for async operations
-    let rt = tokio::runtime::Runtime::new().expect("Failed to create Tokio runtime");
+    let rt = tokio::runtime::Runtime::new().context("Failed to create Tokio runtime")?;
 
     // Create event loop
-    let event_loop = EventLoop::new().expect("Failed to create EventLoop");
+    let event_loop = EventLoop::new().context(NO_DISPLAY_HINT)?;
 
     // Build the window and GL display using glutin-winit
     let (window, gl_config) = DisplayBuilder::new()
         .with_window_builder(Some(
             WindowBuilder::new()
-                .with_title("S.H.I.T - Signal Harvesting & Interpretation Toolkit")
+                .with_title(WINDOW_TITLE)
                 .with_inner_size(winit::dpi::LogicalSize::new(1400.0, 900.0))
         ))
         .build(&event_loop, glutin::config::ConfigTemplateBuilder::new()
             .prefer_hardware_accelerated(Some(true)), |mut iter| {
             iter.next().unwrap()
         })
-        .expect("Failed to create window and display");
+        .map_err(|e| anyhow::anyhow!("Failed to create window and GL display: {}", e))
+        .context(NO_DISPLAY_HINT)?;
 
-    let window = window.expect("Failed to create window");
+    let window = window.context("No window was created for the GL config")?;
     let gl_display = gl_config.display();
 
     // Create the context using the proper API
@@ -997,7 +1924,7 @@ fn main() {
             &glutin::context::ContextAttributesBuilder::new()
                 .build(Some(window.raw_window_handle())),
         )
-    }.expect("Failed to create GL context");
+    }.context("Failed to create an OpenGL context; the GPU driver may lack OpenGL 3 support (try LIBGL_ALWAYS_SOFTWARE=1)")?;
 
     // Create surface and make context current
     let attrs = window.build_surface_attributes(
@@ -1006,9 +1933,9 @@ fn main() {
 
     let surface = unsafe {
         gl_display.create_window_surface(&gl_config, &attrs)
-    }.expect("Failed to create surface");
+    }.context("Failed to create the window's GL surface")?;
 
-    let context = context.make_current(&surface).expect("Failed to make context current");
+    let context = context.make_current(&surface).context("Failed to make the GL context current")?;
 
     // Create glow context for renderer
     let gl = unsafe {
@@ -1019,6 +1946,9 @@ fn main() {
 
     // Set up imgui
     let mut imgui = Context::create();
+    if let Some(clipboard) = ui::clipboard::SystemClipboard::new() {
+        imgui.set_clipboard_backend(clipboard);
+    }
 
     // Disable ImGui debug log window
     imgui.set_log_filename(None::<std::path::PathBuf>);
@@ -1086,7 +2016,7 @@ fn main() {
     platform.attach_window(imgui.io_mut(), &window, HiDpiMode::Default);
 
     let mut renderer = imgui_glow_renderer::AutoRenderer::initialize(gl, &mut imgui)
-        .expect("Failed to initialize renderer");
+        .map_err(|e| anyhow::anyhow!("Failed to initialize the OpenGL renderer: {}", e))?;
 
     // Create a second glow context for clearing (both reference the same GL context)
     let gl_clear = unsafe {
@@ -1097,8 +2027,15 @@ fn main() {
 
     // Create app state
     let mut state = AppState::new();
+    if let Some(path) = open {
+        state.open_path(&path);
+    }
     let mut last_frame_time = Instant::now();
-    let mut last_settings_save = Instant::now();
+    // Playback advances by real elapsed time, whatever the frame rate
+    let mut last_playback_update = Instant::now();
+    let mut last_settings_poll = Instant::now();
+    // Whether the title currently shows the DBC as unsaved
+    let mut title_dirty = false;
 
     // Main loop
     event_loop.run(move |event, window_target| {
@@ -1112,28 +2049,41 @@ fn main() {
                 // Process file dialogs
                 state.process_file_dialogs();
                 state.process_savestate_save(&mut imgui);
+                state.process_workspace_save(&mut imgui);
 
                 // Apply pending layout from savestate load
                 if let Some(layout) = state.pending_layout_apply.take() {
                     imgui.load_ini_settings(&layout);
                 }
 
+                // Apply theme change from the View menu
+                if let Some(theme) = state.pending_theme_apply.take() {
+                    theme.apply(imgui.style_mut());
+                }
+
                 // Process async loading
                 state.process_loading();
+                state.process_dbc_loading();
+                state.check_dbc_file_changed();
 
                 // Process background analysis results
                 state.process_analysis_results();
 
-                // Update playback
-                state.playback.update(std::time::Duration::from_millis(16));
+                // Update playback, with the idle-gap cap applied (a no-op unless it changed or the engine was replaced)
+                let max_gap = state.cap_playback_gaps
+                    .then(|| Duration::microseconds((state.playback_gap_cap_secs * 1_000_000.0) as i64));
+                state.playback.set_max_gap(max_gap);
+                let now = Instant::now();
+                state.playback.update(now - last_playback_update);
+                last_playback_update = now;
 
                 // Update graphs with decoded signals
                 state.update_graphs();
 
-                // Save settings periodically (every 30 seconds)
-                if last_settings_save.elapsed().as_secs() >= 30 {
-                    state.save_settings();
-                    last_settings_save = Instant::now();
+                // Persist settings when they've changed (debounced)
+                if last_settings_poll.elapsed() >= SETTINGS_POLL_INTERVAL {
+                    state.poll_settings_save();
+                    last_settings_poll = Instant::now();
                 }
 
                 platform.prepare_frame(imgui.io_mut(), &window)
@@ -1171,6 +2121,21 @@ fn main() {
                     }
                 }
 
+                // Anomaly navigation (F8 / Shift+F8)
+                if !ui.io().want_text_input && ui.is_key_pressed(imgui::Key::F8) {
+                    state.jump_to_anomaly(!ui.io().key_shift);
+                }
+
+                // First/last frame (Home / End), unless the message list is using them
+                let list_has_keys = state.show_messages && state.message_list.has_key_focus();
+                if !ui.io().want_text_input && !list_has_keys {
+                    if ui.is_key_pressed(imgui::Key::Home) {
+                        state.seek_to_edge(true);
+                    } else if ui.is_key_pressed(imgui::Key::End) {
+                        state.seek_to_edge(false);
+                    }
+                }
+
                 // Menu bar
                 ui.main_menu_bar(|| {
                     ui.menu("File", || {
@@ -1180,26 +2145,79 @@ fn main() {
                         if ui.menu_item("Open Cabana Session...") {
                             state.show_cabana_folder_pending = true;
                         }
+                        ui.menu("CSV Timestamps", || {
+                            for base in input::CsvTimeBase::ALL {
+                                if ui.menu_item_config(base.label()).selected(state.csv_time_base == base).build() {
+                                    state.csv_time_base = base;
+                                }
+                            }
+                            ui.separator();
+                            ui.text_disabled("Relative times start at");
+                            let mut at_load = state.csv_epoch.is_none();
+                            if ui.checkbox("Time of loading", &mut at_load) {
+                                state.csv_epoch = if at_load { None } else { input::parse_epoch(&state.csv_epoch_text).or(Some(Utc::now())) };
+                                if let Some(epoch) = state.csv_epoch {
+                                    state.csv_epoch_text = epoch.format(input::EPOCH_INPUT_FORMAT).to_string();
+                                }
+                            }
+                            if !at_load {
+                                ui.set_next_item_width(200.0);
+                                if ui.input_text("UTC##csv_epoch", &mut state.csv_epoch_text).hint("2024-01-31 12:00:00").build() {
+                                    if let Some(epoch) = input::parse_epoch(&state.csv_epoch_text) {
+                                        state.csv_epoch = Some(epoch);
+                                    }
+                                }
+                                if input::parse_epoch(&state.csv_epoch_text).is_none() {
+                                    ui.text_colored([1.0, 0.3, 0.3, 1.0], "Not a time (YYYY-MM-DD HH:MM:SS)");
+                                }
+                            }
+                        });
+                        if ui.menu_item("New DBC") {
+                            state.request_dbc_discard(DbcDiscard::Reset(DbcReset::New));
+                        }
                         if ui.menu_item("Load DBC...") {
-                            state.show_dbc_open_pending = true;
+                            state.request_dbc_discard(DbcDiscard::Load(None));
+                        }
+                        if ui.menu_item_config("Close DBC").enabled(state.dbc_loaded).build() {
+                            state.request_dbc_discard(DbcDiscard::Reset(DbcReset::Close));
                         }
                         if ui.menu_item("Save DBC...") {
+                            state.save_dbc_as();
+                        }
+                        if ui.menu_item_config("Reload DBC on change").selected(state.auto_reload_dbc).build() {
+                            state.auto_reload_dbc = !state.auto_reload_dbc;
+                        }
+                        if ui.is_item_hovered() {
+                            ui.tooltip_text("Re-parse the DBC when it is saved in another editor (not over unsaved edits here)");
+                        }
+                        if ui.menu_item_config("Export new signals to DBC...")
+                            .enabled(state.bit_visualizer.has_session_signals())
+                            .build()
+                        {
                             if let Some(path) = FileDialogs::save_dbc_file() {
-                                if let Some(path_str) = path.to_str() {
-                                    match state.dbc_file.save(&path) {
-                                        Ok(()) => {
-                                            state.add_recent_dbc_file(path_str);
-                                            state.status_message = Some(format!("Saved DBC to {}", path_str));
-                                        }
-                                        Err(e) => {
-                                            state.status_message = Some(format!("Failed to save DBC: {}", e));
-                                        }
-                                    }
-                                }
+                                let session_dbc = state.bit_visualizer.session_dbc(&state.dbc_file);
+                                state.status_message = Some(match session_dbc.save(&path) {
+                                    Ok(()) => format!(
+                                        "Exported {} new signals to {}",
+                                        session_dbc.messages.iter().map(|m| m.signals.len()).sum::<usize>(),
+                                        path.display()
+                                    ),
+                                    Err(e) => format!("Failed to export signals: {}", e),
+                                });
                             }
                         }
                         if ui.menu_item("Export to CSV...") {
-                            state.export_dialog.show();
+                            let log_secs = match (state.messages.first(), state.messages.last()) {
+                                (Some(first), Some(last)) => (last.timestamp - first.timestamp).num_microseconds().unwrap_or(0) as f64 / 1_000_000.0,
+                                _ => 0.0,
+                            };
+                            let data = state.timeline_window.timeline().data();
+                            let loop_region = data.loop_start.zip(data.loop_end)
+                                .map(|(start, end)| (start as f64 * log_secs, end as f64 * log_secs));
+                            state.export_dialog.show(log_secs, loop_region);
+                        }
+                        if ui.menu_item_config("Generate test data from DBC...").enabled(state.dbc_loaded).build() {
+                            state.generate_dialog.show();
                         }
                         ui.separator();
                         if let Some(_menu) = ui.begin_menu("Recently opened") {
@@ -1238,7 +2256,7 @@ fn main() {
                                     let label = format!("{}##dbc_{}", display, path);
                                     if std::path::Path::new(&path).exists() {
                                         if ui.menu_item(&label) {
-                                            state.load_dbc(&path);
+                                            state.request_dbc_discard(DbcDiscard::Load(Some(path.clone())));
                                         }
                                     } else {
                                         ui.text_disabled(&format!("{} (missing)", display));
@@ -1283,7 +2301,7 @@ fn main() {
                             ui.separator();
                         }
                         if ui.menu_item("Exit") {
-                            window_target.exit();
+                            state.request_dbc_discard(DbcDiscard::Exit);
                         }
                     });
 
@@ -1298,7 +2316,79 @@ fn main() {
                             state.playback.stop();
                         }
                         ui.separator();
-                        ui.text(format!("Speed: {:.1}x", state.playback.speed()));
+                        if ui.menu_item_config("First Frame").shortcut("Home").build() {
+                            state.seek_to_edge(true);
+                        }
+                        if ui.menu_item_config("Last Frame").shortcut("End").build() {
+                            state.seek_to_edge(false);
+                        }
+                        ui.separator();
+                        let has_anomalies = !state.anomalies.is_empty();
+                        if ui.menu_item_config(format!("Next Anomaly ({})", state.anomalies.len()))
+                            .shortcut("F8").enabled(has_anomalies).build()
+                        {
+                            state.jump_to_anomaly(true);
+                        }
+                        if ui.menu_item_config("Previous Anomaly").shortcut("Shift+F8").enabled(has_anomalies).build() {
+                            state.jump_to_anomaly(false);
+                        }
+                        ui.separator();
+                        let mut speed = state.playback.speed();
+                        ui.set_next_item_width(120.0);
+                        if ui.input_scalar("Speed (x)", &mut speed)
+                            .step(0.1)
+                            .step_fast(1.0)
+                            .display_format("%.1f")
+                            .build()
+                        {
+                            state.playback.set_speed(speed);
+                        }
+                        if ui.is_item_hovered() {
+                            ui.tooltip_text(format!("{}x to {}x", MIN_SPEED, MAX_SPEED));
+                        }
+                        for preset in [0.5, 1.0, 2.0, 5.0, 10.0] {
+                            if ui.small_button(format!("{}x", preset)) {
+                                state.playback.set_speed(preset);
+                            }
+                            ui.same_line();
+                        }
+                        ui.new_line();
+                        ui.checkbox("Cap idle gaps at", &mut state.cap_playback_gaps);
+                        ui.same_line();
+                        ui.set_next_item_width(90.0);
+                        let _gap_cap_off = ui.begin_disabled(!state.cap_playback_gaps);
+                        if ui.input_scalar("s##gap_cap", &mut state.playback_gap_cap_secs)
+                            .step(0.5)
+                            .display_format("%.1f")
+                            .build()
+                        {
+                            state.playback_gap_cap_secs = state.playback_gap_cap_secs.max(0.1);
+                        }
+                        drop(_gap_cap_off);
+                        if ui.is_item_hovered() {
+                            ui.tooltip_text("Longer silences play back at this length; timestamps stay as recorded");
+                        }
+
+                        ui.separator();
+                        ui.set_next_item_width(160.0);
+                        let go = ui.input_text("##goto_time", &mut state.goto_time_input)
+                            .hint("mm:ss or RFC 3339")
+                            .enter_returns_true(true)
+                            .build();
+                        ui.same_line();
+                        if go | ui.button("Go to time") {
+                            let target = state.playback.start_time()
+                                .and_then(|start| parse_seek_target(&state.goto_time_input, start));
+                            match target {
+                                Some(target) => {
+                                    state.playback.seek_to_time(Some(target));
+                                    state.seek_triggered_ui_update = true;
+                                }
+                                None => {
+                                    state.status_message = Some(format!("Invalid time: '{}'", state.goto_time_input));
+                                }
+                            }
+                        }
                     });
 
                     ui.menu("View", || {
@@ -1350,6 +2440,42 @@ fn main() {
                         }
                         drop(_tok);
 
+                        let _tok = if state.show_coverage { Some(ui.push_style_color(imgui::StyleColor::Text, [0.0, 1.0, 0.0, 1.0])) } else { None };
+                        if ui.menu_item("DBC Coverage") {
+                            state.show_coverage = !state.show_coverage;
+                        }
+                        drop(_tok);
+
+                        let _tok = if state.show_range_check { Some(ui.push_style_color(imgui::StyleColor::Text, [0.0, 1.0, 0.0, 1.0])) } else { None };
+                        if ui.menu_item("Signal Range Check") {
+                            state.show_range_check = !state.show_range_check;
+                        }
+                        drop(_tok);
+
+                        let _tok = if state.show_rate_graph { Some(ui.push_style_color(imgui::StyleColor::Text, [0.0, 1.0, 0.0, 1.0])) } else { None };
+                        if ui.menu_item("Message Rate") {
+                            state.show_rate_graph = !state.show_rate_graph;
+                        }
+                        drop(_tok);
+
+                        let _tok = if state.show_diagnostics { Some(ui.push_style_color(imgui::StyleColor::Text, [0.0, 1.0, 0.0, 1.0])) } else { None };
+                        if ui.menu_item("Diagnostics") {
+                            state.show_diagnostics = !state.show_diagnostics;
+                        }
+                        drop(_tok);
+
+                        let _tok = if state.show_watch { Some(ui.push_style_color(imgui::StyleColor::Text, [0.0, 1.0, 0.0, 1.0])) } else { None };
+                        if ui.menu_item("Watch") {
+                            state.show_watch = !state.show_watch;
+                        }
+                        drop(_tok);
+
+                        let _tok = if state.show_timeline { Some(ui.push_style_color(imgui::StyleColor::Text, [0.0, 1.0, 0.0, 1.0])) } else { None };
+                        if ui.menu_item("Timeline") {
+                            state.show_timeline = !state.show_timeline;
+                        }
+                        drop(_tok);
+
                         ui.separator();
 
                         // Bit Visualizer
@@ -1367,6 +2493,26 @@ fn main() {
                             state.show_log = !state.show_log;
                         }
                         drop(_tok);
+
+                        ui.separator();
+                        ui.menu("Workspace", || {
+                            let action = state.workspaces.render_menu(ui);
+                            state.handle_workspace_action(action);
+                        });
+                        ui.menu("Theme", || {
+                            for theme in Theme::ALL {
+                                if ui.menu_item_config(theme.name()).selected(state.theme == theme).build() {
+                                    state.set_theme(theme);
+                                }
+                            }
+                        });
+                        ui.menu("Color Palette", || {
+                            for palette in ui::colors::ColorPalette::ALL {
+                                if ui.menu_item_config(palette.name()).selected(state.color_palette == palette).build() {
+                                    state.set_color_palette(palette);
+                                }
+                            }
+                        });
                     });
 
                     ui.menu("Plugins", || {
@@ -1403,6 +2549,38 @@ fn main() {
                     ui.text_colored([0.5, 0.5, 0.5, 1.0], version_text);
                 });
 
+                // New/Close/Load DBC or exit with DBC edits not yet saved
+                if let Some(action) = state.dbc_discard_pending.clone() {
+                    ui.open_popup("Save DBC changes?");
+                    ui.modal_popup_config("Save DBC changes?").always_auto_resize(true).build(|| {
+                        ui.text("The DBC has signal edits that have not been saved.");
+                        if ui.button("Save...") && state.save_dbc_as() {
+                            state.dbc_discard_pending = None;
+                            state.run_dbc_discard(action.clone());
+                            ui.close_current_popup();
+                        }
+                        ui.same_line();
+                        if ui.button("Discard") {
+                            state.dbc_discard_pending = None;
+                            state.run_dbc_discard(action.clone());
+                            ui.close_current_popup();
+                        }
+                        ui.same_line();
+                        if ui.button("Cancel") {
+                            state.dbc_discard_pending = None;
+                            ui.close_current_popup();
+                        }
+                    });
+                }
+                if std::mem::take(&mut state.exit_requested) {
+                    state.save_settings();
+                    window_target.exit();
+                }
+                if state.dbc_dirty != title_dirty {
+                    title_dirty = state.dbc_dirty;
+                    window.set_title(&if title_dirty { format!("{} *", WINDOW_TITLE) } else { WINDOW_TITLE.to_string() });
+                }
+
                 // Status bar
                 let window_size = window.inner_size();
                 ui.set_cursor_pos([0.0, window_size.height as f32 / hidpi_factor as f32 - 25.0]);
@@ -1420,7 +2598,11 @@ fn main() {
                             ui.text(format!(
                                 "Messages: {} | DBC: {} | Position: {}",
                                 state.messages.len(),
-                                if state.dbc_loaded { "Loaded" } else { "None" },
+                                match (state.dbc_loaded, state.dbc_dirty) {
+                                    (true, true) => "Loaded*",
+                                    (true, false) => "Loaded",
+                                    (false, _) => "None",
+                                },
                                 state.playback.position()
                             ));
                         } else {
@@ -1428,6 +2610,25 @@ fn main() {
                         }
                     });
 
+                // DBC parse progress, over everything until it's done
+                if state.dbc_loading_receiver.is_some() {
+                    let display = ui.io().display_size;
+                    ui.window("Loading DBC")
+                        .position([display[0] / 2.0, display[1] / 2.0], Condition::Always)
+                        .position_pivot([0.5, 0.5])
+                        .size([320.0, 0.0], Condition::Always)
+                        .title_bar(false)
+                        .resizable(false)
+                        .movable(false)
+                        .build(|| {
+                            ui.text("Loading DBC...");
+                            imgui::ProgressBar::new(state.dbc_loading_progress / 100.0)
+                                .size([-1.0, 0.0])
+                                .overlay_text(format!("{:.0}%", state.dbc_loading_progress))
+                                .build(ui);
+                        });
+                }
+
                 // Create a dockspace over the main viewport
                 // This allows windows to be docked/rearranged within the main window
                 // Windows can be dragged and docked to different areas, but stay within the app
@@ -1442,9 +2643,11 @@ fn main() {
 
                 // Process incremental chart data loading (runs even when charts window is hidden)
                 state.process_pending_signal_loads();
+                state.process_pending_virtual_loads();
 
                 if state.show_charts {
                     let current_time = state.playback.current_time();
+                    state.charts.set_live(state.hardware_manager.state().is_active);
                     ui.window("Charts")
                         .size([600.0, 350.0], Condition::FirstUseEver)
                         .position([400.0, 30.0], Condition::FirstUseEver)
@@ -1452,6 +2655,9 @@ fn main() {
                         .build(|| {
                             state.charts.render(ui, current_time, state.playback.is_playing());
                         });
+                    for key in state.charts.take_picker_additions() {
+                        state.populate_chart_data_for_signal(&key);
+                    }
 
                     // Handle seek request from chart click
                     // All values from chart are relative offsets from current time
@@ -1472,11 +2678,21 @@ fn main() {
                             TimelineAction::Pause => state.playback.pause(),
                             TimelineAction::StepBack => state.playback.step_back(),
                             TimelineAction::StepForward => state.playback.step_forward(),
+                            TimelineAction::GoToStart => state.seek_to_edge(true),
+                            TimelineAction::GoToEnd => state.seek_to_edge(false),
                             TimelineAction::None => {}
                         }
                     }
                 }
 
+                // Finished self-test
+                if let Some(report) = state.self_test_receiver.as_ref().and_then(|rx| rx.try_recv().ok()) {
+                    state.self_test_receiver = None;
+                    info!("[S.H.I.T] Self-test {}: {:?}", if report.passed() { "passed" } else { "failed" }, report.steps);
+                    state.status_message = Some(format!("Self-test {}", if report.passed() { "passed" } else { "failed" }));
+                    state.hardware_manager.set_self_test_report(report);
+                }
+
                 // Hardware Manager with action handling
                 if state.show_hardware_manager {
                     let action = state.hardware_manager.render(&ui, &mut state.show_hardware_manager);
@@ -1499,10 +2715,16 @@ fn main() {
                                 &interface,
                                 crate::hardware::can_interface::CanConfig {
                                     bitrate: config.bitrate,
-                                    fd_mode: false,
+                                    fd_mode: config.fd_mode,
                                     listen_only: config.listen_only,
+                                    rx_buffer_size: config.rx_buffer_size,
+                                    rx_overflow: config.rx_overflow,
+                                    tx_min_gap: config.tx_min_gap,
+                                    loopback: false,
+                                    status_poll_interval: config.status_poll_interval,
                                 },
                                 interface_type,
+                                config.bus_id,
                             ));
 
                             info!("[S.H.I.T] Connect result: {:?}", result);
@@ -1558,25 +2780,28 @@ fn main() {
                         }
                         LiveModeAction::StopRecording => {
                             let live_state = state.hardware_manager.state();
-                            let msg_count = live_state.live_messages.len();
+                            let msg_count = live_state.recorded_count();
                             info!("[S.H.I.T] Recording stopped - {} messages captured", msg_count);
 
-                            if !live_state.live_messages.is_empty() {
+                            if let Some((path, first)) = live_state.spilled_recording() {
+                                // Unbounded recording: only the tail is in memory, stream the temp file back in
+                                let path = path.to_string_lossy().to_string();
+                                state.load_recording(&path, first);
+                            } else if !live_state.live_messages.is_empty() {
                                 // Convert live messages to CanMessage format and load into main state
                                 let recorded_messages: Vec<CanMessage> = live_state.live_messages
                                     .iter()
-                                    .map(|lm| CanMessage {
-                                        timestamp: lm.timestamp,
-                                        bus: lm.bus,
-                                        id: lm.id,
-                                        data: lm.data.clone().into(),
-                                    })
+                                    .map(|lm| lm.to_can_message())
                                     .collect();
 
                                 // Load into main state
                                 state.messages = recorded_messages.clone();
                                 state.playback = PlaybackEngine::new(recorded_messages.clone());
+                                state.coverage_window.set_messages(&recorded_messages);
+                                state.rate_window.set_messages(&recorded_messages);
                                 state.message_list.set_messages(recorded_messages);
+                                state.diagnostics_window.clear();
+                                state.rebuild_timeline_density();
                                 state.file_loaded = true;
                                 state.initial_data_populated = false;
 
@@ -1596,38 +2821,16 @@ fn main() {
                             state.status_message = Some(format!("Recording stopped - {} messages loaded into playback", msg_count));
                         }
                         LiveModeAction::SaveData => {
-                            info!("[S.H.I.T] Save data requested - {} messages", state.hardware_manager.state().live_messages.len());
-                            // Save to CSV file
-                            let live_state = state.hardware_manager.state();
+                            info!("[S.H.I.T] Save data requested - {} messages", state.hardware_manager.state().recorded_count());
+                            // Save to CSV file (time,addr,bus,data - loadable again)
                             if let Some(path) = crate::ui::FileDialogs::export_csv_file() {
-                                match std::fs::File::create(&path) {
-                                    Ok(mut file) => {
-                                        use std::io::Write;
-                                        // Write CSV header matching 130b.csv format
-                                        let _ = writeln!(file, "time,addr,bus,data");
-                                        // Use recording_start for accurate relative timestamps
-                                        let start_time = live_state.recording_start;
-                                        // Write messages with actual relative time (realtime)
-                                        for msg in &live_state.live_messages {
-                                            // Calculate relative time in seconds with microsecond precision
-                                            let rel_time = if let Some(start) = start_time {
-                                                (msg.timestamp - start).num_microseconds().unwrap_or(0) as f64 / 1_000_000.0
-                                            } else {
-                                                0.0
-                                            };
-                                            // Data as hex string with 0x prefix
-                                            let data_hex = if msg.data.is_empty() {
-                                                "0x".to_string()
-                                            } else {
-                                                format!("0x{}", msg.data.iter()
-                                                    .map(|b| format!("{:02X}", b))
-                                                    .collect::<String>())
-                                            };
-                                            let _ = writeln!(file, "{:.3},0x{:03X},{},{}",
-                                                rel_time, msg.id, msg.bus, data_hex);
-                                        }
-                                        state.status_message = Some(format!("Saved {} messages to {}", live_state.live_messages.len(), path.display()));
-                                        info!("[S.H.I.T] Saved {} messages to {}", live_state.live_messages.len(), path.display());
+                                let live = state.hardware_manager.state_mut();
+                                let range = live.save_range_only.then_some(live.save_range);
+                                let rebase = live.save_rebase;
+                                match live.save_csv(&path, range, rebase) {
+                                    Ok(count) => {
+                                        state.status_message = Some(format!("Saved {} messages to {}", count, path.display()));
+                                        info!("[S.H.I.T] Saved {} messages to {}", count, path.display());
                                     }
                                     Err(e) => {
                                         state.status_message = Some(format!("Failed to save: {}", e));
@@ -1636,6 +2839,17 @@ fn main() {
                                 }
                             }
                         }
+                        LiveModeAction::SelfTest => {
+                            // Runs for up to a few seconds; the result is picked up below on a later frame
+                            if state.self_test_receiver.is_none() {
+                                let (tx, rx) = channel();
+                                rt.spawn(async move {
+                                    let _ = tx.send(crate::ui::self_test::run_self_test(16).await);
+                                });
+                                state.self_test_receiver = Some(rx);
+                                state.hardware_manager.set_self_test_running();
+                            }
+                        }
                         LiveModeAction::None => {}
                     }
                 }
@@ -1654,6 +2868,7 @@ fn main() {
                     // Sync interface stats from CanManagerCollection
                     let stats = rt.block_on(state.can_collection.get_stats());
                     state.hardware_manager.state_mut().sync_interface_stats(&stats);
+                    state.sync_live_virtuals();
 
                     let live_state = state.hardware_manager.state_mut();
                     let is_recording = live_state.is_recording;
@@ -1662,7 +2877,7 @@ fn main() {
                     for msg in &live_messages {
                         // Only store messages if recording is active
                         if is_recording {
-                            live_state.add_message(msg.message.id, msg.message.data.to_vec(), msg.message.bus);
+                            live_state.add_message(msg);
                         }
 
                         // Always update statistics
@@ -1671,12 +2886,20 @@ fn main() {
                         // Update Messages panel with live data
                         state.message_list.update_message(&msg.message);
 
-                        // Decode and add to charts if signals are charted
-                        let decoded = state.signal_decoder.decode_message(&msg.message);
-                        for signal in &decoded {
-                            let key = format!("{}@bus{}", signal.name, msg.message.bus);
-                            if state.charts.has_signal(&key) {
-                                state.charts.add_point(&key, signal.physical_value, msg.timestamp);
+                        // Decode and add to charts if signals are charted (rate-capped per signal)
+                        let (id, bus) = (msg.message.id, msg.message.bus);
+                        let mut values = Vec::new();
+                        state.signal_decoder.visit_signals(
+                            &msg.message,
+                            |signal| state.charts.has_signal(&signal_key(&signal.name, id, bus)),
+                            |signal, decoded| values.push((signal_key(&signal.name, id, bus), decoded.map(|d| d.physical_value))),
+                        );
+                        for (key, value) in values {
+                            state.charts.add_live_point(&key, value, msg.timestamp);
+                        }
+                        for (key, eval) in &mut state.live_virtuals {
+                            if let Some(value) = eval.feed_live(&state.signal_decoder, &msg.message) {
+                                state.charts.add_live_point(key, Some(value), msg.timestamp);
                             }
                         }
                     }
@@ -1689,10 +2912,19 @@ fn main() {
 
                 // Message Sender window
                 if state.show_message_sender {
-                    let is_connected = state.hardware_manager.state().is_active;
-                    if let Some((id, data)) = state.message_sender.render(&ui, is_connected, &mut state.show_message_sender) {
-                        info!("Send CAN message: 0x{:03X} {:?}", id, data);
-                        // TODO: Actually send the message through the interface
+                    let buses: Vec<u8> = state.hardware_manager.state()
+                        .connected_interfaces
+                        .iter()
+                        .filter(|i| matches!(i.status, hardware::can_manager::ConnectionStatus::Connected))
+                        .map(|i| i.bus_id)
+                        .collect();
+                    if let Some((bus, id, data)) = state.message_sender.render(&ui, &buses, &mut state.show_message_sender) {
+                        info!("Send CAN message on bus {}: 0x{:03X} {:?}", bus, id, data);
+                        let msg = CanMessage::new(bus, id, data.into());
+                        match rt.block_on(state.can_collection.send_to_bus(bus, msg.clone())) {
+                            Ok(()) => state.message_list.add_sent_message(&msg),
+                            Err(e) => state.status_message = Some(format!("Send failed: {}", e)),
+                        }
                     }
                 }
 
@@ -1720,6 +2952,7 @@ fn main() {
                         state.plugin_message_buffer.push(ManagerMessage {
                             message: msg.clone(),
                             timestamp: msg.timestamp,
+                            device_timestamp: None,
                         });
                     }
                     let window = state.playback.get_window(
@@ -1730,6 +2963,7 @@ fn main() {
                         state.plugin_message_buffer.push(ManagerMessage {
                             message: msg.clone(),
                             timestamp: msg.timestamp,
+                            device_timestamp: None,
                         });
                     }
                 }
@@ -1738,15 +2972,11 @@ fn main() {
                     let live_state = state.hardware_manager.state();
                     let discovery_count = 10_000.min(live_state.live_messages.len());
                     let start = live_state.live_messages.len().saturating_sub(discovery_count);
-                    for lm in &live_state.live_messages[start..] {
+                    for lm in live_state.live_messages.range(start..) {
                         state.plugin_message_buffer.push(ManagerMessage {
-                            message: crate::core::CanMessage {
-                                timestamp: lm.timestamp,
-                                bus: lm.bus,
-                                id: lm.id,
-                                data: lm.data.clone().into(),
-                            },
+                            message: lm.to_can_message(),
                             timestamp: lm.timestamp,
+                            device_timestamp: None,
                         });
                     }
                 }
@@ -1791,12 +3021,87 @@ fn main() {
 
                 // Message Statistics window
                 if state.show_message_stats {
-                    state.message_stats.render(&ui, &mut state.show_message_stats);
+                    if state.message_stats.render(ui, &mut state.show_message_stats) {
+                        state.rescan_gaps();
+                    }
                 }
 
                 // Pattern Analyzer window
-                if state.show_pattern_analyzer {
-                    state.pattern_analyzer.render(&ui, &mut state.show_pattern_analyzer);
+                if state.show_pattern_analyzer
+                    && state.pattern_analyzer.render(ui, &mut state.dbc_file, &mut state.show_pattern_analyzer)
+                {
+                    // Findings were written as comments - keep the decoder and message list copies in sync
+                    state.signal_decoder.set_dbc(state.dbc_file.clone());
+                    state.watch_window.dbc_changed();
+                    state.live_virtuals.clear();
+                    state.message_list.set_dbc(state.dbc_file.clone());
+                    state.dbc_dirty = true;
+                    state.status_message = Some("Pattern findings saved as DBC comments".to_string());
+                }
+
+                // DBC Coverage window
+                if state.show_coverage
+                    && state.coverage_window.render(ui, &mut state.dbc_file, &mut state.show_coverage)
+                {
+                    // Stubs were added - sync DBC to the decoder and message list
+                    state.signal_decoder.set_dbc(state.dbc_file.clone());
+                    state.watch_window.dbc_changed();
+                    state.live_virtuals.clear();
+                    state.message_list.set_dbc(state.dbc_file.clone());
+                    state.dbc_loaded = true;
+                    state.dbc_dirty = true;
+                    state.status_message = Some(format!("DBC now defines {} messages", state.dbc_file.messages.len()));
+                }
+
+                // Signal Range Check window
+                if state.show_range_check {
+                    state.range_check_window.render(ui, &mut state.show_range_check);
+                }
+
+                // Message Rate window
+                if state.show_rate_graph {
+                    state.rate_window.render(ui, &state.dbc_file, &mut state.show_rate_graph);
+                }
+
+                // Diagnostics window
+                if state.show_diagnostics {
+                    state.diagnostics_window.render(ui, &state.messages, &mut state.show_diagnostics);
+                }
+
+                // Watch window: values of the newest frames at the playhead or on the live bus
+                if state.show_watch {
+                    let message_list = &state.message_list;
+                    state.watch_window.update(&state.signal_decoder, &state.dbc_file, |id, bus| {
+                        message_list.get_state(id, bus).map(|s| CanMessage::new(bus, id, s.data.as_slice().into()))
+                    });
+                    state.watch_window.render(ui, &state.dbc_file, &mut state.show_watch);
+                }
+
+                // Timeline window: follows the playhead, and seeks/steps playback
+                if state.show_timeline {
+                    let timeline = state.timeline_window.timeline();
+                    if !timeline.data().dragging {
+                        if let Some(ct) = state.playback.current_time() {
+                            timeline.seek_to_time(ct);
+                        }
+                    }
+                    timeline.set_playing(state.playback.is_playing());
+
+                    use crate::ui::timeline::TimelineAction;
+                    match state.timeline_window.render(ui, &mut state.show_timeline) {
+                        TimelineAction::Seek(pos) => {
+                            let target = state.timeline_window.timeline().data().time_at_position(pos);
+                            if target.is_some() {
+                                state.playback.seek_to_time(target);
+                                state.seek_triggered_ui_update = true;
+                            }
+                        }
+                        TimelineAction::Play => state.playback.play(),
+                        TimelineAction::Pause => state.playback.pause(),
+                        TimelineAction::StepBack => state.playback.step_back(),
+                        TimelineAction::StepForward => state.playback.step_forward(),
+                        TimelineAction::Zoom | TimelineAction::LoopSet | TimelineAction::LoopClear | TimelineAction::None => {}
+                    }
                 }
 
                 // Bit Visualizer window - update with message data
@@ -1806,6 +3111,25 @@ fn main() {
                         state.bit_visualizer.set_message(selected_msg.id, selected_msg.bus, &selected_msg.data);
                     }
 
+                    // Go-to-ID: latest frame at the playhead, or the live list's last frame
+                    if let Some(id) = state.bit_visualizer.take_goto_request() {
+                        let frame = state.playback.latest_frame(id).cloned().or_else(|| {
+                            state.message_list.latest_frame_for_id(id).cloned()
+                        });
+                        match frame {
+                            Some(msg) => state.bit_visualizer.show_goto_frame(&msg),
+                            None => state.bit_visualizer.goto_not_found(id),
+                        }
+                    }
+
+                    // Signal create/edit dialog: that message's frames from the log (byte-order guess, scaling fit)
+                    if let Some((id, bus)) = state.bit_visualizer.take_frame_sample_request() {
+                        let frames = state.messages.iter()
+                            .filter(|m| m.id == id && m.bus == bus)
+                            .map(|m| m.data);
+                        state.bit_visualizer.set_dialog_frames(frames);
+                    }
+
                     // Playback: update ALL quadrants with latest data for their respective messages
                     for (id, bus) in state.bit_visualizer.quadrant_messages() {
                         if let Some(msg_state) = state.message_list.get_state(id, bus) {
@@ -1816,25 +3140,28 @@ fn main() {
                     // Get list of charted signals
                     let charted: Vec<String> = state.charts.get_charted_signals();
                     state.bit_visualizer.set_charted_signals(charted);
+                    state.bit_visualizer.set_custom_colors(state.charts.custom_colors());
 
                     state.bit_visualizer.render(&ui, &mut state.dbc_file, &mut state.show_bit_visualizer);
+                    if state.bit_visualizer.take_dbc_changed() {
+                        state.dbc_dirty = true;
+                    }
 
-                    // Check for chart toggle requests
-                    if let Some(signal_name) = state.bit_visualizer.take_chart_toggle_request() {
-                        use std::io::Write;
-                        let mut f = std::fs::OpenOptions::new()
-                            .create(true)
-                            .append(true)
-                            .open("/tmp/can-viz-chart-debug.txt")
-                            .ok();
-                        if let Some(ref mut f) = f {
-                            let _ = writeln!(f, "main.rs: received chart toggle request for: {}", signal_name);
+                    // Send an edited frame from the bit visualizer's edit mode
+                    if let Some((id, bus, data)) = state.bit_visualizer.take_send_request() {
+                        let msg = CanMessage::new(bus, id, data.into());
+                        match rt.block_on(state.can_collection.send_to_bus(bus, msg.clone())) {
+                            Ok(()) => state.message_list.add_sent_message(&msg),
+                            Err(e) => state.status_message = Some(format!("Send failed: {}", e)),
                         }
+                    }
 
+                    // Check for chart toggle requests
+                    if let Some(signal_name) = state.bit_visualizer.take_chart_toggle_request() {
                         let was_charted = state.charts.has_signal(&signal_name);
-                        if let Some(ref mut f) = f { let _ = writeln!(f, "  was_charted: {}", was_charted); }
+                        debug!("Chart toggle request from bit visualizer: {} (was charted: {})", signal_name, was_charted);
 
-                        // signal_name is now a bus-aware key from bit visualizer ("name@busN")
+                        // signal_name is now a bus-aware key from bit visualizer ("name@0xID@busN")
                         state.charts.toggle_signal_by_name(&signal_name);
                         // If signal was newly added, populate its data
                         if !was_charted {
@@ -1844,6 +3171,13 @@ fn main() {
 
                     // Sync DBC changes to other components
                     state.signal_decoder.set_dbc(state.dbc_file.clone());
+                    state.watch_window.dbc_changed();
+                    state.live_virtuals.clear();
+
+                    if state.bit_visualizer.take_checksums_changed() {
+                        state.rescan_anomalies();
+                        state.announce_anomalies = true;
+                    }
                 }
 
                 // Log window
@@ -1858,28 +3192,18 @@ fn main() {
 
                 // Export Dialog
                 if let Some(export_request) = state.export_dialog.render(&ui) {
-                    if let Some(path) = FileDialogs::export_csv_file() {
-                        if let Ok(mut file) = std::fs::File::create(&path) {
-                            use std::io::Write;
-                            let _ = writeln!(file, "time,addr,bus,data");
-                            let first_ts = state.messages.first().map(|m| m.timestamp);
-                            for msg in &state.messages {
-                                let rel_time = first_ts
-                                    .map(|t| (msg.timestamp - t).num_microseconds().unwrap_or(0) as f64 / 1_000_000.0)
-                                    .unwrap_or(0.0);
-                                let data_hex = if msg.data.is_empty() {
-                                    "0x".to_string()
-                                } else {
-                                    format!("0x{}", msg.data.iter().map(|b| format!("{:02X}", b)).collect::<String>())
-                                };
-                                let _ = writeln!(file, "{:.6},0x{:03X},{},{}", rel_time, msg.id, msg.bus, data_hex);
-                            }
-                            state.status_message = Some(format!("Exported {} messages to {}", state.messages.len(), path.display()));
-                            info!("Exported {} messages to {}", state.messages.len(), path.display());
-                        } else {
-                            state.status_message = Some("Failed to create export file".to_string());
-                        }
-                    }
+                    state.handle_export(&export_request);
+                }
+
+                // Generate test data dialog
+                if let Some(options) = state.generate_dialog.render(ui) {
+                    let messages = input::generate_from_dbc(&state.dbc_file, &options, Utc::now());
+                    let count = messages.len();
+                    state.set_loaded_messages(messages);
+                    state.status_message = Some(format!(
+                        "Generated {} messages ({:.0}s from {} DBC messages)",
+                        count, options.duration_secs, state.dbc_file.messages.len()
+                    ));
                 }
 
                 // About Dialog
@@ -1901,9 +3225,15 @@ fn main() {
 
                 surface.swap_buffers(&context).expect("Failed to swap buffers");
             }
+            Event::WindowEvent { event: WindowEvent::DroppedFile(ref path), .. } => {
+                state.open_path(path);
+            }
             Event::WindowEvent { event: WindowEvent::CloseRequested, .. } => {
-                state.save_settings();
-                window_target.exit();
+                state.request_dbc_discard(DbcDiscard::Exit);
+                if std::mem::take(&mut state.exit_requested) {
+                    state.save_settings();
+                    window_target.exit();
+                }
             }
             Event::WindowEvent { event: WindowEvent::Resized(size), .. } => {
                 // Resize the GL surface to match the new window size
@@ -1924,5 +3254,6 @@ fn main() {
         }
 
         platform.handle_event(imgui.io_mut(), &window, &event);
-    }).expect("EventLoop error");
+    }).context("Event loop error")?;
+    Ok(())
 }
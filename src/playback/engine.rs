@@ -1,7 +1,7 @@
 use crate::core::CanMessage;
-use crate::playback::{PlaybackConfig, PlaybackState};
+use crate::playback::{PlaybackConfig, PlaybackState, MAX_SPEED, MIN_SPEED};
 use chrono::{DateTime, Utc, Duration};
-use std::time::{Duration as StdDuration, Instant};
+use std::time::Duration as StdDuration;
 
 /// Playback engine for CAN data
 pub struct PlaybackEngine {
@@ -9,8 +9,11 @@ pub struct PlaybackEngine {
     config: PlaybackConfig,
     state: PlaybackState,
     current_position: usize,
-    virtual_start_time: Option<Instant>,
-    real_start_time: Option<DateTime<Utc>>,
+    /// Playhead on the playback timeline (see `playback_offset`), µs, advanced by each
+    /// `update`'s elapsed time. None resyncs it to `current_position` (after seeks).
+    playhead_offset: Option<f64>,
+    /// Playback-timeline offset of each message, µs, when idle gaps are capped
+    capped_offsets: Option<Vec<i64>>,
 }
 
 impl PlaybackEngine {
@@ -20,11 +23,12 @@ impl PlaybackEngine {
             config: PlaybackConfig {
                 speed: 1.0,
                 loop_playback: false,
+                max_gap: None,
             },
             state: PlaybackState::Stopped,
             current_position: 0,
-            virtual_start_time: None,
-            real_start_time: None,
+            playhead_offset: None,
+            capped_offsets: None,
         }
     }
 
@@ -40,7 +44,9 @@ impl PlaybackEngine {
 
     /// Append messages (for streaming load). Messages must be in timestamp order.
     pub fn append_messages(&mut self, msgs: &[CanMessage]) {
+        let from = self.messages.len();
         self.messages.extend_from_slice(msgs);
+        self.extend_capped_offsets(from);
     }
 
     /// Get current playback state
@@ -50,8 +56,7 @@ impl PlaybackEngine {
 
     /// Set playback speed
     pub fn set_speed(&mut self, speed: f64) {
-        self.config.speed = speed.max(0.1).min(10.0);
-        self.virtual_start_time = None; // Reset timing when speed changes
+        self.config.speed = speed.clamp(MIN_SPEED, MAX_SPEED);
     }
 
     /// Get current playback speed
@@ -59,6 +64,63 @@ impl PlaybackEngine {
         self.config.speed
     }
 
+    /// Cap idle gaps between frames to `max_gap` during playback (None plays them as recorded).
+    /// Only the pace changes: message order and timestamps are untouched. No-op if unchanged.
+    pub fn set_max_gap(&mut self, max_gap: Option<Duration>) {
+        if self.config.max_gap == max_gap {
+            return;
+        }
+        self.config.max_gap = max_gap;
+        self.capped_offsets = max_gap.map(|_| Vec::with_capacity(self.messages.len()));
+        self.extend_capped_offsets(0);
+        // Carry on from the current message on the new timeline
+        self.playhead_offset = None;
+    }
+
+    /// Fill in capped playback offsets for messages from index `from` on
+    fn extend_capped_offsets(&mut self, from: usize) {
+        let (Some(offsets), Some(max_gap)) = (self.capped_offsets.as_mut(), self.config.max_gap) else {
+            return;
+        };
+        let cap = max_gap.num_microseconds().unwrap_or(i64::MAX);
+        for i in from..self.messages.len() {
+            let offset = match i.checked_sub(1) {
+                Some(prev) => {
+                    let gap = (self.messages[i].timestamp - self.messages[prev].timestamp).num_microseconds().unwrap_or(i64::MAX);
+                    offsets[prev] + gap.clamp(0, cap)
+                }
+                None => 0,
+            };
+            offsets.push(offset);
+        }
+    }
+
+    /// Time of message `idx` on the playback timeline, µs from the first message:
+    /// its log time, less any idle time cut by the gap cap
+    fn playback_offset(&self, idx: usize) -> i64 {
+        match &self.capped_offsets {
+            Some(offsets) => offsets.get(idx).copied().unwrap_or(0),
+            None => match (self.messages.get(idx), self.messages.first()) {
+                (Some(msg), Some(first)) => (msg.timestamp - first.timestamp).num_microseconds().unwrap_or(0),
+                _ => 0,
+            },
+        }
+    }
+
+    /// First message at or after `offset` on the playback timeline
+    fn position_at_offset(&self, offset: i64) -> usize {
+        match &self.capped_offsets {
+            Some(offsets) => offsets.partition_point(|&o| o < offset),
+            None => match self.messages.first() {
+                Some(first) => {
+                    let target = first.timestamp + Duration::microseconds(offset);
+                    self.messages.partition_point(|msg| msg.timestamp < target)
+                }
+                None => 0,
+            },
+        }
+    }
+
     /// Start/resume playback
     pub fn play(&mut self) {
         // If at end, restart from beginning
@@ -70,22 +132,20 @@ impl PlaybackEngine {
         }
 
         self.state = PlaybackState::Playing;
-        self.virtual_start_time = Some(Instant::now());
-        self.real_start_time = Some(self.current_time().unwrap_or_else(|| Utc::now()));
+        self.playhead_offset = None;
     }
 
     /// Pause playback
     pub fn pause(&mut self) {
         self.state = PlaybackState::Paused;
-        self.virtual_start_time = None;
+        self.playhead_offset = None;
     }
 
     /// Stop playback and reset
     pub fn stop(&mut self) {
         self.state = PlaybackState::Stopped;
         self.current_position = 0;
-        self.virtual_start_time = None;
-        self.real_start_time = None;
+        self.playhead_offset = None;
     }
 
     /// Seek to a specific time in the log
@@ -98,14 +158,14 @@ impl PlaybackEngine {
                 })
                 .unwrap_or_else(|pos| pos);
 
-            self.virtual_start_time = None;
+            self.playhead_offset = None;
         }
     }
 
     /// Seek to a specific position (index)
     pub fn seek_to_position(&mut self, pos: usize) {
         self.current_position = pos.clamp(0, self.messages.len());
-        self.virtual_start_time = None;
+        self.playhead_offset = None;
     }
 
     /// Step forward by one frame
@@ -115,7 +175,7 @@ impl PlaybackEngine {
         }
         // Pause when stepping
         self.state = PlaybackState::Paused;
-        self.virtual_start_time = None;
+        self.playhead_offset = None;
     }
 
     /// Step backward by one frame
@@ -125,7 +185,7 @@ impl PlaybackEngine {
         }
         // Pause when stepping
         self.state = PlaybackState::Paused;
-        self.virtual_start_time = None;
+        self.playhead_offset = None;
     }
 
     /// Check if currently playing
@@ -156,57 +216,28 @@ impl PlaybackEngine {
         self.messages.last().map(|m| m.timestamp)
     }
 
-    /// Update playback state (call each frame)
-    pub fn update(&mut self, _delta_time: StdDuration) {
-        if self.state != PlaybackState::Playing {
+    /// Advance the playhead by `delta_time` of real time, scaled by the playback speed
+    /// (call each frame with the time since the previous call)
+    pub fn update(&mut self, delta_time: StdDuration) {
+        if self.state != PlaybackState::Playing || self.messages.is_empty() {
             return;
         }
 
-        // Reinitialize virtual_start_time if it was reset (e.g., by seeking)
-        if self.virtual_start_time.is_none() {
-            if let Some(real_start) = self.real_start_time {
-                // Calculate new virtual_start based on current position
-                let current_time = self.messages.get(self.current_position)
-                    .map(|m| m.timestamp)
-                    .unwrap_or(real_start);
-                let elapsed_so_far = (current_time - real_start).num_milliseconds() as f64 / 1000.0;
-                if elapsed_so_far < 0.0 {
-                    // Seeked backward past real_start - restart timing from current position
-                    self.real_start_time = Some(current_time);
-                }
-                // Clamp to 0: Duration::from_secs_f64 panics on negative
-                let elapsed_secs = (elapsed_so_far / self.config.speed).max(0.0);
-                let elapsed_instant = StdDuration::from_secs_f64(elapsed_secs);
-                self.virtual_start_time = Some(Instant::now() - elapsed_instant);
-            }
-        }
+        let current = self.playhead_offset
+            .unwrap_or_else(|| self.playback_offset(self.current_position.min(self.messages.len() - 1)) as f64);
+        let target_offset = current + delta_time.as_secs_f64() * 1_000_000.0 * self.config.speed;
+        self.playhead_offset = Some(target_offset);
+        self.current_position = self.position_at_offset(target_offset as i64);
 
-        if let Some(virtual_start) = self.virtual_start_time {
-            let elapsed = virtual_start.elapsed();
-            let scaled_elapsed = StdDuration::from_secs_f64(elapsed.as_secs_f64() * self.config.speed);
-
-            if let Some(real_start) = self.real_start_time {
-                let target_time = real_start + scaled_elapsed;
-
-                // Find new position based on target time
-                let new_pos = self.messages
-                    .binary_search_by(|msg| msg.timestamp.cmp(&target_time))
-                    .unwrap_or_else(|pos| pos);
-
-                self.current_position = new_pos;
-
-                // Check if we've reached the end
-                if self.current_position >= self.messages.len() {
-                    if self.config.loop_playback {
-                        self.seek_to_time(self.start_time());
-                    } else {
-                        self.state = PlaybackState::Stopped;
-                        // Reset to beginning so we can play again easily
-                        self.current_position = 0;
-                        self.virtual_start_time = None;
-                        self.real_start_time = None;
-                    }
-                }
+        // Check if we've reached the end
+        if self.current_position >= self.messages.len() {
+            if self.config.loop_playback {
+                self.seek_to_time(self.start_time());
+            } else {
+                self.state = PlaybackState::Stopped;
+                // Reset to beginning so we can play again easily
+                self.current_position = 0;
+                self.playhead_offset = None;
             }
         }
     }
@@ -218,6 +249,14 @@ impl PlaybackEngine {
         &self.messages[..end]
     }
 
+    /// Latest frame with `id` at or before the playhead, or its first frame after it
+    pub fn latest_frame(&self, id: u32) -> Option<&CanMessage> {
+        let split = (self.current_position + 1).min(self.messages.len());
+        let (played, upcoming) = self.messages.split_at(split);
+        played.iter().rev().find(|m| m.id == id)
+            .or_else(|| upcoming.iter().find(|m| m.id == id))
+    }
+
     /// Get messages visible in the current time window
     pub fn get_window(&self, before: Duration, after: Duration) -> &[CanMessage] {
         if let Some(current) = self.current_time() {
@@ -238,3 +277,93 @@ impl PlaybackEngine {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_max_gap_compresses_playback_timeline() {
+        let base = Utc::now();
+        let messages: Vec<CanMessage> = [0, 100, 10_000, 10_050]
+            .iter()
+            .map(|&ms| {
+                let mut msg = CanMessage::new(0, 0x100, vec![0u8].into());
+                msg.timestamp = base + Duration::milliseconds(ms);
+                msg
+            })
+            .collect();
+        let mut engine = PlaybackEngine::new(messages[..2].to_vec());
+        engine.set_max_gap(Some(Duration::seconds(2)));
+        engine.append_messages(&messages[2..]);
+
+        // The 9.9 s silence plays as 2 s; the stored timestamps are untouched
+        let offsets: Vec<i64> = (0..4).map(|i| engine.playback_offset(i)).collect();
+        assert_eq!(offsets, vec![0, 100_000, 2_100_000, 2_150_000]);
+        assert_eq!(engine.position_at_offset(2_100_000), 2);
+        assert_eq!(engine.end_time(), Some(base + Duration::milliseconds(10_050)));
+
+        // Turning the cap off restores the recorded pacing
+        engine.set_max_gap(None);
+        assert_eq!(engine.playback_offset(2), 10_000_000);
+        assert_eq!(engine.position_at_offset(2_100_000), 2);
+        assert_eq!(engine.position_at_offset(10_000_001), 3);
+    }
+
+    #[test]
+    fn test_update_advances_by_elapsed_time() {
+        let base = Utc::now();
+        let messages: Vec<CanMessage> = (0..=100)
+            .map(|i| {
+                let mut msg = CanMessage::new(0, 0x100, vec![0u8].into());
+                msg.timestamp = base + Duration::milliseconds(i * 10);
+                msg
+            })
+            .collect();
+        let mut engine = PlaybackEngine::new(messages);
+        engine.play();
+
+        // Uneven frame times add up: 5 + 30 + 15 ms = 50 ms = message 5
+        for ms in [5, 30, 15] {
+            engine.update(StdDuration::from_millis(ms));
+        }
+        assert_eq!(engine.position(), 5);
+
+        // At 2x, 100 ms of frames covers 200 ms of log however it is split
+        engine.set_speed(2.0);
+        engine.update(StdDuration::from_millis(70));
+        engine.update(StdDuration::from_millis(30));
+        assert_eq!(engine.position(), 25);
+
+        // Paused time doesn't count; a seek restarts from the new message
+        engine.pause();
+        engine.update(StdDuration::from_secs(1));
+        assert_eq!(engine.position(), 25);
+        engine.seek_to_position(50);
+        engine.play();
+        engine.update(StdDuration::from_millis(5));
+        assert_eq!(engine.position(), 51);
+
+        // Running off the end stops and rewinds
+        engine.update(StdDuration::from_secs(1));
+        assert_eq!((engine.state(), engine.position()), (PlaybackState::Stopped, 0));
+    }
+
+    #[test]
+    fn test_seek_to_first_and_last_frame() {
+        let base = Utc::now();
+        let messages: Vec<CanMessage> = [0, 40, 90]
+            .iter()
+            .map(|&ms| {
+                let mut msg = CanMessage::new(0, 0x100, vec![0u8].into());
+                msg.timestamp = base + Duration::milliseconds(ms);
+                msg
+            })
+            .collect();
+        let mut engine = PlaybackEngine::new(messages);
+        engine.seek_to_time(engine.end_time());
+        assert_eq!((engine.position(), engine.current_time()), (2, engine.end_time()));
+        engine.seek_to_time(engine.start_time());
+        assert_eq!((engine.position(), engine.current_time()), (0, Some(base)));
+    }
+}
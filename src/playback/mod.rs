@@ -3,7 +3,7 @@ pub mod engine;
 pub use engine::PlaybackEngine;
 
 use crate::core::CanMessage;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
 
 /// Playback state
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -18,4 +18,63 @@ pub enum PlaybackState {
 pub struct PlaybackConfig {
     pub speed: f64,  // 1.0 = real-time, 2.0 = 2x speed
     pub loop_playback: bool,
+    /// Idle gaps between frames play back at most this long (None = as recorded)
+    pub max_gap: Option<Duration>,
+}
+
+/// Slowest and fastest replay speed the engine accepts
+pub const MIN_SPEED: f64 = 0.1;
+pub const MAX_SPEED: f64 = 100.0;
+
+/// Idle-gap cap offered when the user first turns it on, in seconds
+pub const DEFAULT_MAX_GAP_SECS: f64 = 2.0;
+
+/// Parse a "Go to time" entry into an absolute log time
+///
+/// Accepts an offset from `start` as `ss[.fff]`, `mm:ss[.fff]` or `hh:mm:ss[.fff]`,
+/// or an absolute RFC 3339 timestamp (e.g. `2024-01-01T12:00:00Z`).
+pub fn parse_seek_target(input: &str, start: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    let input = input.trim();
+    if input.is_empty() {
+        return None;
+    }
+    if let Ok(absolute) = DateTime::parse_from_rfc3339(input) {
+        return Some(absolute.with_timezone(&Utc));
+    }
+
+    let parts: Vec<&str> = input.split(':').collect();
+    if parts.len() > 3 {
+        return None;
+    }
+    let (seconds, whole) = parts.split_last()?;
+    let mut offset_secs: f64 = seconds.parse().ok().filter(|s: &f64| s.is_finite() && *s >= 0.0)?;
+    for (i, part) in whole.iter().rev().enumerate() {
+        let value: u32 = part.parse().ok()?;
+        offset_secs += value as f64 * 60f64.powi(i as i32 + 1);
+    }
+    Some(start + chrono::Duration::microseconds((offset_secs * 1_000_000.0).round() as i64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_parse_seek_target() {
+        let start = Utc.timestamp_opt(1_700_000_000, 0).unwrap();
+        let offset = |s: &str| parse_seek_target(s, start).map(|t| (t - start).num_milliseconds());
+
+        assert_eq!(offset("12.5"), Some(12_500));
+        assert_eq!(offset("01:30"), Some(90_000));
+        assert_eq!(offset(" 2:05.250 "), Some(125_250));
+        assert_eq!(offset("1:00:00"), Some(3_600_000));
+        assert_eq!(offset(""), None);
+        assert_eq!(offset("1:2:3:4"), None);
+        assert_eq!(offset("ab:cd"), None);
+        assert_eq!(offset("-5"), None);
+
+        let absolute = parse_seek_target("2023-11-14T22:13:30Z", start).unwrap();
+        assert_eq!((absolute - start).num_seconds(), 10);
+    }
 }
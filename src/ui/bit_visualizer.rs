@@ -1,21 +1,15 @@
 use imgui::{Condition, StyleColor, Ui};
-use crate::core::dbc::{DbcFile, DbcMessage, DbcSignal, ByteOrder, ValueType, ValueDescription};
+use crate::core::{CanData, CanMessage};
+use crate::core::message::parse_can_id;
+use crate::core::dbc::{signal_bits, DbcFile, DbcMessage, DbcSignal, ByteOrder, ValueType, ValueDescription};
+use crate::decode::anomaly::{ChecksumAlgorithm, ChecksumCheck, MAX_CHECKSUM_BITS};
 use crate::decode::decoder::extract_bits;
+use crate::decode::router::signal_key;
+use crate::decode::units::{conversions_for, UnitConversion};
+use crate::ui::colors::{signal_color, ColorPalette, CustomColors};
+use crate::ui::signal_notes::SignalNotes;
 use std::cell::RefCell;
-
-/// Signal color palette for visualizing different signals (more vibrant)
-const SIGNAL_COLORS: [[f32; 4]; 10] = [
-    [0.3, 0.5, 0.9, 0.7],  // Blue
-    [0.3, 0.7, 0.4, 0.7],  // Green
-    [0.9, 0.6, 0.2, 0.7],  // Orange
-    [0.7, 0.4, 0.8, 0.7],  // Purple
-    [0.8, 0.3, 0.4, 0.7],  // Red
-    [0.3, 0.8, 0.8, 0.7],  // Cyan
-    [0.8, 0.8, 0.3, 0.7],  // Yellow
-    [0.6, 0.4, 0.3, 0.7],  // Brown
-    [0.5, 0.5, 0.7, 0.7],  // Slate
-    [0.7, 0.5, 0.7, 0.7],  // Mauve
-];
+use std::collections::{HashMap, HashSet};
 
 /// Callback type for when a signal is created
 pub type SignalCreatedCallback = Box<dyn FnMut(u32, DbcSignal)>;
@@ -23,18 +17,98 @@ pub type SignalCreatedCallback = Box<dyn FnMut(u32, DbcSignal)>;
 /// Callback type for toggling a signal on the chart
 pub type ToggleChartCallback = Box<dyn FnMut(&str)>;
 
+/// Frames of the selected message sampled to guess a new signal's byte order
+const BYTE_ORDER_SAMPLE_FRAMES: usize = 2000;
+
+/// The smoother byte order must be at most this fraction as rough as the other to be suggested
+const BYTE_ORDER_GUESS_MARGIN: f64 = 0.5;
+
+/// Bit-grid zoom range and +/- step
+const GRID_ZOOM_MIN: f32 = 0.75;
+const GRID_ZOOM_MAX: f32 = 3.0;
+const GRID_ZOOM_STEP: f32 = 0.25;
+
+/// Byte order suggested for the create dialog's selection, from the loaded log
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum ByteOrderGuess {
+    /// Waiting for frames of the message (see `take_frame_sample_request`)
+    Pending,
+    /// Decodes clearly smoother in this order over this many frames
+    Suggested(ByteOrder, usize),
+    /// Both orders read alike over this many frames (e.g. the selection is within one byte)
+    Undecided(usize),
+}
+
+/// Inputs of the dialogs' "fit factor/offset" helper
+#[derive(Default)]
+struct ScalingFit {
+    physical_min: String,
+    physical_max: String,
+    /// Outcome of the last fit, shown under the inputs
+    status: Option<String>,
+}
+
+/// How bit positions are labelled in the grid. Display only: the grid is always
+/// drawn MSB-left and signals are always stored with DBC start bits.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+enum BitNumbering {
+    /// Global DBC start-bit numbers (byte * 8 + bit, bit 0 = LSB)
+    #[default]
+    Dbc,
+    /// byte.bit with bit 0 = LSB
+    Lsb0,
+    /// byte.bit with bit 0 = MSB
+    Msb0,
+}
+
+impl BitNumbering {
+    const ALL: [BitNumbering; 3] = [BitNumbering::Dbc, BitNumbering::Lsb0, BitNumbering::Msb0];
+
+    fn label(self) -> &'static str {
+        match self {
+            BitNumbering::Dbc => "DBC start bit",
+            BitNumbering::Lsb0 => "byte.bit LSB0",
+            BitNumbering::Msb0 => "byte.bit MSB0",
+        }
+    }
+
+    /// Header number over grid column `col` (0 = leftmost = MSB)
+    fn column_header(self, col: usize) -> usize {
+        match self {
+            BitNumbering::Dbc | BitNumbering::Lsb0 => 7 - col,
+            BitNumbering::Msb0 => col,
+        }
+    }
+
+    /// Name of the bit at grid position `display_pos` in this scheme
+    fn bit_name(self, display_pos: usize) -> String {
+        let (byte, col) = (display_pos / 8, display_pos % 8);
+        match self {
+            BitNumbering::Dbc => display_pos_to_dbc_bit(display_pos).to_string(),
+            BitNumbering::Lsb0 => format!("{}.{}", byte, 7 - col),
+            BitNumbering::Msb0 => format!("{}.{}", byte, col),
+        }
+    }
+}
+
 /// State for a single quadrant in the 4-panel bit visualizer
 #[derive(Clone)]
 struct QuadrantState {
     selected_message_id: Option<u32>,
     selected_bus: Option<u8>,
     current_data: [u8; 8],
+    /// Bytes actually present in the last frame; the rest of `current_data` is zero padding
+    dlc: usize,
+    /// Frame being composed in edit mode (None until the first bit is toggled)
+    edited_data: Option<[u8; 8]>,
     bit_flip_counts: [u32; 64],
     last_data: [u8; 8],
     max_flip_count: u32,
     selection_start: Option<usize>,
     selection_end: Option<usize>,
     is_dragging: bool,
+    /// Locked to its message: list selections don't replace it
+    pinned: bool,
 }
 
 impl QuadrantState {
@@ -43,12 +117,15 @@ impl QuadrantState {
             selected_message_id: None,
             selected_bus: None,
             current_data: [0; 8],
+            dlc: 8,
+            edited_data: None,
             bit_flip_counts: [0; 64],
             last_data: [0; 8],
             max_flip_count: 0,
             selection_start: None,
             selection_end: None,
             is_dragging: false,
+            pinned: false,
         }
     }
 
@@ -76,6 +153,7 @@ impl QuadrantState {
                 self.current_data[i] = byte;
             }
         }
+        self.dlc = data.len().min(8);
     }
 
     fn update_activity(&mut self, old_data: &[u8; 8], new_data: &[u8; 8]) {
@@ -100,9 +178,26 @@ impl QuadrantState {
         self.selected_message_id = None;
         self.selected_bus = None;
         self.current_data = [0; 8];
+        self.dlc = 8;
+        self.edited_data = None;
         self.selection_start = None;
         self.selection_end = None;
         self.is_dragging = false;
+        self.pinned = false;
+    }
+
+    /// Bytes to show: the edited frame while composing, otherwise the last received frame
+    fn display_data(&self, edit_mode: bool) -> [u8; 8] {
+        match self.edited_data {
+            Some(edited) if edit_mode => edited,
+            _ => self.current_data,
+        }
+    }
+
+    /// Flip one bit of the edited frame (starting from the last received frame)
+    fn toggle_edit_bit(&mut self, byte_idx: usize, bit_idx: usize) {
+        let data = self.edited_data.get_or_insert(self.current_data);
+        data[byte_idx] ^= 1 << bit_idx;
     }
 
     /// Set selection for savestate restore (data will be populated when messages arrive)
@@ -119,6 +214,22 @@ pub struct BitVisualizerWindow {
     focused_quadrant: usize,
     /// Show signal overlays
     show_signals: bool,
+    /// Clicking a bit toggles it in the edited frame instead of starting a signal selection
+    edit_mode: bool,
+    bit_numbering: BitNumbering,
+    /// Scale of the bit-grid cells and their labels (1.0 = normal font size)
+    grid_zoom: f32,
+    /// Last (id, bus) taken from the message list, so a list selection only lands once
+    list_selection: Option<(u32, u8)>,
+
+    // Go-to-ID box
+    goto_id_input: String,
+    goto_request: Option<u32>,
+    goto_error: Option<String>,
+    /// Signal-name search across all DBC messages
+    signal_search: String,
+    /// Signal picked from the search, highlighted in its message's signal list
+    found_signal: Option<(u32, String)>,
 
     // Signal creation dialog
     show_create_dialog: bool,
@@ -126,6 +237,13 @@ pub struct BitVisualizerWindow {
     new_signal_name: String,
     new_signal_is_signed: bool,
     new_signal_is_little_endian: bool,
+    /// Byte order left to the guess from the log (falls back to `new_signal_is_little_endian`)
+    new_signal_auto_order: bool,
+    byte_order_guess: ByteOrderGuess,
+    /// Frames of the create/edit dialog's message, for the byte-order guess and scaling fit
+    frame_sample_request: Option<(u32, u8)>,
+    dialog_frames: Vec<CanData>,
+    scaling_fit: ScalingFit,
     new_signal_factor: String,
     new_signal_offset: String,
     new_signal_unit: String,
@@ -148,11 +266,31 @@ pub struct BitVisualizerWindow {
     edit_new_val_desc: String,
     edit_original_signal_name: String,
 
+    /// Signals created or edited in this session, by message ID (for "export new signals")
+    session_signals: HashMap<u32, HashSet<String>>,
+    /// The user's own notes on signals, kept outside the DBC
+    notes: SignalNotes,
+    /// Display-unit override per decoded signal, by (message ID, signal name)
+    display_units: HashMap<(u32, String), &'static UnitConversion>,
+    /// Checksum signal and algorithm per message ID, used to flag anomalous frames
+    checksums: HashMap<u32, ChecksumCheck>,
+    checksums_changed: bool,
+    /// A signal was created, edited or deleted in the DBC since the last check
+    dbc_changed: bool,
+    /// Outcome of the last "Copy signal to message": (quadrant, source message ID, text, is a warning)
+    signal_copy_status: Option<(usize, u32, String, bool)>,
+
     // Callbacks
     on_signal_created: RefCell<Option<SignalCreatedCallback>>,
     on_toggle_chart: RefCell<Option<ToggleChartCallback>>,
     charted_signals: RefCell<Vec<String>>,
+    /// User-picked signal colors, shared with the charts
+    custom_colors: CustomColors,
+    /// Palette signal colors are hashed into, matching the charts
+    color_palette: ColorPalette,
     chart_toggle_request: RefCell<Option<String>>,
+    /// Edited frame to transmit: (id, bus, data)
+    send_request: RefCell<Option<(u32, u8, Vec<u8>)>>,
 }
 
 impl BitVisualizerWindow {
@@ -166,11 +304,25 @@ impl BitVisualizerWindow {
             ],
             focused_quadrant: 0,
             show_signals: true,
+            edit_mode: false,
+            bit_numbering: BitNumbering::default(),
+            grid_zoom: 1.0,
+            list_selection: None,
+            goto_id_input: String::new(),
+            goto_request: None,
+            goto_error: None,
+            signal_search: String::new(),
+            found_signal: None,
             show_create_dialog: false,
             create_quadrant: None,
             new_signal_name: String::new(),
             new_signal_is_signed: false,
             new_signal_is_little_endian: true,
+            new_signal_auto_order: false,
+            byte_order_guess: ByteOrderGuess::Pending,
+            frame_sample_request: None,
+            dialog_frames: Vec::new(),
+            scaling_fit: ScalingFit::default(),
             new_signal_factor: String::from("1"),
             new_signal_offset: String::from("0"),
             new_signal_unit: String::new(),
@@ -190,10 +342,20 @@ impl BitVisualizerWindow {
             edit_new_val_value: String::new(),
             edit_new_val_desc: String::new(),
             edit_original_signal_name: String::new(),
+            session_signals: HashMap::new(),
+            notes: SignalNotes::default(),
+            display_units: HashMap::new(),
+            checksums: HashMap::new(),
+            checksums_changed: false,
+            dbc_changed: false,
+            signal_copy_status: None,
             on_signal_created: RefCell::new(None),
             on_toggle_chart: RefCell::new(None),
             charted_signals: RefCell::new(Vec::new()),
+            custom_colors: CustomColors::new(),
+            color_palette: ColorPalette::default(),
             chart_toggle_request: RefCell::new(None),
+            send_request: RefCell::new(None),
         }
     }
 
@@ -212,15 +374,26 @@ impl BitVisualizerWindow {
         *self.on_toggle_chart.borrow_mut() = Some(Box::new(callback));
     }
 
+    /// Palette to color signals with, the same one the charts use
+    pub fn set_color_palette(&mut self, palette: ColorPalette) {
+        self.color_palette = palette;
+    }
+
+    /// Colors picked in the chart legend, so signals here match their chart series
+    pub fn set_custom_colors(&mut self, colors: &CustomColors) {
+        if self.custom_colors != *colors {
+            self.custom_colors.clone_from(colors);
+        }
+    }
+
     /// Update the list of charted signals
     pub fn set_charted_signals(&self, signals: Vec<String>) {
         *self.charted_signals.borrow_mut() = signals;
     }
 
-    /// Check if a signal on the given bus is charted
-    fn is_signal_charted(&self, signal_name: &str, bus: u8) -> bool {
-        let key = format!("{}@bus{}", signal_name, bus);
-        self.charted_signals.borrow().contains(&key)
+    /// Check if a message's signal on the given bus is charted
+    fn is_signal_charted(&self, signal_name: &str, msg_id: u32, bus: u8) -> bool {
+        self.charted_signals.borrow().contains(&signal_key(signal_name, msg_id, bus))
     }
 
     /// Check if there's a pending chart toggle request and return the signal name
@@ -229,9 +402,33 @@ impl BitVisualizerWindow {
     }
 
     /// Request to toggle a signal on the chart
-    fn request_chart_toggle(&self, signal_name: String, bus: u8) {
-        let key = format!("{}@bus{}", signal_name, bus);
-        *self.chart_toggle_request.borrow_mut() = Some(key);
+    fn request_chart_toggle(&self, signal_name: &str, msg_id: u32, bus: u8) {
+        *self.chart_toggle_request.borrow_mut() = Some(signal_key(signal_name, msg_id, bus));
+    }
+
+    /// Checksum hooks set from the signal context menu, by message ID
+    pub fn checksums(&self) -> &HashMap<u32, ChecksumCheck> {
+        &self.checksums
+    }
+
+    /// Check whether a checksum hook was set or cleared since the last call
+    pub fn take_checksums_changed(&mut self) -> bool {
+        std::mem::take(&mut self.checksums_changed)
+    }
+
+    /// Use these signal notes (loaded from the config dir)
+    pub fn set_signal_notes(&mut self, notes: SignalNotes) {
+        self.notes = notes;
+    }
+
+    /// Check whether a signal was created, edited or deleted since the last call
+    pub fn take_dbc_changed(&mut self) -> bool {
+        std::mem::take(&mut self.dbc_changed)
+    }
+
+    /// Take a pending "Send" of an edited frame: (id, bus, data)
+    pub fn take_send_request(&self) -> Option<(u32, u8, Vec<u8>)> {
+        self.send_request.borrow_mut().take()
     }
 
     /// Get the currently selected (message_id, bus) from the focused quadrant
@@ -244,8 +441,110 @@ impl BitVisualizerWindow {
     }
 
     /// Set focused quadrant's message (called when user selects from message list)
+    ///
+    /// Called every frame with the list's selection; only a new selection (or a
+    /// newly focused quadrant) changes what's shown, so a go-to-ID isn't overwritten.
     pub fn set_message(&mut self, id: u32, bus: u8, data: &[u8]) {
-        self.quadrants[self.focused_quadrant].update_message(id, bus, data);
+        if self.list_selection == Some((id, bus)) {
+            return;
+        }
+        self.list_selection = Some((id, bus));
+        let quadrant = &mut self.quadrants[self.focused_quadrant];
+        if !quadrant.pinned {
+            quadrant.update_message(id, bus, data);
+        }
+    }
+
+    pub fn has_session_signals(&self) -> bool {
+        self.session_signals.values().any(|names| !names.is_empty())
+    }
+
+    /// Forget which signals were created this session (the DBC they lived in was dropped)
+    pub fn clear_session_signals(&mut self) {
+        self.session_signals.clear();
+    }
+
+    /// The signals created or edited this session, as a standalone DBC
+    pub fn session_dbc(&self, dbc: &DbcFile) -> DbcFile {
+        dbc.filtered(|msg, signal| {
+            self.session_signals.get(&msg.id).is_some_and(|names| names.contains(&signal.name))
+        })
+    }
+
+    /// Take a pending go-to-ID lookup; answer with `show_goto_frame` or `goto_not_found`
+    pub fn take_goto_request(&mut self) -> Option<u32> {
+        self.goto_request.take()
+    }
+
+    /// Show a looked-up frame in the focused quadrant
+    pub fn show_goto_frame(&mut self, msg: &CanMessage) {
+        self.goto_error = None;
+        self.quadrants[self.focused_quadrant].update_message(msg.id, msg.bus, &msg.data);
+    }
+
+    pub fn goto_not_found(&mut self, id: u32) {
+        self.goto_error = Some(format!("0x{:03X} is not in the log", id));
+    }
+
+    /// Take a pending request for the log's frames of (id, bus); answer with `set_dialog_frames`
+    pub fn take_frame_sample_request(&mut self) -> Option<(u32, u8)> {
+        self.frame_sample_request.take()
+    }
+
+    /// Frames of the open dialog's message, in log order: guesses the create dialog's
+    /// byte order and backs the scaling fit of either dialog
+    pub fn set_dialog_frames(&mut self, frames: impl Iterator<Item = CanData>) {
+        self.dialog_frames = frames.collect();
+        if !self.show_create_dialog {
+            return;
+        }
+        let Some((first, last)) = self.create_quadrant.and_then(|q| self.selection_span(q)) else {
+            return;
+        };
+        let sample = self.dialog_frames.iter().take(BYTE_ORDER_SAMPLE_FRAMES).map(|d| d.as_slice());
+        let (order, sampled) = guess_byte_order(sample, first, last);
+        self.byte_order_guess = match order {
+            Some(order) => ByteOrderGuess::Suggested(order, sampled),
+            None => ByteOrderGuess::Undecided(sampled),
+        };
+    }
+
+    /// Physical range inputs and "Fit" button; a fit overwrites `factor` and `offset`
+    fn render_scaling_fit(&mut self, ui: &Ui, layout: (u8, u8, ByteOrder), signed: bool, factor: &mut String, offset: &mut String) {
+        ui.text("Fit to range:");
+        ui.same_line();
+        ui.set_next_item_width(70.0);
+        ui.input_text("##fit_min", &mut self.scaling_fit.physical_min).hint("min").build();
+        ui.same_line();
+        ui.set_next_item_width(70.0);
+        ui.input_text("##fit_max", &mut self.scaling_fit.physical_max).hint("max").build();
+        ui.same_line();
+        if ui.button("Fit factor/offset") {
+            self.scaling_fit.status = Some(match self.fit_scaling(layout, signed) {
+                Ok((fit_factor, fit_offset, (lo, hi))) => {
+                    *factor = fit_factor.to_string();
+                    *offset = fit_offset.to_string();
+                    format!("Raw {}..{} over {} frames", lo, hi, self.dialog_frames.len())
+                }
+                Err(e) => e,
+            });
+        }
+        if let Some(status) = &self.scaling_fit.status {
+            ui.text_disabled(status);
+        }
+    }
+
+    /// Factor, offset and the observed raw range mapping the dialog's signal onto the entered physical range
+    fn fit_scaling(&self, (start_bit, bit_length, byte_order): (u8, u8, ByteOrder), signed: bool) -> Result<(f64, f64, (f64, f64)), String> {
+        let physical = (self.scaling_fit.physical_min.trim().parse::<f64>(), self.scaling_fit.physical_max.trim().parse::<f64>());
+        let (Ok(min), Ok(max)) = physical else {
+            return Err("Enter the expected physical min and max".to_string());
+        };
+        let raw = raw_range(&self.dialog_frames, start_bit, bit_length, byte_order, signed)
+            .ok_or("Signal doesn't appear in the log")?;
+        let (factor, offset) = fit_factor_offset(raw, (min, max))
+            .ok_or_else(|| format!("Raw value is always {}: nothing to fit", raw.0))?;
+        Ok((factor, offset, raw))
     }
 
     /// Update data for any quadrant displaying this (id, bus) - for playback of all quadrants
@@ -288,6 +587,20 @@ impl BitVisualizerWindow {
         }
     }
 
+    pub fn grid_zoom(&self) -> f32 {
+        self.grid_zoom
+    }
+
+    pub fn set_grid_zoom(&mut self, zoom: f32) {
+        self.grid_zoom = zoom.clamp(GRID_ZOOM_MIN, GRID_ZOOM_MAX);
+    }
+
+    /// Zoom one step in (`steps` > 0) or out, snapping to the step grid
+    fn step_grid_zoom(&mut self, steps: i32) {
+        let snapped = (self.grid_zoom / GRID_ZOOM_STEP).round() * GRID_ZOOM_STEP;
+        self.set_grid_zoom(snapped + steps as f32 * GRID_ZOOM_STEP);
+    }
+
     pub fn render(&mut self, ui: &Ui, dbc: &mut DbcFile, is_open: &mut bool) {
         ui.window("Bit Visualizer")
             .size([900.0, 700.0], Condition::FirstUseEver)
@@ -304,12 +617,39 @@ impl BitVisualizerWindow {
         if self.show_edit_dialog {
             self.render_edit_dialog(ui, dbc);
         }
+
+        // The sample can hold every frame of a message: drop it once both dialogs are closed
+        if !self.show_create_dialog && !self.show_edit_dialog && self.dialog_frames.capacity() > 0 {
+            self.dialog_frames = Vec::new();
+        }
     }
 
     fn render_content(&mut self, ui: &Ui, dbc: &mut DbcFile) {
         ui.checkbox("Show Signal Colors", &mut self.show_signals);
         ui.same_line();
+        ui.checkbox("Edit Frame", &mut self.edit_mode);
+        if ui.is_item_hovered() {
+            ui.tooltip_text("Click bits to toggle them and compose a frame to send or copy");
+        }
+        ui.same_line();
+        ui.set_next_item_width(130.0);
+        if let Some(_combo) = ui.begin_combo("##bit_numbering", self.bit_numbering.label()) {
+            for numbering in BitNumbering::ALL {
+                if ui.selectable_config(numbering.label()).selected(self.bit_numbering == numbering).build() {
+                    self.bit_numbering = numbering;
+                }
+            }
+        }
+        if ui.is_item_hovered() {
+            ui.tooltip_text("Bit numbering shown in the grid. New signals always use DBC start bits.");
+        }
+        ui.same_line();
+        self.render_grid_zoom(ui);
+        ui.same_line();
+        self.render_goto_id(ui);
+        ui.same_line();
         ui.text_colored([0.6, 0.6, 0.6, 1.0], "Click a quadrant to focus it, then select a message from the list");
+        self.render_signal_search(ui, dbc);
         ui.separator();
 
         // 2x2 layout: each quadrant gets ~half width and half height
@@ -326,6 +666,7 @@ impl BitVisualizerWindow {
                 ui.child_window(format!("quad_{}", idx))
                     .size([quad_w, quad_h])
                     .border(true)
+                    .horizontal_scrollbar(true)
                     .build(|| {
                         self.render_quadrant(ui, dbc, idx);
                     });
@@ -342,11 +683,12 @@ impl BitVisualizerWindow {
         // Header: click to focus, message info, clear/reset
         if let Some(id) = q.selected_message_id {
             let bus = q.selected_bus.unwrap_or(0);
-            let header = format!("{}. 0x{:03X} [Bus {}]", idx + 1, id, bus);
+            let header = format!("{}. 0x{:03X} [Bus {}]{}", idx + 1, id, bus, if q.pinned { " (pinned)" } else { "" });
             let header_color = if is_focused { [0.3, 0.6, 0.9, 1.0] } else { [0.6, 0.6, 0.6, 1.0] };
             let _tok = ui.push_style_color(StyleColor::Text, header_color);
             if ui.selectable(&format!("{}##qh{}", header, idx)) {
                 self.focused_quadrant = idx;
+                self.list_selection = None;
             }
             drop(_tok);
             if ui.is_item_hovered() {
@@ -355,6 +697,13 @@ impl BitVisualizerWindow {
                 });
             }
             ui.same_line();
+            if ui.small_button(format!("{}##pin{}", if q.pinned { "Unpin" } else { "Pin" }, idx)) {
+                q.pinned = !q.pinned;
+            }
+            if ui.is_item_hovered() {
+                ui.tooltip_text("Keep this message here while browsing the list for others");
+            }
+            ui.same_line();
             if ui.small_button(&format!("Clear##q{}", idx)) {
                 q.clear();
             }
@@ -362,15 +711,23 @@ impl BitVisualizerWindow {
             if ui.small_button(&format!("Reset##q{}", idx)) {
                 q.reset_activity();
             }
+            ui.same_line();
+            let dlc_color = if q.dlc < 8 { [0.9, 0.7, 0.3, 1.0] } else { [0.6, 0.6, 0.6, 1.0] };
+            ui.text_colored(dlc_color, format!("DLC: {}", q.dlc));
             if let Some(msg_def) = dbc.get_message(id) {
                 ui.same_line();
-                ui.text_colored([0.5, 0.8, 0.5, 1.0], &format!("({})", msg_def.name));
+                if msg_def.has_transmitter() {
+                    ui.text_colored([0.5, 0.8, 0.5, 1.0], format!("({} from {})", msg_def.name, msg_def.transmitter));
+                } else {
+                    ui.text_colored([0.5, 0.8, 0.5, 1.0], &format!("({})", msg_def.name));
+                }
             }
         } else {
             let label = format!("{}. 0x--- [--]  (click to focus, select message)", idx + 1);
             let _tok = ui.push_style_color(StyleColor::Text, [0.5, 0.5, 0.5, 1.0]);
             if ui.selectable(&label) {
                 self.focused_quadrant = idx;
+                self.list_selection = None;
             }
             drop(_tok);
         }
@@ -383,6 +740,9 @@ impl BitVisualizerWindow {
         }
 
         self.render_bit_grid_quadrant(ui, dbc, idx);
+        if self.edit_mode {
+            self.render_edited_frame(ui, idx);
+        }
         ui.separator();
         self.render_decoded_signals_quadrant(ui, dbc, idx);
     }
@@ -393,10 +753,28 @@ impl BitVisualizerWindow {
         let mut bit_rects: Vec<(usize, [f32; 2], [f32; 2])> = Vec::new();
         let mut header_positions: Vec<[f32; 2]> = Vec::new();
 
+        let dlc = self.quadrants[idx].dlc;
+        let edit_mode = self.edit_mode;
+        let frame = self.quadrants[idx].display_data(edit_mode);
+        let received = self.quadrants[idx].current_data;
+
+        // Cells are sized by their label text, so scaling the font scales the grid; overlays
+        // and the header row are placed from the drawn item rects and follow along
+        let zoom = self.grid_zoom;
+        if zoom > 1.0 {
+            // Keep the column headers (drawn above byte 0) clear of the quadrant header
+            ui.dummy([0.0, ui.text_line_height() * (zoom - 1.0)]);
+        }
+        ui.set_window_font_scale(zoom);
+
         for byte_idx in 0..8 {
-            let byte_val = self.quadrants[idx].current_data[byte_idx];
+            let byte_val = frame[byte_idx];
+            // Bytes past the DLC aren't in the frame: draw them dimmed and hatched, not as zeros
+            let is_padding = byte_idx >= dlc;
 
+            let _byte_label_tok = is_padding.then(|| ui.push_style_color(StyleColor::Text, [0.4, 0.4, 0.4, 1.0]));
             ui.text(format!("B{}:", byte_idx));
+            drop(_byte_label_tok);
             ui.same_line();
 
             for bit_idx in (0..8).rev() {
@@ -417,9 +795,14 @@ impl BitVisualizerWindow {
                     }
                 }
 
+                if is_padding {
+                    bg_color = [bg_color[0] * 0.35, bg_color[1] * 0.35, bg_color[2] * 0.35, 1.0];
+                }
+
                 let is_selected = selection_bits.contains(&abs_bit_pos);
                 let indicator = if is_msb { "M" } else if is_lsb { "L" } else { " " };
-                let button_label = format!("{}{}##q{}b{}", bit_val, indicator, idx, abs_bit_pos);
+                let value_char = if is_padding { "-".to_string() } else { bit_val.to_string() };
+                let button_label = format!("{}{}##q{}b{}", value_char, indicator, idx, abs_bit_pos);
 
                 let _color_token = ui.push_style_color(StyleColor::Button, bg_color);
                 let _hover_token = ui.push_style_color(StyleColor::ButtonHovered, [
@@ -432,30 +815,66 @@ impl BitVisualizerWindow {
                 let min = ui.item_rect_min();
                 let max = [min[0] + ui.item_rect_size()[0], min[1] + ui.item_rect_size()[1]];
                 bit_rects.push((abs_bit_pos, min, max));
+                if is_padding {
+                    let draw_list = ui.get_window_draw_list();
+                    draw_list.add_line([min[0], max[1]], [max[0], min[1]], [0.5, 0.5, 0.5, 0.5]).build();
+                    // A signal mapped onto missing bytes can't be decoded from this frame
+                    if signal_name.is_some() {
+                        draw_list.add_rect(min, max, [0.9, 0.3, 0.3, 1.0]).build();
+                    }
+                }
                 if byte_idx == 0 && header_positions.len() < 8 {
                     header_positions.push([(min[0] + max[0]) / 2.0, min[1]]);
                 }
-                if is_selected {
+                if is_selected && !edit_mode {
                     let draw_list = ui.get_window_draw_list();
-                    draw_list.add_rect(min, max, [1.0, 1.0, 0.0, 1.0]).thickness(2.0).build();
+                    draw_list.add_rect(min, max, [1.0, 1.0, 0.0, 1.0]).thickness(2.0 * zoom).build();
+                }
+                // Bits that differ from the received frame
+                let is_edited = edit_mode && ((byte_val ^ received[byte_idx]) >> bit_idx) & 1 == 1;
+                if is_edited {
+                    let draw_list = ui.get_window_draw_list();
+                    draw_list.add_rect(min, max, [1.0, 0.4, 1.0, 1.0]).thickness(2.0 * zoom).build();
                 }
                 if ui.is_item_hovered() {
-                    if ui.is_mouse_clicked(imgui::MouseButton::Left) {
+                    if edit_mode {
+                        if !is_padding && ui.is_mouse_clicked(imgui::MouseButton::Left) {
+                            self.quadrants[idx].toggle_edit_bit(byte_idx, bit_idx);
+                        }
+                    } else if ui.is_mouse_clicked(imgui::MouseButton::Left) {
                         self.quadrants[idx].selection_start = Some(abs_bit_pos);
                         self.quadrants[idx].selection_end = Some(abs_bit_pos);
                         self.quadrants[idx].is_dragging = true;
                     }
                     let activity_val = self.get_bit_activity_quadrant(idx, abs_bit_pos);
                     let sig_name = signal_name.clone();
+                    let note = sig_name.as_deref()
+                        .zip(self.quadrants[idx].selected_message_id)
+                        .and_then(|(name, id)| self.notes.get(id, name))
+                        .map(str::to_string);
                     let dbc_bit = display_pos_to_dbc_bit(abs_bit_pos);
+                    let numbering = self.bit_numbering;
                     ui.tooltip(|| {
-                        ui.text(format!("DBC bit {} (byte {}, bit {})", dbc_bit, byte_idx, bit_idx));
-                        ui.text(format!("Value: {}", bit_val));
+                        match numbering {
+                            BitNumbering::Dbc => ui.text(format!("DBC bit {} (byte {}, bit {})", dbc_bit, byte_idx, bit_idx)),
+                            _ => ui.text(format!("Bit {} (DBC bit {})", numbering.bit_name(abs_bit_pos), dbc_bit)),
+                        }
+                        if is_padding {
+                            ui.text_colored([0.9, 0.7, 0.3, 1.0], format!("Not in frame (DLC {})", dlc));
+                        } else {
+                            ui.text(format!("Value: {}", bit_val));
+                            if is_edited {
+                                ui.text_colored([1.0, 0.4, 1.0, 1.0], "Edited");
+                            }
+                        }
                         if let Some(ref name) = sig_name {
                             ui.separator();
                             ui.text_colored([0.5, 0.8, 1.0, 1.0], format!("Signal: {}", name));
                             if is_msb { ui.text_colored([0.9, 0.9, 0.5, 1.0], "(MSB)"); }
                             if is_lsb { ui.text_colored([0.9, 0.9, 0.5, 1.0], "(LSB)"); }
+                            if let Some(ref note) = note {
+                                ui.text_colored([0.8, 0.8, 0.6, 1.0], format!("Note: {}", note));
+                            }
                         }
                         if activity_val > 0.0 {
                             ui.text_colored([1.0, 0.7, 0.4, 1.0], format!("Activity: {:.0}%", activity_val * 100.0));
@@ -465,11 +884,15 @@ impl BitVisualizerWindow {
                 if bit_idx > 0 { ui.same_line(); }
             }
             ui.same_line();
-            ui.text_colored([0.6, 0.6, 0.6, 1.0], format!("{:02X}", byte_val));
+            if is_padding {
+                ui.text_colored([0.4, 0.4, 0.4, 1.0], "--");
+            } else {
+                ui.text_colored([0.6, 0.6, 0.6, 1.0], format!("{:02X}", byte_val));
+            }
             if byte_idx == 0 && !header_positions.is_empty() {
                 let draw_list = ui.get_window_draw_list();
                 for (i, pos) in header_positions.iter().enumerate() {
-                    let bit = 7 - i;
+                    let bit = self.bit_numbering.column_header(i);
                     let text = format!("{}", bit);
                     let text_width = ui.calc_text_size(&text)[0];
                     let text_y = pos[1] - ui.text_line_height_with_spacing();
@@ -477,6 +900,7 @@ impl BitVisualizerWindow {
                 }
             }
         }
+        ui.set_window_font_scale(1.0);
 
         if self.quadrants[idx].is_dragging {
             let mouse_pos = ui.io().mouse_pos;
@@ -503,7 +927,12 @@ impl BitVisualizerWindow {
                 let max_dbc = display_pos_to_dbc_bit(max_disp);
                 let (min_bit, max_bit) = (min_dbc.min(max_dbc), min_dbc.max(max_dbc));
                 let bit_count = max_bit - min_bit + 1;
-                ui.text_colored([1.0, 1.0, 0.0, 1.0], format!("DBC bits {}-{} ({} bits)", min_bit, max_bit, bit_count));
+                let label = match self.bit_numbering {
+                    BitNumbering::Dbc => format!("DBC bits {}-{} ({} bits)", min_bit, max_bit, bit_count),
+                    numbering => format!("Bits {}-{} (DBC {}-{}, {} bits)",
+                        numbering.bit_name(min_disp), numbering.bit_name(max_disp), min_bit, max_bit, bit_count),
+                };
+                ui.text_colored([1.0, 1.0, 0.0, 1.0], label);
                 ui.same_line();
                 if ui.small_button(&format!("Clear##sel{}", idx)) {
                     self.quadrants[idx].selection_start = None;
@@ -513,6 +942,127 @@ impl BitVisualizerWindow {
         }
     }
 
+    /// -/+ buttons scaling the bit grid; Ctrl+wheel over the window does the same
+    fn render_grid_zoom(&mut self, ui: &Ui) {
+        if ui.small_button("-##grid_zoom") {
+            self.step_grid_zoom(-1);
+        }
+        ui.same_line();
+        ui.text(format!("{:.0}%", self.grid_zoom * 100.0));
+        if ui.is_item_hovered() {
+            ui.tooltip_text("Bit grid zoom (Ctrl+wheel). Click to reset.");
+            if ui.is_mouse_clicked(imgui::MouseButton::Left) {
+                self.grid_zoom = 1.0;
+            }
+        }
+        ui.same_line();
+        if ui.small_button("+##grid_zoom") {
+            self.step_grid_zoom(1);
+        }
+        let io = ui.io();
+        if io.key_ctrl && io.mouse_wheel != 0.0 && ui.is_window_hovered_with_flags(imgui::WindowHoveredFlags::CHILD_WINDOWS) {
+            self.step_grid_zoom(io.mouse_wheel.signum() as i32);
+        }
+    }
+
+    /// ID box that loads that ID's latest frame into the focused quadrant
+    fn render_goto_id(&mut self, ui: &Ui) {
+        ui.set_next_item_width(90.0);
+        let entered = ui.input_text("##goto_id", &mut self.goto_id_input)
+            .hint("ID (hex)")
+            .enter_returns_true(true)
+            .build();
+        ui.same_line();
+        if ui.small_button("Go##goto_id") || entered {
+            match parse_can_id(&self.goto_id_input) {
+                Some(id) => self.goto_request = Some(id),
+                None => self.goto_error = Some(format!("'{}' is not a CAN ID", self.goto_id_input.trim())),
+            }
+        }
+        if let Some(ref error) = self.goto_error {
+            ui.same_line();
+            ui.text_colored([0.9, 0.4, 0.4, 1.0], error);
+        }
+    }
+
+    /// Search box matching signal names in every message; clicking a match loads
+    /// that message into the focused quadrant and highlights the signal
+    fn render_signal_search(&mut self, ui: &Ui, dbc: &DbcFile) {
+        ui.set_next_item_width(200.0);
+        ui.input_text("##find_signal", &mut self.signal_search)
+            .hint("Find signal in any message")
+            .build();
+        if self.signal_search.trim().is_empty() {
+            return;
+        }
+        ui.same_line();
+        if ui.small_button("x##find_signal") {
+            self.signal_search.clear();
+            return;
+        }
+
+        let matches = dbc.find_signals(&self.signal_search);
+        if matches.is_empty() {
+            ui.same_line();
+            ui.text_colored([0.6, 0.6, 0.6, 1.0], "No matching signals");
+            return;
+        }
+        let height = (matches.len() as f32 * ui.text_line_height_with_spacing()).min(120.0) + 8.0;
+        let mut picked = None;
+        ui.child_window("signal_search_results").size([0.0, height]).build(|| {
+            for (msg, signal) in &matches {
+                let label = format!("{}##find_{}_{}", signal.name, msg.id, signal.name);
+                // Sized to the name so the message can follow on the same line
+                if ui.selectable_config(&label).size([ui.calc_text_size(&signal.name)[0], 0.0]).build() {
+                    picked = Some((msg.id, signal.name.clone()));
+                }
+                ui.same_line();
+                ui.text_colored([0.6, 0.6, 0.6, 1.0], format!("{} (0x{:03X})", msg.name, msg.id));
+            }
+        });
+        if let Some((id, name)) = picked {
+            self.show_found_signal(id, name);
+        }
+    }
+
+    /// Jump to the message carrying a searched-for signal
+    fn show_found_signal(&mut self, id: u32, name: String) {
+        self.goto_request = Some(id);
+        self.goto_id_input = format!("{:03X}", id);
+        self.found_signal = Some((id, name));
+        self.signal_search.clear();
+    }
+
+    /// Edited frame as hex with Copy / Send / Revert
+    fn render_edited_frame(&mut self, ui: &Ui, idx: usize) {
+        let q = &self.quadrants[idx];
+        let (Some(id), bus) = (q.selected_message_id, q.selected_bus.unwrap_or(0)) else {
+            return;
+        };
+        let data = q.display_data(true)[..q.dlc].to_vec();
+        let hex = data.iter().map(|b| format!("{:02X}", b)).collect::<Vec<_>>().join(" ");
+        let is_edited = q.edited_data.is_some();
+
+        ui.text_colored([1.0, 0.4, 1.0, 1.0], format!("Edited: {}", if hex.is_empty() { "(empty)" } else { &hex }));
+        ui.same_line();
+        if ui.small_button(format!("Copy##edit_copy{}", idx)) {
+            ui.set_clipboard_text(&hex);
+        }
+        ui.same_line();
+        if ui.small_button(format!("Send##edit_send{}", idx)) {
+            *self.send_request.borrow_mut() = Some((id, bus, data));
+        }
+        if ui.is_item_hovered() {
+            ui.tooltip_text(format!("Send 0x{:03X} on bus {}", id, bus));
+        }
+        if is_edited {
+            ui.same_line();
+            if ui.small_button(format!("Revert##edit_revert{}", idx)) {
+                self.quadrants[idx].edited_data = None;
+            }
+        }
+    }
+
     fn get_bit_activity_quadrant(&self, idx: usize, bit_pos: usize) -> f32 {
         let q = &self.quadrants[idx];
         if q.max_flip_count == 0 { return 0.0; }
@@ -531,7 +1081,19 @@ impl BitVisualizerWindow {
         }
     }
 
+    /// First and last grid positions of a quadrant's bit selection
+    fn selection_span(&self, quadrant: usize) -> Option<(usize, usize)> {
+        let q = &self.quadrants[quadrant];
+        let (s, e) = (q.selection_start?, q.selection_end?);
+        Some((s.min(e), s.max(e)))
+    }
+
     fn open_create_dialog(&mut self, quadrant: usize) {
+        let q = &self.quadrants[quadrant];
+        self.frame_sample_request = q.selected_message_id.zip(q.selected_bus);
+        self.dialog_frames.clear();
+        self.byte_order_guess = ByteOrderGuess::Pending;
+        self.scaling_fit = ScalingFit::default();
         self.signal_counter += 1;
         self.new_signal_name = format!("NEW_SIGNAL_{}", self.signal_counter);
         self.new_signal_factor = String::from("1");
@@ -542,6 +1104,10 @@ impl BitVisualizerWindow {
     }
 
     fn open_edit_dialog(&mut self, quadrant: usize, signal_idx: usize, signal: &DbcSignal, dbc: &DbcFile) {
+        let q = &self.quadrants[quadrant];
+        self.frame_sample_request = q.selected_message_id.zip(q.selected_bus);
+        self.dialog_frames.clear();
+        self.scaling_fit = ScalingFit::default();
         self.edit_quadrant = Some(quadrant);
         self.editing_signal_idx = Some(signal_idx);
         self.editing_signal_name = signal.name.clone();
@@ -567,21 +1133,21 @@ impl BitVisualizerWindow {
             Some(q) => q,
             None => return,
         };
-        let q = &self.quadrants[quadrant];
-        let (start_bit, bit_length) = if let (Some(s), Some(e)) = (q.selection_start, q.selection_end) {
-            let (min_disp, max_disp) = if s <= e { (s, e) } else { (e, s) };
-            let min_dbc = display_pos_to_dbc_bit(min_disp);
-            let max_dbc = display_pos_to_dbc_bit(max_disp);
-            let start = min_dbc.min(max_dbc);
-            let end = min_dbc.max(max_dbc);
-            (start as u8, (end - start + 1) as u8)
-        } else {
-            (0, 1)
-        };
+        let span = self.selection_span(quadrant);
+        let layout = |order: ByteOrder| span.map_or((0, 1), |(first, last)| selection_layout(first, last, order));
 
         let mut dialog_open = self.show_create_dialog;
         let mut name = self.new_signal_name.clone();
         let mut is_little_endian = self.new_signal_is_little_endian;
+        let mut auto_order = self.new_signal_auto_order;
+        let guess = self.byte_order_guess;
+        let suggested = match guess {
+            ByteOrderGuess::Suggested(order, _) => Some(order),
+            _ => None,
+        };
+        let manual_order = if is_little_endian { ByteOrder::Intel } else { ByteOrder::Motorola };
+        let byte_order = if auto_order { suggested.unwrap_or(manual_order) } else { manual_order };
+        let (start_bit, bit_length) = layout(byte_order);
         let mut is_signed = self.new_signal_is_signed;
         let mut factor = self.new_signal_factor.clone();
         let mut offset = self.new_signal_offset.clone();
@@ -605,10 +1171,24 @@ impl BitVisualizerWindow {
                 ui.separator();
 
                 ui.text("Byte order:");
-                if ui.selectable_config(format!("Intel (little-endian){}", if is_little_endian { " *" } else { "" }))
-                    .selected(is_little_endian).build() { is_little_endian = true; }
-                if ui.selectable_config(format!("Motorola (big-endian){}", if !is_little_endian { " *" } else { "" }))
-                    .selected(!is_little_endian).build() { is_little_endian = false; }
+                let mark = |order: ByteOrder| if suggested == Some(order) { " (suggested)" } else { "" };
+                let intel = !auto_order && is_little_endian;
+                let motorola = !auto_order && !is_little_endian;
+                if ui.selectable_config(format!("Intel (little-endian){}{}", mark(ByteOrder::Intel), if intel { " *" } else { "" }))
+                    .selected(intel).build() { is_little_endian = true; auto_order = false; }
+                if ui.selectable_config(format!("Motorola (big-endian){}{}", mark(ByteOrder::Motorola), if motorola { " *" } else { "" }))
+                    .selected(motorola).build() { is_little_endian = false; auto_order = false; }
+                if ui.selectable_config(format!("Auto (guess from log){}", if auto_order { " *" } else { "" }))
+                    .selected(auto_order).build() { auto_order = true; }
+                match guess {
+                    ByteOrderGuess::Pending => ui.text_disabled("Sampling the log..."),
+                    ByteOrderGuess::Suggested(order, frames) => ui.text_disabled(format!(
+                        "{} decodes smoother over {} frames", if order == ByteOrder::Intel { "Intel" } else { "Motorola" }, frames
+                    )),
+                    ByteOrderGuess::Undecided(frames) => ui.text_disabled(format!(
+                        "No suggestion: both orders read alike over {} frames", frames
+                    )),
+                }
 
                 ui.separator();
 
@@ -624,6 +1204,7 @@ impl BitVisualizerWindow {
                 ui.input_text("##factor", &mut factor).build();
                 ui.text("Offset:"); ui.same_line();
                 ui.input_text("##offset", &mut offset).build();
+                self.render_scaling_fit(ui, (start_bit, bit_length, byte_order), is_signed, &mut factor, &mut offset);
                 ui.text("Unit:"); ui.same_line();
                 ui.input_text("##unit", &mut unit).build();
 
@@ -636,6 +1217,7 @@ impl BitVisualizerWindow {
 
         self.new_signal_name = name;
         self.new_signal_is_little_endian = is_little_endian;
+        self.new_signal_auto_order = auto_order;
         self.new_signal_is_signed = is_signed;
         self.new_signal_factor = factor;
         self.new_signal_offset = offset;
@@ -656,7 +1238,7 @@ impl BitVisualizerWindow {
                             name: self.new_signal_name.clone(),
                             start_bit,
                             bit_length,
-                            byte_order: if self.new_signal_is_little_endian { ByteOrder::Intel } else { ByteOrder::Motorola },
+                            byte_order,
                             value_type: if self.new_signal_is_signed { ValueType::Signed } else { ValueType::Unsigned },
                             factor: factor_val,
                             offset: offset_val,
@@ -674,6 +1256,8 @@ impl BitVisualizerWindow {
                         if let Some(msg) = dbc.get_message_mut(msg_id) {
                             msg.add_signal(signal.clone());
                         }
+                        self.session_signals.entry(msg_id).or_default().insert(signal.name.clone());
+                        self.dbc_changed = true;
 
                         if let Some(ref mut callback) = *self.on_signal_created.borrow_mut() {
                             callback(msg_id, signal);
@@ -758,6 +1342,8 @@ impl BitVisualizerWindow {
                 ui.input_text("##factor", &mut factor).build();
                 ui.text("Offset:"); ui.same_line();
                 ui.input_text("##offset", &mut offset).build();
+                let byte_order = if is_little_endian { ByteOrder::Intel } else { ByteOrder::Motorola };
+                self.render_scaling_fit(ui, (start_bit, bit_length, byte_order), is_signed, &mut factor, &mut offset);
                 ui.text("Unit:"); ui.same_line();
                 ui.input_text("##unit", &mut unit).build();
 
@@ -817,8 +1403,12 @@ impl BitVisualizerWindow {
                         if let Some(msg) = dbc.get_message_mut(msg_id) {
                             if idx < msg.signals.len() {
                                 msg.signals.remove(idx);
+                                self.dbc_changed = true;
                             }
                         }
+                        if let Some(names) = self.session_signals.get_mut(&msg_id) {
+                            names.remove(&self.edit_original_signal_name);
+                        }
                     }
                 }
             }
@@ -843,6 +1433,13 @@ impl BitVisualizerWindow {
                                         msg.signals[idx].unit = if self.edit_unit.is_empty() { None } else { Some(self.edit_unit.clone()) };
                                     }
                                 }
+                                let names = self.session_signals.entry(msg_id).or_default();
+                                names.remove(&self.edit_original_signal_name);
+                                names.insert(self.editing_signal_name.clone());
+                                if self.edit_original_signal_name != self.editing_signal_name {
+                                    self.notes.rename(msg_id, &self.edit_original_signal_name, &self.editing_signal_name);
+                                    self.notes.save();
+                                }
                             }
                         }
                     }
@@ -860,6 +1457,7 @@ impl BitVisualizerWindow {
             } else {
                 dbc.value_tables.remove(&self.editing_signal_name);
             }
+            self.dbc_changed = true;
             self.show_edit_dialog = false;
             self.edit_quadrant = None;
             self.editing_signal_idx = None;
@@ -875,15 +1473,14 @@ impl BitVisualizerWindow {
             if let Some(bus) = q.selected_bus {
                 if let Some(msg_def) = dbc.get_message(id) {
                     for (i, signal) in msg_def.signals.iter().enumerate() {
-                        // Use hash of signal name for consistent color across messages
-                        // This ensures the same signal name always gets the same color
-                        let color_idx = Self::hash_color_index(&signal.name);
+                        // Same color as the signal's chart series
+                        let color = Self::cell_color(signal_color(self.color_palette, &signal.name, id, bus, &self.custom_colors));
                         result.push(SignalInfo {
                             name: signal.name.clone(),
                             start_bit: signal.start_bit,
                             bit_length: signal.bit_length,
                             byte_order: signal.byte_order,
-                            color_idx,
+                            color,
                             bus_id: bus,  // Include bus in signal info
                         });
                     }
@@ -898,7 +1495,7 @@ impl BitVisualizerWindow {
         for signal in signals {
             let display_bits = signal.get_display_positions();
             if display_bits.contains(&display_pos) {
-                let color = SIGNAL_COLORS[signal.color_idx];
+                let color = signal.color;
                 let is_msb = display_pos == signal.get_msb_display_pos();
                 let is_lsb = display_pos == signal.get_lsb_display_pos();
                 return (color, Some(signal.name.clone()), is_msb, is_lsb);
@@ -908,12 +1505,13 @@ impl BitVisualizerWindow {
     }
 
     fn render_decoded_signals_quadrant(&mut self, ui: &Ui, dbc: &mut DbcFile, idx: usize) {
-        let (id, bus, current_data) = {
+        let (id, bus, current_data, dlc) = {
             let q = &self.quadrants[idx];
             (
                 q.selected_message_id,
                 q.selected_bus.unwrap_or(0),
-                q.current_data,
+                q.display_data(self.edit_mode),
+                q.dlc,
             )
         };
         ui.text("Signals:");
@@ -944,6 +1542,13 @@ impl BitVisualizerWindow {
                 // Get charted signals for highlighting (clone to avoid borrow issues)
                 let charted: Vec<String> = self.charted_signals.borrow().clone();
 
+                // Messages a signal can be copied into, and the pick (applied after the list)
+                let copy_targets: Vec<(u32, String)> = dbc.messages.iter()
+                    .filter(|m| m.id != id)
+                    .map(|m| (m.id, m.name.clone()))
+                    .collect();
+                let mut copy_request: Option<(String, u32)> = None;
+
                 // Three columns: Signal name, Value (fixed-width formats, no bounce), Chart button
                 let avail_width = ui.content_region_avail()[0];
                 let chart_btn_width = 45.0;
@@ -956,7 +1561,7 @@ impl BitVisualizerWindow {
                 ui.set_column_width(2, chart_btn_width);
 
                 for (i, (name, start_bit, bit_length, byte_order, value_type, factor, offset, unit)) in signal_data.iter().enumerate() {
-                    let color = SIGNAL_COLORS[i % SIGNAL_COLORS.len()];
+                    let color = Self::cell_color(signal_color(self.color_palette, name, id, bus, &self.custom_colors));
 
                     // Column 0: Color swatch + Signal name (clickable for edit)
                     let _color_token = ui.push_style_color(StyleColor::Button, color);
@@ -966,7 +1571,8 @@ impl BitVisualizerWindow {
 
                     // Signal name - muted color to distinguish from values
                     let _name_color = ui.push_style_color(StyleColor::Text, [0.7, 0.7, 0.75, 1.0]);
-                    let is_selected = self.edit_quadrant == Some(idx) && self.editing_signal_idx == Some(i);
+                    let is_selected = (self.edit_quadrant == Some(idx) && self.editing_signal_idx == Some(i))
+                        || self.found_signal.as_ref().is_some_and(|(found_id, found)| *found_id == id && found == name);
                     let signal = DbcSignal {
                         name: name.clone(),
                         start_bit: *start_bit,
@@ -984,17 +1590,78 @@ impl BitVisualizerWindow {
                         self.open_edit_dialog(idx, i, &signal, dbc);
                     }
                     drop(_name_color);
+                    if let Some(_popup) = ui.begin_popup_context_item() {
+                        if ui.menu_item("Copy signal definition") {
+                            ui.set_clipboard_text(msg_def.signals[i].to_dbc_line());
+                        }
+                        if let Some(_menu) = ui.begin_menu_with_enabled("Copy signal to message", !copy_targets.is_empty()) {
+                            for (target_id, target_name) in &copy_targets {
+                                if ui.menu_item(format!("0x{:03X} {}##copyto", target_id, target_name)) {
+                                    copy_request = Some((name.clone(), *target_id));
+                                }
+                            }
+                        }
+                        let mut note = self.notes.get(id, name).unwrap_or_default().to_string();
+                        ui.set_next_item_width(240.0);
+                        if ui.input_text(format!("##note_q{}s{}", idx, i), &mut note).hint("Note (kept locally, not in the DBC)").build() {
+                            self.notes.set(id, name, &note);
+                        }
+                        if ui.is_item_deactivated_after_edit() {
+                            self.notes.save();
+                        }
+                        let source_unit = unit.as_deref().unwrap_or("");
+                        let conversions = conversions_for(source_unit);
+                        if !conversions.is_empty() {
+                            if let Some(_menu) = ui.begin_menu("Display unit") {
+                                let key = (id, name.clone());
+                                let current = self.display_units.get(&key).copied();
+                                if ui.menu_item_config(source_unit).selected(current.is_none()).build() {
+                                    self.display_units.remove(&key);
+                                }
+                                for conversion in conversions {
+                                    if ui.menu_item_config(conversion.to).selected(current == Some(conversion)).build() {
+                                        self.display_units.insert(key.clone(), conversion);
+                                    }
+                                }
+                            }
+                        }
+                        if !ChecksumCheck::fits(*bit_length) {
+                            ui.menu_item_config(format!("Checksum (max {} bits)", MAX_CHECKSUM_BITS)).enabled(false).build();
+                        } else if let Some(_menu) = ui.begin_menu("Checksum") {
+                            let current = self.checksums.get(&id)
+                                .filter(|c| &c.signal == name)
+                                .map(|c| c.algorithm);
+                            if ui.menu_item_config("Not a checksum").selected(current.is_none()).build() && current.is_some() {
+                                self.checksums.remove(&id);
+                                self.checksums_changed = true;
+                            }
+                            for algorithm in ChecksumAlgorithm::ALL {
+                                if ui.menu_item_config(algorithm.label()).selected(current == Some(algorithm)).build() {
+                                    self.checksums.insert(id, ChecksumCheck { signal: name.clone(), algorithm });
+                                    self.checksums_changed = true;
+                                }
+                            }
+                        }
+                    }
 
                     if ui.is_item_hovered() {
                         ui.tooltip(|| {
-                            ui.text_colored([0.7, 0.7, 0.7, 1.0], "Click to edit");
+                            if let Some(note) = self.notes.get(id, name) {
+                                ui.text(note);
+                                ui.separator();
+                            }
+                            ui.text_colored([0.7, 0.7, 0.7, 1.0], "Click to edit, right-click to copy, add a note, change the display unit or mark as checksum");
                         });
                     }
 
                     ui.next_column();
 
                     // Column 1: Decoded value - fixed width, left-aligned, clipped to prevent overlap
-                    let (value_str, raw_str): (String, Option<String>) = if let Some(raw_value) = extract_bits(
+                    let mut raw_bits: Option<(i64, u64)> = None;
+                    let last_byte = signal_last_byte(*start_bit, *bit_length, *byte_order);
+                    let (value_str, raw_str): (String, Option<String>) = if last_byte >= dlc {
+                        (format!("{:>12}", "beyond DLC"), None)
+                    } else if let Some(raw_value) = extract_bits(
                         &current_data,
                         *start_bit,
                         *bit_length,
@@ -1005,6 +1672,7 @@ impl BitVisualizerWindow {
                         } else {
                             raw_value as i64
                         };
+                        raw_bits = Some((raw_value_i64, raw_value));
 
                         let value_desc = dbc.value_tables.get(name)
                             .and_then(|descriptions| {
@@ -1019,9 +1687,14 @@ impl BitVisualizerWindow {
                             // Enum: pad to 10 chars
                             (format!("{:>10}", desc), Some(raw_fmt))
                         } else {
-                            let physical_value = (raw_value_i64 as f64) * factor + offset;
+                            let mut physical_value = (raw_value_i64 as f64) * factor + offset;
+                            let mut unit = unit.as_deref();
+                            if let Some(conversion) = self.display_units.get(&(id, name.clone())) {
+                                physical_value = conversion.apply(physical_value);
+                                unit = Some(conversion.to);
+                            }
                             // Numeric: pad to 10.3 + 4 for unit = fixed width
-                            let s = if let Some(ref u) = unit {
+                            let s = if let Some(u) = unit {
                                 if u.is_empty() {
                                     format!("{:>12.3}", physical_value)
                                 } else {
@@ -1037,16 +1710,37 @@ impl BitVisualizerWindow {
                     };
 
                     // Draw value + raw directly in column (no child window - was causing overlap)
-                    ui.text_colored([0.45, 0.9, 1.0, 1.0], &value_str);
-                    if let Some(ref r) = raw_str {
-                        ui.same_line();
-                        ui.text_colored([0.5, 0.5, 0.55, 1.0], r);
+                    ui.group(|| {
+                        let value_color = if last_byte >= dlc { [0.9, 0.3, 0.3, 1.0] } else { [0.45, 0.9, 1.0, 1.0] };
+                        ui.text_colored(value_color, &value_str);
+                        if let Some(ref r) = raw_str {
+                            ui.same_line();
+                            ui.text_colored([0.5, 0.5, 0.55, 1.0], r);
+                        }
+                    });
+
+                    if last_byte >= dlc && ui.is_item_hovered() {
+                        ui.tooltip_text(format!("Signal needs byte {} but the frame has only {} (DLC {})", last_byte, dlc, dlc));
+                    }
+
+                    // Raw bit pattern, to line up with the highlighted cells in the grid
+                    if let Some((raw_i64, raw_bits)) = raw_bits {
+                        if ui.is_item_hovered() {
+                            ui.tooltip(|| {
+                                ui.text(format!("Raw: {}", raw_i64));
+                                ui.text(format!("Hex: {}", format_raw_hex(raw_bits, *bit_length)));
+                                ui.text(format!("Bin: {}", format_raw_binary(raw_bits, *bit_length)));
+                                if *value_type == ValueType::Signed {
+                                    ui.text_colored([0.6, 0.6, 0.6, 1.0], format!("{}-bit two's complement", bit_length));
+                                }
+                            });
+                        }
                     }
 
                     ui.next_column();
 
                     // Column 2: Chart button
-                    let is_charted = self.is_signal_charted(name, bus);
+                    let is_charted = self.is_signal_charted(name, id, bus);
                     let btn_color = if is_charted {
                         [0.2, 0.6, 0.3, 0.9]  // Green if charted
                     } else {
@@ -1057,7 +1751,7 @@ impl BitVisualizerWindow {
                     // Use simple ASCII characters that render everywhere
                     let btn_label = if is_charted { "+" } else { "+" };
                     if ui.small_button(&format!("{}##chart{}q{}", btn_label, i, idx)) {
-                        self.request_chart_toggle(name.clone(), bus);
+                        self.request_chart_toggle(name, id, bus);
                     }
                     drop(_chart_color);
 
@@ -1075,6 +1769,16 @@ impl BitVisualizerWindow {
                 }
 
                 ui.columns(1, "", false);
+
+                if let Some((name, target_id)) = copy_request {
+                    self.copy_signal_to(dbc, idx, id, &name, target_id);
+                }
+                if let Some((_, _, text, is_warning)) = self.signal_copy_status.as_ref()
+                    .filter(|(quadrant, from, _, _)| *quadrant == idx && *from == id)
+                {
+                    let color = if *is_warning { [0.9, 0.7, 0.3, 1.0] } else { [0.6, 0.6, 0.6, 1.0] };
+                    ui.text_colored(color, text);
+                }
             } else {
                 ui.text_colored([0.6, 0.6, 0.6, 1.0], "  Not in DBC");
             }
@@ -1083,14 +1787,28 @@ impl BitVisualizerWindow {
         }
     }
 
-    /// Generate a consistent color index for a signal name using a simple hash
-    /// This ensures the same signal name always gets the same color
-    fn hash_color_index(name: &str) -> usize {
-        let mut hash: usize = 5381;
-        for c in name.bytes() {
-            hash = hash.wrapping_mul(33).wrapping_add(c as usize);
-        }
-        hash % SIGNAL_COLORS.len()
+    /// Copy signal `name` of message `from` into message `to`, noting the new name and
+    /// any overlap in the target under quadrant `quadrant`'s signal list
+    fn copy_signal_to(&mut self, dbc: &mut DbcFile, quadrant: usize, from: u32, name: &str, to: u32) {
+        let (text, is_warning) = match dbc.copy_signal(from, name, to) {
+            Ok(copied) => {
+                self.session_signals.entry(to).or_default().insert(copied.name.clone());
+                self.dbc_changed = true;
+                let target = format!("Copied to 0x{:03X} as '{}'", to, copied.name);
+                if copied.overlaps.is_empty() {
+                    (target, false)
+                } else {
+                    (format!("{}, overlapping {}", target, copied.overlaps.join(", ")), true)
+                }
+            }
+            Err(error) => (error, true),
+        };
+        self.signal_copy_status = Some((quadrant, from, text, is_warning));
+    }
+
+    /// Signal colors are drawn translucent so bit values stay readable on top
+    fn cell_color([r, g, b, _]: [f32; 4]) -> [f32; 4] {
+        [r, g, b, 0.7]
     }
 }
 
@@ -1105,6 +1823,25 @@ fn sign_extend(value: u64, bit_length: u8) -> i64 {
     }
 }
 
+/// Raw value in hex, zero-padded to the signal's width (`bit_length` rounded up to a nibble)
+fn format_raw_hex(raw: u64, bit_length: u8) -> String {
+    let digits = (bit_length as usize).div_ceil(4).max(1);
+    format!("0x{:0width$X}", raw, width = digits)
+}
+
+/// Raw value in binary, exactly `bit_length` digits, MSB first, grouped in nibbles from the LSB
+fn format_raw_binary(raw: u64, bit_length: u8) -> String {
+    let len = (bit_length as usize).clamp(1, 64);
+    let mut out = String::with_capacity(len + len / 4);
+    for i in (0..len).rev() {
+        out.push(if (raw >> i) & 1 != 0 { '1' } else { '0' });
+        if i > 0 && i.is_multiple_of(4) {
+            out.push(' ');
+        }
+    }
+    out
+}
+
 impl Default for BitVisualizerWindow {
     fn default() -> Self {
         Self::new()
@@ -1116,7 +1853,7 @@ struct SignalInfo {
     start_bit: u8,
     bit_length: u8,
     byte_order: ByteOrder,
-    color_idx: usize,
+    color: [f32; 4],
     bus_id: u8,
 }
 
@@ -1132,22 +1869,96 @@ fn display_pos_to_dbc_bit(display_pos: usize) -> usize {
     (display_pos / 8) * 8 + (7 - (display_pos % 8))
 }
 
+/// Signal layout (start bit, length) for the grid selection `first..=last` in a byte order.
+/// Motorola puts the MSB at the leftmost selected cell and runs cell by cell through the
+/// selection; Intel spans the lowest through the highest selected DBC bit.
+fn selection_layout(first: usize, last: usize, byte_order: ByteOrder) -> (u8, u8) {
+    match byte_order {
+        ByteOrder::Intel => {
+            let (lo, hi) = (first..=last)
+                .map(display_pos_to_dbc_bit)
+                .fold((usize::MAX, 0), |(lo, hi), bit| (lo.min(bit), hi.max(bit)));
+            (lo as u8, (hi - lo + 1) as u8)
+        }
+        ByteOrder::Motorola => (display_pos_to_dbc_bit(first) as u8, (last - first + 1) as u8),
+    }
+}
+
+/// Raw value of the grid selection `first..=last` read in a byte order: Motorola reads the
+/// cells left to right as MSB to LSB (the sawtooth runs along the grid), Intel decodes the
+/// selection's Intel layout
+fn read_selection(data: &[u8], first: usize, last: usize, byte_order: ByteOrder) -> Option<u64> {
+    if last - first >= 64 {
+        return None;
+    }
+    let (start, length) = selection_layout(first, last, byte_order);
+    if signal_last_byte(start, length, byte_order) >= data.len() {
+        return None;
+    }
+    extract_bits(data, start, length, byte_order)
+}
+
+/// Mean step between consecutive values as a fraction of their range: low for smooth or
+/// counting signals, high for the sawtooth a wrong byte order makes of them
+fn roughness(values: &[u64]) -> Option<f64> {
+    let (lo, hi) = (*values.iter().min()?, *values.iter().max()?);
+    if values.len() < 3 || lo == hi {
+        return None;
+    }
+    let steps: f64 = values.windows(2).map(|w| (w[1] as f64 - w[0] as f64).abs()).sum();
+    Some(steps / (values.len() - 1) as f64 / (hi - lo) as f64)
+}
+
+/// Byte order in which the selection `first..=last` decodes to the clearly smoother series
+/// over `frames`, or None if they read alike; also returns the number of frames sampled
+fn guess_byte_order<'a>(frames: impl Iterator<Item = &'a [u8]>, first: usize, last: usize) -> (Option<ByteOrder>, usize) {
+    let mut intel = Vec::new();
+    let mut motorola = Vec::new();
+    for data in frames {
+        if let (Some(i), Some(m)) = (read_selection(data, first, last, ByteOrder::Intel), read_selection(data, first, last, ByteOrder::Motorola)) {
+            intel.push(i);
+            motorola.push(m);
+        }
+    }
+    let guess = match (roughness(&intel), roughness(&motorola)) {
+        (Some(i), Some(m)) if i < m * BYTE_ORDER_GUESS_MARGIN => Some(ByteOrder::Intel),
+        (Some(i), Some(m)) if m < i * BYTE_ORDER_GUESS_MARGIN => Some(ByteOrder::Motorola),
+        _ => None,
+    };
+    (guess, intel.len())
+}
+
+/// Observed (min, max) raw value of a signal layout over `frames`
+fn raw_range(frames: &[CanData], start_bit: u8, bit_length: u8, byte_order: ByteOrder, signed: bool) -> Option<(f64, f64)> {
+    frames.iter()
+        .filter_map(|data| extract_bits(data, start_bit, bit_length, byte_order))
+        .map(|raw| if signed { sign_extend(raw, bit_length) as f64 } else { raw as f64 })
+        .fold(None, |range, v| match range {
+            None => Some((v, v)),
+            Some((lo, hi)) => Some((v.min(lo), v.max(hi))),
+        })
+}
+
+/// Factor and offset mapping the raw range onto the physical range (None if the raw value never changes)
+fn fit_factor_offset((raw_min, raw_max): (f64, f64), (min, max): (f64, f64)) -> Option<(f64, f64)> {
+    if raw_max <= raw_min {
+        return None;
+    }
+    let factor = (max - min) / (raw_max - raw_min);
+    Some((factor, min - raw_min * factor))
+}
+
+/// Highest byte index a signal's bits touch
+fn signal_last_byte(start_bit: u8, bit_length: u8, byte_order: ByteOrder) -> usize {
+    signal_bits(start_bit, bit_length, byte_order).max().unwrap_or(start_bit as usize) / 8
+}
+
 impl SignalInfo {
-    /// DBC bit positions (0=LSB, 7=MSB within byte 0)
+    /// DBC bit positions (0=LSB, 7=MSB within byte 0), from the start bit on
     /// - Intel (@1+): start_bit = LSB, signal spans [start_bit, start_bit+length-1]
-    /// - Motorola (@0+): start_bit = MSB, signal spans [start_bit-length+1, start_bit]
+    /// - Motorola (@0+): start_bit = MSB, counting down and on into the next byte's bit 7
     fn get_dbc_bit_positions(&self) -> Vec<usize> {
-        let (start, end) = match self.byte_order {
-            ByteOrder::Intel => {
-                (self.start_bit as usize, self.start_bit as usize + self.bit_length as usize)
-            }
-            ByteOrder::Motorola => {
-                let msb = self.start_bit as usize;
-                let lsb = msb + 1 - self.bit_length as usize;
-                (lsb, msb + 1)
-            }
-        };
-        (start..end).collect()
+        signal_bits(self.start_bit, self.bit_length, self.byte_order).collect()
     }
 
     /// Display grid positions for highlighting (0=leftmost/MSB, 7=rightmost/LSB within byte 0)
@@ -1159,18 +1970,225 @@ impl SignalInfo {
     }
 
     fn get_msb_display_pos(&self) -> usize {
+        let bits = self.get_dbc_bit_positions();
         let dbc_msb = match self.byte_order {
-            ByteOrder::Intel => self.start_bit as usize + self.bit_length as usize - 1,
-            ByteOrder::Motorola => self.start_bit as usize,
+            ByteOrder::Intel => bits.last(),
+            ByteOrder::Motorola => bits.first(),
         };
-        dbc_bit_to_display_pos(dbc_msb)
+        dbc_bit_to_display_pos(dbc_msb.copied().unwrap_or(self.start_bit as usize))
     }
 
     fn get_lsb_display_pos(&self) -> usize {
+        let bits = self.get_dbc_bit_positions();
         let dbc_lsb = match self.byte_order {
-            ByteOrder::Intel => self.start_bit as usize,
-            ByteOrder::Motorola => self.start_bit as usize + self.bit_length as usize - 1,
+            ByteOrder::Intel => bits.first(),
+            ByteOrder::Motorola => bits.last(),
         };
-        dbc_bit_to_display_pos(dbc_lsb)
+        dbc_bit_to_display_pos(dbc_lsb.copied().unwrap_or(self.start_bit as usize))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_raw_formatting_matches_bit_length() {
+        // -5 as a signed 12-bit value
+        let raw = (-5i64 as u64) & 0xFFF;
+        assert_eq!(sign_extend(raw, 12), -5);
+        assert_eq!(format_raw_hex(raw, 12), "0xFFB");
+        assert_eq!(format_raw_binary(raw, 12), "1111 1111 1011");
+
+        // Odd widths keep exactly bit_length digits with the short group at the MSB end
+        assert_eq!(format_raw_hex(0b101, 3), "0x5");
+        assert_eq!(format_raw_binary(0b101, 3), "101");
+        assert_eq!(format_raw_binary(0b1_0000, 5), "1 0000");
+        assert_eq!(format_raw_binary(1, 1), "1");
+    }
+
+    #[test]
+    fn test_bit_numbering_labels_same_grid_position() {
+        // Grid position 10: byte 1, third column from the left = bit 5 of the byte
+        assert_eq!(display_pos_to_dbc_bit(10), 13);
+        assert_eq!(BitNumbering::Dbc.bit_name(10), "13");
+        assert_eq!(BitNumbering::Lsb0.bit_name(10), "1.5");
+        assert_eq!(BitNumbering::Msb0.bit_name(10), "1.2");
+
+        let headers = |n: BitNumbering| (0..8).map(|c| n.column_header(c)).collect::<Vec<_>>();
+        assert_eq!(headers(BitNumbering::Lsb0), vec![7, 6, 5, 4, 3, 2, 1, 0]);
+        assert_eq!(headers(BitNumbering::Msb0), vec![0, 1, 2, 3, 4, 5, 6, 7]);
+    }
+
+    #[test]
+    fn test_found_signal_goes_to_its_message() {
+        let mut viz = BitVisualizerWindow::new();
+        viz.signal_search = "rpm".to_string();
+        viz.show_found_signal(0x1A0, "EngineRpm".to_string());
+        assert_eq!(viz.take_goto_request(), Some(0x1A0));
+        assert_eq!(viz.found_signal, Some((0x1A0, "EngineRpm".to_string())));
+        assert!(viz.signal_search.is_empty());
+    }
+
+    #[test]
+    fn test_copy_signal_to_message_marks_dbc_changed() {
+        let mut dbc = DbcFile::new();
+        let mut source = DbcMessage::new(0x100, "Source", 8);
+        source.add_signal(DbcSignal::new("Speed", 0, 16));
+        dbc.add_message(source);
+        let mut target = DbcMessage::new(0x200, "Target", 8);
+        target.add_signal(DbcSignal::new("Flags", 8, 8));
+        dbc.add_message(target);
+
+        let mut viz = BitVisualizerWindow::new();
+        viz.copy_signal_to(&mut dbc, 1, 0x100, "Speed", 0x200);
+        assert!(viz.take_dbc_changed());
+        assert!(viz.has_session_signals());
+        let (quadrant, from, text, is_warning) = viz.signal_copy_status.clone().unwrap();
+        assert_eq!((quadrant, from, is_warning), (1, 0x100, true));
+        assert!(text.contains("'Speed'") && text.contains("Flags"));
+
+        viz.copy_signal_to(&mut dbc, 1, 0x100, "Gone", 0x200);
+        assert!(!viz.take_dbc_changed());
+        assert_eq!(dbc.get_message(0x200).unwrap().signals.len(), 2);
+    }
+
+    #[test]
+    fn test_goto_frame_survives_list_selection_refresh() {
+        let mut viz = BitVisualizerWindow::new();
+        viz.set_message(0x100, 0, &[1]);
+        viz.show_goto_frame(&CanMessage::new(1, 0x200, vec![2, 3].into()));
+        // The list still reports 0x100 every frame; that must not undo the go-to
+        viz.set_message(0x100, 0, &[1]);
+        assert_eq!(viz.get_selected(), Some((0x200, 1)));
+
+        viz.goto_not_found(0x300);
+        assert!(viz.goto_error.as_deref().unwrap().contains("0x300"));
+    }
+
+    #[test]
+    fn test_dlc_tracks_short_frames() {
+        let mut q = QuadrantState::new();
+        q.update_message(0x100, 0, &[0xAA, 0x00, 0x55]);
+        assert_eq!(q.dlc, 3);
+        assert_eq!(q.current_data, [0xAA, 0x00, 0x55, 0, 0, 0, 0, 0]);
+
+        // Bytes 0-1 fit, a 16-bit Intel signal at bit 16 needs byte 3
+        assert_eq!(signal_last_byte(0, 16, ByteOrder::Intel), 1);
+        assert!(signal_last_byte(16, 16, ByteOrder::Intel) >= q.dlc);
+        assert_eq!(signal_last_byte(23, 8, ByteOrder::Motorola), 2);
+        // Motorola continues into the next byte: 7|16@0 is bytes 0 and 1
+        assert_eq!(signal_last_byte(7, 16, ByteOrder::Motorola), 1);
+
+        q.clear();
+        assert_eq!(q.dlc, 8);
+    }
+
+    #[test]
+    fn test_set_message_records_bus_for_signal_overlays() {
+        use crate::core::dbc::DbcMessage;
+
+        let mut dbc = DbcFile::new();
+        let mut msg = DbcMessage::new(0x123, "Engine", 8);
+        msg.add_signal(DbcSignal::new("Rpm", 0, 16));
+        msg.add_signal(DbcSignal::new("Temp", 16, 8));
+        dbc.add_message(msg);
+
+        let mut window = BitVisualizerWindow::new();
+        window.set_message(0x123, 2, &[0x10, 0x27, 0x50]);
+        assert_eq!(window.get_selected(), Some((0x123, 2)));
+
+        let signals = window.get_signal_info_quadrant(&dbc, 0);
+        assert_eq!(signals.len(), 2);
+        assert!(signals.iter().all(|s| s.bus_id == 2));
+        assert_eq!(signals[0].name, "Rpm");
+    }
+
+    #[test]
+    fn test_pinned_quadrant_ignores_list_selection() {
+        let mut window = BitVisualizerWindow::new();
+        window.set_message(0x123, 0, &[0x01]);
+        window.quadrants[0].pinned = true;
+
+        window.set_message(0x456, 0, &[0x02]);
+        assert_eq!(window.get_selected(), Some((0x123, 0)));
+        // Playback still refreshes the pinned message's frame
+        window.update_message_data(0x123, 0, &[0x03]);
+        assert_eq!(window.quadrants[0].current_data[0], 0x03);
+
+        // Unpinning doesn't yank in the selection made while pinned
+        window.quadrants[0].pinned = false;
+        window.set_message(0x456, 0, &[0x02]);
+        assert_eq!(window.get_selected(), Some((0x123, 0)));
+        window.set_message(0x789, 0, &[0x04]);
+        assert_eq!(window.get_selected(), Some((0x789, 0)));
+    }
+
+    #[test]
+    fn test_grid_zoom_steps_and_clamps() {
+        let mut window = BitVisualizerWindow::new();
+        window.step_grid_zoom(1);
+        assert_eq!(window.grid_zoom(), 1.25);
+        // An off-grid zoom from the settings snaps back onto the steps
+        window.set_grid_zoom(1.3);
+        window.step_grid_zoom(-1);
+        assert_eq!(window.grid_zoom(), 1.0);
+        for _ in 0..20 {
+            window.step_grid_zoom(1);
+        }
+        assert_eq!(window.grid_zoom(), GRID_ZOOM_MAX);
+        window.set_grid_zoom(0.1);
+        assert_eq!(window.grid_zoom(), GRID_ZOOM_MIN);
+    }
+
+    #[test]
+    fn test_edit_frame_toggles_without_touching_received() {
+        let mut q = QuadrantState::new();
+        q.update_message(0x100, 0, &[0x00, 0xFF]);
+        assert_eq!(q.display_data(true), q.current_data);
+
+        q.toggle_edit_bit(0, 7);
+        q.toggle_edit_bit(1, 0);
+        assert_eq!(&q.display_data(true)[..2], &[0x80, 0xFE]);
+        assert_eq!(&q.display_data(false)[..2], &[0x00, 0xFF]);
+
+        // New frames update the received data but keep the edit
+        q.update_message(0x100, 0, &[0x01, 0x02]);
+        assert_eq!(&q.display_data(true)[..2], &[0x80, 0xFE]);
+        assert_eq!(&q.current_data[..2], &[0x01, 0x02]);
+    }
+
+    #[test]
+    fn test_guess_byte_order_from_counter() {
+        // A 16-bit counter stepping across byte boundaries, grid cells 0..=15 (bytes 0-1)
+        let big: Vec<Vec<u8>> = (0x00F0u16..0x0310).map(|v| v.to_be_bytes().to_vec()).collect();
+        let little: Vec<Vec<u8>> = (0x00F0u16..0x0310).map(|v| v.to_le_bytes().to_vec()).collect();
+        fn frames(f: &[Vec<u8>]) -> impl Iterator<Item = &[u8]> {
+            f.iter().map(Vec::as_slice)
+        }
+
+        assert_eq!(guess_byte_order(frames(&big), 0, 15), (Some(ByteOrder::Motorola), 544));
+        assert_eq!(guess_byte_order(frames(&little), 0, 15), (Some(ByteOrder::Intel), 544));
+        // Within one byte both orders read the same bits
+        assert_eq!(guess_byte_order(frames(&big), 8, 15).0, None);
+
+        assert_eq!(selection_layout(0, 15, ByteOrder::Intel), (0, 16));
+        assert_eq!(selection_layout(0, 15, ByteOrder::Motorola), (7, 16));
+        // Cells 4..=11: low nibble of byte 0 then high nibble of byte 1
+        assert_eq!(selection_layout(4, 11, ByteOrder::Motorola), (3, 8));
+        assert_eq!(read_selection(&[0xA5, 0x3C], 4, 11, ByteOrder::Motorola), Some(0x53));
+    }
+
+    #[test]
+    fn test_fit_factor_offset_from_raw_range() {
+        let frames: Vec<CanData> = [20u8, 120, 220].iter().map(|&b| vec![b, 0xFF].into()).collect();
+        let raw = raw_range(&frames, 0, 8, ByteOrder::Intel, false).unwrap();
+        assert_eq!(raw, (20.0, 220.0));
+        // 20..220 raw is 0..100 %
+        assert_eq!(fit_factor_offset(raw, (0.0, 100.0)), Some((0.5, -10.0)));
+        // Signed: 0xFF reads as -1
+        assert_eq!(raw_range(&frames, 8, 8, ByteOrder::Intel, true), Some((-1.0, -1.0)));
+        assert_eq!(fit_factor_offset((-1.0, -1.0), (0.0, 1.0)), None);
+        assert_eq!(raw_range(&[], 0, 8, ByteOrder::Intel, false), None);
     }
 }
@@ -0,0 +1,31 @@
+//! System clipboard backend for imgui (the default one never leaves the process)
+
+use imgui::ClipboardBackend;
+use tracing::warn;
+
+pub struct SystemClipboard(arboard::Clipboard);
+
+impl SystemClipboard {
+    /// Open the OS clipboard; `None` if no clipboard is available (e.g. headless)
+    pub fn new() -> Option<Self> {
+        match arboard::Clipboard::new() {
+            Ok(clipboard) => Some(Self(clipboard)),
+            Err(e) => {
+                warn!("System clipboard unavailable: {}", e);
+                None
+            }
+        }
+    }
+}
+
+impl ClipboardBackend for SystemClipboard {
+    fn get(&mut self) -> Option<String> {
+        self.0.get_text().ok()
+    }
+
+    fn set(&mut self, value: &str) {
+        if let Err(e) = self.0.set_text(value) {
+            warn!("Failed to copy to clipboard: {}", e);
+        }
+    }
+}
@@ -0,0 +1,164 @@
+//! Signal colors shared by every view, so a signal looks the same in the charts
+//! and in the bit visualizer.
+//!
+//! Each view holds the palette it colors with (set alongside the theme); the
+//! color-blind-safe one swaps red/green pairs for the Okabe-Ito colors in signal,
+//! series and byte-change coloring alike.
+
+use crate::decode::router::signal_key;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Palette signal names are hashed into
+const SIGNAL_PALETTE: [[f32; 4]; 10] = [
+    [0.3, 0.5, 0.9, 1.0],  // Blue
+    [0.3, 0.7, 0.4, 1.0],  // Green
+    [0.9, 0.6, 0.2, 1.0],  // Orange
+    [0.7, 0.4, 0.8, 1.0],  // Purple
+    [0.8, 0.3, 0.4, 1.0],  // Red
+    [0.3, 0.8, 0.8, 1.0],  // Cyan
+    [0.8, 0.8, 0.3, 1.0],  // Yellow
+    [0.6, 0.4, 0.3, 1.0],  // Brown
+    [0.5, 0.5, 0.7, 1.0],  // Slate
+    [0.7, 0.5, 0.7, 1.0],  // Mauve
+];
+
+/// Series colors by index (buses, rate layers)
+const SERIES_PALETTE: [[f32; 4]; 8] = [
+    [0.0, 0.75, 1.0, 1.0],
+    [1.0, 0.4, 0.4, 1.0],
+    [0.4, 1.0, 0.4, 1.0],
+    [1.0, 1.0, 0.4, 1.0],
+    [1.0, 0.4, 1.0, 1.0],
+    [0.4, 1.0, 1.0, 1.0],
+    [1.0, 0.6, 0.2, 1.0],
+    [0.6, 0.4, 1.0, 1.0],
+];
+
+/// Okabe-Ito colors (black left out: it vanishes on dark backgrounds)
+const OKABE_ITO: [[f32; 4]; 8] = [
+    [0.90, 0.62, 0.00, 1.0],  // Orange
+    [0.34, 0.71, 0.91, 1.0],  // Sky blue
+    [0.00, 0.62, 0.45, 1.0],  // Bluish green
+    [0.94, 0.89, 0.26, 1.0],  // Yellow
+    [0.00, 0.45, 0.70, 1.0],  // Blue
+    [0.84, 0.37, 0.00, 1.0],  // Vermillion
+    [0.80, 0.47, 0.65, 1.0],  // Reddish purple
+    [0.60, 0.60, 0.60, 1.0],  // Grey
+];
+
+/// Colors of changed bytes in the message list
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ByteChangeColors {
+    /// Every bit flipped
+    pub toggled: [f32; 4],
+    pub increased: [f32; 4],
+    pub decreased: [f32; 4],
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ColorPalette {
+    #[default]
+    Standard,
+    ColorBlindSafe,
+}
+
+impl ColorPalette {
+    pub const ALL: [ColorPalette; 2] = [ColorPalette::Standard, ColorPalette::ColorBlindSafe];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ColorPalette::Standard => "Standard",
+            ColorPalette::ColorBlindSafe => "Color-Blind Safe",
+        }
+    }
+
+    fn signal_colors(self) -> &'static [[f32; 4]] {
+        match self {
+            ColorPalette::Standard => &SIGNAL_PALETTE,
+            ColorPalette::ColorBlindSafe => &OKABE_ITO,
+        }
+    }
+
+    fn series_colors(self) -> &'static [[f32; 4]] {
+        match self {
+            ColorPalette::Standard => &SERIES_PALETTE,
+            ColorPalette::ColorBlindSafe => &OKABE_ITO,
+        }
+    }
+
+    /// Colors of changed bytes in the message list
+    pub fn byte_change_colors(self) -> ByteChangeColors {
+        match self {
+            ColorPalette::Standard => ByteChangeColors {
+                toggled: [0.9, 0.6, 0.2, 1.0],   // Orange
+                increased: [0.3, 0.7, 0.4, 1.0], // Green
+                decreased: [0.7, 0.4, 0.3, 1.0], // Red
+            },
+            // Blue/orange stay apart for every common form of color blindness
+            ColorPalette::ColorBlindSafe => ByteChangeColors {
+                toggled: OKABE_ITO[3],
+                increased: OKABE_ITO[1],
+                decreased: OKABE_ITO[5],
+            },
+        }
+    }
+}
+
+/// Colors the user picked, by chart key "signal_name@0xID@busN"
+pub type CustomColors = BTreeMap<String, [f32; 4]>;
+
+/// Color for the series at `index` (buses, rate layers)
+pub fn series_color(palette: ColorPalette, index: usize) -> [f32; 4] {
+    let colors = palette.series_colors();
+    colors[index % colors.len()]
+}
+
+/// Palette color for a signal name: the same name always gets the same color
+pub fn hashed_signal_color(palette: ColorPalette, name: &str) -> [f32; 4] {
+    let mut hash: usize = 5381;
+    for c in name.bytes() {
+        hash = hash.wrapping_mul(33).wrapping_add(c as usize);
+    }
+    let colors = palette.signal_colors();
+    colors[hash % colors.len()]
+}
+
+/// Color of a message's signal on a bus: the user's pick if there is one (picks saved
+/// before keys named the message still apply), else its hashed palette color
+pub fn signal_color(palette: ColorPalette, name: &str, msg_id: u32, bus: u8, custom: &CustomColors) -> [f32; 4] {
+    custom.get(&signal_key(name, msg_id, bus))
+        .or_else(|| custom.get(&format!("{}@bus{}", name, bus)))
+        .copied()
+        .unwrap_or_else(|| hashed_signal_color(palette, name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signal_color_resolution() {
+        let palette = ColorPalette::Standard;
+        assert_eq!(hashed_signal_color(palette, "EngineSpeed"), hashed_signal_color(palette, "EngineSpeed"));
+        let custom = CustomColors::from([
+            ("EngineSpeed@bus1".to_string(), [1.0, 0.0, 0.0, 1.0]),
+            ("EngineSpeed@0x200@bus1".to_string(), [0.0, 1.0, 0.0, 1.0]),
+        ]);
+        assert_eq!(signal_color(palette, "EngineSpeed", 0x100, 1, &custom), [1.0, 0.0, 0.0, 1.0]);
+        assert_eq!(signal_color(palette, "EngineSpeed", 0x200, 1, &custom), [0.0, 1.0, 0.0, 1.0]);
+        assert_eq!(signal_color(palette, "EngineSpeed", 0x100, 0, &custom), hashed_signal_color(palette, "EngineSpeed"));
+    }
+
+    #[test]
+    fn test_color_blind_palette_replaces_every_color() {
+        let safe = ColorPalette::ColorBlindSafe;
+        for name in ["EngineSpeed", "Rpm", "Temp"] {
+            assert!(OKABE_ITO.contains(&hashed_signal_color(safe, name)));
+        }
+        let bytes = safe.byte_change_colors();
+        assert!([bytes.toggled, bytes.increased, bytes.decreased].iter().all(|c| OKABE_ITO.contains(c)));
+        assert_ne!(bytes, ColorPalette::Standard.byte_change_colors());
+        assert!(safe.series_colors().iter().all(|c| !SERIES_PALETTE.contains(c)));
+    }
+}
@@ -0,0 +1,182 @@
+//! DBC coverage window: which defined messages appear in the log, and which logged IDs are undefined.
+
+use imgui::{Condition, TreeNodeFlags, Ui};
+use crate::core::{CanMessage, DbcFile, DbcMessage};
+use std::collections::BTreeMap;
+
+/// Result of intersecting DBC message IDs with the IDs seen in a log
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct DbcCoverage {
+    /// Defined in the DBC and present in the log
+    pub defined_seen: Vec<u32>,
+    /// Defined in the DBC but never seen in the log
+    pub defined_not_seen: Vec<u32>,
+    /// Seen in the log but not defined (reverse-engineering targets)
+    pub seen_not_defined: Vec<u32>,
+}
+
+impl DbcCoverage {
+    /// Compute coverage from the DBC and the set of logged IDs (id -> max observed length)
+    pub fn compute(dbc: &DbcFile, seen: &BTreeMap<u32, u8>) -> Self {
+        let mut coverage = Self::default();
+
+        let mut defined = dbc.message_ids();
+        defined.sort_unstable();
+        defined.dedup();
+
+        for id in defined {
+            if seen.contains_key(&id) {
+                coverage.defined_seen.push(id);
+            } else {
+                coverage.defined_not_seen.push(id);
+            }
+        }
+        coverage.seen_not_defined = seen.keys()
+            .copied()
+            .filter(|id| dbc.get_message(*id).is_none())
+            .collect();
+
+        coverage
+    }
+
+    /// Fraction of logged IDs that have a DBC definition (0.0-1.0)
+    pub fn seen_defined_ratio(&self) -> f32 {
+        let seen = self.defined_seen.len() + self.seen_not_defined.len();
+        if seen == 0 {
+            return 0.0;
+        }
+        self.defined_seen.len() as f32 / seen as f32
+    }
+}
+
+/// Window showing DBC coverage of the loaded log
+pub struct CoverageWindow {
+    /// IDs present in the loaded log, with the max data length seen for each
+    seen: BTreeMap<u32, u8>,
+}
+
+impl CoverageWindow {
+    pub fn new() -> Self {
+        Self {
+            seen: BTreeMap::new(),
+        }
+    }
+
+    /// Rebuild the set of logged IDs (call once per load, not per frame)
+    pub fn set_messages(&mut self, messages: &[CanMessage]) {
+        self.seen.clear();
+        for msg in messages {
+            let len = self.seen.entry(msg.id).or_insert(0);
+            *len = (*len).max(msg.data.len() as u8);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.seen.clear();
+    }
+
+    /// Add empty `DbcMessage` stubs for every logged ID the DBC doesn't define.
+    /// Returns the number of stubs created.
+    pub fn create_stubs(&self, dbc: &mut DbcFile) -> usize {
+        let coverage = DbcCoverage::compute(dbc, &self.seen);
+        for &id in &coverage.seen_not_defined {
+            let size = self.seen.get(&id).copied().unwrap_or(8);
+            dbc.add_message(DbcMessage::new(id, &format!("MSG_{:03X}", id), size));
+        }
+        coverage.seen_not_defined.len()
+    }
+
+    /// Render the window. Returns true if the DBC was modified.
+    pub fn render(&mut self, ui: &Ui, dbc: &mut DbcFile, is_open: &mut bool) -> bool {
+        let mut modified = false;
+        ui.window("DBC Coverage")
+            .size([350.0, 400.0], Condition::FirstUseEver)
+            .position([450.0, 30.0], Condition::FirstUseEver)
+            .opened(is_open)
+            .build(|| {
+                modified = self.render_content(ui, dbc);
+            });
+        modified
+    }
+
+    fn render_content(&mut self, ui: &Ui, dbc: &mut DbcFile) -> bool {
+        if self.seen.is_empty() {
+            ui.text_colored([0.5, 0.5, 0.5, 1.0], "Load a log to see DBC coverage");
+            return false;
+        }
+
+        // Cheap: DBC ID lists are small, the logged ID set is cached
+        let coverage = DbcCoverage::compute(dbc, &self.seen);
+        ui.text(format!(
+            "{} of {} logged IDs defined ({:.0}%)",
+            coverage.defined_seen.len(),
+            self.seen.len(),
+            coverage.seen_defined_ratio() * 100.0
+        ));
+
+        let mut modified = false;
+        if !coverage.seen_not_defined.is_empty()
+            && ui.button(format!("Create stubs for {} undefined IDs", coverage.seen_not_defined.len()))
+        {
+            modified = self.create_stubs(dbc) > 0;
+        }
+
+        ui.separator();
+
+        let sections = [
+            ("Seen, not defined", &coverage.seen_not_defined, [1.0, 0.7, 0.3, 1.0]),
+            ("Defined and seen", &coverage.defined_seen, [0.5, 0.8, 0.5, 1.0]),
+            ("Defined, not seen", &coverage.defined_not_seen, [0.6, 0.6, 0.6, 1.0]),
+        ];
+        for (title, ids, color) in sections {
+            let header = format!("{} ({})##cov_{}", title, ids.len(), title);
+            if ui.collapsing_header(&header, TreeNodeFlags::DEFAULT_OPEN) {
+                ui.indent();
+                for id in ids {
+                    match dbc.get_message(*id) {
+                        Some(msg) => ui.text_colored(color, format!("0x{:03X}  {}", id, msg.name)),
+                        None => ui.text_colored(color, format!("0x{:03X}", id)),
+                    }
+                }
+                ui.unindent();
+            }
+        }
+
+        modified
+    }
+}
+
+impl Default for CoverageWindow {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_coverage_and_stubs() {
+        let mut dbc = DbcFile::new();
+        dbc.add_message(DbcMessage::new(0x100, "Seen", 8));
+        dbc.add_message(DbcMessage::new(0x200, "NotSeen", 8));
+
+        let messages = vec![
+            CanMessage::new(0, 0x100, vec![0u8; 8].into()),
+            CanMessage::new(0, 0x300, vec![0u8; 4].into()),
+        ];
+        let mut window = CoverageWindow::new();
+        window.set_messages(&messages);
+
+        let coverage = DbcCoverage::compute(&dbc, &window.seen);
+        assert_eq!(coverage.defined_seen, vec![0x100]);
+        assert_eq!(coverage.defined_not_seen, vec![0x200]);
+        assert_eq!(coverage.seen_not_defined, vec![0x300]);
+
+        assert_eq!(window.create_stubs(&mut dbc), 1);
+        let stub = dbc.get_message(0x300).unwrap();
+        assert_eq!(stub.size, 4);
+        assert!(DbcCoverage::compute(&dbc, &window.seen).seen_not_defined.is_empty());
+    }
+}
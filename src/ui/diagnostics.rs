@@ -0,0 +1,156 @@
+//! Diagnostics window: ISO-TP payloads reassembled from a request/response ID pair,
+//! with UDS services decoded into readable lines.
+
+use imgui::{Condition, Ui};
+use crate::core::CanMessage;
+use crate::core::message::parse_can_id;
+use crate::decode::isotp::{IsotpEvent, IsotpStream, DEFAULT_REQUEST_ID, DEFAULT_RESPONSE_ID};
+use crate::decode::uds;
+
+/// Payload bytes shown inline; the full payload is in the row tooltip
+const INLINE_PAYLOAD_BYTES: usize = 24;
+
+fn hex_bytes(data: &[u8]) -> String {
+    data.iter().map(|b| format!("{:02X}", b)).collect::<Vec<_>>().join(" ")
+}
+
+/// Window listing the ISO-TP traffic between a configurable request/response ID pair
+pub struct DiagnosticsWindow {
+    request_input: String,
+    response_input: String,
+    /// Reassembly of the current ID pair, fed as the log grows
+    stream: Option<IsotpStream>,
+    show_flow_control: bool,
+}
+
+impl DiagnosticsWindow {
+    pub fn new() -> Self {
+        Self {
+            request_input: format!("{:03X}", DEFAULT_REQUEST_ID),
+            response_input: format!("{:03X}", DEFAULT_RESPONSE_ID),
+            stream: None,
+            show_flow_control: true,
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.stream = None;
+    }
+
+    pub fn render(&mut self, ui: &Ui, messages: &[CanMessage], is_open: &mut bool) {
+        ui.window("Diagnostics")
+            .size([700.0, 400.0], Condition::FirstUseEver)
+            .position([300.0, 120.0], Condition::FirstUseEver)
+            .opened(is_open)
+            .build(|| {
+                self.render_content(ui, messages);
+            });
+    }
+
+    fn render_content(&mut self, ui: &Ui, messages: &[CanMessage]) {
+        ui.set_next_item_width(80.0);
+        ui.input_text("Request ID", &mut self.request_input).build();
+        ui.same_line();
+        ui.set_next_item_width(80.0);
+        ui.input_text("Response ID", &mut self.response_input).build();
+        ui.same_line();
+        ui.checkbox("Flow control", &mut self.show_flow_control);
+
+        let ids = parse_can_id(&self.request_input).zip(parse_can_id(&self.response_input));
+        let Some((request_id, response_id)) = ids else {
+            ui.text_colored([1.0, 0.8, 0.3, 1.0], "Enter the request and response IDs in hex");
+            return;
+        };
+
+        // Start over when the pair changes or the log shrinks; otherwise only new frames are fed
+        let stream = match &mut self.stream {
+            Some(stream) if stream.ids() == (request_id, response_id) && stream.fed() <= messages.len() => stream,
+            stream => stream.insert(IsotpStream::new(request_id, response_id)),
+        };
+        stream.feed(messages);
+        let pending = stream.pending();
+        let events = stream.events();
+
+        if events.is_empty() && pending.is_empty() {
+            ui.text_colored([0.5, 0.5, 0.5, 1.0], format!("No ISO-TP traffic on 0x{:03X}/0x{:03X}", request_id, response_id));
+            return;
+        }
+        let start = messages.first().map(|m| m.timestamp);
+        let pdus = events.iter().chain(&pending).filter(|e| matches!(e, IsotpEvent::Pdu(_))).count();
+        ui.text(format!("{} PDUs", pdus));
+        ui.separator();
+
+        ui.child_window("isotp_list").build(|| {
+            ui.columns(5, "isotp_cols", true);
+            for header in ["Time", "Dir", "Length", "Service", "Payload"] {
+                ui.text(header);
+                ui.next_column();
+            }
+            ui.separator();
+
+            // Transfers still in progress go last
+            for event in events.iter().chain(&pending) {
+                if matches!(event, IsotpEvent::FlowControl(_)) && !self.show_flow_control {
+                    continue;
+                }
+                match start {
+                    Some(start) => ui.text(format!("{:.3}s", (event.timestamp() - start).num_microseconds().unwrap_or(0) as f64 / 1e6)),
+                    None => ui.text(event.timestamp().format("%H:%M:%S%.3f").to_string()),
+                }
+                ui.next_column();
+                match event {
+                    IsotpEvent::Pdu(pdu) => {
+                        ui.text(pdu.direction.label());
+                        ui.next_column();
+                        ui.text(if pdu.frames > 1 {
+                            format!("{} ({} frames)", pdu.expected_len, pdu.frames)
+                        } else {
+                            pdu.expected_len.to_string()
+                        });
+                        ui.next_column();
+                        // A PDU cut short would decode as garbage, so only whole ones get a UDS line
+                        match uds::describe(&pdu.data).filter(|_| pdu.error.is_none()) {
+                            Some(line) => {
+                                ui.text(&line);
+                                if ui.is_item_hovered() {
+                                    ui.tooltip_text(&line);
+                                }
+                            }
+                            None => ui.text_disabled("-"),
+                        }
+                        ui.next_column();
+                        let shown = &pdu.data[..pdu.data.len().min(INLINE_PAYLOAD_BYTES)];
+                        let ellipsis = if shown.len() < pdu.data.len() { " ..." } else { "" };
+                        match pdu.error {
+                            Some(error) => ui.text_colored([1.0, 0.4, 0.4, 1.0], format!("{}{} [{}]", hex_bytes(shown), ellipsis, error.label())),
+                            None => ui.text(format!("{}{}", hex_bytes(shown), ellipsis)),
+                        }
+                        if ui.is_item_hovered() && !ellipsis.is_empty() {
+                            ui.tooltip_text(hex_bytes(&pdu.data));
+                        }
+                    }
+                    IsotpEvent::FlowControl(fc) => {
+                        ui.text_disabled(fc.direction.label());
+                        ui.next_column();
+                        ui.text_disabled("FC");
+                        ui.next_column();
+                        ui.text_disabled("Flow control");
+                        ui.next_column();
+                        ui.text_disabled(format!(
+                            "{}  block size {}  STmin {} us",
+                            fc.status.label(), fc.block_size, fc.st_min_us
+                        ));
+                    }
+                }
+                ui.next_column();
+            }
+            ui.columns(1, "isotp_cols_end", false);
+        });
+    }
+}
+
+impl Default for DiagnosticsWindow {
+    fn default() -> Self {
+        Self::new()
+    }
+}
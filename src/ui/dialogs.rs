@@ -3,9 +3,11 @@ use std::path::PathBuf;
 
 /// Supported file types for CAN data
 pub const CAN_FILE_FILTERS: &[(&str, &[&str])] = &[
-    ("CAN Logs (CSV, rlog)", &["csv", "rlog"]),
+    ("CAN Logs (CSV, rlog, candump, ASC)", &["csv", "rlog", "log", "asc"]),
     ("CSV Files", &["csv"]),
     ("Cabana/openpilot rlog", &["rlog"]),
+    ("candump log", &["log"]),
+    ("Vector ASC", &["asc"]),
     ("All Files", &["*"]),
 ];
 
@@ -29,9 +31,11 @@ impl FileDialogs {
     /// Open a file dialog for selecting a CAN log file
     pub fn open_can_file() -> Option<PathBuf> {
         FileDialog::new()
-            .add_filter("CAN Logs (CSV, rlog)", &["csv", "rlog"])
+            .add_filter("CAN Logs (CSV, rlog, candump, ASC)", &["csv", "rlog", "log", "asc"])
             .add_filter("CSV Files", &["csv"])
             .add_filter("Cabana/openpilot rlog", &["rlog"])
+            .add_filter("candump log", &["log"])
+            .add_filter("Vector ASC", &["asc"])
             .add_filter("All Files", &["*"])
             .set_title("Open CAN Log File")
             .pick_file()
@@ -81,6 +85,17 @@ impl FileDialogs {
             .save_file()
     }
 
+    /// Open a file dialog for exporting decoded signals to Parquet
+    #[cfg(feature = "parquet")]
+    pub fn export_parquet_file() -> Option<PathBuf> {
+        FileDialog::new()
+            .add_filter("Parquet Files", &["parquet"])
+            .add_filter("All Files", &["*"])
+            .set_title("Export Signals to Parquet")
+            .set_file_name("signals.parquet")
+            .save_file()
+    }
+
     /// Open multiple files for CAN logs
     pub fn open_multiple_can_files() -> Option<Vec<PathBuf>> {
         FileDialog::new()
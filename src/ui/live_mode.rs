@@ -1,7 +1,91 @@
-use imgui::{Condition, StyleColor, Ui};
-use crate::hardware::can_interface::{CanConfig, CanStatus, InterfaceType};
-use crate::hardware::can_manager::ConnectionStatus;
-use chrono::{Utc, Timelike};
+use imgui::{Condition, HistoryDirection, InputTextCallback, InputTextCallbackHandler, StyleColor, TextCallbackData, Ui};
+use crate::hardware::can_interface::{BusErrorStatus, CanCapabilities, CanConfig, CanStatus, ErrorState, InterfaceType, RxOverflowPolicy, DEFAULT_RX_BUFFER_SIZE, DEFAULT_STATUS_POLL_INTERVAL, DEFAULT_TX_MIN_GAP};
+use crate::hardware::can_manager::{ConnectionStatus, ManagerMessage, TimestampSource};
+use crate::core::{CanMessage, DbcFile, DbcMessage};
+use crate::decode::try_encode_message;
+use crate::ui::colors::ColorPalette;
+use crate::ui::self_test::SelfTestReport;
+use crate::ui::windows::{render_colored_bytes, ByteSearch, MessageDirection, MessageState, BYTE_MATCH_COLOR};
+use chrono::{DateTime, Utc, Timelike};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Frames kept in memory for the live view (and the whole recording in ring-buffer mode)
+pub const DEFAULT_LIVE_BUFFER: usize = 10_000;
+
+/// Recently sent frames kept by the message sender
+const SEND_HISTORY_LEN: usize = 20;
+
+/// Frame background of a sender input that won't parse
+const INVALID_INPUT_BG: [f32; 4] = [0.45, 0.12, 0.12, 1.0];
+
+/// Where recorded frames go
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum RecordingMode {
+    /// Keep only the newest frames in memory, for monitoring
+    RingBuffer,
+    /// Stream every frame to a temp file, so long recordings lose nothing and stay out of RAM
+    #[default]
+    SpillToDisk,
+}
+
+impl RecordingMode {
+    pub const ALL: [RecordingMode; 2] = [RecordingMode::RingBuffer, RecordingMode::SpillToDisk];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            RecordingMode::RingBuffer => "Ring buffer",
+            RecordingMode::SpillToDisk => "Unbounded (spill to disk)",
+        }
+    }
+}
+
+/// A frame's CSV time: seconds from `zero`
+fn csv_time(msg: &LiveMessage, zero: Option<DateTime<Utc>>) -> f64 {
+    match zero {
+        Some(start) => (msg.timestamp - start).num_microseconds().unwrap_or(0) as f64 / 1_000_000.0,
+        None => 0.0,
+    }
+}
+
+/// Write one frame as a `time,addr,bus,data` CSV row
+fn write_csv_row(out: &mut impl Write, msg: &LiveMessage, time: f64) -> std::io::Result<()> {
+    let data_hex: String = msg.data.iter().map(|b| format!("{:02X}", b)).collect();
+    writeln!(out, "{:.6},0x{:03X},{},0x{}", time, msg.id, msg.bus, data_hex)
+}
+
+/// Temp CSV an unbounded recording is streamed to
+struct RecordingSpill {
+    path: PathBuf,
+    writer: BufWriter<File>,
+    frames: u64,
+    first_timestamp: Option<DateTime<Utc>>,
+}
+
+impl RecordingSpill {
+    fn create(path: PathBuf) -> std::io::Result<Self> {
+        let mut writer = BufWriter::new(File::create(&path)?);
+        writeln!(writer, "time,addr,bus,data")?;
+        Ok(Self { path, writer, frames: 0, first_timestamp: None })
+    }
+
+    fn append(&mut self, msg: &LiveMessage, zero: Option<DateTime<Utc>>) -> std::io::Result<()> {
+        write_csv_row(&mut self.writer, msg, csv_time(msg, zero))?;
+        self.frames += 1;
+        self.first_timestamp.get_or_insert(msg.timestamp);
+        Ok(())
+    }
+}
+
+impl Drop for RecordingSpill {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
 
 /// Live mode state for hardware interface management
 pub struct LiveModeState {
@@ -19,18 +103,52 @@ pub struct LiveModeState {
     pub status_message: String,
     /// Statistics
     pub stats: LiveStats,
-    /// Messages received in live mode
-    pub live_messages: Vec<LiveMessage>,
-    /// Maximum messages to keep
+    /// Messages received in live mode (the newest `max_live_messages`)
+    pub live_messages: VecDeque<LiveMessage>,
+    /// Maximum messages to keep in memory
     pub max_live_messages: usize,
+    /// Frames trimmed from the front of `live_messages` since it was last cleared
+    trimmed: usize,
+    /// Ring buffer or spill-to-disk, chosen before a recording starts
+    pub recording_mode: RecordingMode,
+    /// Temp file of the current/last unbounded recording
+    spill: Option<RecordingSpill>,
+    /// Why spilling to disk stopped, if it failed
+    pub spill_error: Option<String>,
     /// Recording start time
     pub recording_start: Option<chrono::DateTime<Utc>>,
     /// Request to save data
     pub save_requested: bool,
+    /// Save only the frames in `save_range`
+    pub save_range_only: bool,
+    /// Start/end of the frames to save, in seconds of CSV time
+    pub save_range: (f64, f64),
+    /// Write saved times counting from the range start
+    pub save_rebase: bool,
     /// Connected interfaces (for multi-bus support)
     pub connected_interfaces: Vec<ConnectedInterface>,
+    /// Clock used to stamp recorded frames
+    pub timestamp_source: TimestampSource,
+    /// First recorded frame's time, for `TimestampSource::LogRelative`
+    timestamp_origin: Option<chrono::DateTime<Utc>>,
+    /// Settings last connected with, by interface name (restored when it's selected again)
+    pub adapter_configs: AdapterConfigs,
 }
 
+/// Connection settings remembered for one interface
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AdapterSettings {
+    pub bitrate: u32,
+    pub listen_only: bool,
+    #[serde(default)]
+    pub fd_mode: bool,
+    #[serde(default)]
+    pub bus_id: Option<u8>,
+}
+
+/// Remembered connection settings by interface (port) name, persisted with the app settings
+pub type AdapterConfigs = BTreeMap<String, AdapterSettings>;
+
 /// State for a connected interface
 #[derive(Clone)]
 pub struct ConnectedInterface {
@@ -44,6 +162,16 @@ pub struct ConnectedInterface {
     pub messages_received: u64,
     /// Number of errors
     pub errors: u64,
+    /// Frames dropped because a buffer was full
+    pub dropped_frames: u64,
+    /// Frames that failed to transmit
+    pub tx_dropped_frames: u64,
+    /// Adapter detected on connect (e.g. "CANable (v1.0)")
+    pub adapter: Option<String>,
+    /// Controller error state the adapter last reported
+    pub bus_status: BusErrorStatus,
+    /// Capabilities the connected adapter reported (None until it has identified itself)
+    pub capabilities: Option<CanCapabilities>,
 }
 
 /// Interface info for UI
@@ -53,22 +181,42 @@ pub struct InterfaceInfoUI {
     pub interface_type: InterfaceType,
     pub description: String,
     pub available: bool,
+    /// Settings the interface supports, so unsupported ones can be greyed out
+    pub capabilities: CanCapabilities,
 }
 
 /// Live CAN configuration
 #[derive(Clone, Debug)]
 pub struct LiveCanConfig {
     pub bitrate: u32,
+    /// Enable CAN FD (only offered on FD-capable interfaces)
+    pub fd_mode: bool,
     pub listen_only: bool,
+    /// Bus number to tag this adapter's frames with (None = lowest free)
+    pub bus_id: Option<u8>,
     pub auto_start: bool,
+    /// Interface RX buffer size (frames)
+    pub rx_buffer_size: usize,
+    /// Behaviour when the interface RX buffer is full
+    pub rx_overflow: RxOverflowPolicy,
+    /// Minimum spacing between transmitted frames
+    pub tx_min_gap: Duration,
+    /// How often to poll the adapter's error state (None = never)
+    pub status_poll_interval: Option<Duration>,
 }
 
 impl Default for LiveCanConfig {
     fn default() -> Self {
         Self {
             bitrate: 500_000,
+            fd_mode: false,
             listen_only: false,
+            bus_id: None,
             auto_start: true,
+            rx_buffer_size: DEFAULT_RX_BUFFER_SIZE,
+            rx_overflow: RxOverflowPolicy::default(),
+            tx_min_gap: DEFAULT_TX_MIN_GAP,
+            status_poll_interval: Some(DEFAULT_STATUS_POLL_INTERVAL),
         }
     }
 }
@@ -79,6 +227,12 @@ pub struct LiveStats {
     pub messages_received: u64,
     pub messages_sent: u64,
     pub errors: u64,
+    /// Frames dropped across all interfaces because a buffer was full
+    pub dropped_frames: u64,
+    /// Frames across all interfaces that failed to transmit
+    pub tx_dropped_frames: u64,
+    /// Worst controller error state across all interfaces
+    pub bus_state: ErrorState,
     pub bytes_received: u64,
     pub start_time: Option<chrono::DateTime<Utc>>,
 }
@@ -90,6 +244,22 @@ pub struct LiveMessage {
     pub id: u32,
     pub data: Vec<u8>,
     pub bus: u8,
+    pub is_rtr: bool,
+    pub is_error: bool,
+}
+
+impl LiveMessage {
+    /// The frame as a `CanMessage`, for loading into playback or handing to plugins
+    pub fn to_can_message(&self) -> CanMessage {
+        CanMessage {
+            timestamp: self.timestamp,
+            bus: self.bus,
+            id: self.id,
+            data: self.data.as_slice().into(),
+            is_rtr: self.is_rtr,
+            is_error: self.is_error,
+        }
+    }
 }
 
 impl LiveModeState {
@@ -102,14 +272,59 @@ impl LiveModeState {
             config: LiveCanConfig::default(),
             status_message: String::new(),
             stats: LiveStats::default(),
-            live_messages: Vec::new(),
-            max_live_messages: usize::MAX,  // No limit - don't truncate recordings
+            live_messages: VecDeque::new(),
+            max_live_messages: DEFAULT_LIVE_BUFFER,
+            trimmed: 0,
+            recording_mode: RecordingMode::default(),
+            spill: None,
+            spill_error: None,
             recording_start: None,
             save_requested: false,
+            save_range_only: false,
+            save_range: (0.0, 0.0),
+            save_rebase: true,
             connected_interfaces: Vec::new(),
+            timestamp_source: TimestampSource::default(),
+            timestamp_origin: None,
+            adapter_configs: AdapterConfigs::new(),
+        }
+    }
+
+    /// Select an interface, switching to the settings it was last connected with if known
+    pub fn select_interface(&mut self, name: &str) {
+        self.selected_interface = Some(name.to_string());
+        if let Some(saved) = self.adapter_configs.get(name) {
+            self.config.bitrate = saved.bitrate;
+            self.config.listen_only = saved.listen_only;
+            self.config.fd_mode = saved.fd_mode;
+            self.config.bus_id = saved.bus_id;
         }
     }
 
+    /// Remember `config` as the settings to restore for `name`
+    pub fn remember_config(&mut self, name: &str, config: &LiveCanConfig) {
+        self.adapter_configs.insert(name.to_string(), AdapterSettings {
+            bitrate: config.bitrate,
+            listen_only: config.listen_only,
+            fd_mode: config.fd_mode,
+            bus_id: config.bus_id,
+        });
+    }
+
+    /// Capabilities of the selected interface (classic CAN if none is selected): what
+    /// the adapter reported once connected, else what its interface type offers
+    pub fn selected_capabilities(&self) -> CanCapabilities {
+        let name = self.selected_interface.as_ref();
+        let connected = self.connected_interfaces.iter()
+            .find(|c| Some(&c.interface_name) == name)
+            .and_then(|c| c.capabilities.clone());
+        connected
+            .or_else(|| name
+                .and_then(|name| self.available_interfaces.iter().find(|i| &i.name == name))
+                .map(|i| i.capabilities.clone()))
+            .unwrap_or_default()
+    }
+
     /// Refresh the list of available interfaces
     pub fn refresh_interfaces(&mut self) {
         // Get serial ports
@@ -122,6 +337,7 @@ impl LiveModeState {
                 interface_type: InterfaceType::Serial,
                 description: format!("Serial: {}", name),
                 available: true,
+                capabilities: crate::hardware::serial_can::SerialCanInterface::slcan_capabilities(),
             })
             .collect();
 
@@ -131,6 +347,7 @@ impl LiveModeState {
             interface_type: InterfaceType::Virtual,
             description: "Virtual/Mock Interface (for testing)".to_string(),
             available: true,
+            capabilities: crate::hardware::mock::MockCanInterface::mock_capabilities(),
         });
 
         // Sort by type then name
@@ -143,27 +360,108 @@ impl LiveModeState {
         });
     }
 
-    /// Add a live message
-    pub fn add_message(&mut self, id: u32, data: Vec<u8>, bus: u8) {
+    /// Add a live message, stamped according to `timestamp_source`
+    pub fn add_message(&mut self, manager_msg: &ManagerMessage) {
         let msg = LiveMessage {
-            timestamp: Utc::now(),
-            id,
-            data,
-            bus,
+            timestamp: self.timestamp_source.resolve(manager_msg, &mut self.timestamp_origin),
+            id: manager_msg.message.id,
+            data: manager_msg.message.data.to_vec(),
+            bus: manager_msg.message.bus,
+            is_rtr: manager_msg.message.is_rtr,
+            is_error: manager_msg.message.is_error,
         };
 
-        self.live_messages.push(msg);
+        if self.is_recording {
+            let zero = self.time_zero();
+            if let Some(spill) = &mut self.spill {
+                if let Err(e) = spill.append(&msg, zero) {
+                    self.spill_error = Some(format!("Writing {} failed: {}", spill.path.display(), e));
+                    self.spill = None;
+                }
+            }
+        }
+
+        self.live_messages.push_back(msg);
         self.stats.messages_received += 1;
 
         // Trim old messages
         while self.live_messages.len() > self.max_live_messages {
-            self.live_messages.remove(0);
+            self.live_messages.pop_front();
+            self.trimmed += 1;
         }
     }
 
     /// Clear all live messages
     pub fn clear_messages(&mut self) {
         self.live_messages.clear();
+        self.trimmed = 0;
+        self.timestamp_origin = None;
+    }
+
+    /// Frames in the current/last recording, including any no longer held in memory
+    pub fn recorded_count(&self) -> u64 {
+        match &self.spill {
+            Some(spill) => spill.frames,
+            None => self.live_messages.len() as u64,
+        }
+    }
+
+    /// Temp file and first frame time of a spilled recording, to load it back for playback
+    pub fn spilled_recording(&self) -> Option<(&Path, DateTime<Utc>)> {
+        let spill = self.spill.as_ref()?;
+        Some((spill.path.as_path(), spill.first_timestamp?))
+    }
+
+    /// Save the recording as `time,addr,bus,data` CSV with times relative to `time_zero`.
+    /// With a `range` only frames timed inside it (inclusive) are kept, and `rebase`
+    /// makes their times count from its start. Returns the number of frames written.
+    pub fn save_csv(&mut self, path: &Path, range: Option<(f64, f64)>, rebase: bool) -> std::io::Result<u64> {
+        let shift = match range {
+            Some((start, _)) if rebase => start,
+            _ => 0.0,
+        };
+        let in_range = |time: f64| range.is_none_or(|(start, end)| time >= start && time <= end);
+
+        if let Some(spill) = &mut self.spill {
+            spill.writer.flush()?;
+            if range.is_none() {
+                std::fs::copy(&spill.path, path)?;
+                return Ok(spill.frames);
+            }
+            let mut out = BufWriter::new(File::create(path)?);
+            writeln!(out, "time,addr,bus,data")?;
+            let mut count = 0;
+            for line in BufReader::new(File::open(&spill.path)?).lines().skip(1) {
+                let line = line?;
+                let Some((time, rest)) = line.split_once(',') else { continue };
+                let Ok(time) = time.parse::<f64>() else { continue };
+                if in_range(time) {
+                    writeln!(out, "{:.6},{}", time - shift, rest)?;
+                    count += 1;
+                }
+            }
+            out.flush()?;
+            return Ok(count);
+        }
+
+        let mut out = BufWriter::new(File::create(path)?);
+        writeln!(out, "time,addr,bus,data")?;
+        let zero = self.time_zero();
+        let mut count = 0;
+        for msg in &self.live_messages {
+            let time = csv_time(msg, zero);
+            if in_range(time) {
+                write_csv_row(&mut out, msg, time - shift)?;
+                count += 1;
+            }
+        }
+        out.flush()?;
+        Ok(count)
+    }
+
+    /// CSV time of the newest recorded frame (the end of a whole-recording save range)
+    pub fn recorded_secs(&self) -> f64 {
+        self.live_messages.back().map_or(0.0, |msg| csv_time(msg, self.time_zero()))
     }
 
     /// Reset statistics
@@ -189,15 +487,41 @@ impl LiveModeState {
     pub fn start_recording(&mut self) {
         self.is_recording = true;
         self.recording_start = Some(Utc::now());
-        self.live_messages.clear();  // Clear previous recording
+        self.timestamp_origin = None;
+        self.clear_messages();  // Clear previous recording
         self.stats = LiveStats::default();
         self.stats.start_time = Some(Utc::now());
+
+        // Drop (and delete) the previous spill before reusing its path
+        self.spill = None;
+        self.spill_error = None;
+        if self.recording_mode == RecordingMode::SpillToDisk {
+            let path = std::env::temp_dir().join(format!("shit-recording-{}.csv", std::process::id()));
+            match RecordingSpill::create(path.clone()) {
+                Ok(spill) => self.spill = Some(spill),
+                Err(e) => self.spill_error = Some(format!("Can't create {}: {}", path.display(), e)),
+            }
+        }
     }
 
     /// Stop recording
     pub fn stop_recording(&mut self) {
         self.is_recording = false;
         // Don't clear recording_start - it's needed for CSV export timestamps
+        if let Some(spill) = &mut self.spill {
+            if let Err(e) = spill.writer.flush() {
+                self.spill_error = Some(format!("Writing {} failed: {}", spill.path.display(), e));
+                self.spill = None;
+            }
+        }
+    }
+
+    /// Time that recorded timestamps are measured from (t = 0 in exports)
+    pub fn time_zero(&self) -> Option<chrono::DateTime<Utc>> {
+        match self.timestamp_source {
+            TimestampSource::LogRelative => Some(chrono::DateTime::UNIX_EPOCH),
+            _ => self.recording_start,
+        }
     }
 
     /// Get recording duration in seconds
@@ -226,6 +550,11 @@ impl LiveModeState {
             status,
             messages_received: 0,
             errors: 0,
+            dropped_frames: 0,
+            tx_dropped_frames: 0,
+            adapter: None,
+            bus_status: BusErrorStatus::default(),
+            capabilities: None,
         });
         self.update_active_status();
     }
@@ -245,10 +574,15 @@ impl LiveModeState {
     }
 
     /// Update interface statistics
-    pub fn update_interface_stats(&mut self, bus_id: u8, messages_received: u64, errors: u64) {
-        if let Some(iface) = self.connected_interfaces.iter_mut().find(|i| i.bus_id == bus_id) {
-            iface.messages_received = messages_received;
-            iface.errors = errors;
+    pub fn update_interface_stats(&mut self, stat: &crate::hardware::can_collection::InterfaceStats) {
+        if let Some(iface) = self.connected_interfaces.iter_mut().find(|i| i.bus_id == stat.bus_id) {
+            iface.messages_received = stat.messages_received;
+            iface.errors = stat.errors;
+            iface.dropped_frames = stat.dropped_frames;
+            iface.tx_dropped_frames = stat.tx_dropped_frames;
+            iface.adapter.clone_from(&stat.adapter);
+            iface.bus_status = stat.bus_status;
+            iface.capabilities.clone_from(&stat.capabilities);
         }
     }
 
@@ -261,9 +595,13 @@ impl LiveModeState {
     /// Update interface stats from CanManagerCollection
     pub fn sync_interface_stats(&mut self, stats: &[crate::hardware::can_collection::InterfaceStats]) {
         for stat in stats {
-            self.update_interface_stats(stat.bus_id, stat.messages_received, stat.errors);
+            self.update_interface_stats(stat);
             self.update_interface_status(stat.bus_id, stat.status);
         }
+        self.stats.errors = self.connected_interfaces.iter().map(|i| i.errors).sum();
+        self.stats.dropped_frames = self.connected_interfaces.iter().map(|i| i.dropped_frames).sum();
+        self.stats.tx_dropped_frames = self.connected_interfaces.iter().map(|i| i.tx_dropped_frames).sum();
+        self.stats.bus_state = self.connected_interfaces.iter().map(|i| i.bus_status.state).max().unwrap_or_default();
     }
 
     /// Update is_active based on connected interfaces
@@ -274,7 +612,7 @@ impl LiveModeState {
 
     /// Check if recording is empty
     pub fn has_recorded_data(&self) -> bool {
-        !self.live_messages.is_empty()
+        self.recorded_count() > 0
     }
 }
 
@@ -288,7 +626,12 @@ impl Default for LiveModeState {
 pub struct HardwareManagerWindow {
     state: LiveModeState,
     bitrate_input: String,
+    rx_buffer_input: String,
     show_config: bool,
+    /// Result of the last self-test run
+    self_test_report: Option<SelfTestReport>,
+    /// A self-test is in progress
+    self_test_running: bool,
 }
 
 impl HardwareManagerWindow {
@@ -298,8 +641,11 @@ impl HardwareManagerWindow {
 
         Self {
             bitrate_input: "500000".to_string(),
+            rx_buffer_input: DEFAULT_RX_BUFFER_SIZE.to_string(),
             state,
             show_config: true,
+            self_test_report: None,
+            self_test_running: false,
         }
     }
 
@@ -311,6 +657,16 @@ impl HardwareManagerWindow {
         &mut self.state
     }
 
+    /// Mark a self-test as started (the button is disabled until its report arrives)
+    pub fn set_self_test_running(&mut self) {
+        self.self_test_running = true;
+    }
+
+    pub fn set_self_test_report(&mut self, report: SelfTestReport) {
+        self.self_test_report = Some(report);
+        self.self_test_running = false;
+    }
+
     /// Render the hardware manager window
     pub fn render(&mut self, ui: &Ui, is_open: &mut bool) -> LiveModeAction {
         let mut action = LiveModeAction::None;
@@ -386,7 +742,8 @@ impl HardwareManagerWindow {
         ui.same_line();
 
         // Save button
-        let can_save = self.state.has_recorded_data();
+        let range_valid = !self.state.save_range_only || self.state.save_range.0 < self.state.save_range.1;
+        let can_save = self.state.has_recorded_data() && range_valid;
         let _disabled = if !can_save {
             Some(ui.begin_disabled(true))
         } else {
@@ -399,6 +756,60 @@ impl HardwareManagerWindow {
         }
 
         drop(_disabled);
+        ui.same_line();
+        if ui.checkbox("Range only##save", &mut self.state.save_range_only) && self.state.save_range_only {
+            self.state.save_range = (0.0, self.state.recorded_secs());
+        }
+        if self.state.save_range_only {
+            ui.indent();
+            ui.set_next_item_width(90.0);
+            ui.input_scalar("Start (s)##save", &mut self.state.save_range.0).display_format("%.3f").build();
+            ui.same_line();
+            ui.set_next_item_width(90.0);
+            ui.input_scalar("End (s)##save", &mut self.state.save_range.1).display_format("%.3f").build();
+            ui.same_line();
+            if ui.small_button("Whole recording") {
+                self.state.save_range = (0.0, self.state.recorded_secs());
+            }
+            ui.checkbox("Times relative to range start##save", &mut self.state.save_rebase);
+            ui.unindent();
+        }
+
+        // Recording mode (fixed while recording) and what's been captured
+        ui.text("Mode:");
+        ui.same_line();
+        let _disabled = if self.state.is_recording {
+            Some(ui.begin_disabled(true))
+        } else {
+            None
+        };
+        ui.set_next_item_width(190.0);
+        let mut mode_idx = RecordingMode::ALL.iter()
+            .position(|m| *m == self.state.recording_mode)
+            .unwrap_or(0);
+        let labels: Vec<&str> = RecordingMode::ALL.iter().map(|m| m.label()).collect();
+        if ui.combo_simple_string("##recording_mode", &mut mode_idx, &labels) {
+            self.state.recording_mode = RecordingMode::ALL[mode_idx];
+        }
+        drop(_disabled);
+        if ui.is_item_hovered() {
+            ui.tooltip(|| {
+                ui.text(format!("Ring buffer: keep the newest {} frames in memory, for monitoring", self.state.max_live_messages));
+                ui.text("Unbounded: stream every frame to a temp file, for long recordings");
+            });
+        }
+        ui.same_line();
+        match self.state.recording_mode {
+            RecordingMode::RingBuffer => ui.text(format!(
+                "{} / {} frames",
+                self.state.live_messages.len(),
+                self.state.max_live_messages
+            )),
+            RecordingMode::SpillToDisk => ui.text(format!("{} frames", self.state.recorded_count())),
+        }
+        if let Some(err) = &self.state.spill_error {
+            ui.text_colored([1.0, 0.3, 0.3, 1.0], err);
+        }
 
         ui.separator();
 
@@ -407,6 +818,7 @@ impl HardwareManagerWindow {
         if self.state.available_interfaces.is_empty() {
             ui.text_colored([0.7, 0.7, 0.7, 1.0], "No interfaces found");
         } else {
+            let mut selected = None;
             for iface in &self.state.available_interfaces {
                 let is_selected = self.state.selected_interface.as_ref() == Some(&iface.name);
                 let _tok = if is_selected {
@@ -425,7 +837,7 @@ impl HardwareManagerWindow {
                 let label = format!("{} {}", type_icon, iface.name);
 
                 if ui.selectable(&label) {
-                    self.state.selected_interface = Some(iface.name.clone());
+                    selected = Some(iface.name.clone());
                 }
 
                 drop(_tok);
@@ -435,15 +847,35 @@ impl HardwareManagerWindow {
                     ui.tooltip(|| {
                         ui.text(&iface.description);
                         ui.text(format!("Type: {:?}", iface.interface_type));
+                        let connected = self.state.connected_interfaces.iter()
+                            .find(|c| c.interface_name == iface.name);
+                        if let Some(adapter) = connected.and_then(|c| c.adapter.as_deref()) {
+                            ui.text(format!("Adapter: {}", adapter));
+                        }
+                        let caps = connected.and_then(|c| c.capabilities.as_ref()).unwrap_or(&iface.capabilities);
+                        ui.text(format!(
+                            "CAN FD: {} | Listen-only: {} | HW timestamps: {} | Max payload: {} bytes",
+                            if caps.fd { "yes" } else { "no" },
+                            if caps.listen_only { "yes" } else { "no" },
+                            if caps.hardware_timestamps { "yes" } else { "no" },
+                            caps.max_payload
+                        ));
                     });
                 }
             }
+            if let Some(name) = selected {
+                self.state.select_interface(&name);
+                self.bitrate_input = self.state.config.bitrate.to_string();
+            }
         }
 
         ui.separator();
 
         // Configuration
         if ui.collapsing_header("Configuration", imgui::TreeNodeFlags::empty()) {
+            // Greyed-out options below are ones the selected interface can't honour
+            let caps = self.state.selected_capabilities();
+
             // Bitrate
             ui.text("Bitrate:");
             ui.same_line();
@@ -456,6 +888,7 @@ impl HardwareManagerWindow {
             ui.text("Presets:");
             ui.same_line();
             for &preset in &[125_000, 250_000, 500_000, 1_000_000] {
+                let _unsupported = ui.begin_disabled(!caps.supports_bitrate(preset));
                 if ui.small_button(&format!("{}", preset / 1000)) {
                     self.state.config.bitrate = preset;
                     self.bitrate_input = preset.to_string();
@@ -463,17 +896,135 @@ impl HardwareManagerWindow {
                 ui.same_line();
             }
             ui.new_line();
+            if !caps.supports_bitrate(self.state.config.bitrate) {
+                ui.text_colored([1.0, 0.6, 0.2, 1.0], "Bitrate not supported by this interface");
+            }
+
+            // CAN FD
+            let _no_fd = ui.begin_disabled(!caps.fd);
+            ui.checkbox("CAN FD", &mut self.state.config.fd_mode);
+            drop(_no_fd);
+            if !caps.fd {
+                ui.same_line();
+                ui.text_disabled("(classic CAN only)");
+            }
 
             // Listen only mode
+            let _no_listen_only = ui.begin_disabled(!caps.listen_only);
             ui.checkbox("Listen Only Mode", &mut self.state.config.listen_only);
+            drop(_no_listen_only);
             if ui.is_item_hovered() {
                 ui.tooltip(|| {
                     ui.text("When enabled, only receives messages without transmitting");
                 });
             }
 
+            // Bus number
+            let mut fixed_bus = self.state.config.bus_id.is_some();
+            if ui.checkbox("Fixed Bus Number", &mut fixed_bus) {
+                self.state.config.bus_id = fixed_bus.then_some(0);
+            }
+            if ui.is_item_hovered() {
+                ui.tooltip(|| {
+                    ui.text("Tag this adapter's frames with a chosen bus number");
+                    ui.text("Otherwise it gets the lowest bus number not in use");
+                });
+            }
+            if let Some(bus_id) = self.state.config.bus_id {
+                ui.same_line();
+                let mut bus = bus_id as i32;
+                ui.set_next_item_width(80.0);
+                if ui.input_int("##bus_id", &mut bus).build() {
+                    self.state.config.bus_id = Some(bus.clamp(0, u8::MAX as i32 - 1) as u8);
+                }
+            }
+
             // Auto-start
             ui.checkbox("Auto-start Capture", &mut self.state.config.auto_start);
+
+            // RX buffer / backpressure
+            ui.text("RX Buffer:");
+            ui.same_line();
+            ui.input_text("##rx_buffer", &mut self.rx_buffer_input).build();
+            if let Ok(val) = self.rx_buffer_input.parse::<usize>() {
+                if val > 0 {
+                    self.state.config.rx_buffer_size = val;
+                }
+            }
+            let mut drop_oldest = self.state.config.rx_overflow == RxOverflowPolicy::DropOldest;
+            if ui.checkbox("Drop Oldest When Full", &mut drop_oldest) {
+                self.state.config.rx_overflow = if drop_oldest {
+                    RxOverflowPolicy::DropOldest
+                } else {
+                    RxOverflowPolicy::DropNewest
+                };
+            }
+            if ui.is_item_hovered() {
+                ui.tooltip(|| {
+                    ui.text("When the RX buffer is full, discard the oldest frame instead of the newest");
+                    ui.text("Applies to new connections");
+                });
+            }
+
+            // TX pacing
+            ui.text("TX Gap (ms):");
+            ui.same_line();
+            let mut gap_ms = self.state.config.tx_min_gap.as_secs_f32() * 1000.0;
+            ui.set_next_item_width(80.0);
+            if ui.input_float("##tx_gap", &mut gap_ms).step(0.5).display_format("%.1f").build() {
+                self.state.config.tx_min_gap = Duration::from_secs_f32(gap_ms.clamp(0.0, 1000.0) / 1000.0);
+            }
+            if ui.is_item_hovered() {
+                ui.tooltip(|| {
+                    ui.text("Minimum spacing between transmitted frames, so bursts don't overrun the adapter's TX buffer");
+                    ui.text("Applies to new connections");
+                });
+            }
+
+            // Error-state polling
+            ui.text("Status Poll (ms):");
+            ui.same_line();
+            let mut poll_ms = self.state.config.status_poll_interval.map_or(0, |d| d.as_millis() as i32);
+            ui.set_next_item_width(80.0);
+            if ui.input_int("##status_poll", &mut poll_ms).step(250).build() {
+                self.state.config.status_poll_interval = (poll_ms > 0)
+                    .then(|| Duration::from_millis(poll_ms.clamp(50, 60_000) as u64));
+            }
+            if ui.is_item_hovered() {
+                ui.tooltip(|| {
+                    ui.text("How often to ask an SLCAN adapter for its error state (F command), 0 = never");
+                    ui.text("Error-passive and bus-off are flagged under Connected Interfaces");
+                    ui.text("The F flags have no bus-off bit: bus-off only shows on adapters that send s status reports");
+                    ui.text("Polling stops if the adapter rejects F (or didn't identify itself)");
+                    ui.text("Applies to new connections");
+                });
+            }
+
+            // Timestamp source for recordings
+            ui.text("Timestamps:");
+            ui.same_line();
+            ui.set_next_item_width(180.0);
+            let mut source_idx = TimestampSource::ALL.iter()
+                .position(|s| *s == self.state.timestamp_source)
+                .unwrap_or(0);
+            if let Some(_combo) = ui.begin_combo("##timestamp_source", TimestampSource::ALL[source_idx].label()) {
+                for (i, source) in TimestampSource::ALL.iter().enumerate() {
+                    // Without adapter timestamps "Device" would silently fall back to arrival time
+                    let unsupported = *source == TimestampSource::DeviceIfAvailable && !caps.hardware_timestamps;
+                    let _disabled = ui.begin_disabled(unsupported);
+                    if ui.selectable_config(source.label()).selected(i == source_idx).build() {
+                        source_idx = i;
+                        self.state.timestamp_source = *source;
+                    }
+                }
+            }
+            if ui.is_item_hovered() {
+                ui.tooltip(|| {
+                    ui.text("Device: adapter hardware timestamps (SLCAN with timestamping on), else arrival time");
+                    ui.text("Arrival: host time each frame was received (includes OS scheduling jitter)");
+                    ui.text("Log-relative: seconds from the first recorded frame");
+                });
+            }
         }
 
         ui.separator();
@@ -482,6 +1033,13 @@ impl HardwareManagerWindow {
         if !self.state.connected_interfaces.is_empty() {
             ui.text(format!("Connected Interfaces ({}):", self.state.connected_interfaces.len()));
 
+            // Shown outside the collapsed headers: a faulted bus otherwise just goes quiet
+            if self.state.stats.bus_state.is_fault() {
+                for iface in self.state.connected_interfaces.iter().filter(|i| i.bus_status.state.is_fault()) {
+                    ui.text_colored([1.0, 0.3, 0.3, 1.0], format!("Bus {} is {}", iface.bus_id, iface.bus_status.describe().to_lowercase()));
+                }
+            }
+
             for iface in &self.state.connected_interfaces {
                 let status_color = match iface.status {
                     ConnectionStatus::Connected => [0.0, 1.0, 0.0, 1.0],
@@ -496,9 +1054,23 @@ impl HardwareManagerWindow {
 
                     // Status indicator
                     ui.text_colored(status_color, format!("{:?}", iface.status));
+                    if let Some(adapter) = &iface.adapter {
+                        ui.same_line();
+                        ui.text_disabled(format!("Adapter: {}", adapter));
+                    }
 
                     // Statistics
                     ui.text(format!("Messages: {} | Errors: {}", iface.messages_received, iface.errors));
+                    if iface.bus_status.state != ErrorState::Active {
+                        let color = if iface.bus_status.state.is_fault() { [1.0, 0.3, 0.3, 1.0] } else { [1.0, 0.8, 0.0, 1.0] };
+                        ui.text_colored(color, iface.bus_status.describe());
+                    }
+                    if iface.dropped_frames > 0 {
+                        ui.text_colored([1.0, 0.3, 0.3, 1.0], format!("{} RX frames dropped (buffer full)", iface.dropped_frames));
+                    }
+                    if iface.tx_dropped_frames > 0 {
+                        ui.text_colored([1.0, 0.3, 0.3, 1.0], format!("{} TX frames dropped", iface.tx_dropped_frames));
+                    }
 
                     // Disconnect button for this interface
                     if ui.small_button(&format!("Disconnect Bus {}", iface.bus_id)) {
@@ -529,15 +1101,46 @@ impl HardwareManagerWindow {
             if let Some(ref iface) = self.state.selected_interface {
                 self.state.stats.start_time = Some(Utc::now());
                 self.state.status_message = format!("Connecting to {}...", iface);
+                let caps = self.state.selected_capabilities();
+                let mut config = self.state.config.clone();
+                config.fd_mode &= caps.fd;
+                config.listen_only &= caps.listen_only;
+                let iface = iface.clone();
+                self.state.remember_config(&iface, &config);
                 action = LiveModeAction::Connect {
-                    interface: iface.clone(),
-                    config: self.state.config.clone(),
+                    interface: iface,
+                    config,
                 };
             }
         }
 
         drop(_disabled);
 
+        ui.same_line();
+        let _running = ui.begin_disabled(self.self_test_running);
+        if ui.button(if self.self_test_running { "Testing..." } else { "Self-test" }) {
+            action = LiveModeAction::SelfTest;
+        }
+        drop(_running);
+        if ui.is_item_hovered() {
+            ui.tooltip(|| {
+                ui.text("Send known frames through a loopback mock interface and check");
+                ui.text("they come back, decode correctly and reach the charts");
+            });
+        }
+        if let Some(report) = &self.self_test_report {
+            let (color, verdict) = if report.passed() {
+                ([0.0, 1.0, 0.0, 1.0], "Self-test passed")
+            } else {
+                ([1.0, 0.3, 0.3, 1.0], "Self-test FAILED")
+            };
+            ui.text_colored(color, verdict);
+            for step in &report.steps {
+                let mark = if step.passed { "OK  " } else { "FAIL" };
+                ui.text_wrapped(format!("{} {}: {}", mark, step.name, step.detail));
+            }
+        }
+
         // Status message
         if !self.state.status_message.is_empty() {
             ui.text_colored([0.7, 0.7, 0.7, 1.0], &self.state.status_message);
@@ -550,6 +1153,12 @@ impl HardwareManagerWindow {
             ui.text(format!("Messages Received: {}", self.state.stats.messages_received));
             ui.text(format!("Messages Sent: {}", self.state.stats.messages_sent));
             ui.text(format!("Errors: {}", self.state.stats.errors));
+            if self.state.stats.dropped_frames > 0 {
+                ui.text_colored([1.0, 0.3, 0.3, 1.0], format!("{} RX frames dropped (buffer full)", self.state.stats.dropped_frames));
+            }
+            if self.state.stats.tx_dropped_frames > 0 {
+                ui.text_colored([1.0, 0.3, 0.3, 1.0], format!("{} TX frames dropped (write failed)", self.state.stats.tx_dropped_frames));
+            }
             ui.text(format!("Rate: {:.1} msg/s", self.state.get_rate()));
 
             if let Some(start) = self.state.stats.start_time {
@@ -592,6 +1201,8 @@ pub enum LiveModeAction {
     StartRecording,
     StopRecording,
     SaveData,
+    /// Run the mock loopback self-test
+    SelfTest,
 }
 
 /// Live message list window (separate from manager)
@@ -599,6 +1210,15 @@ pub struct LiveMessageWindow {
     filter_id: String,
     auto_scroll: bool,
     show_timestamp: bool,
+    /// One row per (ID, bus) updated in place, instead of the scrolling log
+    changed_only: bool,
+    /// Latest state per (ID, bus) for the changed-only view
+    states: BTreeMap<(u32, u8), MessageState>,
+    /// Live messages already folded into `states`
+    folded: usize,
+    byte_search: ByteSearch,
+    /// Palette for changed bytes in the changed-only view
+    color_palette: ColorPalette,
 }
 
 impl LiveMessageWindow {
@@ -607,9 +1227,45 @@ impl LiveMessageWindow {
             filter_id: String::new(),
             auto_scroll: true,
             show_timestamp: true,
+            changed_only: false,
+            states: BTreeMap::new(),
+            folded: 0,
+            byte_search: ByteSearch::default(),
+            color_palette: ColorPalette::default(),
         }
     }
 
+    pub fn set_color_palette(&mut self, palette: ColorPalette) {
+        self.color_palette = palette;
+    }
+
+    /// Fold messages received since the last call into the per-ID states
+    fn fold_new_messages(&mut self, state: &LiveModeState) {
+        // `folded` counts frames ever received, so ring-buffer trimming doesn't hide new ones;
+        // a cleared capture starts over
+        let total = state.trimmed + state.live_messages.len();
+        if total < self.folded {
+            self.states.clear();
+            self.folded = 0;
+        }
+        for live in state.live_messages.range(self.folded.saturating_sub(state.trimmed)..) {
+            let mut msg = CanMessage::new(live.bus, live.id, live.data.as_slice().into());
+            msg.timestamp = live.timestamp;
+            self.states.entry((live.id, live.bus))
+                .or_insert_with(|| MessageState::new(live.id, live.bus, MessageDirection::Rx))
+                .update(&msg, None, self.color_palette);
+        }
+        self.folded = total;
+    }
+
+    fn matches_filter(&self, id: u32) -> bool {
+        if self.filter_id.is_empty() {
+            return true;
+        }
+        let filter_lower = self.filter_id.to_lowercase();
+        format!("{:03x}", id).contains(&filter_lower) || format!("0x{:03x}", id).contains(&filter_lower)
+    }
+
     pub fn render(&mut self, ui: &Ui, state: &LiveModeState, is_open: &mut bool) {
         ui.window("Live Messages")
             .size([450.0, 350.0], Condition::FirstUseEver)
@@ -630,12 +1286,23 @@ impl LiveMessageWindow {
             .build();
 
         ui.same_line();
-        ui.checkbox("Auto-scroll", &mut self.auto_scroll);
-        ui.same_line();
-        ui.checkbox("Show Timestamp", &mut self.show_timestamp);
+        ui.checkbox("Show changed only", &mut self.changed_only);
+        if !self.changed_only {
+            ui.same_line();
+            ui.checkbox("Auto-scroll", &mut self.auto_scroll);
+            ui.same_line();
+            ui.checkbox("Show Timestamp", &mut self.show_timestamp);
+        }
+        self.byte_search.render(ui);
 
         ui.separator();
 
+        self.fold_new_messages(state);
+        if self.changed_only {
+            self.render_changed_only(ui);
+            return;
+        }
+
         // Message count
         ui.text(format!("{} messages", state.live_messages.len()));
 
@@ -653,13 +1320,8 @@ impl LiveMessageWindow {
                 let msg = &state.live_messages[i];
 
                 // Apply filter
-                if !self.filter_id.is_empty() {
-                    let filter_lower = self.filter_id.to_lowercase();
-                    let id_str = format!("{:03x}", msg.id);
-                    if !id_str.contains(&filter_lower) &&
-                       !format!("0x{:03x}", msg.id).contains(&filter_lower) {
-                        continue;
-                    }
+                if !self.matches_filter(msg.id) {
+                    continue;
                 }
 
                 let data_hex: String = msg.data.iter()
@@ -667,8 +1329,8 @@ impl LiveMessageWindow {
                     .collect::<Vec<_>>()
                     .join(" ");
 
-                if self.show_timestamp {
-                    ui.text(format!(
+                let row = if self.show_timestamp {
+                    format!(
                         "{:02}:{:02}:{:02}.{:03} | 0x{:03X} | {}",
                         msg.timestamp.hour(),
                         msg.timestamp.minute(),
@@ -676,13 +1338,34 @@ impl LiveMessageWindow {
                         msg.timestamp.nanosecond() / 1_000_000,
                         msg.id,
                         data_hex
-                    ));
+                    )
+                } else {
+                    format!("0x{:03X} | {}", msg.id, data_hex)
+                };
+                if self.byte_search.matches(&msg.to_can_message()) {
+                    ui.text_colored(BYTE_MATCH_COLOR, row);
                 } else {
-                    ui.text(format!("0x{:03X} | {}", msg.id, data_hex));
+                    ui.text(row);
                 }
             }
         }
     }
+
+    /// One row per (ID, bus) with the latest payload, bytes colored by their last change
+    fn render_changed_only(&self, ui: &Ui) {
+        ui.text(format!("{} IDs", self.states.len()));
+        ui.child_window("live_changed_only").build(|| {
+            ui.columns(2, "live_changed_cols", false);
+            ui.set_column_width(0, 200.0);
+            for msg_state in self.states.values().filter(|s| self.matches_filter(s.id)) {
+                ui.text(format!("0x{:03X}  bus {}  {:>8}  {:>6}", msg_state.id, msg_state.bus, msg_state.freq_str(), msg_state.count));
+                ui.next_column();
+                render_colored_bytes(ui, msg_state, self.byte_search.find(&msg_state.data));
+                ui.next_column();
+            }
+            ui.columns(1, "", false);
+        });
+    }
 }
 
 impl Default for LiveMessageWindow {
@@ -695,7 +1378,111 @@ impl Default for LiveMessageWindow {
 pub struct MessageSenderWindow {
     id_input: String,
     data_input: String,
-    last_error: Option<String>,
+    /// Messages from the loaded DBC, sorted by ID
+    dbc_messages: Vec<DbcMessage>,
+    /// Build the payload from DBC signal values instead of raw hex
+    use_dbc: bool,
+    selected_dbc_msg: usize,
+    /// Physical values per signal name for the selected message
+    signal_values: HashMap<String, f64>,
+    /// Frames sent this session, newest first, without repeats
+    history: VecDeque<(u32, Vec<u8>)>,
+    /// History entry recalled into the inputs with Up/Down
+    history_cursor: Option<usize>,
+    /// Bus frames are sent on
+    bus: u8,
+}
+
+/// Which sender input a history recall is filling
+#[derive(Clone, Copy)]
+enum SendField {
+    Id,
+    Data,
+}
+
+/// ID as it will be sent: 3 digits for 11-bit IDs, 8 for extended ones
+fn format_send_id(id: u32) -> String {
+    if id > 0x7FF {
+        format!("0x{:08X}", id)
+    } else {
+        format!("0x{:03X}", id)
+    }
+}
+
+/// Hex digits of a typed ID, without the 0x prefix
+fn id_digits(text: &str) -> &str {
+    let text = text.trim();
+    text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")).unwrap_or(text)
+}
+
+/// Parse the sender's hex CAN ID ("0x123" or "123")
+fn parse_send_id(text: &str) -> Result<u32, &'static str> {
+    let digits = id_digits(text);
+    if digits.is_empty() {
+        return Err("Enter an ID");
+    }
+    if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err("Not hex");
+    }
+    match u32::from_str_radix(digits, 16) {
+        Ok(id) if id <= 0x1FFF_FFFF => Ok(id),
+        _ => Err("Above 0x1FFFFFFF"),
+    }
+}
+
+/// Parse the sender's payload: bytes separated by spaces ("01 2 FF"), or runs of
+/// digit pairs ("0102FF")
+fn parse_send_data(text: &str) -> Result<Vec<u8>, String> {
+    let mut bytes = Vec::new();
+    for token in text.split_whitespace() {
+        if !token.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(format!("'{}' is not hex", token));
+        }
+        if token.len() > 2 && token.len() % 2 != 0 {
+            return Err(format!("'{}' has an odd digit count", token));
+        }
+        // All ASCII hex digits, so every slice is on a char boundary and parses
+        for start in (0..token.len()).step_by(2) {
+            let pair = &token[start..(start + 2).min(token.len())];
+            bytes.extend(u8::from_str_radix(pair, 16).ok());
+        }
+    }
+    match bytes.len() {
+        0 => Err("Enter 1-8 bytes".to_string()),
+        n if n > 8 => Err(format!("{} bytes, max 8", n)),
+        _ => Ok(bytes),
+    }
+}
+
+fn format_send_data(data: &[u8]) -> String {
+    data.iter().map(|b| format!("{:02X}", b)).collect::<Vec<_>>().join(" ")
+}
+
+/// Up/Down in the ID or data input steps through the send history, shell style
+struct HistoryRecall<'a> {
+    history: &'a VecDeque<(u32, Vec<u8>)>,
+    cursor: &'a mut Option<usize>,
+    field: SendField,
+}
+
+impl InputTextCallbackHandler for HistoryRecall<'_> {
+    fn on_history(&mut self, direction: HistoryDirection, mut data: TextCallbackData) {
+        let next = match (direction, *self.cursor) {
+            (HistoryDirection::Up, None) => 0,
+            (HistoryDirection::Up, Some(i)) => i + 1,
+            (HistoryDirection::Down, Some(i)) if i > 0 => i - 1,
+            (HistoryDirection::Down, _) => return,
+        };
+        let Some((id, bytes)) = self.history.get(next) else {
+            return;
+        };
+        *self.cursor = Some(next);
+        data.clear();
+        data.push_str(&match self.field {
+            SendField::Id => format_send_id(*id),
+            SendField::Data => format_send_data(bytes),
+        });
+    }
 }
 
 impl MessageSenderWindow {
@@ -703,74 +1490,242 @@ impl MessageSenderWindow {
         Self {
             id_input: "0x000".to_string(),
             data_input: "00 00 00 00 00 00 00 00".to_string(),
-            last_error: None,
+            dbc_messages: Vec::new(),
+            use_dbc: false,
+            selected_dbc_msg: 0,
+            signal_values: HashMap::new(),
+            history: VecDeque::new(),
+            history_cursor: None,
+            bus: 0,
+        }
+    }
+
+    /// Put a sent frame at the top of the history
+    fn record_sent(&mut self, id: u32, data: &[u8]) {
+        self.history.retain(|(i, d)| (*i, d.as_slice()) != (id, data));
+        self.history.push_front((id, data.to_vec()));
+        self.history.truncate(SEND_HISTORY_LEN);
+        self.history_cursor = None;
+    }
+
+    /// Fill the hex inputs with a history entry, to tweak and send again
+    fn load_history_entry(&mut self, idx: usize) {
+        if let Some((id, data)) = self.history.get(idx) {
+            self.id_input = format_send_id(*id);
+            self.data_input = format_send_data(data);
+            self.use_dbc = false;
         }
     }
 
-    pub fn render(&mut self, ui: &Ui, is_connected: bool, is_open: &mut bool) -> Option<(u32, Vec<u8>)> {
+    /// Offer the DBC's messages in the signal editor
+    pub fn set_dbc(&mut self, dbc: &DbcFile) {
+        self.dbc_messages = dbc.messages.clone();
+        self.dbc_messages.sort_by_key(|m| m.id);
+        self.selected_dbc_msg = 0;
+        self.signal_values.clear();
+    }
+
+    /// `buses` are the connected bus IDs; a sent frame comes back as (bus, id, data)
+    pub fn render(&mut self, ui: &Ui, buses: &[u8], is_open: &mut bool) -> Option<(u8, u32, Vec<u8>)> {
         let mut result = None;
 
         ui.window("Send Message")
-            .size([300.0, 180.0], Condition::FirstUseEver)
+            .size([360.0, 300.0], Condition::FirstUseEver)
             .position([780.0, 30.0], Condition::FirstUseEver)
             .opened(is_open)
             .build(|| {
-                result = self.render_content(ui, is_connected);
+                result = self.render_content(ui, buses);
             });
 
         result
     }
 
     /// Render content without window wrapper - for embedding in workspace
-    pub fn render_content(&mut self, ui: &Ui, is_connected: bool) -> Option<(u32, Vec<u8>)> {
-        if !is_connected {
+    pub fn render_content(&mut self, ui: &Ui, buses: &[u8]) -> Option<(u8, u32, Vec<u8>)> {
+        if buses.is_empty() {
             ui.text_colored([1.0, 0.5, 0.0, 1.0], "Not connected to CAN interface");
             return None;
         }
+        self.render_bus_selector(ui, buses);
+
+        if !self.dbc_messages.is_empty() {
+            ui.checkbox("From DBC", &mut self.use_dbc);
+        }
+        let sent = if self.use_dbc && !self.dbc_messages.is_empty() {
+            self.render_dbc_editor(ui)
+        } else {
+            self.render_hex_editor(ui)
+        };
+        let sent = sent.or_else(|| self.render_history(ui));
+        if let Some((id, data)) = &sent {
+            self.record_sent(*id, data);
+        }
+        sent.map(|(id, data)| (self.bus, id, data))
+    }
+
+    /// Bus to send on, falling back to the first connected one when the chosen bus goes away
+    fn render_bus_selector(&mut self, ui: &Ui, buses: &[u8]) {
+        let mut idx = buses.iter().position(|&b| b == self.bus).unwrap_or(0);
+        self.bus = buses[idx];
+        if buses.len() > 1 {
+            let labels: Vec<String> = buses.iter().map(|b| format!("Bus {}", b)).collect();
+            ui.set_next_item_width(120.0);
+            if ui.combo_simple_string("Bus##send_bus", &mut idx, &labels) {
+                self.bus = buses[idx];
+            }
+        } else {
+            ui.text_disabled(format!("Bus {}", self.bus));
+        }
+    }
+
+    /// Raw ID and hex payload inputs, checked as they're typed
+    fn render_hex_editor(&mut self, ui: &Ui) -> Option<(u32, Vec<u8>)> {
+        let recalled = self.history_cursor;
+        let id = parse_send_id(&self.id_input);
+        let data = parse_send_data(&self.data_input);
 
         ui.text("CAN ID (hex):");
         ui.same_line();
+        ui.set_next_item_width(120.0);
+        let invalid_bg = id.is_err().then(|| ui.push_style_color(StyleColor::FrameBg, INVALID_INPUT_BG));
         ui.input_text("##id", &mut self.id_input)
             .hint("0x123 or 123")
+            .callback(InputTextCallback::HISTORY, HistoryRecall {
+                history: &self.history,
+                cursor: &mut self.history_cursor,
+                field: SendField::Id,
+            })
             .build();
+        drop(invalid_bg);
+        let history_hint = ui.is_item_hovered();
+        ui.same_line();
+        match &id {
+            Ok(id) if *id > 0x7FF && id_digits(&self.id_input).len() <= 3 => {
+                ui.text_colored([1.0, 0.7, 0.2, 1.0], format!("{} extended", format_send_id(*id)));
+                if ui.is_item_hovered() {
+                    ui.tooltip_text("Above 0x7FF, the 11-bit maximum: this will be sent as a 29-bit extended ID");
+                }
+            }
+            Ok(id) if *id > 0x7FF => ui.text_disabled(format!("{} extended", format_send_id(*id))),
+            Ok(id) => ui.text_disabled(format_send_id(*id)),
+            Err(e) => ui.text_colored([1.0, 0.3, 0.3, 1.0], e),
+        }
 
         ui.text("Data (hex):");
         ui.same_line();
+        ui.set_next_item_width(190.0);
+        let invalid_bg = data.is_err().then(|| ui.push_style_color(StyleColor::FrameBg, INVALID_INPUT_BG));
         ui.input_text("##data", &mut self.data_input)
             .hint("01 02 03 04 05 06 07 08")
+            .callback(InputTextCallback::HISTORY, HistoryRecall {
+                history: &self.history,
+                cursor: &mut self.history_cursor,
+                field: SendField::Data,
+            })
             .build();
+        drop(invalid_bg);
+        let history_hint = history_hint || ui.is_item_hovered();
+        ui.same_line();
+        match &data {
+            Ok(bytes) => ui.text_disabled(format!("{} byte{}", bytes.len(), if bytes.len() == 1 { "" } else { "s" })),
+            Err(e) => ui.text_colored([1.0, 0.3, 0.3, 1.0], e),
+        }
+        if history_hint && !self.history.is_empty() {
+            ui.tooltip_text("Up/Down: recall recently sent frames");
+        }
 
-        if let Some(ref err) = self.last_error {
-            ui.text_colored([1.0, 0.3, 0.3, 1.0], err);
+        // The focused input recalled its half of the entry; fill in the other one
+        if self.history_cursor != recalled {
+            if let Some((id, data)) = self.history_cursor.and_then(|i| self.history.get(i)) {
+                self.id_input = format_send_id(*id);
+                self.data_input = format_send_data(data);
+            }
         }
 
-        if ui.button("Send") {
-            // Parse ID
-            let id_str = self.id_input.trim_start_matches("0x").trim_start_matches("0X");
-            let id = match u32::from_str_radix(id_str, 16) {
-                Ok(v) if v <= 0x7FF || (v <= 0x1FFFFFFF) => v,
-                _ => {
-                    self.last_error = Some("Invalid CAN ID".to_string());
-                    return None;
-                }
-            };
+        let mut sent = None;
+        ui.enabled(id.is_ok() && data.is_ok(), || {
+            if ui.button("Send") {
+                sent = id.ok().zip(data.ok());
+            }
+        });
+        sent
+    }
 
-            // Parse data
-            let data: Vec<u8> = self.data_input
-                .split_whitespace()
-                .filter_map(|s| u8::from_str_radix(s, 16).ok())
-                .collect();
+    /// Recently sent frames: Resend sends one again, clicking it loads it into the inputs
+    fn render_history(&mut self, ui: &Ui) -> Option<(u32, Vec<u8>)> {
+        if self.history.is_empty() {
+            return None;
+        }
+        ui.separator();
+        ui.text("History:");
+        ui.same_line();
+        if ui.small_button("Clear##send_history") {
+            self.history.clear();
+            self.history_cursor = None;
+            return None;
+        }
 
-            if data.is_empty() || data.len() > 8 {
-                self.last_error = Some("Data must be 1-8 bytes".to_string());
-                return None;
+        let mut resend = None;
+        let mut load = None;
+        for (idx, (id, data)) in self.history.iter().enumerate() {
+            if ui.small_button(format!("Resend##send_history{}", idx)) {
+                resend = Some((*id, data.clone()));
+            }
+            ui.same_line();
+            if ui.selectable(format!("{}  {}##send_history_entry{}", format_send_id(*id), format_send_data(data), idx)) {
+                load = Some(idx);
             }
+            if ui.is_item_hovered() {
+                ui.tooltip_text("Load into the editor");
+            }
+        }
+        if let Some(idx) = load {
+            self.load_history_entry(idx);
+        }
+        resend
+    }
 
-            self.last_error = None;
-            return Some((id, data));
+    /// Pick a DBC message and set each signal's physical value; the payload is encoded from them
+    fn render_dbc_editor(&mut self, ui: &Ui) -> Option<(u32, Vec<u8>)> {
+        self.selected_dbc_msg = self.selected_dbc_msg.min(self.dbc_messages.len() - 1);
+        let labels: Vec<String> = self.dbc_messages.iter()
+            .map(|m| format!("0x{:03X} {}", m.id, m.name))
+            .collect();
+        if ui.combo_simple_string("Message", &mut self.selected_dbc_msg, &labels) {
+            self.signal_values.clear();
+        }
+
+        let msg = &self.dbc_messages[self.selected_dbc_msg];
+        ui.separator();
+        for signal in &msg.signals {
+            let value = self.signal_values.entry(signal.name.clone()).or_insert(signal.offset);
+            ui.set_next_item_width(120.0);
+            ui.input_scalar(&signal.name, value).build();
+            let (min, max) = signal.value_range();
+            ui.same_line();
+            ui.text_disabled(format!("{} [{} .. {}]", signal.unit.as_deref().unwrap_or(""), min, max));
+        }
+        ui.separator();
+
+        let len = (msg.size as usize).min(8);
+        let encoded = try_encode_message(msg, &self.signal_values).map(|buf| buf[..len].to_vec());
+        match &encoded {
+            Ok(data) => ui.text(format!(
+                "Data: {}",
+                data.iter().map(|b| format!("{:02X}", b)).collect::<Vec<_>>().join(" ")
+            )),
+            Err(e) => ui.text_colored([1.0, 0.3, 0.3, 1.0], e.to_string()),
         }
 
-        None
+        let mut result = None;
+        ui.enabled(encoded.is_ok(), || {
+            if ui.button("Send") {
+                if let Ok(data) = &encoded {
+                    result = Some((msg.id, data.clone()));
+                }
+            }
+        });
+        result
     }
 }
 
@@ -779,3 +1734,255 @@ impl Default for MessageSenderWindow {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_changed_only_folds_per_id() {
+        let mut live = LiveModeState::new();
+        let mut window = LiveMessageWindow::new();
+        let frame = |id: u32, byte: u8| ManagerMessage {
+            message: CanMessage::new(0, id, vec![byte, 0].into()),
+            timestamp: Utc::now(),
+            device_timestamp: None,
+        };
+        for (id, byte) in [(0x100, 1), (0x200, 7), (0x100, 2), (0x100, 2)] {
+            live.add_message(&frame(id, byte));
+        }
+
+        window.fold_new_messages(&live);
+        assert_eq!(window.states.len(), 2);
+        let row = &window.states[&(0x100, 0)];
+        assert_eq!((row.count, row.data.clone()), (3, vec![2, 0]));
+
+        // Only new frames are folded in; a cleared capture starts over
+        live.add_message(&frame(0x200, 8));
+        window.fold_new_messages(&live);
+        assert_eq!(window.states[&(0x200, 0)].count, 2);
+        live.clear_messages();
+        live.add_message(&frame(0x300, 0));
+        window.fold_new_messages(&live);
+        assert_eq!(window.states.keys().copied().collect::<Vec<_>>(), vec![(0x300, 0)]);
+
+        // Ring-buffer trimming keeps the length constant, new frames still get folded
+        live.max_live_messages = 1;
+        live.add_message(&frame(0x300, 1));
+        window.fold_new_messages(&live);
+        assert_eq!(window.states[&(0x300, 0)].count, 2);
+    }
+
+    #[test]
+    fn test_sender_input_validation() {
+        assert_eq!(parse_send_id("0x123"), Ok(0x123));
+        assert_eq!(parse_send_id(" 7df "), Ok(0x7DF));
+        assert_eq!(parse_send_id("18DAF110"), Ok(0x18DA_F110));
+        assert!(parse_send_id("").is_err());
+        assert!(parse_send_id("12G").is_err());
+        assert!(parse_send_id("+12").is_err());
+        assert!(parse_send_id("20000000").is_err());
+        assert_eq!((format_send_id(0x7FF), format_send_id(0x800)), ("0x7FF".to_string(), "0x00000800".to_string()));
+
+        assert_eq!(parse_send_data("01 2 FF"), Ok(vec![0x01, 0x02, 0xFF]));
+        assert_eq!(parse_send_data("DEADBEEF 00"), Ok(vec![0xDE, 0xAD, 0xBE, 0xEF, 0x00]));
+        assert!(parse_send_data("").is_err());
+        assert!(parse_send_data("01 zz").is_err());
+        assert!(parse_send_data("123").is_err());
+        assert!(parse_send_data("00 00 00 00 00 00 00 00 00").is_err());
+    }
+
+    #[test]
+    fn test_send_history_newest_first_without_repeats() {
+        let mut sender = MessageSenderWindow::new();
+        sender.record_sent(0x100, &[1, 2]);
+        sender.record_sent(0x200, &[3]);
+        sender.record_sent(0x100, &[1, 2]);
+        assert_eq!(Vec::from(sender.history.clone()), vec![(0x100, vec![1, 2]), (0x200, vec![3])]);
+
+        for i in 0..SEND_HISTORY_LEN as u32 {
+            sender.record_sent(0x300 + i, &[0]);
+        }
+        assert_eq!(sender.history.len(), SEND_HISTORY_LEN);
+        assert_eq!(sender.history[0].0, 0x300 + SEND_HISTORY_LEN as u32 - 1);
+
+        sender.use_dbc = true;
+        sender.record_sent(0x7DF, &[0x02, 0x01, 0x0D]);
+        sender.load_history_entry(0);
+        assert_eq!((sender.id_input.as_str(), sender.data_input.as_str()), ("0x7DF", "02 01 0D"));
+        assert!(!sender.use_dbc);
+    }
+
+    #[test]
+    fn test_interface_settings_restored_on_select() {
+        let mut live = LiveModeState::new();
+        live.config.bitrate = 250_000;
+        live.config.listen_only = true;
+        live.config.bus_id = Some(2);
+        live.remember_config("/dev/ttyACM0", &live.config.clone());
+
+        live.config = LiveCanConfig::default();
+        live.select_interface("/dev/ttyUSB1");
+        assert_eq!((live.config.bitrate, live.config.listen_only), (500_000, false));
+        live.select_interface("/dev/ttyACM0");
+        assert_eq!(live.selected_interface.as_deref(), Some("/dev/ttyACM0"));
+        assert_eq!((live.config.bitrate, live.config.listen_only), (250_000, true));
+        assert_eq!(live.config.bus_id, Some(2));
+    }
+
+    #[test]
+    fn test_worst_bus_state_across_interfaces() {
+        let mut live = LiveModeState::new();
+        live.add_connected_interface(0, "/dev/ttyACM0".to_string(), ConnectionStatus::Connected);
+        live.add_connected_interface(1, "/dev/ttyACM1".to_string(), ConnectionStatus::Connected);
+        let stat = |bus_id, state| crate::hardware::can_collection::InterfaceStats {
+            bus_id,
+            interface_name: String::new(),
+            status: ConnectionStatus::Connected,
+            messages_received: 0,
+            messages_sent: 0,
+            errors: 0,
+            dropped_frames: 0,
+            adapter: None,
+            tx_dropped_frames: 0,
+            bus_status: BusErrorStatus { state, counters: None },
+            capabilities: None,
+        };
+
+        live.sync_interface_stats(&[stat(0, ErrorState::Warning), stat(1, ErrorState::Active)]);
+        assert_eq!(live.stats.bus_state, ErrorState::Warning);
+        assert!(!live.stats.bus_state.is_fault());
+
+        live.sync_interface_stats(&[stat(0, ErrorState::Warning), stat(1, ErrorState::BusOff)]);
+        assert_eq!(live.stats.bus_state, ErrorState::BusOff);
+        assert_eq!(live.connected_interfaces[1].bus_status.state, ErrorState::BusOff);
+
+        // Recovered
+        live.sync_interface_stats(&[stat(0, ErrorState::Active), stat(1, ErrorState::Active)]);
+        assert_eq!(live.stats.bus_state, ErrorState::Active);
+    }
+
+    #[test]
+    fn test_selected_capabilities_follow_connected_adapter() {
+        let mut live = LiveModeState::new();
+        live.available_interfaces.push(InterfaceInfoUI {
+            name: "/dev/ttyACM0".to_string(),
+            interface_type: InterfaceType::Serial,
+            description: String::new(),
+            available: true,
+            capabilities: crate::hardware::serial_can::SerialCanInterface::slcan_capabilities(),
+        });
+        live.select_interface("/dev/ttyACM0");
+        assert!(!live.selected_capabilities().hardware_timestamps);
+
+        // A Lawicel adapter reports timestamps as soon as it is connected
+        live.add_connected_interface(0, "/dev/ttyACM0".to_string(), ConnectionStatus::Connected);
+        live.connected_interfaces[0].capabilities = Some(CanCapabilities {
+            hardware_timestamps: true,
+            ..crate::hardware::serial_can::SerialCanInterface::slcan_capabilities()
+        });
+        assert!(live.selected_capabilities().hardware_timestamps);
+
+        live.remove_connected_interface(0);
+        assert!(!live.selected_capabilities().hardware_timestamps);
+    }
+
+    #[test]
+    fn test_recording_modes() {
+        let frame = |id: u32| ManagerMessage {
+            message: CanMessage::new(1, id, vec![0xAB].into()),
+            timestamp: Utc::now(),
+            device_timestamp: None,
+        };
+
+        let mut ring = LiveModeState::new();
+        ring.recording_mode = RecordingMode::RingBuffer;
+        ring.max_live_messages = 3;
+        ring.start_recording();
+        for id in 0..5 {
+            ring.add_message(&frame(id));
+        }
+        ring.stop_recording();
+        assert_eq!(ring.recorded_count(), 3);
+        assert_eq!(ring.live_messages.front().map(|m| m.id), Some(2));
+        assert!(ring.spilled_recording().is_none());
+
+        // Spilled recordings keep every frame on disk while memory holds only the tail
+        let mut spill = LiveModeState::new();
+        spill.max_live_messages = 3;
+        spill.start_recording();
+        for id in 0..5 {
+            spill.add_message(&frame(id));
+        }
+        spill.stop_recording();
+        assert_eq!(spill.live_messages.len(), 3);
+        assert_eq!(spill.recorded_count(), 5);
+        let spill_path = spill.spilled_recording().unwrap().0.to_path_buf();
+
+        let out = std::env::temp_dir().join("test_recording_modes.csv");
+        assert_eq!(spill.save_csv(&out, None, false).unwrap(), 5);
+        let saved = std::fs::read_to_string(&out).unwrap();
+        let _ = std::fs::remove_file(&out);
+        let lines: Vec<&str> = saved.lines().collect();
+        assert_eq!(lines.len(), 6);
+        assert_eq!(lines[0], "time,addr,bus,data");
+        assert!(lines[5].ends_with(",0x004,1,0xAB"));
+
+        // A new recording replaces (and deletes) the old temp file
+        spill.recording_mode = RecordingMode::RingBuffer;
+        spill.start_recording();
+        assert_eq!(spill.recorded_count(), 0);
+        assert!(!spill_path.exists());
+    }
+
+    #[test]
+    fn test_save_csv_time_range() {
+        let start = Utc::now();
+        let frame = |id: u32| ManagerMessage {
+            message: CanMessage::new(0, id, vec![id as u8].into()),
+            timestamp: start + chrono::Duration::seconds(id as i64),
+            device_timestamp: None,
+        };
+        let out = std::env::temp_dir().join(format!("test_save_csv_time_range-{}.csv", std::process::id()));
+
+        for mode in RecordingMode::ALL {
+            let mut live = LiveModeState::new();
+            live.recording_mode = mode;
+            live.timestamp_source = TimestampSource::LogRelative;
+            live.start_recording();
+            for id in 0..5 {
+                live.add_message(&frame(id));
+            }
+            live.stop_recording();
+            assert_eq!(live.recorded_secs(), 4.0);
+
+            assert_eq!(live.save_csv(&out, Some((1.0, 3.0)), false).unwrap(), 3);
+            let saved = std::fs::read_to_string(&out).unwrap();
+            let lines: Vec<&str> = saved.lines().collect();
+            assert_eq!(lines, vec!["time,addr,bus,data", "1.000000,0x001,0,0x01", "2.000000,0x002,0,0x02", "3.000000,0x003,0,0x03"]);
+
+            assert_eq!(live.save_csv(&out, Some((3.0, 10.0)), true).unwrap(), 2);
+            let saved = std::fs::read_to_string(&out).unwrap();
+            let lines: Vec<&str> = saved.lines().skip(1).collect();
+            assert_eq!(lines, vec!["0.000000,0x003,0,0x03", "1.000000,0x004,0,0x04"]);
+        }
+        let _ = std::fs::remove_file(&out);
+    }
+
+    #[test]
+    fn test_live_message_keeps_frame_flags() {
+        let mut live = LiveModeState::new();
+        let mut rtr = CanMessage::new(0, 0x123, vec![0; 4].into());
+        rtr.is_rtr = true;
+        let mut error = CanMessage::new(1, 0x4, vec![0, 0x10].into());
+        error.is_error = true;
+        for message in [rtr, error] {
+            live.add_message(&ManagerMessage { message, timestamp: Utc::now(), device_timestamp: None });
+        }
+
+        let converted: Vec<CanMessage> = live.live_messages.iter().map(LiveMessage::to_can_message).collect();
+        assert!(converted[0].is_rtr && !converted[0].is_error);
+        assert!(converted[1].is_error && !converted[1].is_rtr);
+        assert_eq!(&converted[1].data[..], &[0, 0x10]);
+    }
+}
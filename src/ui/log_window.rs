@@ -3,11 +3,19 @@
 use imgui::{Condition, Ui};
 
 /// Log window that displays recent log lines from the tracing buffer.
-pub struct LogWindow;
+pub struct LogWindow {
+    /// Only show lines containing this text (case-insensitive)
+    filter: String,
+    /// Keep the view pinned to the newest line
+    auto_scroll: bool,
+}
 
 impl LogWindow {
     pub fn new() -> Self {
-        Self
+        Self {
+            filter: String::new(),
+            auto_scroll: true,
+        }
     }
 
     pub fn render(&mut self, ui: &Ui, is_open: &mut bool) {
@@ -27,8 +35,26 @@ impl LogWindow {
         }
 
         let buffer = crate::logging::log_buffer();
-        let lines = match buffer.lock() {
-            Ok(guard) => guard.clone(),
+
+        ui.set_next_item_width(200.0);
+        ui.input_text("Filter##log", &mut self.filter)
+            .hint("e.g. DEBUG or chart")
+            .build();
+        ui.same_line();
+        ui.checkbox("Auto-scroll", &mut self.auto_scroll);
+        ui.same_line();
+        if ui.button("Clear") {
+            if let Ok(mut guard) = buffer.lock() {
+                guard.clear();
+            }
+        }
+
+        let filter = self.filter.to_lowercase();
+        let lines: Vec<String> = match buffer.lock() {
+            Ok(guard) => guard.iter()
+                .filter(|line| filter.is_empty() || line.to_lowercase().contains(&filter))
+                .cloned()
+                .collect(),
             Err(_) => return,
         };
 
@@ -38,6 +64,9 @@ impl LogWindow {
                 for line in &lines {
                     ui.text_wrapped(line);
                 }
+                if self.auto_scroll && ui.scroll_y() >= ui.scroll_max_y() {
+                    ui.set_scroll_here_y_with_ratio(1.0);
+                }
             });
     }
 }
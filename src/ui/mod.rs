@@ -6,12 +6,32 @@ pub mod windows;
 pub mod dialogs;
 pub mod bit_visualizer;
 pub mod log_window;
+pub mod coverage;
+pub mod range_check;
+pub mod rate_graph;
+pub mod theme;
+pub mod colors;
+pub mod clipboard;
+pub mod self_test;
+pub mod workspace;
+pub mod diagnostics;
+pub mod watch;
+pub mod signal_notes;
+pub mod timeline;
 
-pub use multi_graph::{MultiSignalGraph, SignalInfo};
+pub use multi_graph::{MissingValuePolicy, MultiSignalGraph, SignalInfo};
 pub use live_mode::{HardwareManagerWindow, LiveModeState, LiveModeAction, LiveMessageWindow, MessageSenderWindow};
 pub use statistics::{MessageStatistics, MessageStatsWindow, PatternAnalyzer, PatternAnalyzerWindow};
-pub use shortcuts::{ShortcutManager, ShortcutAction, ExportDialog, AboutDialog, ExportRequest, ExportType};
+pub use shortcuts::{ShortcutManager, ShortcutAction, ExportDialog, AboutDialog, GenerateDialog, ExportRequest, ExportType};
 pub use windows::{MessageListWindow, MessageState, MessageDirection};
 pub use dialogs::FileDialogs;
 pub use bit_visualizer::BitVisualizerWindow;
 pub use log_window::LogWindow;
+pub use coverage::CoverageWindow;
+pub use range_check::RangeCheckWindow;
+pub use rate_graph::MessageRateWindow;
+pub use theme::Theme;
+pub use workspace::{WorkspaceAction, WorkspaceStore};
+pub use diagnostics::DiagnosticsWindow;
+pub use watch::WatchWindow;
+pub use timeline::TimelineWindow;
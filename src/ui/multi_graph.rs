@@ -1,6 +1,117 @@
 use imgui::{StyleColor, Ui, MouseButton};
 use chrono::{DateTime, Utc, Duration};
+use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
+use crate::decode::formula::{Formula, VirtualSignal, VIRTUAL_MSG_ID};
+use crate::decode::router::{parse_signal_key, signal_key};
+use crate::decode::units::{conversions_for, UnitConversion};
+use crate::ui::colors::{signal_color, ColorPalette, CustomColors};
+use crate::ui::theme::{ChartPalette, Theme};
+use tracing::debug;
+
+/// Default total number of points kept across all charted series
+pub const DEFAULT_POINTS_BUDGET: usize = 2_000_000;
+
+/// Default cap on live chart points per second per series
+pub const DEFAULT_LIVE_RATE_CAP_HZ: u32 = 100;
+
+/// How long the "throttled" marker stays up after a live point was dropped
+const THROTTLE_INDICATOR_HOLD: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// Per-series floor so a crowded chart still shows a usable trace
+const MIN_POINTS_PER_SERIES: usize = 1_000;
+
+/// Largest point budget the chart settings accept
+const MAX_POINTS_BUDGET: usize = 50_000_000;
+
+/// Default max time gap between paired samples in XY mode
+const DEFAULT_XY_TOLERANCE_MS: i32 = 50;
+
+/// Scatter points drawn in XY mode before decimating
+const MAX_XY_POINTS: usize = 20_000;
+
+/// Quick time-window buttons next to the zoom slider (label, seconds); "All" is added for the whole log
+const TIME_WINDOW_PRESETS: &[(&str, f32)] = &[("1s", 1.0), ("5s", 5.0), ("30s", 30.0), ("1m", 60.0)];
+
+/// Sample markers are skipped when a series has more visible points than this per pixel column
+const MAX_MARKERS_PER_PIXEL: f32 = 0.25;
+
+/// How close (pixels) the mouse must be to a series' playhead crossing to pin its value label
+const CURSOR_VALUE_HOVER_PX: f32 = 8.0;
+
+/// Units longer than this are shortened in the Y-axis labels, which sit side by side
+const MAX_Y_LABEL_UNIT_CHARS: usize = 6;
+
+/// A value in plot coordinates: log10 on a log-scale axis, where values <= 0
+/// have no place and become NaN (a line break)
+fn plot_value(value: f64, log: bool) -> f64 {
+    match log {
+        false => value,
+        true if value > 0.0 => value.log10(),
+        true => f64::NAN,
+    }
+}
+
+/// Unit as shown in a Y-axis label, cut short with ".." if too long
+fn short_unit(unit: &str) -> String {
+    if unit.chars().count() <= MAX_Y_LABEL_UNIT_CHARS {
+        unit.to_string()
+    } else {
+        unit.chars().take(MAX_Y_LABEL_UNIT_CHARS - 2).chain("..".chars()).collect()
+    }
+}
+
+/// Pair each `x` sample with the nearest-in-time `y` sample, dropping pairs further apart than `tolerance`
+///
+/// Both slices must be time-sorted. Returns `(x_value, y_value)` pairs in `x` order.
+pub fn pair_nearest(
+    x: &[(f64, DateTime<Utc>)],
+    y: &[(f64, DateTime<Utc>)],
+    tolerance: Duration,
+) -> Vec<(f64, f64)> {
+    let mut pairs = Vec::new();
+    if y.is_empty() {
+        return pairs;
+    }
+    for &(xv, xt) in x {
+        let idx = y.partition_point(|(_, ts)| *ts < xt);
+        let nearest = [idx.checked_sub(1), Some(idx).filter(|&i| i < y.len())]
+            .into_iter()
+            .flatten()
+            .min_by_key(|&i| (y[i].1 - xt).abs());
+        if let Some(i) = nearest {
+            if (y[i].1 - xt).abs() <= tolerance {
+                pairs.push((xv, y[i].0));
+            }
+        }
+    }
+    pairs
+}
+
+/// What a series records when a frame carries its message but the signal
+/// can't be decoded from it (frame too short for the signal's bits)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum MissingValuePolicy {
+    /// Record nothing; the line joins the neighbouring samples
+    #[default]
+    Skip,
+    /// Record a NaN so the line visibly breaks there
+    Break,
+    /// Repeat the last decoded value
+    HoldLast,
+}
+
+impl MissingValuePolicy {
+    pub const ALL: [MissingValuePolicy; 3] = [MissingValuePolicy::Skip, MissingValuePolicy::Break, MissingValuePolicy::HoldLast];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            MissingValuePolicy::Skip => "Skip",
+            MissingValuePolicy::Break => "Break line",
+            MissingValuePolicy::HoldLast => "Hold last value",
+        }
+    }
+}
 
 /// A single data series for plotting
 #[derive(Clone)]
@@ -11,7 +122,20 @@ pub struct DataSeries {
     pub data_points: Vec<(f64, DateTime<Utc>)>,
     pub color: [f32; 4],
     pub visible: bool,
+    /// Fixed Y range used instead of auto-ranging while `y_locked`
+    pub y_locked: bool,
+    pub y_lock_range: (f64, f64),
+    /// Plot on a log10 Y scale (own axis only; values <= 0 aren't drawn)
+    pub log_scale: bool,
+    /// Range from the DBC definition, offered as a lock preset
+    pub dbc_range: Option<(f64, f64)>,
+    /// Unit from the DBC definition; points are always stored in it
+    pub unit: String,
+    /// Conversion applied to labels and readouts only, None = show the DBC unit
+    pub display_conversion: Option<&'static UnitConversion>,
     max_points: usize,
+    /// Time bucket of the last live point kept, for the live rate cap
+    last_live_bucket: Option<i64>,
 }
 
 impl DataSeries {
@@ -23,7 +147,14 @@ impl DataSeries {
             data_points: Vec::new(),
             color,
             visible: true,
+            y_locked: false,
+            log_scale: false,
+            y_lock_range: (0.0, 1.0),
+            dbc_range: None,
+            unit: String::new(),
+            display_conversion: None,
             max_points: 200000,  // Increased to handle large datasets
+            last_live_bucket: None,
         }
     }
 
@@ -41,33 +172,129 @@ impl DataSeries {
 
     pub fn clear(&mut self) {
         self.data_points.clear();
+        self.last_live_bucket = None;
+    }
+
+    /// The point to record for a decoded value, or for a miss under `policy`
+    fn resolve_missing(&self, value: Option<f64>, policy: MissingValuePolicy) -> Option<f64> {
+        match (value, policy) {
+            (Some(value), _) => Some(value),
+            (None, MissingValuePolicy::Skip) => None,
+            // One NaN is enough to break the line
+            (None, MissingValuePolicy::Break) => match self.data_points.last() {
+                Some((v, _)) if !v.is_nan() => Some(f64::NAN),
+                _ => None,
+            },
+            (None, MissingValuePolicy::HoldLast) => self.current_value(),
+        }
+    }
+
+    /// A stored value as shown to the user, in the display unit
+    pub fn display_value(&self, value: f64) -> f64 {
+        self.display_conversion.map_or(value, |c| c.apply(value))
+    }
+
+    /// Unit label matching `display_value`
+    pub fn display_unit(&self) -> &str {
+        self.display_conversion.map_or(self.unit.as_str(), |c| c.to)
+    }
+
+    /// Change the point cap, dropping the oldest points right away if over it
+    pub fn set_max_points(&mut self, max_points: usize) {
+        self.max_points = max_points;
+        if self.data_points.len() > max_points {
+            let keep_from = self.data_points.len() - max_points;
+            self.data_points.drain(0..keep_from);
+        }
     }
 
     /// Get min/max value in the time window. Uses binary search to slice — O(log n + k) instead of O(n).
     /// Critical for performance with large logs: previously iterated all 200k+ points per call.
     pub fn get_value_range_in_window(&self, time_start: DateTime<Utc>, time_end: DateTime<Utc>) -> (f64, f64) {
+        match self.get_stats_in_window(time_start, time_end) {
+            Some(stats) => (stats.min, stats.max),
+            None => (0.0, 1.0),
+        }
+    }
+
+    /// Y range to plot against: the locked range if set, otherwise auto-ranged over the window
+    pub fn display_range(&self, time_start: DateTime<Utc>, time_end: DateTime<Utc>) -> (f64, f64) {
+        if self.y_locked {
+            self.y_lock_range
+        } else {
+            self.get_value_range_in_window(time_start, time_end)
+        }
+    }
+
+    /// Y range in plot coordinates (see `plot_value`). On a log axis this is the
+    /// positive part of the locked range, or of the window's samples.
+    pub fn plot_range(&self, time_start: DateTime<Utc>, time_end: DateTime<Utc>, log: bool) -> (f64, f64) {
+        if !log {
+            return self.display_range(time_start, time_end);
+        }
+        let locked = Some(self.y_lock_range).filter(|(min, _)| self.y_locked && *min > 0.0);
+        let range = locked.or_else(|| {
+            let start_idx = self.data_points.partition_point(|(_, ts)| *ts < time_start);
+            let end_idx = self.data_points.partition_point(|(_, ts)| *ts <= time_end);
+            self.data_points[start_idx..end_idx].iter()
+                .map(|(v, _)| *v)
+                .filter(|v| *v > 0.0)
+                .fold(None, |range, v| match range {
+                    None => Some((v, v)),
+                    Some((min, max)) => Some((v.min(min), v.max(max))),
+                })
+        });
+        range.map_or((0.0, 1.0), |(min, max)| (min.log10(), max.log10()))
+    }
+
+    /// Get min/max/mean of the samples in the time window (single pass over the sliced window).
+    /// Returns None when the window holds no samples.
+    pub fn get_stats_in_window(&self, time_start: DateTime<Utc>, time_end: DateTime<Utc>) -> Option<WindowStats> {
         let start_idx = self.data_points.partition_point(|(_, ts)| *ts < time_start);
         let end_idx = self.data_points.partition_point(|(_, ts)| *ts <= time_end);
         let window = &self.data_points[start_idx..end_idx];
 
-        let (min, max) = window.iter()
-            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), (v, _)| {
-                (min.min(*v), max.max(*v))
+        // NaN points are line breaks, not samples
+        let (min, max, sum, count) = window.iter()
+            .filter(|(v, _)| !v.is_nan())
+            .fold((f64::INFINITY, f64::NEG_INFINITY, 0.0, 0), |(min, max, sum, count), (v, _)| {
+                (min.min(*v), max.max(*v), sum + *v, count + 1)
             });
-
-        if min == f64::INFINITY {
-            (0.0, 1.0)
-        } else {
-            (min, max)
+        if count == 0 {
+            return None;
         }
+
+        Some(WindowStats {
+            min,
+            max,
+            mean: sum / count as f64,
+            count,
+        })
     }
 
+    /// Newest decoded value, ignoring line breaks
     pub fn current_value(&self) -> Option<f64> {
-        self.data_points.last().map(|(v, _)| *v)
+        self.data_points.iter().rev().map(|(v, _)| *v).find(|v| !v.is_nan())
     }
 
-    /// Get interpolated value at a specific time. Returns None if outside data range.
+    /// The real sample closest in time to `t`
+    pub fn nearest_sample(&self, t: DateTime<Utc>) -> Option<(f64, DateTime<Utc>)> {
+        let idx = self.data_points.partition_point(|(_, ts)| *ts < t);
+        [idx.checked_sub(1), Some(idx).filter(|&i| i < self.data_points.len())]
+            .into_iter()
+            .flatten()
+            .map(|i| self.data_points[i])
+            .filter(|(v, _)| !v.is_nan())
+            .min_by_key(|(_, ts)| (*ts - t).abs())
+    }
+
+    /// Get interpolated value at a specific time. Outside the data range this is the
+    /// nearest end's value; inside a line break it is None.
     pub fn get_value_at_time(&self, t: DateTime<Utc>) -> Option<f64> {
+        self.interpolate_at(t).filter(|v| !v.is_nan())
+    }
+
+    fn interpolate_at(&self, t: DateTime<Utc>) -> Option<f64> {
         let idx = self.data_points.partition_point(|(_, ts)| *ts < t);
         if idx == 0 {
             return self.data_points.first().map(|(v, _)| *v);
@@ -86,6 +313,30 @@ impl DataSeries {
     }
 }
 
+/// Summary statistics of a series over the visible time window
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WindowStats {
+    pub min: f64,
+    pub max: f64,
+    pub mean: f64,
+    pub count: usize,
+}
+
+/// IDs a picker query could mean: `0x1A0` is hex only, bare digits match as either hex or decimal
+fn parse_id_query(query: &str) -> Vec<u32> {
+    let query = query.trim();
+    if let Some(hex) = query.strip_prefix("0x").or_else(|| query.strip_prefix("0X")) {
+        return u32::from_str_radix(hex, 16).into_iter().collect();
+    }
+    let mut ids: Vec<u32> = u32::from_str_radix(query, 16).into_iter().collect();
+    if let Ok(dec) = query.parse::<u32>() {
+        if !ids.contains(&dec) {
+            ids.push(dec);
+        }
+    }
+    ids
+}
+
 /// Signal information for the picker
 #[derive(Clone)]
 pub struct SignalInfo {
@@ -94,6 +345,8 @@ pub struct SignalInfo {
     pub bus: u8,
     pub msg_name: String,
     pub unit: String,
+    /// Physical value range from the DBC, if known
+    pub range: Option<(f64, f64)>,
 }
 
 impl SignalInfo {
@@ -102,9 +355,9 @@ impl SignalInfo {
         format!("{} [Bus {}]", self.name, self.bus)
     }
 
-    /// Get the unique key for this signal (name + bus)
+    /// Get the unique key for this signal (name + message + bus)
     pub fn key(&self) -> String {
-        format!("{}@bus{}", self.name, self.bus)
+        signal_key(&self.name, self.msg_id, self.bus)
     }
 }
 
@@ -116,19 +369,25 @@ pub enum TimelineAction {
     Pause,
     StepForward,
     StepBack,
+    /// Jump to the first frame of the log
+    GoToStart,
+    /// Jump to the last frame of the log
+    GoToEnd,
 }
 
 /// Charts panel with signal picker - Cabana-style
 pub struct MultiSignalGraph {
-    series: HashMap<String, DataSeries>,  // Key: "signal_name@busN"
+    series: HashMap<String, DataSeries>,  // Key: "signal_name@0xID@busN"
     available_signals: Vec<SignalInfo>,
     show_legend: bool,
     shared_y_axis: bool,
+    /// Show min/max/mean of the visible window next to each legend entry
+    show_stats: bool,
     time_window_secs: f32,
     graph_height: f32,
     show_signal_picker: bool,
     signal_filter: String,
-    selected_signals: HashSet<String>,  // Keys: "signal_name@busN"
+    selected_signals: HashSet<String>,  // Keys: "signal_name@0xID@busN"
     /// Pending seek request (offset in seconds from current time)
     seek_request: Option<f32>,
     /// Track if zoom slider is being dragged
@@ -140,6 +399,50 @@ pub struct MultiSignalGraph {
     /// Overall data time range (independent of charted signals)
     data_start_time: Option<DateTime<Utc>>,
     data_end_time: Option<DateTime<Utc>>,
+    /// Times of anomalous frames, ticked on the timeline scrubber (sorted)
+    anomaly_times: Vec<DateTime<Utc>>,
+    /// Missing-frame ranges, drawn as red bars along the bottom of the timeline scrubber
+    gap_ranges: Vec<(DateTime<Utc>, DateTime<Utc>)>,
+    /// Error frame times, shaded by density along the top of the timeline scrubber (sorted)
+    error_frame_times: Vec<DateTime<Utc>>,
+    /// Hardware is connected and feeding live frames
+    live: bool,
+    /// While live, pin the window's right edge to the newest sample (roll mode)
+    follow_live: bool,
+    /// Total points shared evenly between all series
+    points_budget: usize,
+    /// Max live points kept per second per series (0 = every frame)
+    live_rate_cap_hz: u32,
+    /// When a live point was last dropped by the rate cap
+    throttled_at: Option<std::time::Instant>,
+    /// What to record for a frame the signal can't be decoded from
+    missing_policy: MissingValuePolicy,
+    /// Chart area colors for the active UI theme
+    palette: ChartPalette,
+    /// Plot one signal against another instead of against time
+    xy_mode: bool,
+    xy_x_key: Option<String>,
+    xy_y_key: Option<String>,
+    /// Max time gap between paired X/Y samples, in milliseconds
+    xy_tolerance_ms: i32,
+    /// Trend line thickness override (None = theme default)
+    line_thickness: Option<f32>,
+    /// Draw a dot at each real sample in the visible window
+    show_markers: bool,
+    /// Pin a value label where the hovered series crosses the playhead line
+    show_cursor_value: bool,
+    /// Colors picked in the legend, by "signal_name@0xID@busN" (kept after the series is removed)
+    custom_colors: CustomColors,
+    /// Palette series colors are hashed into
+    color_palette: ColorPalette,
+    /// User-defined formula signals, offered in the picker after the DBC's
+    virtual_signals: Vec<VirtualSignal>,
+    /// Picker entries for `virtual_signals`
+    virtual_infos: Vec<SignalInfo>,
+    /// Virtual signal being defined in the picker (name, expression, bus)
+    new_virtual: (String, String, i32),
+    /// Keys added from the picker since last taken, for the app to fill with data
+    picker_additions: Vec<String>,
 }
 
 impl MultiSignalGraph {
@@ -149,6 +452,7 @@ impl MultiSignalGraph {
             available_signals: Vec::new(),
             show_legend: true,
             shared_y_axis: false,
+            show_stats: false,
             time_window_secs: 5.0,
             graph_height: 200.0,
             show_signal_picker: false,
@@ -160,7 +464,125 @@ impl MultiSignalGraph {
             timeline_action: None,
             data_start_time: None,
             data_end_time: None,
+            anomaly_times: Vec::new(),
+            gap_ranges: Vec::new(),
+            error_frame_times: Vec::new(),
+            live: false,
+            follow_live: true,
+            points_budget: DEFAULT_POINTS_BUDGET,
+            live_rate_cap_hz: DEFAULT_LIVE_RATE_CAP_HZ,
+            throttled_at: None,
+            missing_policy: MissingValuePolicy::default(),
+            palette: Theme::default().chart_palette(),
+            xy_mode: false,
+            xy_x_key: None,
+            xy_y_key: None,
+            xy_tolerance_ms: DEFAULT_XY_TOLERANCE_MS,
+            line_thickness: None,
+            show_markers: false,
+            show_cursor_value: false,
+            custom_colors: CustomColors::new(),
+            color_palette: ColorPalette::default(),
+            virtual_signals: Vec::new(),
+            virtual_infos: Vec::new(),
+            new_virtual: (String::new(), String::new(), 0),
+            picker_additions: Vec::new(),
+        }
+    }
+
+    /// Set the anomalous-frame times to tick on the timeline scrubber
+    pub fn set_anomaly_times(&mut self, times: Vec<DateTime<Utc>>) {
+        self.anomaly_times = times;
+    }
+
+    /// Set the time ranges where expected messages were missing
+    pub fn set_gap_ranges(&mut self, ranges: Vec<(DateTime<Utc>, DateTime<Utc>)>) {
+        self.gap_ranges = ranges;
+    }
+
+    /// Set the error frame times to shade on the timeline scrubber
+    pub fn set_error_frame_times(&mut self, times: Vec<DateTime<Utc>>) {
+        self.error_frame_times = times;
+    }
+
+    /// Switch the chart area colors to match the UI theme
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.palette = theme.chart_palette();
+    }
+
+    pub fn points_budget(&self) -> usize {
+        self.points_budget
+    }
+
+    /// Colors the user picked, by chart key
+    pub fn custom_colors(&self) -> &CustomColors {
+        &self.custom_colors
+    }
+
+    /// Switch color palettes, recoloring the charted series
+    pub fn set_color_palette(&mut self, palette: ColorPalette) {
+        self.color_palette = palette;
+        for series in self.series.values_mut() {
+            series.color = signal_color(palette, &series.name, series.msg_id, series.bus, &self.custom_colors);
+        }
+    }
+
+    /// Restore picked colors, recoloring any charted series they cover
+    pub fn set_custom_colors(&mut self, colors: CustomColors) {
+        for (key, color) in &colors {
+            if let Some(series) = self.series.get_mut(key) {
+                series.color = *color;
+            }
+        }
+        self.custom_colors = colors;
+    }
+
+    /// Set the total point budget and re-split it across the charted series
+    pub fn set_points_budget(&mut self, budget: usize) {
+        self.points_budget = budget;
+        self.rebalance_points_budget();
+    }
+
+    /// Give every series an equal share of the budget; series over their share lose their oldest points
+    fn rebalance_points_budget(&mut self) {
+        if self.series.is_empty() {
+            return;
         }
+        let share = (self.points_budget / self.series.len()).max(MIN_POINTS_PER_SERIES);
+        for series in self.series.values_mut() {
+            series.set_max_points(share);
+        }
+    }
+
+    /// Whether `series` is drawn on a log axis: its own choice, unless all series share one axis
+    fn log_y(&self, series: &DataSeries) -> bool {
+        series.log_scale && !self.shared_y_axis
+    }
+
+    pub fn live_rate_cap_hz(&self) -> u32 {
+        self.live_rate_cap_hz
+    }
+
+    /// Cap live points per second per series (0 = keep every frame)
+    pub fn set_live_rate_cap_hz(&mut self, hz: u32) {
+        self.live_rate_cap_hz = hz.min(1_000_000);
+    }
+
+    pub fn missing_policy(&self) -> MissingValuePolicy {
+        self.missing_policy
+    }
+
+    pub fn set_missing_policy(&mut self, policy: MissingValuePolicy) {
+        self.missing_policy = policy;
+    }
+
+    /// Tell the chart whether live hardware data is arriving (enables follow-live)
+    pub fn set_live(&mut self, live: bool) {
+        self.live = live;
+    }
+
+    pub fn is_following_live(&self) -> bool {
+        self.live && self.follow_live
     }
 
     /// Take and clear any pending seek request
@@ -178,12 +600,60 @@ impl MultiSignalGraph {
         self.available_signals = signals;
     }
 
+    /// User-defined virtual signals (for settings)
+    pub fn virtual_signals(&self) -> &[VirtualSignal] {
+        &self.virtual_signals
+    }
+
+    /// Replace the virtual signal definitions
+    pub fn set_virtual_signals(&mut self, signals: Vec<VirtualSignal>) {
+        self.virtual_infos = signals.iter().map(|v| SignalInfo {
+            name: v.name.clone(),
+            msg_id: VIRTUAL_MSG_ID,
+            bus: v.bus,
+            msg_name: v.expression.clone(),
+            unit: String::new(),
+            range: None,
+        }).collect();
+        self.virtual_signals = signals;
+    }
+
+    /// The virtual signal a chart key names, if it is one
+    pub fn virtual_signal(&self, key: &str) -> Option<&VirtualSignal> {
+        self.virtual_signals.iter().find(|v| v.key() == key)
+    }
+
+    /// Why a new virtual signal can't be added, if it can't
+    fn virtual_signal_error(&self, name: &str, expression: &str) -> Option<String> {
+        if name.is_empty() {
+            return Some("Name it".to_string());
+        }
+        if name.contains(['@', ' ']) {
+            return Some("Name can't contain '@' or spaces".to_string());
+        }
+        if self.virtual_signals.iter().any(|v| v.name == name) {
+            return Some(format!("{} is already defined", name));
+        }
+        Formula::parse(expression).err().map(|e| e.to_string())
+    }
+
+    /// Keys charted from the picker since the last call; they have no data yet
+    pub fn take_picker_additions(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.picker_additions)
+    }
+
     /// Set the overall data time range (independent of charted signals)
     pub fn set_data_time_range(&mut self, start: DateTime<Utc>, end: DateTime<Utc>) {
         self.data_start_time = Some(start);
         self.data_end_time = Some(end);
     }
 
+    /// Length of the overall data time range, for the "All" window preset
+    fn whole_log_secs(&self) -> Option<f32> {
+        let (start, end) = (self.data_start_time?, self.data_end_time?);
+        Some(((end - start).num_milliseconds() as f32 / 1000.0).max(1.0))
+    }
+
     /// Clear the data time range
     pub fn clear_time_range(&mut self) {
         self.data_start_time = None;
@@ -200,41 +670,27 @@ impl MultiSignalGraph {
         self.series.keys().cloned().collect()
     }
 
-    /// Toggle a signal on/off the chart by key (name@busN format)
+    /// Toggle a signal on/off the chart by key (name@0xID@busN format)
     pub fn toggle_signal_by_name(&mut self, key: &str) {
-        use std::io::Write;
-        let mut f = std::fs::OpenOptions::new()
-            .create(true)
-            .append(true)
-            .open("/tmp/can-viz-chart-debug.txt")
-            .ok();
-        if let Some(ref mut f) = f {
-            let _ = writeln!(f, "toggle_signal_by_name called with: {}", key);
-        }
-
         if self.series.contains_key(key) {
-            if let Some(ref mut f) = f { let _ = writeln!(f, "  signal already in series, removing"); }
-            self.series.remove(key);
-        } else {
-            // Find the signal info by parsing the key to extract name and bus
-            if let Some(pos) = key.find("@bus") {
-                let name = &key[..pos];
-                let bus_str = &key[pos + 4..];
-                if let Ok(bus) = bus_str.parse::<u8>() {
-                    // Match by name only (DBC definitions are bus-agnostic)
-                    // then create a new SignalInfo with the requested bus
-                    if let Some(template) = self.available_signals.iter()
-                        .find(|s| s.name == name)
-                    {
-                        let mut info = template.clone();
-                        info.bus = bus;  // Use the bus from the request key
-                        self.add_signal(&info);
-                    }
-                }
-            }
+            debug!("Removing {} from chart", key);
+            self.remove_signal(key);
+        } else if let Some(info) = self.signal_for_key(key) {
+            debug!("Adding {} to chart", key);
+            self.add_signal(&info);
         }
     }
 
+    /// The available signal a chart key names, on the key's bus. DBC definitions are
+    /// bus-agnostic; a key without a message ID takes the first message with that name.
+    fn signal_for_key(&self, key: &str) -> Option<SignalInfo> {
+        let (name, msg_id, bus) = parse_signal_key(key);
+        let template = self.available_signals.iter()
+            .chain(&self.virtual_infos)
+            .find(|s| s.name == name && msg_id.is_none_or(|id| s.msg_id == id))?;
+        Some(SignalInfo { bus, ..template.clone() })
+    }
+
     /// Add a signal to the chart
     pub fn add_signal(&mut self, info: &SignalInfo) {
         let key = info.key();
@@ -242,34 +698,45 @@ impl MultiSignalGraph {
             return;
         }
 
-        let color = self.generate_color(self.series.len());
-        let series = DataSeries::new(info.name.clone(), info.msg_id, info.bus, color);
+        let color = signal_color(self.color_palette, &info.name, info.msg_id, info.bus, &self.custom_colors);
+        let mut series = DataSeries::new(info.name.clone(), info.msg_id, info.bus, color);
+        series.dbc_range = info.range;
+        series.unit = info.unit.clone();
+        if let Some(range) = info.range {
+            series.y_lock_range = range;
+        }
         self.series.insert(key.clone(), series);
         self.selected_signals.insert(key);
+        self.rebalance_points_budget();
+    }
+
+    /// Add or remove a picker entry, keyed by `SignalInfo::key()` like the series map
+    fn toggle_picker_signal(&mut self, info: &SignalInfo) {
+        let key = info.key();
+        if self.has_signal(&key) {
+            self.remove_signal(&key);
+        } else {
+            self.add_signal(info);
+            self.picker_additions.push(key);
+        }
     }
 
     /// Remove a signal from the chart by key
     pub fn remove_signal(&mut self, key: &str) {
         self.series.remove(key);
         self.selected_signals.remove(key);
+        self.rebalance_points_budget();
     }
 
-    /// Restore chart signals from savestate (keys like "signal@bus0")
+    /// Restore chart signals from savestate (keys like "signal@0x100@bus0", or
+    /// "signal@bus0" from savestates older than message IDs in keys)
     pub fn restore_signals(&mut self, keys: &[String]) {
         for key in keys {
             if self.series.contains_key(key) {
                 continue;
             }
-            if let Some(pos) = key.find("@bus") {
-                let name = &key[..pos];
-                let bus_str = &key[pos + 4..];
-                if let Ok(bus) = bus_str.parse::<u8>() {
-                    if let Some(template) = self.available_signals.iter().find(|s| s.name == name) {
-                        let mut info = template.clone();
-                        info.bus = bus;
-                        self.add_signal(&info);
-                    }
-                }
+            if let Some(info) = self.signal_for_key(key) {
+                self.add_signal(&info);
             }
         }
     }
@@ -281,6 +748,49 @@ impl MultiSignalGraph {
         }
     }
 
+    /// Add a decoded value to a series; `None` (the signal couldn't be decoded
+    /// from the frame) is recorded according to the missing-value policy
+    pub fn add_decoded_point(&mut self, key: &str, value: Option<f64>, timestamp: DateTime<Utc>) {
+        let policy = self.missing_policy;
+        if let Some(series) = self.series.get_mut(key) {
+            if let Some(value) = series.resolve_missing(value, policy) {
+                series.add_point(value, timestamp);
+            }
+        }
+    }
+
+    /// Add a live decoded value, keeping only the first point of each `1 / live_rate_cap_hz` time bucket
+    pub fn add_live_point(&mut self, key: &str, value: Option<f64>, timestamp: DateTime<Utc>) {
+        let policy = self.missing_policy;
+        let Some(series) = self.series.get_mut(key) else {
+            return;
+        };
+        let Some(value) = series.resolve_missing(value, policy) else {
+            return;
+        };
+        if self.live_rate_cap_hz > 0 {
+            let bucket = timestamp.timestamp_micros().div_euclid(1_000_000 / self.live_rate_cap_hz as i64);
+            if series.last_live_bucket == Some(bucket) {
+                self.throttled_at = Some(std::time::Instant::now());
+                return;
+            }
+            series.last_live_bucket = Some(bucket);
+        }
+        series.add_point(value, timestamp);
+    }
+
+    /// Points currently held by a series
+    pub fn points(&self, key: &str) -> Option<&[(f64, DateTime<Utc>)]> {
+        self.series.get(key).map(|s| s.data_points.as_slice())
+    }
+
+    /// Drop one series' points (before it is recomputed)
+    pub fn clear_points(&mut self, key: &str) {
+        if let Some(series) = self.series.get_mut(key) {
+            series.clear();
+        }
+    }
+
     /// Clear all data (keep signals, just clear values)
     pub fn clear_data(&mut self) {
         for series in self.series.values_mut() {
@@ -294,21 +804,6 @@ impl MultiSignalGraph {
         self.selected_signals.clear();
     }
 
-    /// Generate a distinct color for a series based on index
-    fn generate_color(&self, index: usize) -> [f32; 4] {
-        let colors = [
-            [0.0, 0.75, 1.0, 1.0],
-            [1.0, 0.4, 0.4, 1.0],
-            [0.4, 1.0, 0.4, 1.0],
-            [1.0, 1.0, 0.4, 1.0],
-            [1.0, 0.4, 1.0, 1.0],
-            [0.4, 1.0, 1.0, 1.0],
-            [1.0, 0.6, 0.2, 1.0],
-            [0.6, 0.4, 1.0, 1.0],
-        ];
-        colors[index % colors.len()]
-    }
-
     /// Get list of charted signal names
     pub fn charted_signals(&self) -> Vec<&str> {
         self.series.keys().map(|s| s.as_str()).collect()
@@ -328,8 +823,83 @@ impl MultiSignalGraph {
         ui.same_line();
         ui.checkbox("Shared Y", &mut self.shared_y_axis);
         ui.same_line();
+        ui.checkbox("Stats", &mut self.show_stats);
+        ui.same_line();
+        if ui.checkbox("XY", &mut self.xy_mode) && self.xy_mode {
+            self.default_xy_signals();
+        }
+        ui.same_line();
+        if ui.small_button("Style") {
+            ui.open_popup("##chart_style");
+        }
+        ui.popup("##chart_style", || {
+            let mut thickness = self.line_thickness.unwrap_or(self.palette.line_thickness);
+            ui.set_next_item_width(120.0);
+            if ui.slider_config("Line thickness", 0.5, 6.0).display_format("%.1f").build(&mut thickness) {
+                self.line_thickness = Some(thickness);
+            }
+            if self.line_thickness.is_some() {
+                ui.same_line();
+                if ui.small_button("Theme default") {
+                    self.line_thickness = None;
+                }
+            }
+            ui.checkbox("Sample markers", &mut self.show_markers);
+            ui.checkbox("Value at cursor", &mut self.show_cursor_value);
+            if ui.is_item_hovered() {
+                ui.tooltip_text("Hover a series where it crosses the playhead to label its value");
+            }
+            let mut policy_idx = MissingValuePolicy::ALL.iter()
+                .position(|p| *p == self.missing_policy)
+                .unwrap_or(0);
+            let labels: Vec<&str> = MissingValuePolicy::ALL.iter().map(|p| p.label()).collect();
+            ui.set_next_item_width(120.0);
+            if ui.combo_simple_string("Undecodable", &mut policy_idx, &labels) {
+                self.missing_policy = MissingValuePolicy::ALL[policy_idx];
+            }
+            if ui.is_item_hovered() {
+                ui.tooltip_text("What to chart when a frame is too short to hold the signal (applies to newly loaded data)");
+            }
+            let mut budget_k = (self.points_budget / 1_000) as i32;
+            ui.set_next_item_width(120.0);
+            if ui.input_int("Point budget (k)", &mut budget_k).step(500).build() {
+                let budget = (budget_k.max(0) as usize * 1_000).clamp(MIN_POINTS_PER_SERIES, MAX_POINTS_BUDGET);
+                self.set_points_budget(budget);
+            }
+            if ui.is_item_hovered() {
+                ui.tooltip_text("Points kept across all charted signals, split evenly; past it the oldest points are dropped");
+            }
+        });
+        ui.same_line();
+        if self.live {
+            ui.checkbox("Follow live", &mut self.follow_live);
+            ui.same_line();
+            let mut cap = self.live_rate_cap_hz as i32;
+            ui.set_next_item_width(60.0);
+            if ui.input_int("Hz cap", &mut cap).step(0).build() {
+                self.set_live_rate_cap_hz(cap.max(0) as u32);
+            }
+            if ui.is_item_hovered() {
+                ui.tooltip_text("Max chart points per second per signal while live (0 = every frame)");
+            }
+            if self.throttled_at.is_some_and(|at| at.elapsed() < THROTTLE_INDICATOR_HOLD) {
+                ui.same_line();
+                ui.text_colored([1.0, 0.8, 0.3, 1.0], "throttled");
+                if ui.is_item_hovered() {
+                    ui.tooltip_text("Live frames are arriving faster than the cap; some are not charted");
+                }
+            }
+            ui.same_line();
+        }
         ui.text("    ");  // spacing
         ui.same_line();
+        if ui.small_button("|<") {
+            self.timeline_action = Some(TimelineAction::GoToStart);
+        }
+        if ui.is_item_hovered() {
+            ui.tooltip_text("First frame (Home)");
+        }
+        ui.same_line();
         if ui.small_button("<<") {
             self.timeline_action = Some(TimelineAction::StepBack);
         }
@@ -341,6 +911,17 @@ impl MultiSignalGraph {
         if ui.small_button(">>") {
             self.timeline_action = Some(TimelineAction::StepForward);
         }
+        ui.same_line();
+        if ui.small_button(">|") {
+            self.timeline_action = Some(TimelineAction::GoToEnd);
+        }
+        if ui.is_item_hovered() {
+            ui.tooltip_text("Last frame (End)");
+        }
+
+        if self.xy_mode {
+            self.render_xy_controls(ui);
+        }
 
         ui.spacing();
 
@@ -362,6 +943,7 @@ impl MultiSignalGraph {
                     // Positive value = relative offset from current time
                     let seek_offset_secs = (target_time - ct).num_milliseconds() as f32 / 1000.0;
                     self.seek_request = Some(seek_offset_secs);
+                    self.follow_live = false;
                 }
             }
         }
@@ -386,8 +968,22 @@ impl MultiSignalGraph {
             }
         }.max(5.0); // Minimum 5 second recording
 
+        // Window presets - the slider reads `time_window_secs` each frame, so it follows along
+        let whole_log_secs = self.whole_log_secs();
+        let presets = TIME_WINDOW_PRESETS.iter().copied().chain(whole_log_secs.map(|secs| ("All", secs)));
+        for (label, secs) in presets {
+            let active = (self.time_window_secs - secs).abs() < 0.05;
+            let _tok = if active { Some(ui.push_style_color(StyleColor::Text, [0.0, 1.0, 0.0, 1.0])) } else { None };
+            if ui.small_button(label) {
+                self.time_window_secs = secs;
+            }
+            drop(_tok);
+            ui.same_line();
+        }
+
         let slider_width = ui.content_region_avail()[0];
-        self.log_slider_widget_full_width(ui, "##time_window_slider", 1.0, recording_duration_secs, slider_width);
+        let slider_max = recording_duration_secs.max(whole_log_secs.unwrap_or(0.0));
+        self.log_slider_widget_full_width(ui, "##time_window_slider", 1.0, slider_max, slider_width);
 
         // Signal picker popup
         if self.show_signal_picker {
@@ -409,7 +1005,7 @@ impl MultiSignalGraph {
         let pos_min = cursor_pos;
         let pos_max = [cursor_pos[0] + size[0], cursor_pos[1] + size[1]];
 
-        draw_list.add_rect(pos_min, pos_max, [0.0, 0.0, 0.0, 1.0])
+        draw_list.add_rect(pos_min, pos_max, self.palette.background)
             .filled(true).rounding(4.0).build();
 
         // Determine time window - show sliding window around current time
@@ -441,7 +1037,10 @@ impl MultiSignalGraph {
         // Snap time_start to a stable bucket grid to prevent peaks "dancing" when the window
         // slides during playback — without snapping, points near bucket boundaries flip between
         // adjacent pixel columns frame-to-frame.
-        let (time_start, time_end) = if let Some(ct) = current_time {
+        let (time_start, time_end) = if self.is_following_live() {
+            // Roll mode: the newest sample sits at the right edge
+            (data_end - window_duration, data_end)
+        } else if let Some(ct) = current_time {
             let half_window = Duration::seconds((self.time_window_secs / 2.0) as i64);
             let start = (ct - half_window).max(data_start);  // Clamp to data start
             let end = start + window_duration;  // End is always window_duration from start
@@ -466,17 +1065,24 @@ impl MultiSignalGraph {
             (start, end)
         };
 
+        if self.xy_mode {
+            self.draw_xy_plot(ui, &draw_list, pos_min, pos_max, time_start, time_end);
+            ui.dummy(size);
+            self.draw_legend(ui, time_start, time_end);
+            return;
+        }
+
         // Calculate overall value range for the visible window
         let mut overall_min = f64::INFINITY;
         let mut overall_max = f64::NEG_INFINITY;
         for series in self.series.values().filter(|s| s.visible) {
-            let (min, max) = series.get_value_range_in_window(time_start, time_end);
+            let (min, max) = series.display_range(time_start, time_end);
             overall_min = overall_min.min(min);
             overall_max = overall_max.max(max);
         }
 
         // Draw vertical grid lines (always)
-        let grid_color = [0.5, 0.5, 0.5, 0.3];
+        let grid_color = self.palette.grid;
         for i in 0..=10 {
             let x = pos_min[0] + (pos_max[0] - pos_min[0]) * (i as f32 / 10.0);
             draw_list.add_line([x, pos_min[1]], [x, pos_max[1]], grid_color).build();
@@ -487,6 +1093,7 @@ impl MultiSignalGraph {
         }
 
         // Draw each visible series (min-max per-pixel decimation: preserves full vertical range at every pixel column)
+        let mut series_y_ranges = Vec::new();
         for series in self.series.values() {
             if !series.visible {
                 continue;
@@ -501,6 +1108,16 @@ impl MultiSignalGraph {
                 continue;
             }
 
+            // A log axis plots log10 of the values throughout
+            let log = self.log_y(series);
+            let log_points: Vec<(f64, DateTime<Utc>)>;
+            let window_points = if log {
+                log_points = window_points.iter().map(|(v, ts)| (plot_value(*v, true), *ts)).collect();
+                &log_points[..]
+            } else {
+                window_points
+            };
+
             // Min-max decimation: envelope shows oscillation range, trend shows smooth average.
             // Downsample computes min/max in same pass — avoids extra get_value_range iteration.
             let (trend_points, envelope_lines, range_min, range_max) = self.downsample_minmax_to_screen(
@@ -513,16 +1130,24 @@ impl MultiSignalGraph {
 
             let (min_val, max_val) = if self.shared_y_axis {
                 (overall_min, overall_max)
+            } else if series.y_locked {
+                series.plot_range(time_start, time_end, log)
             } else {
                 (range_min, range_max)
             };
+            series_y_ranges.push((series, min_val, max_val));
 
-            // Re-map trend/envelope y coords when shared axis (downsample used per-series range)
-            let (trend_points, envelope_lines) = if self.shared_y_axis {
-                let remap_y = |y: f32| self.value_to_y(
-                    self.y_to_value(y, range_min, range_max, pos_min, pos_max),
-                    overall_min, overall_max, pos_min, pos_max
-                );
+            // Re-map trend/envelope y coords when shared or locked (downsample used per-series auto range).
+            // value_to_y clamps, so locked out-of-range samples clip to the chart edges.
+            let (trend_points, envelope_lines) = if (min_val, max_val) != (range_min, range_max) {
+                let remap_y = |y: f32| if y.is_nan() {
+                    y
+                } else {
+                    self.value_to_y(
+                        self.y_to_value(y, range_min, range_max, pos_min, pos_max),
+                        min_val, max_val, pos_min, pos_max
+                    )
+                };
                 let trend: Vec<_> = trend_points.iter().map(|[x, y]| [*x, remap_y(*y)]).collect();
                 let env: Vec<_> = envelope_lines.iter()
                     .map(|(x, y0, y1)| (*x, remap_y(*y0), remap_y(*y1)))
@@ -548,27 +1173,52 @@ impl MultiSignalGraph {
             }
 
             // Draw smooth trend line on top
-            if trend_points.len() >= 2 {
-                draw_list.add_polyline(trend_points, series.color)
-                    .thickness(2.0).build();
+            let thickness = self.line_thickness.unwrap_or(self.palette.line_thickness);
+            // NaN points break the line (missing-value policy "Break line")
+            for run in trend_points.split(|[_, y]| y.is_nan()) {
+                match run {
+                    [] => {}
+                    [point] => {
+                        draw_list.add_circle(*point, thickness, series.color).filled(true).build();
+                    }
+                    _ => {
+                        draw_list.add_polyline(run.to_vec(), series.color)
+                            .thickness(thickness).build();
+                    }
+                }
+            }
+
+            // Dots at the real samples, once they are far enough apart to tell from the line
+            let max_markers = ((pos_max[0] - pos_min[0]) * MAX_MARKERS_PER_PIXEL) as usize;
+            if self.show_markers && window_points.len() <= max_markers {
+                let radius = thickness + 1.0;
+                for (value, ts) in window_points.iter().filter(|(v, _)| !v.is_nan()) {
+                    let x = self.time_to_x(*ts, time_start, time_end, pos_min, pos_max);
+                    let y = self.value_to_y(*value, min_val, max_val, pos_min, pos_max);
+                    draw_list.add_circle([x, y], radius, series.color).filled(true).build();
+                }
             }
         }
 
         // Current time indicator - show at position within the full data range
+        let mut cursor_value_pinned = false;
         if let Some(ct) = current_time {
             if ct >= time_start && ct <= time_end {
                 let x_pos = self.time_to_x(ct, time_start, time_end, pos_min, pos_max);
-                draw_list.add_line([x_pos, pos_min[1]], [x_pos, pos_max[1]], [1.0, 1.0, 0.0, 0.8])
+                draw_list.add_line([x_pos, pos_min[1]], [x_pos, pos_max[1]], self.palette.playhead)
                     .thickness(2.0).build();
+                if self.show_cursor_value {
+                    cursor_value_pinned = self.draw_cursor_value(ui, &draw_list, &series_y_ranges, (ct, x_pos), pos_min, pos_max);
+                }
             }
         }
 
         // Time labels - show time position relative to data start
         let start_offset = (time_start - data_start).num_seconds() as f64;
         let end_offset = (time_end - data_start).num_seconds() as f64;
-        draw_list.add_text([pos_min[0] + 5.0, pos_max[1] - 15.0], [0.6, 0.6, 0.6, 0.8],
+        draw_list.add_text([pos_min[0] + 5.0, pos_max[1] - 15.0], self.palette.label,
             format!("{:.0}s", start_offset));
-        draw_list.add_text([pos_max[0] - 45.0, pos_max[1] - 15.0], [0.6, 0.6, 0.6, 0.8],
+        draw_list.add_text([pos_max[0] - 45.0, pos_max[1] - 15.0], self.palette.label,
             format!("{:.0}s", end_offset));
 
         // Draw signal-specific Y-axis labels on top (after all other drawing)
@@ -587,7 +1237,7 @@ impl MultiSignalGraph {
         // Draw preview dashed line and value labels when hovering over chart
         if is_in_chart {
             let preview_x = mouse_pos[0];
-            let preview_color = [1.0, 1.0, 1.0, 0.4];  // White with low opacity
+            let preview_color = self.palette.hover_line;
 
             // Compute time at mouse x for value lookup
             let rel_x = (mouse_pos[0] - pos_min[0]) / (pos_max[0] - pos_min[0]).max(0.001);
@@ -606,17 +1256,20 @@ impl MultiSignalGraph {
                 y = segment_end + gap_size;
             }
 
-            // Draw value at intersection for each visible signal (color-coordinated)
+            // Draw value at intersection for each visible signal (color-coordinated),
+            // unless a single series' value is pinned at the playhead instead
             let label_offset = 6.0;
-            for series in self.series.values().filter(|s| s.visible) {
-                if let Some(value) = series.get_value_at_time(mouse_time) {
+            for series in self.series.values().filter(|s| s.visible && !cursor_value_pinned) {
+                let log = self.log_y(series);
+                if let Some(value) = series.get_value_at_time(mouse_time).filter(|v| !plot_value(*v, log).is_nan()) {
                     let (min_val, max_val) = if self.shared_y_axis {
                         (overall_min, overall_max)
                     } else {
-                        series.get_value_range_in_window(time_start, time_end)
+                        series.plot_range(time_start, time_end, log)
                     };
-                    let y_pos = self.value_to_y(value, min_val, max_val, pos_min, pos_max);
-                    let label = format!("{:.1}", value);
+                    let y_pos = self.value_to_y(plot_value(value, log), min_val, max_val, pos_min, pos_max);
+                    let label = format!("{:.1} {}", series.display_value(value), series.display_unit());
+                    let label = label.trim_end();
                     let text_w = label.len() as f32 * 7.0;
                     // Place to the right of line; if that overflows, place to the left
                     let text_x = if preview_x + label_offset + text_w < pos_max[0] - 5.0 {
@@ -640,6 +1293,7 @@ impl MultiSignalGraph {
                     // Calculate relative offset from current time (yellow line) to mouse position
                     let seek_offset_secs = (mouse_time - ct).num_milliseconds() as f32 / 1000.0;
                     self.seek_request = Some(seek_offset_secs);
+                    self.follow_live = false;
                 }
             }
         }
@@ -648,6 +1302,203 @@ impl MultiSignalGraph {
         self.draw_legend(ui, time_start, time_end);
     }
 
+    /// Label the series whose line meets the playhead under the mouse with its sample
+    /// nearest the playhead, pinned at that crossing. Returns whether a label was drawn.
+    fn draw_cursor_value(
+        &self,
+        ui: &Ui,
+        draw_list: &imgui::DrawListMut,
+        series_y_ranges: &[(&DataSeries, f64, f64)],
+        (current_time, cursor_x): (DateTime<Utc>, f32),
+        pos_min: [f32; 2],
+        pos_max: [f32; 2],
+    ) -> bool {
+        let mouse = ui.io().mouse_pos;
+        if (mouse[0] - cursor_x).abs() > CURSOR_VALUE_HOVER_PX || mouse[1] < pos_min[1] || mouse[1] > pos_max[1] {
+            return false;
+        }
+        let hovered = series_y_ranges.iter()
+            .filter_map(|&(series, min_val, max_val)| {
+                let (value, _) = series.nearest_sample(current_time)?;
+                let plotted = Some(plot_value(value, self.log_y(series))).filter(|v| !v.is_nan())?;
+                let y = self.value_to_y(plotted, min_val, max_val, pos_min, pos_max);
+                Some((series, value, y, (mouse[1] - y).abs()))
+            })
+            .filter(|&(_, _, _, dist)| dist <= CURSOR_VALUE_HOVER_PX)
+            .min_by(|a, b| a.3.total_cmp(&b.3));
+        let Some((series, value, y, _)) = hovered else {
+            return false;
+        };
+
+        let label = format!("{:.3} {}", series.display_value(value), series.display_unit());
+        let label = label.trim_end();
+        let [text_w, text_h] = ui.calc_text_size(label);
+        // Above and to the right of the crossing, flipped inside the chart near its edges
+        let text_x = if cursor_x + 6.0 + text_w < pos_max[0] - 4.0 { cursor_x + 6.0 } else { cursor_x - 6.0 - text_w };
+        let text_y = if y - text_h - 6.0 > pos_min[1] { y - text_h - 6.0 } else { y + 6.0 };
+
+        draw_list.add_circle([cursor_x, y], 3.5, series.color).filled(true).build();
+        draw_list.add_rect([text_x - 3.0, text_y - 1.0], [text_x + text_w + 3.0, text_y + text_h + 1.0], [0.1, 0.1, 0.1, 0.9])
+            .filled(true).rounding(2.0).build();
+        draw_list.add_rect([text_x - 3.0, text_y - 1.0], [text_x + text_w + 3.0, text_y + text_h + 1.0], series.color)
+            .rounding(2.0).build();
+        draw_list.add_text([text_x, text_y], series.color, label);
+        true
+    }
+
+    /// Sorted keys of the charted series
+    fn series_keys(&self) -> Vec<String> {
+        let mut keys: Vec<String> = self.series.keys().cloned().collect();
+        keys.sort();
+        keys
+    }
+
+    /// Fill in missing or stale X/Y assignments with the first charted signals
+    fn default_xy_signals(&mut self) {
+        let keys = self.series_keys();
+        if !self.xy_x_key.as_ref().is_some_and(|k| self.series.contains_key(k)) {
+            self.xy_x_key = keys.first().cloned();
+        }
+        if !self.xy_y_key.as_ref().is_some_and(|k| self.series.contains_key(k)) {
+            self.xy_y_key = keys.iter().find(|k| Some(*k) != self.xy_x_key.as_ref()).or(keys.first()).cloned();
+        }
+    }
+
+    fn render_xy_controls(&mut self, ui: &Ui) {
+        let keys = self.series_keys();
+        let label_of = |key: &Option<String>| match key.as_ref().and_then(|k| self.series.get(k)) {
+            Some(series) => format!("{} (bus {})", series.name, series.bus),
+            None => "(none)".to_string(),
+        };
+        let x_label = label_of(&self.xy_x_key);
+        let y_label = label_of(&self.xy_y_key);
+        let labels: Vec<String> = keys.iter().map(|k| label_of(&Some(k.clone()))).collect();
+
+        for (axis, current, slot) in [("X", x_label, 0), ("Y", y_label, 1)] {
+            ui.text(axis);
+            ui.same_line();
+            ui.set_next_item_width(180.0);
+            if let Some(_combo) = ui.begin_combo(format!("##xy_{}", axis), &current) {
+                for (key, label) in keys.iter().zip(&labels) {
+                    let target = if slot == 0 { &mut self.xy_x_key } else { &mut self.xy_y_key };
+                    let selected = target.as_ref() == Some(key);
+                    if ui.selectable_config(label).selected(selected).build() {
+                        *target = Some(key.clone());
+                    }
+                }
+            }
+            ui.same_line();
+        }
+        if ui.small_button("Swap") {
+            std::mem::swap(&mut self.xy_x_key, &mut self.xy_y_key);
+        }
+        ui.same_line();
+        ui.set_next_item_width(100.0);
+        if ui.input_int("Tolerance (ms)", &mut self.xy_tolerance_ms).build() {
+            self.xy_tolerance_ms = self.xy_tolerance_ms.max(0);
+        }
+    }
+
+    /// Scatter plot of the X signal against the Y signal over the visible window
+    fn draw_xy_plot(
+        &self,
+        ui: &Ui,
+        draw_list: &imgui::DrawListMut,
+        pos_min: [f32; 2],
+        pos_max: [f32; 2],
+        time_start: DateTime<Utc>,
+        time_end: DateTime<Utc>,
+    ) {
+        let lookup = |key: &Option<String>| key.as_ref().and_then(|k| self.series.get(k));
+        let (Some(x_series), Some(y_series)) = (lookup(&self.xy_x_key), lookup(&self.xy_y_key)) else {
+            draw_list.add_text([pos_min[0] + 10.0, pos_min[1] + 10.0], self.palette.label,
+                "Pick X and Y signals to plot");
+            return;
+        };
+
+        let tolerance = Duration::milliseconds(self.xy_tolerance_ms as i64);
+        let window = |s: &DataSeries| {
+            let lo = s.data_points.partition_point(|(_, ts)| *ts < time_start - tolerance);
+            let hi = s.data_points.partition_point(|(_, ts)| *ts <= time_end + tolerance);
+            (lo, hi)
+        };
+        let (x_lo, x_hi) = window(x_series);
+        let (y_lo, y_hi) = window(y_series);
+        let x_points: Vec<_> = x_series.data_points[x_lo..x_hi].iter()
+            .filter(|(_, ts)| *ts >= time_start && *ts <= time_end)
+            .copied()
+            .collect();
+        let pairs = pair_nearest(&x_points, &y_series.data_points[y_lo..y_hi], tolerance);
+
+        // Axis names
+        draw_list.add_text([pos_min[0] + 5.0, pos_min[1] + 5.0], y_series.color, &y_series.name);
+        let x_name_w = x_series.name.len() as f32 * 7.0;
+        draw_list.add_text([pos_max[0] - x_name_w - 5.0, pos_max[1] - 30.0], x_series.color, &x_series.name);
+
+        if pairs.is_empty() {
+            draw_list.add_text([pos_min[0] + 10.0, pos_min[1] + 25.0], self.palette.label,
+                "No samples within tolerance in this window");
+            return;
+        }
+
+        let range = |vals: &mut dyn Iterator<Item = f64>| {
+            vals.fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), v| (lo.min(v), hi.max(v)))
+        };
+        let (x_min, x_max) = range(&mut pairs.iter().map(|p| p.0));
+        let (y_min, y_max) = range(&mut pairs.iter().map(|p| p.1));
+
+        // Grid
+        let grid_color = self.palette.grid;
+        for i in 1..5 {
+            let f = i as f32 / 5.0;
+            let gx = pos_min[0] + f * (pos_max[0] - pos_min[0]);
+            let gy = pos_min[1] + f * (pos_max[1] - pos_min[1]);
+            draw_list.add_line([gx, pos_min[1]], [gx, pos_max[1]], grid_color).thickness(1.0).build();
+            draw_list.add_line([pos_min[0], gy], [pos_max[0], gy], grid_color).thickness(1.0).build();
+        }
+
+        // value_to_y maps onto the [1] component; feeding it the x extents reversed maps left-to-right
+        let x_axis_min = [0.0, pos_max[0]];
+        let x_axis_max = [0.0, pos_min[0]];
+        let to_screen = |(xv, yv): (f64, f64)| {
+            [
+                self.value_to_y(xv, x_min, x_max, x_axis_min, x_axis_max),
+                self.value_to_y(yv, y_min, y_max, pos_min, pos_max),
+            ]
+        };
+
+        let step = pairs.len().div_ceil(MAX_XY_POINTS).max(1);
+        for &pair in pairs.iter().step_by(step) {
+            draw_list.add_circle(to_screen(pair), 1.5, y_series.color).filled(true).build();
+        }
+
+        // Axis extents
+        let label_color = self.palette.label;
+        draw_list.add_text([pos_min[0] + 5.0, pos_min[1] + 20.0], label_color, format!("{:.2}", y_series.display_value(y_max)));
+        draw_list.add_text([pos_min[0] + 5.0, pos_max[1] - 30.0], label_color, format!("{:.2}", y_series.display_value(y_min)));
+        draw_list.add_text([pos_min[0] + 5.0, pos_max[1] - 15.0], label_color, format!("{:.2}", x_series.display_value(x_min)));
+        let x_max_label = format!("{:.2}", x_series.display_value(x_max));
+        draw_list.add_text([pos_max[0] - x_max_label.len() as f32 * 7.0 - 5.0, pos_max[1] - 15.0],
+            label_color, x_max_label);
+        draw_list.add_text([pos_max[0] - 140.0, pos_min[1] + 5.0], label_color,
+            format!("{} pairs", pairs.len()));
+
+        // Hover readout in signal units
+        let mouse_pos = ui.io().mouse_pos;
+        if mouse_pos[0] >= pos_min[0] && mouse_pos[0] <= pos_max[0]
+            && mouse_pos[1] >= pos_min[1] && mouse_pos[1] <= pos_max[1]
+        {
+            let rel_x = (mouse_pos[0] - pos_min[0]) / (pos_max[0] - pos_min[0]).max(0.001);
+            let xv = x_min + rel_x.clamp(0.0, 1.0) as f64 * (x_max - x_min);
+            let yv = self.y_to_value(mouse_pos[1], y_min, y_max, pos_min, pos_max);
+            ui.tooltip_text(format!(
+                "{}: {:.2} {}\n{}: {:.2} {}",
+                x_series.name, x_series.display_value(xv), x_series.display_unit(),
+                y_series.name, y_series.display_value(yv), y_series.display_unit(),
+            ));
+        }
+    }
+
     fn render_signal_picker(&mut self, ui: &Ui) {
         ui.separator();
         ui.text("Add Signal:");
@@ -661,56 +1512,111 @@ impl MultiSignalGraph {
         ui.indent();
         let filter_lower = self.signal_filter.to_lowercase();
 
-        // Collect signals to add (can't add while iterating)
-        let mut to_add: Vec<SignalInfo> = Vec::new();
-        let mut to_remove: Vec<String> = Vec::new();
+        // Collect signals to toggle (can't modify while iterating)
+        let mut to_toggle: Vec<SignalInfo> = Vec::new();
+
+        let filter_ids = parse_id_query(&self.signal_filter);
 
-        for (idx, signal) in self.available_signals.iter().enumerate() {
+        for (idx, signal) in self.available_signals.iter().chain(&self.virtual_infos).enumerate() {
             if !filter_lower.is_empty() {
                 let name_lower = signal.name.to_lowercase();
                 let msg_lower = signal.msg_name.to_lowercase();
-                if !name_lower.contains(&filter_lower) && !msg_lower.contains(&filter_lower) {
+                if !name_lower.contains(&filter_lower)
+                    && !msg_lower.contains(&filter_lower)
+                    && !filter_ids.contains(&signal.msg_id)
+                {
                     continue;
                 }
             }
 
-            let is_charted = self.has_signal(&signal.name);
+            let is_charted = self.has_signal(&signal.key());
             let label = if is_charted { "[x]" } else { "[ ]" };
 
             let _id = ui.push_id_int(idx as i32);
             if ui.small_button(label) {
-                if is_charted {
-                    to_remove.push(signal.name.clone());
-                } else {
-                    to_add.push(signal.clone());
-                }
+                to_toggle.push(signal.clone());
             }
             ui.same_line();
             ui.text_colored([0.6, 0.8, 1.0, 1.0], &signal.name);
+            if ui.is_item_hovered() {
+                ui.tooltip_text(format!("{} in {} (ID 0x{:03X} / {})", signal.name, signal.msg_name, signal.msg_id, signal.msg_id));
+            }
             ui.same_line();
-            ui.text_colored([0.5, 0.5, 0.5, 1.0], format!("({})", signal.msg_name));
+            if signal.msg_id == VIRTUAL_MSG_ID {
+                ui.text_colored([0.5, 0.5, 0.5, 1.0], format!("(= {})", signal.msg_name));
+            } else {
+                ui.text_colored([0.5, 0.5, 0.5, 1.0], format!("(0x{:03X} {})", signal.msg_id, signal.msg_name));
+            }
         }
 
         // Apply changes after iteration
-        for info in to_add {
-            self.add_signal(&info);
-        }
-        for name in to_remove {
-            self.remove_signal(&name);
+        for info in to_toggle {
+            self.toggle_picker_signal(&info);
         }
 
         ui.unindent();
+        self.render_virtual_signal_editor(ui);
         ui.separator();
     }
 
+    /// Define and delete virtual signals
+    fn render_virtual_signal_editor(&mut self, ui: &Ui) {
+        if !ui.collapsing_header("Virtual Signals", imgui::TreeNodeFlags::empty()) {
+            return;
+        }
+        let mut remove = None;
+        for (idx, signal) in self.virtual_signals.iter().enumerate() {
+            let _id = ui.push_id(format!("virtual_{}", idx));
+            if ui.small_button("Delete") {
+                remove = Some(idx);
+            }
+            ui.same_line();
+            ui.text(format!("{} = {}  [Bus {}]", signal.name, signal.expression, signal.bus));
+        }
+        if let Some(idx) = remove {
+            let mut signals = self.virtual_signals.clone();
+            let removed = signals.remove(idx);
+            self.remove_signal(&removed.key());
+            self.set_virtual_signals(signals);
+        }
+
+        let (name, expression, bus) = &mut self.new_virtual;
+        ui.set_next_item_width(100.0);
+        ui.input_text("##virtual_name", name).hint("Power").build();
+        ui.same_line();
+        ui.text("=");
+        ui.same_line();
+        ui.set_next_item_width(220.0);
+        ui.input_text("##virtual_expr", expression).hint("Voltage * Current").build();
+        ui.same_line();
+        ui.set_next_item_width(80.0);
+        if ui.input_int("Bus##virtual_bus", bus).build() {
+            *bus = (*bus).clamp(0, u8::MAX as i32);
+        }
+        ui.same_line();
+        let error = self.virtual_signal_error(&self.new_virtual.0, &self.new_virtual.1);
+        let _invalid = ui.begin_disabled(error.is_some());
+        if ui.button("Add##virtual_add") {
+            let (name, expression, bus) = std::mem::take(&mut self.new_virtual);
+            let mut signals = self.virtual_signals.clone();
+            signals.push(VirtualSignal { name, expression, bus: bus as u8 });
+            self.set_virtual_signals(signals);
+        }
+        drop(_invalid);
+        // An empty form needs no complaint
+        if let Some(error) = error.filter(|_| !self.new_virtual.1.is_empty()) {
+            ui.text_colored([1.0, 0.5, 0.4, 1.0], error);
+        }
+    }
+
     fn draw_grid(&self, draw_list: &imgui::DrawListMut, pos_min: [f32; 2], pos_max: [f32; 2], min_val: f64, max_val: f64) {
-        let grid_color = [0.5, 0.5, 0.5, 0.3];
+        let grid_color = self.palette.grid;
         for i in 0..=5 {
             let y = pos_min[1] + (pos_max[1] - pos_min[1]) * (i as f32 / 5.0);
             draw_list.add_line([pos_min[0], y], [pos_max[0], y], grid_color).build();
 
             let value = max_val - (max_val - min_val) * (i as f64 / 5.0);
-            draw_list.add_text([pos_min[0] + 5.0, y + 2.0], [0.7, 0.7, 0.7, 0.8], format!("{:.1}", value));
+            draw_list.add_text([pos_min[0] + 5.0, y + 2.0], self.palette.label, format!("{:.1}", value));
         }
 
         for i in 0..=10 {
@@ -723,12 +1629,21 @@ impl MultiSignalGraph {
     /// Labels are positioned horizontally at the top of the chart: max on top, min below, each in its signal's color
     fn draw_signal_y_labels(&self, draw_list: &imgui::DrawListMut, pos_min: [f32; 2], pos_max: [f32; 2],
                               time_start: DateTime<Utc>, time_end: DateTime<Utc>) {
-        // Collect series data first to avoid borrow issues
-        let series_data: Vec<(String, [f32; 4], f64, f64)> = self.series.values()
+        // Collect (color, max label, min label) first to avoid borrow issues.
+        // The max label carries the unit, the min label marks a log axis.
+        let series_data: Vec<([f32; 4], String, String)> = self.series.values()
             .filter(|s| s.visible)
             .map(|s| {
-                let (min_val, max_val) = s.get_value_range_in_window(time_start, time_end);
-                (s.name.clone(), s.color, min_val, max_val)
+                let log = self.log_y(s);
+                let (min_val, max_val) = s.plot_range(time_start, time_end, log);
+                let (min_val, max_val) = if log {
+                    (10f64.powf(min_val), 10f64.powf(max_val))
+                } else {
+                    (min_val, max_val)
+                };
+                let max_label = format!("{:.1} {}", s.display_value(max_val), short_unit(s.display_unit()));
+                let min_label = format!("{:.1}{}", s.display_value(min_val), if log { " log" } else { "" });
+                (s.color, max_label.trim_end().to_string(), min_label)
             })
             .collect();
 
@@ -744,12 +1659,12 @@ impl MultiSignalGraph {
         let text_height = 14.0;         // Approximate text height per row
 
         // First pass: calculate total width needed (max of max/min label widths per signal)
+        let label_width = |max_label: &str, min_label: &str| {
+            max_label.chars().count().max(min_label.chars().count()) as f32 * 7.0
+        };
         let mut total_width = 0.0;
-        for (_name, _color, min_val, max_val) in &series_data {
-            let max_label = format!("{:.1}", max_val);
-            let min_label = format!("{:.1}", min_val);
-            let width = (max_label.len().max(min_label.len()) as f32 * 7.0) + label_spacing;
-            total_width += width;
+        for (_color, max_label, min_label) in &series_data {
+            total_width += label_width(max_label, min_label) + label_spacing;
         }
 
         // Draw semi-transparent gray background behind all labels (covers both rows)
@@ -763,10 +1678,8 @@ impl MultiSignalGraph {
 
         // Draw max labels on top row, min labels on bottom row
         let mut x_pos = start_x;
-        for (_name, color, min_val, max_val) in &series_data {
-            let max_label = format!("{:.1}", max_val);
-            let min_label = format!("{:.1}", min_val);
-            let text_width = max_label.len().max(min_label.len()) as f32 * 7.0;
+        for (color, max_label, min_label) in &series_data {
+            let text_width = label_width(max_label, min_label);
 
             draw_list.add_text([x_pos, y_max], *color, max_label);
             draw_list.add_text([x_pos, y_min], *color, min_label);
@@ -881,6 +1794,46 @@ impl MultiSignalGraph {
         draw_list.add_rect(bg_min, bg_max, style.colors[imgui::StyleColor::Border as usize])
             .rounding(4.0).build();
 
+        // Anomaly ticks, at most one per pixel column
+        if let Some(data_start) = self.data_start_time {
+            let mut last_px = None;
+            for t in &self.anomaly_times {
+                let offset_secs = (*t - data_start).num_milliseconds() as f32 / 1000.0;
+                let x = bg_min[0] + (offset_secs / total_duration_secs).clamp(0.0, 1.0) * width;
+                if last_px != Some(x as i32) {
+                    last_px = Some(x as i32);
+                    draw_list.add_line([x, bg_min[1]], [x, bg_max[1]], [1.0, 0.3, 0.3, 0.8]).build();
+                }
+            }
+
+            // Missing-frame ranges, at least a pixel wide
+            let to_x = |t: DateTime<Utc>| {
+                let offset_secs = (t - data_start).num_milliseconds() as f32 / 1000.0;
+                bg_min[0] + (offset_secs / total_duration_secs).clamp(0.0, 1.0) * width
+            };
+            for (start, end) in &self.gap_ranges {
+                let (x0, x1) = (to_x(*start), to_x(*end));
+                draw_list.add_rect([x0, bg_max[1] - 4.0], [x1.max(x0 + 1.0), bg_max[1]], [1.0, 0.2, 0.2, 0.9])
+                    .filled(true).build();
+            }
+
+            // Error frame density: errors per pixel column, opacity relative to the busiest column
+            let mut columns: Vec<(i32, u32)> = Vec::new();
+            for t in &self.error_frame_times {
+                let px = to_x(*t) as i32;
+                match columns.last_mut() {
+                    Some((last, count)) if *last == px => *count += 1,
+                    _ => columns.push((px, 1)),
+                }
+            }
+            let busiest = columns.iter().map(|(_, count)| *count).max().unwrap_or(1) as f32;
+            for (px, count) in columns {
+                let alpha = 0.35 + 0.65 * count as f32 / busiest;
+                draw_list.add_rect([px as f32, bg_min[1]], [px as f32 + 1.0, bg_min[1] + 4.0], [1.0, 0.1, 0.6, alpha])
+                    .filled(true).build();
+            }
+        }
+
         // Calculate grab position
         let grab_x = bg_min[0] + current_pos * (bg_max[0] - bg_min[0]);
         let grab_min = [grab_x - grab_size / 2.0, bg_min[1] + 2.0];
@@ -1034,26 +1987,98 @@ impl MultiSignalGraph {
         // Collect changes to apply after iteration
         let mut visibility_changes: Vec<(String, bool)> = Vec::new();
         let mut to_remove: Vec<String> = Vec::new();
+        let mut unit_changes: Vec<(String, Option<&'static UnitConversion>)> = Vec::new();
+        let mut color_changes: Vec<(String, [f32; 4])> = Vec::new();
         let series_names: Vec<String> = self.series.keys().cloned().collect();
 
         for (idx, name) in series_names.iter().enumerate() {
             if let Some(series) = self.series.get(name) {
                 ui.same_line();
-                ui.color_button("##color", series.color);
+                let _id = ui.push_id_int(idx as i32);
+                let mut color = series.color;
+                if ui.color_edit4_config("##color", &mut color).inputs(false).label(false).alpha(false).build() {
+                    color_changes.push((name.clone(), color));
+                }
                 ui.same_line();
 
                 let mut visible = series.visible;
-                let _id = ui.push_id_int(idx as i32);
                 if ui.checkbox(&series.name, &mut visible) {
                     visibility_changes.push((name.clone(), visible));
                 }
 
                 ui.same_line();
 
+                // Display unit: converts labels and readouts only, stored points stay in the DBC unit
+                let conversions = conversions_for(&series.unit);
+                if !conversions.is_empty() {
+                    ui.set_next_item_width(60.0);
+                    if let Some(_combo) = ui.begin_combo("##unit", series.display_unit()) {
+                        let current = series.display_conversion;
+                        if ui.selectable_config(&series.unit).selected(current.is_none()).build() {
+                            unit_changes.push((name.clone(), None));
+                        }
+                        for conversion in conversions {
+                            if ui.selectable_config(conversion.to).selected(current == Some(conversion)).build() {
+                                unit_changes.push((name.clone(), Some(conversion)));
+                            }
+                        }
+                    }
+                    ui.same_line();
+                }
+
                 // X button to remove
                 if ui.small_button("x") {
                     to_remove.push(name.clone());
                 }
+
+                if self.show_stats {
+                    ui.same_line();
+                    match series.get_stats_in_window(time_start, time_end) {
+                        Some(stats) => ui.text_colored(series.color, format!(
+                            "min {:.2}  max {:.2}  mean {:.2} {}",
+                            series.display_value(stats.min),
+                            series.display_value(stats.max),
+                            series.display_value(stats.mean),
+                            series.display_unit(),
+                        )),
+                        None => ui.text_disabled("no samples"),
+                    }
+                }
+            }
+
+            // Y range lock: fixed min/max (or the DBC range) instead of auto-ranging
+            if let Some(series) = self.series.get_mut(name) {
+                let _id = ui.push_id_int(idx as i32);
+                ui.same_line();
+                if ui.checkbox("Lock Y", &mut series.y_locked) && series.y_locked && series.dbc_range.is_none() {
+                    // No DBC range to start from: freeze the current auto range
+                    series.y_lock_range = series.get_value_range_in_window(time_start, time_end);
+                }
+                if series.y_locked {
+                    let (mut min, mut max) = series.y_lock_range;
+                    ui.same_line();
+                    ui.set_next_item_width(60.0);
+                    let min_changed = ui.input_scalar("##ymin", &mut min).build();
+                    ui.same_line();
+                    ui.set_next_item_width(60.0);
+                    let max_changed = ui.input_scalar("##ymax", &mut max).build();
+                    if (min_changed || max_changed) && max > min {
+                        series.y_lock_range = (min, max);
+                    }
+                    if let Some(range) = series.dbc_range {
+                        ui.same_line();
+                        if ui.small_button("DBC") {
+                            series.y_lock_range = range;
+                        }
+                    }
+                }
+                ui.same_line();
+                ui.disabled(self.shared_y_axis, || {
+                    ui.checkbox("Log", &mut series.log_scale);
+                });
+                if ui.is_item_hovered_with_flags(imgui::ItemHoveredFlags::ALLOW_WHEN_DISABLED) {
+                    ui.tooltip_text("Log10 Y scale for this signal (values <= 0 aren't drawn; not with Shared Y)");
+                }
             }
         }
 
@@ -1063,6 +2088,17 @@ impl MultiSignalGraph {
                 s.visible = visible;
             }
         }
+        for (name, conversion) in unit_changes {
+            if let Some(s) = self.series.get_mut(&name) {
+                s.display_conversion = conversion;
+            }
+        }
+        for (name, color) in color_changes {
+            if let Some(s) = self.series.get_mut(&name) {
+                s.color = color;
+            }
+            self.custom_colors.insert(name, color);
+        }
         for name in to_remove {
             self.remove_signal(&name);
         }
@@ -1116,7 +2152,12 @@ impl MultiSignalGraph {
             let trend = points.iter()
                 .map(|(v, t)| {
                     let x = round_to_pixel(self.time_to_x(*t, time_start, time_end, pos_min, pos_max));
-                    let y = round_to_pixel(self.value_to_y(*v, min_val, max_val, pos_min, pos_max));
+                    // A NaN sample is a line break; keep it NaN for the caller to split on
+                    let y = if v.is_nan() {
+                        f32::NAN
+                    } else {
+                        round_to_pixel(self.value_to_y(*v, min_val, max_val, pos_min, pos_max))
+                    };
                     [x, y]
                 })
                 .collect();
@@ -1128,12 +2169,14 @@ impl MultiSignalGraph {
             return (vec![], vec![], 0.0, 1.0);
         }
 
-        // Per-pixel-column bucket: tracks min/max/sum for envelope + average trend
+        // Per-pixel-column bucket: tracks min/max/sum for envelope + average trend,
+        // and whether the column ends on a line break (NaN sample)
         struct Bucket {
             min: f64,
             max: f64,
             sum: f64,
             count: usize,
+            ends_broken: bool,
         }
 
         // Assign every data point to its pixel column bucket
@@ -1144,27 +2187,26 @@ impl MultiSignalGraph {
             let frac = elapsed_ms / total_duration_ms;
             let px = ((frac * width as f64).floor() as usize).min(width - 1);
 
-            match &mut buckets[px] {
-                None => {
-                    buckets[px] = Some(Bucket {
-                        min: *v,
-                        max: *v,
-                        sum: *v,
-                        count: 1,
-                    });
-                }
-                Some(b) => {
-                    if *v < b.min { b.min = *v; }
-                    if *v > b.max { b.max = *v; }
-                    b.sum += *v;
-                    b.count += 1;
-                }
+            let b = buckets[px].get_or_insert(Bucket {
+                min: f64::INFINITY,
+                max: f64::NEG_INFINITY,
+                sum: 0.0,
+                count: 0,
+                ends_broken: false,
+            });
+            b.ends_broken = v.is_nan();
+            if !v.is_nan() {
+                b.min = b.min.min(*v);
+                b.max = b.max.max(*v);
+                b.sum += *v;
+                b.count += 1;
             }
         }
 
         // Derive overall min/max from buckets (no extra iteration over points)
         let (min_val, max_val) = buckets.iter()
             .filter_map(|b| b.as_ref())
+            .filter(|b| b.count > 0)
             .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), b| {
                 (min.min(b.min), max.max(b.max))
             });
@@ -1182,10 +2224,16 @@ impl MultiSignalGraph {
         let chart_width = pos_max[0] - pos_min[0];
         let col_width = chart_width / width as f32;
         let mut last_avg = None::<f64>;
+        let mut broken = false;
         for (px, bucket) in buckets.iter().enumerate() {
             let x = round_to_pixel(pos_min[0] + (px as f32 + 0.5) * col_width); // center of bucket column
 
-            let (avg, env_opt) = if let Some(b) = bucket {
+            // Don't bridge a line break with the last value; resume at the next sample
+            if broken && bucket.as_ref().is_none_or(|b| b.count == 0) {
+                continue;
+            }
+
+            let (avg, env_opt) = if let Some(b) = bucket.as_ref().filter(|b| b.count > 0) {
                 let avg = b.sum / b.count as f64;
                 last_avg = Some(avg);
                 let y_min = round_to_pixel(self.value_to_y(b.min, min_val, max_val, pos_min, pos_max));
@@ -1204,6 +2252,10 @@ impl MultiSignalGraph {
 
             let y_avg = round_to_pixel(self.value_to_y(avg, min_val, max_val, pos_min, pos_max));
             trend.push([x, y_avg]);
+            broken = bucket.as_ref().is_some_and(|b| b.ends_broken);
+            if broken {
+                trend.push([x, f32::NAN]);
+            }
 
             // LOD: skip envelope when zoomed out — it becomes a solid block, trend line is enough
             if points_per_pixel <= Self::ENVELOPE_SKIP_POINTS_PER_PIXEL {
@@ -1309,3 +2361,334 @@ impl SignalBrowser {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pair_nearest_within_tolerance() {
+        let base = Utc::now();
+        let at = |ms: i64| base + Duration::milliseconds(ms);
+        // X at 10 Hz, Y at ~33 Hz with a gap around 200ms
+        let x: Vec<_> = (0..4).map(|i| (i as f64, at(i * 100))).collect();
+        let y = vec![(10.0, at(0)), (11.0, at(30)), (12.0, at(90)), (13.0, at(120)), (14.0, at(330))];
+
+        let pairs = pair_nearest(&x, &y, Duration::milliseconds(20));
+        assert_eq!(pairs, vec![(0.0, 10.0), (1.0, 12.0)]);
+
+        let pairs = pair_nearest(&x, &y, Duration::milliseconds(40));
+        assert_eq!(pairs, vec![(0.0, 10.0), (1.0, 12.0), (3.0, 14.0)]);
+
+        assert!(pair_nearest(&x, &[], Duration::milliseconds(40)).is_empty());
+    }
+
+    #[test]
+    fn test_nearest_sample() {
+        let base = Utc::now();
+        let mut series = DataSeries::new("Speed".to_string(), 0x100, 0, [1.0; 4]);
+        assert_eq!(series.nearest_sample(base), None);
+        for (ms, v) in [(0, 1.0), (100, 2.0), (300, 3.0)] {
+            series.add_point(v, base + Duration::milliseconds(ms));
+        }
+        let at = |ms| series.nearest_sample(base + Duration::milliseconds(ms)).map(|(v, _)| v);
+        assert_eq!(at(-50), Some(1.0));
+        assert_eq!(at(40), Some(1.0));
+        assert_eq!(at(60), Some(2.0));
+        assert_eq!(at(250), Some(3.0));
+        assert_eq!(at(1_000), Some(3.0));
+    }
+
+    #[test]
+    fn test_stats_in_window() {
+        let base = Utc::now();
+        let mut series = DataSeries::new("Speed".to_string(), 0x100, 0, [1.0; 4]);
+        for (i, v) in [1.0, 5.0, 3.0, 100.0].iter().enumerate() {
+            series.add_point(*v, base + Duration::seconds(i as i64));
+        }
+
+        // Window covering only the first three samples
+        let stats = series.get_stats_in_window(base, base + Duration::seconds(2)).unwrap();
+        assert_eq!(stats.count, 3);
+        assert_eq!(stats.min, 1.0);
+        assert_eq!(stats.max, 5.0);
+        assert_eq!(stats.mean, 3.0);
+
+        assert!(series.get_stats_in_window(base + Duration::seconds(10), base + Duration::seconds(20)).is_none());
+        assert_eq!(series.get_value_range_in_window(base + Duration::seconds(10), base + Duration::seconds(20)), (0.0, 1.0));
+    }
+
+    #[test]
+    fn test_locked_range_clips() {
+        let base = Utc::now();
+        let mut series = DataSeries::new("Speed".to_string(), 0x100, 0, [1.0; 4]);
+        series.add_point(10.0, base);
+        series.add_point(500.0, base + Duration::seconds(1));
+        let window_end = base + Duration::seconds(1);
+        assert_eq!(series.display_range(base, window_end), (10.0, 500.0));
+
+        series.y_locked = true;
+        series.y_lock_range = (0.0, 100.0);
+        assert_eq!(series.display_range(base, window_end), (0.0, 100.0));
+
+        let graph = MultiSignalGraph::new();
+        let (pos_min, pos_max) = ([0.0, 0.0], [100.0, 100.0]);
+        assert_eq!(graph.value_to_y(500.0, 0.0, 100.0, pos_min, pos_max), pos_min[1]);
+        assert_eq!(graph.value_to_y(-50.0, 0.0, 100.0, pos_min, pos_max), pos_max[1]);
+    }
+
+    #[test]
+    fn test_log_plot_range_and_label_units() {
+        let base = Utc::now();
+        let mut series = DataSeries::new("Current".to_string(), 0x100, 0, [1.0; 4]);
+        for (i, v) in [0.0, 0.1, 1000.0, -5.0].into_iter().enumerate() {
+            series.add_point(v, base + Duration::seconds(i as i64));
+        }
+        let window_end = base + Duration::seconds(3);
+        assert_eq!(series.plot_range(base, window_end, false), (-5.0, 1000.0));
+        // Only the positive samples have a place on a log axis
+        let (min, max) = series.plot_range(base, window_end, true);
+        assert!((min + 1.0).abs() < 1e-9 && (max - 3.0).abs() < 1e-9);
+        assert!(plot_value(-5.0, true).is_nan());
+
+        // A lock reaching zero or below can't be drawn in log, so auto-range instead
+        series.y_locked = true;
+        series.y_lock_range = (0.0, 100.0);
+        assert_eq!(series.plot_range(base, window_end, true), (min, max));
+        series.y_lock_range = (1.0, 100.0);
+        assert_eq!(series.plot_range(base, window_end, true), (0.0, 2.0));
+
+        assert_eq!(short_unit("km/h"), "km/h");
+        assert_eq!(short_unit("µs/rev"), "µs/rev");
+        assert_eq!(short_unit("revolutions"), "revo..");
+    }
+
+    #[test]
+    fn test_whole_log_window() {
+        let mut graph = MultiSignalGraph::new();
+        assert_eq!(graph.whole_log_secs(), None);
+        let base = Utc::now();
+        graph.set_data_time_range(base, base + Duration::milliseconds(90_500));
+        assert_eq!(graph.whole_log_secs(), Some(90.5));
+        graph.set_data_time_range(base, base);
+        assert_eq!(graph.whole_log_secs(), Some(1.0));
+    }
+
+    #[test]
+    fn test_parse_id_query() {
+        assert_eq!(parse_id_query("0x1A0"), vec![0x1A0]);
+        assert_eq!(parse_id_query("1a0"), vec![0x1A0]);
+        assert_eq!(parse_id_query("256"), vec![0x256, 256]);
+        assert!(parse_id_query("speed").is_empty());
+    }
+
+    #[test]
+    fn test_picker_toggle_uses_bus_key() {
+        let info = SignalInfo {
+            name: "Speed".to_string(),
+            msg_id: 0x100,
+            bus: 1,
+            msg_name: "Vehicle".to_string(),
+            unit: String::new(),
+            range: None,
+        };
+        let mut graph = MultiSignalGraph::new();
+
+        graph.toggle_picker_signal(&info);
+        assert!(graph.has_signal(&info.key()));
+        assert!(!graph.has_signal("Speed"));
+
+        graph.toggle_picker_signal(&info);
+        assert!(!graph.has_signal(&info.key()));
+    }
+
+    #[test]
+    fn test_virtual_signal_charts_by_key() {
+        let power = VirtualSignal { name: "Power".to_string(), expression: "Voltage * Current".to_string(), bus: 1 };
+        let key = power.key();
+        let mut graph = MultiSignalGraph::new();
+        graph.set_virtual_signals(vec![power]);
+        assert_eq!(graph.virtual_signal_error("Power", "1"), Some("Power is already defined".to_string()));
+        assert!(graph.virtual_signal_error("Torque", "Rpm *").is_some());
+        assert_eq!(graph.virtual_signal_error("Torque", "Power / Rpm"), None);
+
+        graph.restore_signals(std::slice::from_ref(&key));
+        assert!(graph.has_signal(&key));
+        assert_eq!(graph.virtual_signal(&key).map(|v| v.bus), Some(1));
+        assert!(graph.take_picker_additions().is_empty());
+
+        // Picker adds are queued for the app to fill
+        let info = graph.virtual_infos[0].clone();
+        graph.toggle_picker_signal(&info);
+        graph.toggle_picker_signal(&info);
+        assert_eq!(graph.take_picker_additions(), vec![key]);
+    }
+
+    #[test]
+    fn test_same_signal_name_in_two_messages_charts_separately() {
+        let info = |msg_id: u32, msg_name: &str| SignalInfo {
+            name: "Temperature".to_string(),
+            msg_id,
+            bus: 0,
+            msg_name: msg_name.to_string(),
+            unit: "C".to_string(),
+            range: None,
+        };
+        let mut graph = MultiSignalGraph::new();
+        graph.set_available_signals(vec![info(0x100, "Engine"), info(0x200, "Cabin")]);
+
+        graph.toggle_signal_by_name("Temperature@0x200@bus0");
+        graph.toggle_signal_by_name("Temperature@0x100@bus0");
+        assert_eq!(graph.series.len(), 2);
+        assert_eq!(graph.series["Temperature@0x200@bus0"].msg_id, 0x200);
+        assert_eq!(graph.series["Temperature@0x100@bus0"].msg_id, 0x100);
+
+        let now = Utc::now();
+        graph.add_point("Temperature@0x100@bus0", 90.0, now);
+        graph.add_point("Temperature@0x200@bus0", 21.0, now);
+        assert_eq!(graph.points("Temperature@0x100@bus0").unwrap()[0].0, 90.0);
+        assert_eq!(graph.points("Temperature@0x200@bus0").unwrap()[0].0, 21.0);
+
+        graph.toggle_signal_by_name("Temperature@0x100@bus0");
+        assert_eq!(graph.get_charted_signals(), vec!["Temperature@0x200@bus0"]);
+
+        // Keys saved before they named the message resolve to the first message with the name
+        let mut restored = MultiSignalGraph::new();
+        restored.set_available_signals(vec![info(0x100, "Engine"), info(0x200, "Cabin")]);
+        restored.restore_signals(&["Temperature@bus1".to_string()]);
+        assert_eq!(restored.get_charted_signals(), vec!["Temperature@0x100@bus1"]);
+    }
+
+    #[test]
+    fn test_live_rate_cap() {
+        let mut graph = MultiSignalGraph::new();
+        graph.add_signal(&SignalInfo {
+            name: "A".to_string(),
+            msg_id: 0x100,
+            bus: 0,
+            msg_name: "Msg".to_string(),
+            unit: String::new(),
+            range: None,
+        });
+        graph.set_live_rate_cap_hz(100);
+
+        // 1 kHz for one second: one point per 10 ms bucket survives
+        let base = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        for i in 0..1_000 {
+            graph.add_live_point("A@0x100@bus0", Some(i as f64), base + Duration::milliseconds(i));
+        }
+        let points = graph.points("A@0x100@bus0").unwrap();
+        assert_eq!(points.len(), 100);
+        assert_eq!(points[1].0, 10.0);
+        assert!(graph.throttled_at.is_some());
+
+        // No cap keeps every frame
+        graph.clear_data();
+        graph.set_live_rate_cap_hz(0);
+        for i in 0..1_000 {
+            graph.add_live_point("A@0x100@bus0", Some(i as f64), base + Duration::milliseconds(i));
+        }
+        assert_eq!(graph.points("A@0x100@bus0").unwrap().len(), 1_000);
+    }
+
+    fn missing_policy_points(policy: MissingValuePolicy) -> Vec<f64> {
+        let mut graph = MultiSignalGraph::new();
+        graph.add_signal(&SignalInfo {
+            name: "A".to_string(),
+            msg_id: 0x100,
+            bus: 0,
+            msg_name: "Msg".to_string(),
+            unit: String::new(),
+            range: None,
+        });
+        graph.set_missing_policy(policy);
+        let base = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        for (i, value) in [None, Some(1.0), None, None, Some(4.0)].into_iter().enumerate() {
+            graph.add_decoded_point("A@0x100@bus0", value, base + Duration::seconds(i as i64));
+        }
+        graph.points("A@0x100@bus0").unwrap().iter().map(|(v, _)| *v).collect()
+    }
+
+    #[test]
+    fn test_missing_policy_skip() {
+        assert_eq!(missing_policy_points(MissingValuePolicy::Skip), vec![1.0, 4.0]);
+    }
+
+    #[test]
+    fn test_missing_policy_break() {
+        // Nothing to break before the first value, and one NaN per run of misses
+        let points = missing_policy_points(MissingValuePolicy::Break);
+        assert_eq!(points.len(), 3);
+        assert_eq!(points[0], 1.0);
+        assert!(points[1].is_nan());
+        assert_eq!(points[2], 4.0);
+
+        let mut series = DataSeries::new("A".to_string(), 0x100, 0, [1.0; 4]);
+        let base = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        for (i, v) in points.iter().enumerate() {
+            series.add_point(*v, base + Duration::seconds(i as i64));
+        }
+        let stats = series.get_stats_in_window(base, base + Duration::seconds(2)).unwrap();
+        assert_eq!((stats.min, stats.max, stats.count), (1.0, 4.0, 2));
+        assert_eq!(series.get_value_at_time(base + Duration::milliseconds(500)), None);
+        assert_eq!(series.current_value(), Some(4.0));
+    }
+
+    #[test]
+    fn test_missing_policy_hold_last() {
+        assert_eq!(missing_policy_points(MissingValuePolicy::HoldLast), vec![1.0, 1.0, 1.0, 4.0]);
+    }
+
+    #[test]
+    fn test_points_budget_split() {
+        let mut graph = MultiSignalGraph::new();
+        graph.set_points_budget(4_000);
+        for name in ["A", "B"] {
+            graph.add_signal(&SignalInfo {
+                name: name.to_string(),
+                msg_id: 0x100,
+                bus: 0,
+                msg_name: "Msg".to_string(),
+                unit: String::new(),
+                range: None,
+            });
+        }
+        assert!(graph.series.values().all(|s| s.max_points == 2_000));
+
+        let base = Utc::now();
+        for i in 0..2_000 {
+            graph.add_point("A@0x100@bus0", i as f64, base + Duration::milliseconds(i));
+        }
+
+        // Halving the budget keeps the newest half of the points
+        graph.set_points_budget(2_000);
+        let a = &graph.series["A@0x100@bus0"];
+        assert_eq!(a.data_points.len(), 1_000);
+        assert_eq!(a.data_points[0].0, 1_000.0);
+    }
+
+    #[test]
+    fn test_series_colors_stable() {
+        let info = |name: &str| SignalInfo {
+            name: name.to_string(),
+            msg_id: 0x100,
+            bus: 0,
+            msg_name: "Msg".to_string(),
+            unit: String::new(),
+            range: None,
+        };
+        let mut graph = MultiSignalGraph::new();
+        graph.set_custom_colors(CustomColors::from([("B@bus0".to_string(), [0.5, 0.5, 0.5, 1.0])]));
+        for name in ["A", "B", "C"] {
+            graph.add_signal(&info(name));
+        }
+        assert_eq!(graph.series["B@0x100@bus0"].color, [0.5, 0.5, 0.5, 1.0]);
+
+        // Colors follow the signal, not its position in the chart
+        let c_color = graph.series["C@0x100@bus0"].color;
+        graph.remove_signal("A@0x100@bus0");
+        graph.add_signal(&info("D"));
+        assert_eq!(graph.series["C@0x100@bus0"].color, c_color);
+        assert_eq!(graph.series["D@0x100@bus0"].color, crate::ui::colors::hashed_signal_color(ColorPalette::Standard, "D"));
+    }
+}
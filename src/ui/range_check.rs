@@ -0,0 +1,235 @@
+//! Signal range check window: observed min/max of every DBC signal in the log,
+//! with heuristics pointing at definitions that look wrong.
+
+use imgui::{Condition, Ui};
+use crate::core::{CanMessage, DbcFile};
+use crate::decode::SignalDecoder;
+use std::collections::HashMap;
+
+/// Observed span must overshoot the declared range by this fraction of it to be flagged
+const EXCEED_TOLERANCE: f64 = 0.5;
+
+/// What the observed values suggest about a signal definition
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum RangeVerdict {
+    /// Observed values far outside `[minimum|maximum]`: wrong bit length, byte order or scaling?
+    ExceedsDbc,
+    /// Same value in every frame: signal misplaced over unused bits?
+    NeverChanges,
+    /// Message never appears in the log (or the mux page never selected)
+    NotSeen,
+    Ok,
+}
+
+impl RangeVerdict {
+    pub fn label(self) -> &'static str {
+        match self {
+            RangeVerdict::ExceedsDbc => "exceeds DBC range",
+            RangeVerdict::NeverChanges => "never changes",
+            RangeVerdict::NotSeen => "not in log",
+            RangeVerdict::Ok => "ok",
+        }
+    }
+
+    pub fn looks_wrong(self) -> bool {
+        matches!(self, RangeVerdict::ExceedsDbc | RangeVerdict::NeverChanges)
+    }
+}
+
+/// Observed range of one signal
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignalRange {
+    pub msg_id: u32,
+    pub msg_name: String,
+    pub signal: String,
+    pub unit: String,
+    /// Frames the signal was decoded from
+    pub samples: usize,
+    /// Observed (min, max) physical values, None if never decoded
+    pub observed: Option<(f64, f64)>,
+    /// Declared `[minimum|maximum]`, None if the DBC leaves it unset
+    pub declared: Option<(f64, f64)>,
+    pub verdict: RangeVerdict,
+}
+
+fn verdict(samples: usize, observed: Option<(f64, f64)>, declared: Option<(f64, f64)>) -> RangeVerdict {
+    let Some((lo, hi)) = observed else {
+        return RangeVerdict::NotSeen;
+    };
+    if let Some((min, max)) = declared {
+        let overshoot = (min - lo).max(0.0) + (hi - max).max(0.0);
+        if overshoot > (max - min) * EXCEED_TOLERANCE {
+            return RangeVerdict::ExceedsDbc;
+        }
+    }
+    if samples > 1 && lo == hi {
+        return RangeVerdict::NeverChanges;
+    }
+    RangeVerdict::Ok
+}
+
+/// Scan the log and report the observed range of every DBC signal, suspicious ones first
+pub fn scan_signal_ranges(messages: &[CanMessage], dbc: &DbcFile) -> Vec<SignalRange> {
+    let mut decoder = SignalDecoder::new();
+    decoder.set_dbc(dbc.clone());
+
+    // (id, signal name) -> (samples, min, max)
+    let mut seen: HashMap<(u32, String), (usize, f64, f64)> = HashMap::new();
+    for msg in messages {
+        for signal in decoder.decode_message(msg) {
+            let v = signal.physical_value;
+            let entry = seen.entry((msg.id, signal.name)).or_insert((0, v, v));
+            entry.0 += 1;
+            entry.1 = entry.1.min(v);
+            entry.2 = entry.2.max(v);
+        }
+    }
+
+    let mut ranges: Vec<SignalRange> = dbc.messages.iter()
+        .flat_map(|msg| msg.signals.iter().map(move |signal| (msg, signal)))
+        .map(|(msg, signal)| {
+            let (samples, observed) = match seen.get(&(msg.id, signal.name.clone())) {
+                Some(&(n, lo, hi)) => (n, Some((lo, hi))),
+                None => (0, None),
+            };
+            let declared = match (signal.minimum, signal.maximum) {
+                (Some(min), Some(max)) if max > min => Some((min, max)),
+                _ => None,
+            };
+            SignalRange {
+                msg_id: msg.id,
+                msg_name: msg.name.clone(),
+                signal: signal.name.clone(),
+                unit: signal.unit.clone().unwrap_or_default(),
+                samples,
+                observed,
+                declared,
+                verdict: verdict(samples, observed, declared),
+            }
+        })
+        .collect();
+    ranges.sort_by(|a, b| a.verdict.cmp(&b.verdict).then(a.msg_id.cmp(&b.msg_id)));
+    ranges
+}
+
+/// Window listing observed signal ranges against the DBC
+pub struct RangeCheckWindow {
+    ranges: Vec<SignalRange>,
+    only_suspicious: bool,
+}
+
+impl RangeCheckWindow {
+    pub fn new() -> Self {
+        Self {
+            ranges: Vec::new(),
+            only_suspicious: true,
+        }
+    }
+
+    /// Replace the results with a new scan (run off the UI thread)
+    pub fn set_ranges(&mut self, ranges: Vec<SignalRange>) {
+        self.ranges = ranges;
+    }
+
+    pub fn clear(&mut self) {
+        self.ranges.clear();
+    }
+
+    pub fn render(&mut self, ui: &Ui, is_open: &mut bool) {
+        ui.window("Signal Range Check")
+            .size([640.0, 400.0], Condition::FirstUseEver)
+            .position([450.0, 60.0], Condition::FirstUseEver)
+            .opened(is_open)
+            .build(|| {
+                self.render_content(ui);
+            });
+    }
+
+    fn render_content(&mut self, ui: &Ui) {
+        if self.ranges.is_empty() {
+            ui.text_colored([0.5, 0.5, 0.5, 1.0], "Load a log and a DBC to check signal ranges");
+            return;
+        }
+
+        let suspicious = self.ranges.iter().filter(|r| r.verdict.looks_wrong()).count();
+        ui.text(format!("{} of {} signals look wrong", suspicious, self.ranges.len()));
+        ui.same_line();
+        ui.checkbox("Only suspicious", &mut self.only_suspicious);
+        ui.separator();
+
+        let fmt_range = |range: Option<(f64, f64)>| match range {
+            Some((lo, hi)) => format!("{:.3} .. {:.3}", lo, hi),
+            None => "-".to_string(),
+        };
+
+        ui.child_window("range_list").build(|| {
+            ui.columns(6, "range_cols", true);
+            for header in ["Message", "Signal", "Samples", "Observed", "DBC range", "Verdict"] {
+                ui.text(header);
+                ui.next_column();
+            }
+            ui.separator();
+
+            for range in self.ranges.iter().filter(|r| !self.only_suspicious || r.verdict.looks_wrong()) {
+                let color = match range.verdict {
+                    RangeVerdict::ExceedsDbc => [1.0, 0.4, 0.4, 1.0],
+                    RangeVerdict::NeverChanges => [1.0, 0.8, 0.3, 1.0],
+                    RangeVerdict::NotSeen => [0.6, 0.6, 0.6, 1.0],
+                    RangeVerdict::Ok => [0.5, 0.8, 0.5, 1.0],
+                };
+                ui.text(format!("0x{:03X} {}", range.msg_id, range.msg_name));
+                ui.next_column();
+                ui.text(&range.signal);
+                ui.next_column();
+                ui.text(range.samples.to_string());
+                ui.next_column();
+                ui.text(format!("{} {}", fmt_range(range.observed), range.unit));
+                ui.next_column();
+                ui.text(fmt_range(range.declared));
+                ui.next_column();
+                ui.text_colored(color, range.verdict.label());
+                ui.next_column();
+            }
+            ui.columns(1, "range_cols_end", false);
+        });
+    }
+}
+
+impl Default for RangeCheckWindow {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::dbc::{DbcMessage, DbcSignal};
+
+    #[test]
+    fn test_range_verdicts() {
+        let mut msg = DbcMessage::new(0x100, "Engine", 8);
+        msg.add_signal(DbcSignal::new("Rpm", 0, 16).with_range(0.0, 8000.0));
+        msg.add_signal(DbcSignal::new("Temp", 16, 8).with_range(0.0, 100.0));
+        msg.add_signal(DbcSignal::new("Spare", 24, 8));
+        let mut dbc = DbcFile::new();
+        dbc.add_message(msg);
+        let mut absent = DbcMessage::new(0x200, "Absent", 8);
+        absent.add_signal(DbcSignal::new("Ghost", 0, 8));
+        dbc.add_message(absent);
+
+        // Temp reads up to 200 against a declared 0..100; Spare stays 0
+        let messages: Vec<CanMessage> = (0..5u8)
+            .map(|i| CanMessage::new(0, 0x100, vec![i * 10, 0x0F, 50 * i, 0].into()))
+            .collect();
+        let ranges = scan_signal_ranges(&messages, &dbc);
+        let find = |name: &str| ranges.iter().find(|r| r.signal == name).unwrap();
+
+        assert_eq!(find("Rpm").verdict, RangeVerdict::Ok);
+        assert_eq!(find("Rpm").observed, Some((3840.0, 3880.0)));
+        assert_eq!(find("Temp").verdict, RangeVerdict::ExceedsDbc);
+        assert_eq!(find("Spare").verdict, RangeVerdict::NeverChanges);
+        assert_eq!(find("Ghost").verdict, RangeVerdict::NotSeen);
+        assert_eq!(ranges[0].signal, "Temp");
+    }
+}
@@ -0,0 +1,282 @@
+//! Message rate window: bus load (messages/sec) over the log, stacked by the busiest IDs.
+
+use imgui::{Condition, Ui};
+use crate::core::{CanMessage, DbcFile};
+use crate::ui::colors::{series_color, ColorPalette};
+use std::collections::HashMap;
+
+/// Number of time bins the log duration is split into
+pub const DEFAULT_RATE_BINS: usize = 200;
+
+/// Per-ID message counts binned over the log duration
+#[derive(Debug, Clone, Default)]
+pub struct RateBins {
+    /// Width of one bin in seconds
+    pub bin_secs: f64,
+    /// Per-ID counts for each bin, sorted by total count (busiest first)
+    pub per_id: Vec<(u32, Vec<u32>)>,
+}
+
+/// One layer of the stacked chart: a single ID, or everything else lumped together
+#[derive(Debug, Clone, PartialEq)]
+pub struct RateLayer {
+    /// `None` for the "other" layer
+    pub id: Option<u32>,
+    /// Messages/sec for each bin
+    pub rates: Vec<f64>,
+}
+
+impl RateBins {
+    /// Bin message timestamps per ID over the first..last timestamp range
+    pub fn compute(messages: &[CanMessage], num_bins: usize) -> Self {
+        let num_bins = num_bins.max(1);
+        let (Some(first), Some(last)) = (
+            messages.iter().map(|m| m.timestamp).min(),
+            messages.iter().map(|m| m.timestamp).max(),
+        ) else {
+            return Self::default();
+        };
+
+        let span = (last - first).num_microseconds().unwrap_or(0).max(1) as f64 / 1_000_000.0;
+        let bin_secs = span / num_bins as f64;
+
+        let mut counts: HashMap<u32, Vec<u32>> = HashMap::new();
+        for msg in messages {
+            let offset = (msg.timestamp - first).num_microseconds().unwrap_or(0) as f64 / 1_000_000.0;
+            let bin = ((offset / bin_secs) as usize).min(num_bins - 1);
+            counts.entry(msg.id).or_insert_with(|| vec![0; num_bins])[bin] += 1;
+        }
+
+        let mut per_id: Vec<(u32, Vec<u32>)> = counts.into_iter().collect();
+        per_id.sort_by(|a, b| {
+            let total_a: u64 = a.1.iter().map(|&c| c as u64).sum();
+            let total_b: u64 = b.1.iter().map(|&c| c as u64).sum();
+            total_b.cmp(&total_a).then(a.0.cmp(&b.0))
+        });
+
+        Self { bin_secs, per_id }
+    }
+
+    pub fn num_bins(&self) -> usize {
+        self.per_id.first().map(|(_, bins)| bins.len()).unwrap_or(0)
+    }
+
+    /// Break out the `top_n` busiest IDs and lump the rest into one "other" layer
+    pub fn layers(&self, top_n: usize) -> Vec<RateLayer> {
+        let num_bins = self.num_bins();
+        let to_rate = |count: u32| count as f64 / self.bin_secs;
+
+        let mut layers: Vec<RateLayer> = self.per_id.iter()
+            .take(top_n)
+            .map(|(id, bins)| RateLayer {
+                id: Some(*id),
+                rates: bins.iter().map(|&c| to_rate(c)).collect(),
+            })
+            .collect();
+
+        if self.per_id.len() > top_n {
+            let mut other = vec![0u32; num_bins];
+            for (_, bins) in self.per_id.iter().skip(top_n) {
+                for (sum, &c) in other.iter_mut().zip(bins) {
+                    *sum += c;
+                }
+            }
+            layers.push(RateLayer {
+                id: None,
+                rates: other.into_iter().map(to_rate).collect(),
+            });
+        }
+
+        layers
+    }
+}
+
+/// Window plotting total and per-ID message rates as a stacked area chart
+pub struct MessageRateWindow {
+    bins: RateBins,
+    /// How many IDs get their own layer
+    top_n: i32,
+    /// Layers for the current `top_n` (rebuilt only when it changes)
+    layers: Vec<RateLayer>,
+    color_palette: ColorPalette,
+}
+
+impl MessageRateWindow {
+    pub fn new() -> Self {
+        Self {
+            bins: RateBins::default(),
+            top_n: 5,
+            layers: Vec::new(),
+            color_palette: ColorPalette::default(),
+        }
+    }
+
+    pub fn set_color_palette(&mut self, palette: ColorPalette) {
+        self.color_palette = palette;
+    }
+
+    /// Rebin the loaded log (call once per load, not per frame)
+    pub fn set_messages(&mut self, messages: &[CanMessage]) {
+        self.bins = RateBins::compute(messages, DEFAULT_RATE_BINS);
+        self.layers = self.bins.layers(self.top_n as usize);
+    }
+
+    pub fn clear(&mut self) {
+        self.bins = RateBins::default();
+        self.layers.clear();
+    }
+
+    pub fn render(&mut self, ui: &Ui, dbc: &DbcFile, is_open: &mut bool) {
+        ui.window("Message Rate")
+            .size([600.0, 350.0], Condition::FirstUseEver)
+            .position([200.0, 200.0], Condition::FirstUseEver)
+            .opened(is_open)
+            .build(|| {
+                self.render_content(ui, dbc);
+            });
+    }
+
+    fn layer_label(dbc: &DbcFile, layer: &RateLayer) -> String {
+        match layer.id {
+            Some(id) => match dbc.get_message(id) {
+                Some(msg) => format!("0x{:03X} {}", id, msg.name),
+                None => format!("0x{:03X}", id),
+            },
+            None => "Other".to_string(),
+        }
+    }
+
+    fn render_content(&mut self, ui: &Ui, dbc: &DbcFile) {
+        let num_bins = self.bins.num_bins();
+        if num_bins == 0 {
+            ui.text_colored([0.5, 0.5, 0.5, 1.0], "Load a log to see message rates");
+            return;
+        }
+
+        ui.set_next_item_width(150.0);
+        let max_top = self.bins.per_id.len().min(16) as i32;
+        if ui.slider("Top IDs", 0, max_top, &mut self.top_n) {
+            self.layers = self.bins.layers(self.top_n as usize);
+        }
+
+        let totals: Vec<f64> = (0..num_bins)
+            .map(|i| self.layers.iter().map(|l| l.rates[i]).sum())
+            .collect();
+        let peak = totals.iter().cloned().fold(0.0, f64::max).max(1.0);
+        let mean = totals.iter().sum::<f64>() / num_bins as f64;
+        ui.same_line();
+        ui.text(format!("Peak: {:.0} msg/s  Mean: {:.0} msg/s", peak, mean));
+
+        // Legend
+        for (i, layer) in self.layers.iter().enumerate() {
+            if i > 0 {
+                ui.same_line();
+            }
+            ui.text_colored(series_color(self.color_palette, i), Self::layer_label(dbc, layer));
+        }
+
+        let avail = ui.content_region_avail();
+        let size = [avail[0].max(100.0), avail[1].max(80.0)];
+        let pos_min = ui.cursor_screen_pos();
+        let pos_max = [pos_min[0] + size[0], pos_min[1] + size[1]];
+        ui.invisible_button("##rate_graph", size);
+        let hovered = ui.is_item_hovered();
+
+        let draw_list = ui.get_window_draw_list();
+        draw_list.add_rect(pos_min, pos_max, [0.1, 0.1, 0.1, 1.0]).filled(true).build();
+
+        let grid_color = [0.5, 0.5, 0.5, 0.3];
+        for i in 0..=4 {
+            let y = pos_min[1] + size[1] * (i as f32 / 4.0);
+            draw_list.add_line([pos_min[0], y], [pos_max[0], y], grid_color).build();
+            let value = peak * (1.0 - i as f64 / 4.0);
+            draw_list.add_text([pos_min[0] + 5.0, y + 2.0], [0.7, 0.7, 0.7, 0.8], format!("{:.0}", value));
+        }
+
+        let bin_x = |i: usize| pos_min[0] + size[0] * (i as f32 / (num_bins - 1).max(1) as f32);
+        let rate_y = |rate: f64| pos_max[1] - size[1] * (rate / peak) as f32;
+
+        // Stack layers bottom-up: each segment between adjacent bins is a quad of two triangles
+        let mut lower = vec![0.0f64; num_bins];
+        for (layer_idx, layer) in self.layers.iter().enumerate() {
+            let upper: Vec<f64> = lower.iter().zip(&layer.rates).map(|(l, r)| l + r).collect();
+            let mut color = series_color(self.color_palette, layer_idx);
+            color[3] = 0.6;
+            for i in 0..num_bins.saturating_sub(1) {
+                let (x0, x1) = (bin_x(i), bin_x(i + 1));
+                let (lo0, lo1) = (rate_y(lower[i]), rate_y(lower[i + 1]));
+                let (hi0, hi1) = (rate_y(upper[i]), rate_y(upper[i + 1]));
+                draw_list.add_triangle([x0, lo0], [x0, hi0], [x1, hi1], color).filled(true).build();
+                draw_list.add_triangle([x0, lo0], [x1, hi1], [x1, lo1], color).filled(true).build();
+            }
+            lower = upper;
+        }
+
+        let outline: Vec<[f32; 2]> = totals.iter().enumerate().map(|(i, &t)| [bin_x(i), rate_y(t)]).collect();
+        draw_list.add_polyline(outline, [1.0, 1.0, 1.0, 0.8]).thickness(1.0).build();
+
+        if hovered {
+            let mouse = ui.io().mouse_pos;
+            let frac = ((mouse[0] - pos_min[0]) / size[0]).clamp(0.0, 1.0);
+            let bin = ((frac * (num_bins - 1) as f32).round() as usize).min(num_bins - 1);
+            let x = bin_x(bin);
+            draw_list.add_line([x, pos_min[1]], [x, pos_max[1]], [1.0, 1.0, 1.0, 0.5]).build();
+
+            ui.tooltip(|| {
+                ui.text(format!("t = {:.1}s", bin as f64 * self.bins.bin_secs));
+                ui.text(format!("Total: {:.1} msg/s", totals[bin]));
+                for (i, layer) in self.layers.iter().enumerate() {
+                    ui.text_colored(series_color(self.color_palette, i), format!("{}: {:.1} msg/s", Self::layer_label(dbc, layer), layer.rates[bin]));
+                }
+            });
+        }
+    }
+}
+
+impl Default for MessageRateWindow {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone, Utc};
+
+    #[test]
+    fn test_rate_bins_top_n_and_other() {
+        let start = Utc.timestamp_opt(1_000, 0).unwrap();
+        let mut messages = Vec::new();
+        // 0x100: 10 msgs in first second; 0x200: 4 msgs in second second; 0x300: 1 msg
+        for i in 0..10 {
+            let mut m = CanMessage::new(0, 0x100, vec![0u8; 8].into());
+            m.timestamp = start + Duration::milliseconds(i * 100);
+            messages.push(m);
+        }
+        for i in 0..4 {
+            let mut m = CanMessage::new(0, 0x200, vec![0u8; 8].into());
+            m.timestamp = start + Duration::milliseconds(1_000 + i * 200);
+            messages.push(m);
+        }
+        let mut m = CanMessage::new(0, 0x300, vec![0u8; 8].into());
+        m.timestamp = start + Duration::milliseconds(2_000);
+        messages.push(m);
+
+        let bins = RateBins::compute(&messages, 2);
+        assert_eq!(bins.num_bins(), 2);
+        assert!((bins.bin_secs - 1.0).abs() < 1e-9);
+        assert_eq!(bins.per_id[0].0, 0x100);
+
+        let layers = bins.layers(1);
+        assert_eq!(layers.len(), 2);
+        assert_eq!(layers[0].id, Some(0x100));
+        assert_eq!(layers[0].rates, vec![10.0, 0.0]);
+        // Last message falls on the end boundary and is clamped into the last bin
+        assert_eq!(layers[1].id, None);
+        assert_eq!(layers[1].rates, vec![0.0, 5.0]);
+
+        // Every ID broken out: no "other" layer
+        assert_eq!(bins.layers(3).len(), 3);
+    }
+}
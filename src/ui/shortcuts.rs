@@ -1,5 +1,6 @@
 use imgui::{Ui, Condition};
 use winit::event::{KeyEvent, ElementState};
+use crate::input::{GeneratorOptions, SignalPattern, TimePrecision};
 use winit::keyboard::{KeyCode, PhysicalKey};
 
 /// Keyboard shortcut manager
@@ -32,8 +33,12 @@ pub enum ShortcutAction {
     ClearData,
     SeekForward,
     SeekBackward,
+    GoToStart,
+    GoToEnd,
     SpeedUp,
     SpeedDown,
+    NextAnomaly,
+    PrevAnomaly,
     Quit,
 }
 
@@ -124,6 +129,22 @@ impl ShortcutManager {
             action: ShortcutAction::SeekBackward,
             description: "Seek Backward".to_string(),
         });
+        self.register(Shortcut {
+            key: PhysicalKey::Code(KeyCode::Home),
+            ctrl: false,
+            shift: false,
+            alt: false,
+            action: ShortcutAction::GoToStart,
+            description: "First Frame".to_string(),
+        });
+        self.register(Shortcut {
+            key: PhysicalKey::Code(KeyCode::End),
+            ctrl: false,
+            shift: false,
+            alt: false,
+            action: ShortcutAction::GoToEnd,
+            description: "Last Frame".to_string(),
+        });
         self.register(Shortcut {
             key: PhysicalKey::Code(KeyCode::Equal),
             ctrl: false,
@@ -140,6 +161,22 @@ impl ShortcutManager {
             action: ShortcutAction::SpeedDown,
             description: "Speed Down".to_string(),
         });
+        self.register(Shortcut {
+            key: PhysicalKey::Code(KeyCode::F8),
+            ctrl: false,
+            shift: false,
+            alt: false,
+            action: ShortcutAction::NextAnomaly,
+            description: "Next Anomalous Frame".to_string(),
+        });
+        self.register(Shortcut {
+            key: PhysicalKey::Code(KeyCode::F8),
+            ctrl: false,
+            shift: true,
+            alt: false,
+            action: ShortcutAction::PrevAnomaly,
+            description: "Previous Anomalous Frame".to_string(),
+        });
 
         // View toggles
         self.register(Shortcut {
@@ -228,8 +265,12 @@ impl ShortcutManager {
                         ShortcutAction::Stop |
                         ShortcutAction::SeekForward |
                         ShortcutAction::SeekBackward |
+                        ShortcutAction::GoToStart |
+                        ShortcutAction::GoToEnd |
                         ShortcutAction::SpeedUp |
-                        ShortcutAction::SpeedDown => "Playback",
+                        ShortcutAction::SpeedDown |
+                        ShortcutAction::NextAnomaly |
+                        ShortcutAction::PrevAnomaly => "Playback",
                         ShortcutAction::ToggleMessages |
                         ShortcutAction::ToggleGraph |
                         ShortcutAction::ToggleFullscreen => "View",
@@ -276,6 +317,9 @@ fn key_to_string(key: PhysicalKey) -> String {
             KeyCode::Equal => "+".to_string(),
             KeyCode::Minus => "-".to_string(),
             KeyCode::Delete => "Del".to_string(),
+            KeyCode::Home => "Home".to_string(),
+            KeyCode::End => "End".to_string(),
+            KeyCode::F8 => "F8".to_string(),
             KeyCode::F11 => "F11".to_string(),
             KeyCode::KeyA => "A".to_string(),
             KeyCode::KeyB => "B".to_string(),
@@ -321,6 +365,19 @@ pub struct ExportDialog {
     export_type: ExportType,
     include_timestamps: bool,
     include_decoded: bool,
+    time_precision: TimePrecision,
+    /// Parquet: only the charted signals instead of every DBC signal
+    #[cfg(feature = "parquet")]
+    charted_only: bool,
+    /// Length of the loaded log in seconds, the limit of the time range
+    log_secs: f64,
+    /// Export only frames within `range` (seconds from the start of the log)
+    range_only: bool,
+    range: (f64, f64),
+    /// CSV times count from the range start instead of the log start
+    rebase_to_range: bool,
+    /// CSV times as unix epoch seconds instead of offsets
+    epoch_times: bool,
     status: Option<String>,
 }
 
@@ -329,6 +386,9 @@ pub enum ExportType {
     Csv,
     Json,
     Log,
+    /// Decoded signals as a Parquet time series
+    #[cfg(feature = "parquet")]
+    Parquet,
 }
 
 impl ExportDialog {
@@ -338,13 +398,30 @@ impl ExportDialog {
             export_type: ExportType::Csv,
             include_timestamps: true,
             include_decoded: false,
+            time_precision: TimePrecision::Auto,
+            #[cfg(feature = "parquet")]
+            charted_only: false,
+            log_secs: 0.0,
+            range_only: false,
+            range: (0.0, 0.0),
+            rebase_to_range: true,
+            epoch_times: false,
             status: None,
         }
     }
 
-    pub fn show(&mut self) {
+    /// Open the dialog for a log `log_secs` long. A timeline loop region becomes the
+    /// range to export; otherwise a range that no longer fits is reset to the whole log.
+    pub fn show(&mut self, log_secs: f64, loop_region: Option<(f64, f64)>) {
         self.show = true;
         self.status = None;
+        if let Some(region) = loop_region {
+            self.range = region;
+            self.range_only = true;
+        } else if log_secs != self.log_secs || self.range.1 > log_secs {
+            self.range = (0.0, log_secs);
+        }
+        self.log_secs = log_secs;
     }
 
     pub fn render(&mut self, ui: &Ui) -> Option<ExportRequest> {
@@ -355,7 +432,7 @@ impl ExportDialog {
         let mut result = None;
 
         ui.window("Export Data")
-            .size([400.0, 250.0], Condition::FirstUseEver)
+            .size([420.0, 320.0], Condition::FirstUseEver)
             .build(|| {
                 ui.text("Export CAN Log Data");
                 ui.separator();
@@ -372,6 +449,10 @@ impl ExportDialog {
                 if ui.radio_button("LOG", &mut export_val, ExportType::Log as i32) {
                     self.export_type = ExportType::Log;
                 }
+                #[cfg(feature = "parquet")]
+                if ui.radio_button("Parquet (decoded signals)", &mut export_val, ExportType::Parquet as i32) {
+                    self.export_type = ExportType::Parquet;
+                }
 
                 ui.separator();
 
@@ -383,6 +464,48 @@ impl ExportDialog {
                         ui.text("Requires DBC to be loaded");
                     });
                 }
+                #[cfg(feature = "parquet")]
+                if self.export_type == ExportType::Parquet {
+                    ui.checkbox("Charted Signals Only", &mut self.charted_only);
+                }
+                ui.set_next_item_width(200.0);
+                if let Some(_combo) = ui.begin_combo("Time Precision", self.time_precision.label()) {
+                    for precision in TimePrecision::ALL {
+                        if ui.selectable_config(precision.label()).selected(self.time_precision == precision).build() {
+                            self.time_precision = precision;
+                        }
+                    }
+                }
+                if ui.is_item_hovered() {
+                    ui.tooltip_text("Auto writes microseconds when the log has sub-millisecond timestamps");
+                }
+                if self.export_type == ExportType::Csv {
+                    ui.checkbox("Unix epoch times", &mut self.epoch_times);
+                    if ui.is_item_hovered() {
+                        ui.tooltip_text("Write seconds since 1970 instead of offsets from the log start; loads back with \"Unix epoch\" or auto-detect");
+                    }
+                }
+
+                ui.checkbox("Time range only", &mut self.range_only);
+                if self.range_only {
+                    ui.indent();
+                    ui.set_next_item_width(90.0);
+                    ui.input_scalar("Start (s)", &mut self.range.0).display_format("%.3f").build();
+                    ui.same_line();
+                    ui.set_next_item_width(90.0);
+                    ui.input_scalar("End (s)", &mut self.range.1).display_format("%.3f").build();
+                    ui.same_line();
+                    if ui.small_button("Whole log") {
+                        self.range = (0.0, self.log_secs);
+                    }
+                    ui.enabled(!(self.export_type == ExportType::Csv && self.epoch_times), || {
+                        ui.checkbox("Times relative to range start", &mut self.rebase_to_range);
+                    });
+                    if ui.is_item_hovered() {
+                        ui.tooltip_text("Times start at 0 at the range start; otherwise they keep their offset from the log start");
+                    }
+                    ui.unindent();
+                }
 
                 ui.separator();
 
@@ -392,13 +515,22 @@ impl ExportDialog {
                 }
 
                 // Buttons
-                if ui.button("Export") {
-                    result = Some(ExportRequest {
-                        export_type: self.export_type,
-                        include_timestamps: self.include_timestamps,
-                        include_decoded: self.include_decoded,
-                    });
-                }
+                let valid = !self.range_only || (self.range.0 >= 0.0 && self.range.0 < self.range.1);
+                ui.enabled(valid, || {
+                    if ui.button("Export") {
+                        result = Some(ExportRequest {
+                            export_type: self.export_type,
+                            include_timestamps: self.include_timestamps,
+                            include_decoded: self.include_decoded,
+                            time_precision: self.time_precision,
+                            #[cfg(feature = "parquet")]
+                            charted_only: self.charted_only,
+                            time_range: self.range_only.then_some(self.range),
+                            rebase_to_range: self.rebase_to_range,
+                            epoch_times: self.export_type == ExportType::Csv && self.epoch_times,
+                        });
+                    }
+                });
                 ui.same_line();
                 if ui.button("Cancel") {
                     self.show = false;
@@ -420,6 +552,81 @@ pub struct ExportRequest {
     pub export_type: ExportType,
     pub include_timestamps: bool,
     pub include_decoded: bool,
+    pub time_precision: TimePrecision,
+    #[cfg(feature = "parquet")]
+    pub charted_only: bool,
+    /// Only export frames between these offsets from the log start, in seconds
+    pub time_range: Option<(f64, f64)>,
+    /// With a time range, CSV times count from its start
+    pub rebase_to_range: bool,
+    /// CSV times as unix epoch seconds
+    pub epoch_times: bool,
+}
+
+/// Options dialog for "Generate test data from DBC"
+pub struct GenerateDialog {
+    show: bool,
+    options: GeneratorOptions,
+}
+
+impl GenerateDialog {
+    pub fn new() -> Self {
+        Self {
+            show: false,
+            options: GeneratorOptions::default(),
+        }
+    }
+
+    pub fn show(&mut self) {
+        self.show = true;
+    }
+
+    /// Returns the chosen options when the user clicks Generate
+    pub fn render(&mut self, ui: &Ui) -> Option<GeneratorOptions> {
+        if !self.show {
+            return None;
+        }
+
+        let mut result = None;
+
+        ui.window("Generate Test Data")
+            .size([320.0, 180.0], Condition::FirstUseEver)
+            .build(|| {
+                ui.text("Synthesize a capture from the loaded DBC");
+                ui.separator();
+
+                ui.set_next_item_width(120.0);
+                ui.input_scalar("Duration (s)", &mut self.options.duration_secs).build();
+                ui.set_next_item_width(120.0);
+                ui.input_scalar("Cycle time (ms)", &mut self.options.cycle_ms).build();
+
+                let mut ramp = self.options.pattern == SignalPattern::Ramp;
+                if ui.checkbox("Ramp signals (else mid-range)", &mut ramp) {
+                    self.options.pattern = if ramp { SignalPattern::Ramp } else { SignalPattern::MidRange };
+                }
+
+                ui.separator();
+                let valid = self.options.duration_secs > 0.0 && self.options.cycle_ms > 0.0;
+                ui.enabled(valid, || {
+                    if ui.button("Generate") {
+                        result = Some(self.options.clone());
+                        self.show = false;
+                    }
+                });
+                ui.same_line();
+                if ui.button("Cancel") {
+                    self.show = false;
+                }
+            });
+
+        result
+    }
+}
+
+impl Default for GenerateDialog {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 /// About dialog
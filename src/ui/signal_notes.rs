@@ -0,0 +1,89 @@
+//! Personal notes on signals (confidence, where the definition came from), kept
+//! in the config dir rather than the DBC so a shared DBC stays untouched.
+//! Unlike `CM_` comments these never leave this machine.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+/// Notes by message ID, then signal name, persisted as JSON in the config dir
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SignalNotes {
+    notes: BTreeMap<u32, BTreeMap<String, String>>,
+}
+
+impl SignalNotes {
+    fn config_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|p| p.join("can-viz").join("signal_notes.json"))
+    }
+
+    pub fn load() -> Self {
+        Self::config_path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) {
+        if let Some(path) = Self::config_path() {
+            if let Some(parent) = path.parent() {
+                let _ = fs::create_dir_all(parent);
+            }
+            if let Ok(json) = serde_json::to_string_pretty(self) {
+                let _ = fs::write(&path, json);
+            }
+        }
+    }
+
+    pub fn get(&self, msg_id: u32, signal: &str) -> Option<&str> {
+        self.notes.get(&msg_id)?.get(signal).map(String::as_str)
+    }
+
+    /// Set a signal's note; an empty (or whitespace-only) note removes it
+    pub fn set(&mut self, msg_id: u32, signal: &str, note: &str) {
+        if note.trim().is_empty() {
+            if let Some(signals) = self.notes.get_mut(&msg_id) {
+                signals.remove(signal);
+                if signals.is_empty() {
+                    self.notes.remove(&msg_id);
+                }
+            }
+        } else {
+            self.notes.entry(msg_id).or_default().insert(signal.to_string(), note.to_string());
+        }
+    }
+
+    /// Carry a note over when its signal is renamed
+    pub fn rename(&mut self, msg_id: u32, old: &str, new: &str) {
+        if let Some(note) = self.notes.get_mut(&msg_id).and_then(|signals| signals.remove(old)) {
+            self.notes.entry(msg_id).or_default().insert(new.to_string(), note);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signal_notes_set_rename_and_roundtrip() {
+        let mut notes = SignalNotes::default();
+        notes.set(0x1A0, "Speed", "Confirmed against GPS, +-1 km/h");
+        notes.set(0x1A0, "Rpm", "Guess from idle behaviour");
+        assert_eq!(notes.get(0x1A0, "Speed"), Some("Confirmed against GPS, +-1 km/h"));
+        assert_eq!(notes.get(0x1A1, "Speed"), None);
+
+        notes.rename(0x1A0, "Rpm", "EngineSpeed");
+        assert_eq!(notes.get(0x1A0, "Rpm"), None);
+        assert_eq!(notes.get(0x1A0, "EngineSpeed"), Some("Guess from idle behaviour"));
+
+        let json = serde_json::to_string(&notes).unwrap();
+        let mut restored: SignalNotes = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.get(0x1A0, "Speed"), notes.get(0x1A0, "Speed"));
+
+        restored.set(0x1A0, "Speed", "  ");
+        restored.set(0x1A0, "EngineSpeed", "");
+        assert!(restored.notes.is_empty());
+    }
+}
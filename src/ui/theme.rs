@@ -0,0 +1,111 @@
+//! UI color themes: imgui style presets plus the matching chart palette.
+
+use imgui::{Style, StyleColor};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Theme {
+    #[default]
+    Dark,
+    Light,
+    HighContrast,
+}
+
+/// Colors used by the custom-drawn chart area (not covered by imgui style colors)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChartPalette {
+    pub background: [f32; 4],
+    pub grid: [f32; 4],
+    pub label: [f32; 4],
+    /// Current playback time line
+    pub playhead: [f32; 4],
+    /// Dashed line under the mouse
+    pub hover_line: [f32; 4],
+    /// Thickness of each series' trend line
+    pub line_thickness: f32,
+}
+
+impl Theme {
+    pub const ALL: [Theme; 3] = [Theme::Dark, Theme::Light, Theme::HighContrast];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Theme::Dark => "Dark",
+            Theme::Light => "Light",
+            Theme::HighContrast => "High Contrast",
+        }
+    }
+
+    /// Overwrite the style's colors with this theme
+    pub fn apply(self, style: &mut Style) {
+        match self {
+            Theme::Dark => {
+                style.use_dark_colors();
+            }
+            Theme::Light => {
+                style.use_light_colors();
+            }
+            Theme::HighContrast => {
+                style.use_dark_colors();
+                let black = [0.0, 0.0, 0.0, 1.0];
+                let white = [1.0, 1.0, 1.0, 1.0];
+                let yellow = [1.0, 0.85, 0.0, 1.0];
+                style[StyleColor::Text] = white;
+                style[StyleColor::TextDisabled] = [0.75, 0.75, 0.75, 1.0];
+                style[StyleColor::WindowBg] = black;
+                style[StyleColor::ChildBg] = black;
+                style[StyleColor::PopupBg] = black;
+                style[StyleColor::MenuBarBg] = black;
+                style[StyleColor::Border] = white;
+                style[StyleColor::FrameBg] = [0.12, 0.12, 0.12, 1.0];
+                style[StyleColor::FrameBgHovered] = [0.3, 0.3, 0.3, 1.0];
+                style[StyleColor::FrameBgActive] = [0.45, 0.45, 0.45, 1.0];
+                style[StyleColor::TitleBg] = black;
+                style[StyleColor::TitleBgActive] = [0.2, 0.2, 0.2, 1.0];
+                style[StyleColor::Button] = [0.2, 0.2, 0.2, 1.0];
+                style[StyleColor::ButtonHovered] = [0.4, 0.4, 0.4, 1.0];
+                style[StyleColor::ButtonActive] = yellow;
+                style[StyleColor::Header] = [0.25, 0.25, 0.25, 1.0];
+                style[StyleColor::HeaderHovered] = [0.4, 0.4, 0.4, 1.0];
+                style[StyleColor::HeaderActive] = yellow;
+                style[StyleColor::CheckMark] = yellow;
+                style[StyleColor::SliderGrab] = yellow;
+                style[StyleColor::SliderGrabActive] = white;
+                style[StyleColor::Separator] = white;
+                style[StyleColor::NavHighlight] = yellow;
+            }
+        }
+        // Borders make panel edges visible in bright environments
+        style.frame_border_size = if self == Theme::HighContrast { 1.0 } else { 0.0 };
+    }
+
+    pub fn chart_palette(self) -> ChartPalette {
+        match self {
+            Theme::Dark => ChartPalette {
+                background: [0.0, 0.0, 0.0, 1.0],
+                grid: [0.5, 0.5, 0.5, 0.3],
+                label: [0.7, 0.7, 0.7, 0.8],
+                playhead: [1.0, 1.0, 0.0, 0.8],
+                hover_line: [1.0, 1.0, 1.0, 0.4],
+                line_thickness: 2.0,
+            },
+            // Yellow and white lines vanish on a light chart, so those go dark
+            Theme::Light => ChartPalette {
+                background: [0.97, 0.97, 0.97, 1.0],
+                grid: [0.0, 0.0, 0.0, 0.15],
+                label: [0.25, 0.25, 0.25, 1.0],
+                playhead: [0.85, 0.35, 0.0, 0.9],
+                hover_line: [0.0, 0.0, 0.0, 0.4],
+                line_thickness: 2.0,
+            },
+            Theme::HighContrast => ChartPalette {
+                background: [0.0, 0.0, 0.0, 1.0],
+                grid: [0.85, 0.85, 0.85, 0.7],
+                label: [1.0, 1.0, 1.0, 1.0],
+                playhead: [1.0, 1.0, 0.0, 0.8],
+                hover_line: [1.0, 1.0, 1.0, 0.4],
+                line_thickness: 3.0,
+            },
+        }
+    }
+}
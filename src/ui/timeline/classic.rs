@@ -1,7 +1,7 @@
 //! Classic timeline implementation - the original timeline style
 
 use imgui::{StyleColor, Ui};
-use super::{TimelineAction, TimelineData, TimelineTheme};
+use super::{density_bin_tooltip, TimelineAction, TimelineData, TimelineTheme};
 
 /// Classic timeline renderer
 pub struct ClassicTimeline {
@@ -24,10 +24,6 @@ impl Default for ClassicTimeline {
 }
 
 impl TimelineTheme for ClassicTimeline {
-    fn name(&self) -> &'static str {
-        "Classic"
-    }
-
     fn render(&mut self, ui: &Ui, data: &mut TimelineData) -> TimelineAction {
         let mut action = TimelineAction::None;
 
@@ -101,8 +97,19 @@ impl TimelineTheme for ClassicTimeline {
             [1.0, 1.0, 1.0, 1.0],
         ).filled(true).build();
 
-        // Handle mouse interaction
         let mouse_pos = ui.io().mouse_pos;
+
+        // Density bars: hovering describes the bin under the mouse
+        let density_hovered = mouse_pos[0] >= pos_min[0] && mouse_pos[0] <= pos_max[0] &&
+                              mouse_pos[1] >= pos_min[1] && mouse_pos[1] < pos_min[1] + density_height;
+        if data.show_density_tooltip && density_hovered {
+            let rel_x = (mouse_pos[0] - pos_min[0]) / (pos_max[0] - pos_min[0]);
+            if let Some(bin) = data.view_bin_at(rel_x) {
+                ui.tooltip(|| density_bin_tooltip(ui, &bin));
+            }
+        }
+
+        // Handle mouse interaction
         let is_hovered = mouse_pos[0] >= track_pos_min[0] && mouse_pos[0] <= track_pos_max[0] &&
                         mouse_pos[1] >= track_pos_min[1] && mouse_pos[1] <= track_pos_max[1];
 
@@ -149,20 +156,20 @@ impl TimelineTheme for ClassicTimeline {
         ui.same_line();
         if ui.small_button("-") {
             data.zoom_out();
-            action = TimelineAction::Zoom(data.zoom);
+            action = TimelineAction::Zoom;
         }
         ui.same_line();
         ui.text(format!("{:.1}x", data.zoom));
         ui.same_line();
         if ui.small_button("+") {
             data.zoom_in();
-            action = TimelineAction::Zoom(data.zoom);
+            action = TimelineAction::Zoom;
         }
         ui.same_line();
         if ui.small_button("Reset") {
             data.zoom = 1.0;
             data.pan = 0.0;
-            action = TimelineAction::Zoom(1.0);
+            action = TimelineAction::Zoom;
         }
 
         // Loop controls
@@ -179,7 +186,7 @@ impl TimelineTheme for ClassicTimeline {
                 let loop_size = 0.1;
                 data.loop_start = Some((data.position - loop_size / 2.0).max(0.0));
                 data.loop_end = Some((data.position + loop_size / 2.0).min(1.0));
-                action = TimelineAction::LoopSet(data.loop_start.unwrap(), data.loop_end.unwrap());
+                action = TimelineAction::LoopSet;
             }
         }
 
@@ -202,16 +209,36 @@ impl ClassicTimeline {
         pos_min: [f32; 2],
         pos_max: [f32; 2],
     ) {
-        if data.density.is_empty() {
+        let (bins, range) = data.density_for_view();
+        if range == (0.0, 1.0) {
+            Self::draw_density_bars(draw_list, bins, pos_min, pos_max);
+            return;
+        }
+
+        // Zoomed: a thin full-range overview with the visible window outlined,
+        // and the re-binned detail for that window below it
+        let overview_max = [pos_max[0], pos_min[1] + 6.0];
+        Self::draw_density_bars(draw_list, &data.density, pos_min, overview_max);
+        let width = pos_max[0] - pos_min[0];
+        draw_list.add_rect(
+            [pos_min[0] + range.0 * width, pos_min[1]],
+            [pos_min[0] + range.1 * width, overview_max[1]],
+            [1.0, 1.0, 1.0, 0.8],
+        ).build();
+        Self::draw_density_bars(draw_list, bins, [pos_min[0], overview_max[1] + 1.0], pos_max);
+    }
+
+    fn draw_density_bars(draw_list: &imgui::DrawListMut, bins: &[u32], pos_min: [f32; 2], pos_max: [f32; 2]) {
+        if bins.is_empty() {
             return;
         }
 
-        let max_density = *data.density.iter().max().unwrap_or(&1) as f32;
+        let max_density = (*bins.iter().max().unwrap_or(&1)).max(1) as f32;
         let width = pos_max[0] - pos_min[0];
         let height = pos_max[1] - pos_min[1];
-        let bar_width = width / data.density.len() as f32;
+        let bar_width = width / bins.len() as f32;
 
-        for (i, &density) in data.density.iter().enumerate() {
+        for (i, &density) in bins.iter().enumerate() {
             let x = pos_min[0] + i as f32 * bar_width;
             let bar_height = (density as f32 / max_density) * height;
             let intensity = density as f32 / max_density;
@@ -1,7 +1,7 @@
 //! Minimal/Modern Style timeline - Clean lines with floating time display
 
 use imgui::Ui;
-use super::{TimelineAction, TimelineData, TimelineTheme};
+use super::{density_bin_tooltip, TimelineAction, TimelineData, TimelineTheme};
 
 /// Minimal/modern style timeline with thin slider and circular thumb
 pub struct MinimalTimeline {
@@ -238,17 +238,10 @@ impl Default for MinimalTimeline {
 }
 
 impl TimelineTheme for MinimalTimeline {
-    fn name(&self) -> &'static str {
-        "Minimal"
-    }
-
     fn render(&mut self, ui: &Ui, data: &mut TimelineData) -> TimelineAction {
         let mut action = TimelineAction::None;
 
         // Playback controls on the left
-        let playback_btn_width = 30.0;
-        let playback_margin = 8.0;
-
         // Step back button
         if ui.small_button("|<") {
             action = TimelineAction::StepBack;
@@ -330,12 +323,24 @@ impl TimelineTheme for MinimalTimeline {
             let width = pos_max[0] - pos_min[0];
             let rel_x = (mouse_pos[0] - pos_min[0]) / width;
 
+            // Over the density dots, the tooltip also describes the bin under the mouse
+            let over_dots = (mouse_pos[1] - (track_y + 15.0)).abs() <= 6.0;
+            let bin = if data.show_density_tooltip && over_dots {
+                data.overview_bin_at(rel_x)
+            } else {
+                None
+            };
+
             // Time tooltip on hover
             if let (Some(time), Some(start_time)) = (data.time_at_position(rel_x), data.start_time) {
                 let elapsed = (time - start_time).num_milliseconds() as f64 / 1000.0;
                 ui.tooltip(|| {
                     ui.text_colored([0.7, 0.8, 0.9, 1.0], format!("Seek to: {:.1}s", elapsed));
                     ui.text_colored([0.5, 0.6, 0.7, 1.0], format!("Position: {:.1}%", rel_x * 100.0));
+                    if let Some(bin) = &bin {
+                        ui.separator();
+                        density_bin_tooltip(ui, bin);
+                    }
                 });
             }
 
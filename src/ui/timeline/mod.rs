@@ -20,16 +20,55 @@ pub struct TimelineMarker {
     pub color: [f32; 4],
 }
 
-impl TimelineMarker {
-    pub fn new(position: f32, label: &str, color: [f32; 4]) -> Self {
-        Self {
-            position: position.clamp(0.0, 1.0),
-            label: label.to_string(),
-            color,
+/// How `build_density` assigns messages to histogram bins
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum DensityMode {
+    /// Equal time per bin: bar height is the message count in that time slice
+    #[default]
+    Time,
+    /// Equal frames per bin: bar height is the message rate within those frames,
+    /// so activity around long idle gaps doesn't collapse into a single spike
+    Frames,
+}
+
+impl DensityMode {
+    pub const ALL: [DensityMode; 2] = [DensityMode::Time, DensityMode::Frames];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            DensityMode::Time => "By time",
+            DensityMode::Frames => "By frames",
         }
     }
 }
 
+/// Bins used for the overview and zoomed densities unless `set_density_bins` overrides it
+pub const DEFAULT_DENSITY_BINS: usize = 200;
+/// Range offered by the Timeline window's bin count slider
+pub const MIN_DENSITY_BINS: usize = 20;
+pub const MAX_DENSITY_BINS: usize = 1000;
+
+/// One density bin, as described by the hover tooltip
+#[derive(Clone, Debug, PartialEq)]
+pub struct DensityBin {
+    /// Time span of the bin, in seconds from the log start
+    pub start_secs: f64,
+    pub end_secs: f64,
+    /// Frames in the bin
+    pub frames: usize,
+    /// Error frames in the bin (`density_secondary`), when those bins line up with the drawn ones
+    pub errors: Option<u32>,
+}
+
+/// Tooltip body for a hovered density bin
+pub(super) fn density_bin_tooltip(ui: &Ui, bin: &DensityBin) {
+    ui.text(format!("{:.3}s - {:.3}s", bin.start_secs, bin.end_secs));
+    ui.text(format!("{} frames", bin.frames));
+    if let Some(errors) = bin.errors {
+        ui.text(format!("{} error frames", errors));
+    }
+}
+
 /// Shared data for timeline rendering
 #[derive(Clone, Debug)]
 pub struct TimelineData {
@@ -55,8 +94,20 @@ pub struct TimelineData {
     pub markers: Vec<TimelineMarker>,
     /// Secondary density data (e.g., for errors)
     pub density_secondary: Vec<u32>,
-    /// Tertiary density data (e.g., for warnings)
-    pub density_tertiary: Vec<u32>,
+    /// Bins per density histogram (overview and zoomed alike)
+    pub density_bins: usize,
+    /// Describe the hovered density bin (time span, frame and error counts) in a tooltip
+    pub show_density_tooltip: bool,
+    /// Binning mode of the last `build_density` call, reused for zoomed rebuilds
+    density_mode: DensityMode,
+    /// Sorted copy of the timestamps passed to `build_density`
+    timestamps: Vec<DateTime<Utc>>,
+    /// Sorted error frame timestamps, binned into `density_secondary`
+    error_timestamps: Vec<DateTime<Utc>>,
+    /// Density re-binned over just `zoomed_range`
+    zoomed_density: Vec<u32>,
+    /// Visible range `zoomed_density` was built for, None when not zoomed
+    zoomed_range: Option<(f32, f32)>,
 }
 
 impl Default for TimelineData {
@@ -79,7 +130,13 @@ impl TimelineData {
             end_time: None,
             markers: Vec::new(),
             density_secondary: Vec::new(),
-            density_tertiary: Vec::new(),
+            density_bins: DEFAULT_DENSITY_BINS,
+            show_density_tooltip: true,
+            density_mode: DensityMode::default(),
+            timestamps: Vec::new(),
+            error_timestamps: Vec::new(),
+            zoomed_density: Vec::new(),
+            zoomed_range: None,
         }
     }
 
@@ -122,12 +179,6 @@ impl TimelineData {
         }
     }
 
-    /// Set position and return clamped value
-    pub fn set_position(&mut self, pos: f32) -> f32 {
-        self.position = pos.clamp(0.0, 1.0);
-        self.position
-    }
-
     /// Set zoom level
     pub fn set_zoom(&mut self, zoom: f32) {
         self.zoom = zoom.clamp(1.0, 100.0);
@@ -145,81 +196,217 @@ impl TimelineData {
         self.set_zoom(self.zoom / 1.5);
     }
 
-    /// Set loop region
-    pub fn set_loop_region(&mut self, start: Option<f32>, end: Option<f32>) {
-        self.loop_start = start.map(|v| v.clamp(0.0, 1.0));
-        self.loop_end = end.map(|v| v.clamp(0.0, 1.0));
-    }
-
     /// Clear loop region
     pub fn clear_loop_region(&mut self) {
         self.loop_start = None;
         self.loop_end = None;
     }
 
-    /// Check if position is in loop region
-    pub fn in_loop_region(&self) -> bool {
-        if let (Some(start), Some(end)) = (self.loop_start, self.loop_end) {
-            self.position >= start && self.position <= end
-        } else {
-            false
+    /// Build message density histogram from timestamps
+    ///
+    /// This is the full-range overview; the timestamps are kept so the visible
+    /// slice can be re-binned at full resolution when zoomed (see `refresh_zoomed_density`).
+    pub fn build_density(&mut self, timestamps: &[DateTime<Utc>], num_bins: usize, mode: DensityMode) {
+        self.density_bins = num_bins;
+        self.density_mode = mode;
+        self.timestamps = timestamps.to_vec();
+        self.timestamps.sort_unstable();
+        self.rebuild_density();
+    }
+
+    /// Binning mode the densities were built with
+    pub fn density_mode(&self) -> DensityMode {
+        self.density_mode
+    }
+
+    /// Change the binning mode and rebuild both densities from the stored timestamps
+    pub fn set_density_mode(&mut self, mode: DensityMode) {
+        if mode != self.density_mode {
+            self.density_mode = mode;
+            self.rebuild_density();
         }
     }
 
-    /// Add a marker
-    pub fn add_marker(&mut self, position: f32, label: &str, color: [f32; 4]) {
-        self.markers.push(TimelineMarker::new(position, label, color));
+    /// Bin error frames into `density_secondary`, lined up with the overview bins
+    pub fn set_error_timestamps(&mut self, timestamps: &[DateTime<Utc>]) {
+        self.error_timestamps = timestamps.to_vec();
+        self.error_timestamps.sort_unstable();
+        self.density_secondary = self.error_bins();
     }
 
-    /// Clear all markers
-    pub fn clear_markers(&mut self) {
-        self.markers.clear();
+    /// Change the number of bins and rebuild both densities from the stored timestamps
+    pub fn set_density_bins(&mut self, num_bins: usize) {
+        if num_bins != self.density_bins {
+            self.density_bins = num_bins;
+            self.rebuild_density();
+        }
     }
 
-    /// Build message density histogram from timestamps
-    pub fn build_density(&mut self, timestamps: &[DateTime<Utc>], num_bins: usize) {
-        if timestamps.is_empty() {
+    fn rebuild_density(&mut self) {
+        self.zoomed_density.clear();
+        self.zoomed_range = None;
+        let (Some(&min), Some(&max)) = (self.timestamps.first(), self.timestamps.last()) else {
             self.density.clear();
+            self.density_secondary.clear();
+            self.start_time = None;
+            self.end_time = None;
             return;
+        };
+        self.set_time_range(min, max);
+        self.density = match self.density_mode {
+            DensityMode::Time => time_bins(&self.timestamps, min, max, self.density_bins),
+            DensityMode::Frames => frame_bins(&self.timestamps, self.density_bins),
+        };
+        self.density_secondary = self.error_bins();
+        self.refresh_zoomed_density();
+    }
+
+    /// Error frame count of each overview bin
+    fn error_bins(&self) -> Vec<u32> {
+        let (Some(from), Some(to)) = (self.start_time, self.end_time) else {
+            return Vec::new();
+        };
+        match self.density_mode {
+            DensityMode::Time => time_bins(&self.error_timestamps, from, to, self.density.len()),
+            // Count the errors inside the time span each bin's frames cover
+            DensityMode::Frames => {
+                let n = self.density.len();
+                (0..n).map(|i| {
+                    let frames = &self.timestamps[i * self.timestamps.len() / n..(i + 1) * self.timestamps.len() / n];
+                    let lo = self.error_timestamps.partition_point(|ts| *ts < frames[0]);
+                    let hi = self.error_timestamps.partition_point(|ts| *ts <= frames[frames.len() - 1]);
+                    (hi - lo) as u32
+                }).collect()
+            }
         }
+    }
 
-        // Find time range
-        let min_time = timestamps.iter().min().copied();
-        let max_time = timestamps.iter().max().copied();
+    /// Visible part of the timeline (0.0 to 1.0) for the current zoom/pan
+    pub fn visible_range(&self) -> (f32, f32) {
+        let start = self.pan.clamp(0.0, 1.0);
+        (start, (start + 1.0 / self.zoom.max(1.0)).min(1.0))
+    }
 
-        if let (Some(min), Some(max)) = (min_time, max_time) {
-            self.set_time_range(min, max);
+    /// Re-bin the visible slice when zoomed; a no-op while the view is unchanged
+    pub fn refresh_zoomed_density(&mut self) {
+        if self.zoom <= 1.0 || self.timestamps.is_empty() {
+            self.zoomed_density.clear();
+            self.zoomed_range = None;
+            return;
+        }
+        let range = self.visible_range();
+        if self.zoomed_range != Some(range) {
+            self.build_density_range(range);
+        }
+    }
 
-            let total_duration = (max - min).num_milliseconds() as f64;
-            if total_duration <= 0.0 {
-                return;
-            }
+    /// Bin only the timestamps inside `range` (0.0 to 1.0), keeping the overview and time range
+    pub fn build_density_range(&mut self, range: (f32, f32)) {
+        self.zoomed_range = Some(range);
+        self.zoomed_density.clear();
+        let (Some(from), Some(to)) = (self.time_at_position(range.0), self.time_at_position(range.1)) else {
+            return;
+        };
+        let lo = self.timestamps.partition_point(|ts| *ts < from);
+        let hi = self.timestamps.partition_point(|ts| *ts <= to);
+        let slice = &self.timestamps[lo..hi];
+        self.zoomed_density = match self.density_mode {
+            DensityMode::Time => time_bins(slice, from, to, self.density_bins),
+            DensityMode::Frames => frame_bins(slice, self.density_bins),
+        };
+    }
+
+    /// Density to draw under the visible range: the zoomed re-bin when it's
+    /// current, otherwise the overview. The range is what the bins span.
+    pub fn density_for_view(&self) -> (&[u32], (f32, f32)) {
+        match self.zoomed_range {
+            Some(range) if range == self.visible_range() => (&self.zoomed_density, range),
+            _ => (&self.density, (0.0, 1.0)),
+        }
+    }
 
-            // Build histogram
-            let mut density = vec![0u32; num_bins];
-            for ts in timestamps {
-                let elapsed = (*ts - min).num_milliseconds() as f64;
-                let bin = ((elapsed / total_duration) * (num_bins - 1) as f64) as usize;
-                let bin = bin.min(num_bins - 1);
-                density[bin] += 1;
-            }
+    /// Overview bin under `x` (0.0 to 1.0 across the drawn bars)
+    pub fn overview_bin_at(&self, x: f32) -> Option<DensityBin> {
+        self.bin_at(&self.density, (0.0, 1.0), x)
+    }
+
+    /// Bin under `x` (0.0 to 1.0 across the drawn bars) of `density_for_view`
+    pub fn view_bin_at(&self, x: f32) -> Option<DensityBin> {
+        let (bins, range) = self.density_for_view();
+        self.bin_at(bins, range, x)
+    }
 
-            self.density = density;
+    fn bin_at(&self, bins: &[u32], range: (f32, f32), x: f32) -> Option<DensityBin> {
+        let log_start = self.start_time?;
+        if bins.is_empty() {
+            return None;
         }
+        let n = bins.len();
+        let i = ((x.clamp(0.0, 1.0) * n as f32) as usize).min(n - 1);
+        let slice = if range == (0.0, 1.0) {
+            &self.timestamps[..]
+        } else {
+            let (from, to) = (self.time_at_position(range.0)?, self.time_at_position(range.1)?);
+            let lo = self.timestamps.partition_point(|ts| *ts < from);
+            let hi = self.timestamps.partition_point(|ts| *ts <= to);
+            &self.timestamps[lo..hi]
+        };
+        let secs = |t: DateTime<Utc>| (t - log_start).num_microseconds().unwrap_or(0) as f64 / 1e6;
+
+        let (start, end, frames) = match self.density_mode {
+            // `time_bins` spreads the span over n - 1 bin widths, the last bin holding only the end
+            DensityMode::Time => {
+                let (from, to) = (self.time_at_position(range.0)?, self.time_at_position(range.1)?);
+                let total_us = (to - from).num_microseconds()?;
+                let widths = (n - 1).max(1) as i64;
+                let at = |k: usize| from + chrono::Duration::microseconds(total_us * (k as i64).min(widths) / widths);
+                (at(i), at(i + 1), bins[i] as usize)
+            }
+            // `frame_bins` gives each bin an equal share of the frames
+            DensityMode::Frames => {
+                let lo = i * slice.len() / n;
+                let hi = (i + 1) * slice.len() / n;
+                let frames = slice.get(lo..hi).filter(|f| !f.is_empty())?;
+                (frames[0], frames[frames.len() - 1], frames.len())
+            }
+        };
+        // Error bins are only built for the overview
+        let errors = (range == (0.0, 1.0) && self.density_secondary.len() == n)
+            .then(|| self.density_secondary[i]);
+
+        Some(DensityBin { start_secs: secs(start), end_secs: secs(end), frames, errors })
     }
+}
 
-    /// Convert position to visible position accounting for zoom/pan
-    pub fn position_to_screen(&self, pos: f32, width: f32) -> f32 {
-        let visible_range = 1.0 / self.zoom;
-        let relative_pos = (pos - self.pan) / visible_range;
-        relative_pos * width
+/// Equal-time bins over `from..=to` holding the message count of each slice
+fn time_bins(sorted: &[DateTime<Utc>], from: DateTime<Utc>, to: DateTime<Utc>, num_bins: usize) -> Vec<u32> {
+    let total_us = (to - from).num_microseconds().unwrap_or(i64::MAX) as f64;
+    if num_bins == 0 || total_us <= 0.0 {
+        return Vec::new();
     }
 
-    /// Convert screen position to timeline position accounting for zoom/pan
-    pub fn screen_to_position(&self, screen_x: f32, width: f32) -> f32 {
-        let visible_range = 1.0 / self.zoom;
-        (screen_x / width) * visible_range + self.pan
+    let mut density = vec![0u32; num_bins];
+    for ts in sorted {
+        let elapsed = (*ts - from).num_microseconds().unwrap_or(0) as f64;
+        let bin = ((elapsed / total_us) * (num_bins - 1) as f64) as usize;
+        density[bin.min(num_bins - 1)] += 1;
     }
+    density
+}
+
+/// Bin by message index: each bin holds an equal share of the frames and
+/// stores their rate (messages/sec over the time those frames span)
+fn frame_bins(sorted: &[DateTime<Utc>], num_bins: usize) -> Vec<u32> {
+    let num_bins = num_bins.min(sorted.len());
+    let mut density = Vec::with_capacity(num_bins);
+    for bin in 0..num_bins {
+        let start = bin * sorted.len() / num_bins;
+        let end = (bin + 1) * sorted.len() / num_bins;
+        let frames = &sorted[start..end];
+        let span_ms = (frames[frames.len() - 1] - frames[0]).num_milliseconds().max(1) as f64;
+        density.push((frames.len() as f64 * 1000.0 / span_ms).round() as u32);
+    }
+    density
 }
 
 /// Actions returned by timeline widgets
@@ -227,8 +414,8 @@ impl TimelineData {
 pub enum TimelineAction {
     None,
     Seek(f32),
-    Zoom(f32),
-    LoopSet(f32, f32),
+    Zoom,
+    LoopSet,
     LoopClear,
     // Playback controls
     Play,
@@ -263,9 +450,6 @@ impl TimelineVariant {
 
 /// Trait for timeline theme implementations
 pub trait TimelineTheme {
-    /// Get the name of this theme
-    fn name(&self) -> &'static str;
-
     /// Render the timeline and return any action
     fn render(&mut self, ui: &Ui, data: &mut TimelineData) -> TimelineAction;
 }
@@ -314,62 +498,12 @@ impl TimelineWidget {
         self.variant = variant;
     }
 
-    /// Convenience methods delegating to data
-
-    pub fn set_time_range(&mut self, start: DateTime<Utc>, end: DateTime<Utc>) {
-        self.data.set_time_range(start, end);
-    }
-
-    pub fn set_position(&mut self, pos: f32) {
-        self.data.set_position(pos);
-    }
-
-    pub fn position(&self) -> f32 {
-        self.data.position
-    }
-
-    pub fn current_time(&self) -> Option<DateTime<Utc>> {
-        self.data.current_time()
-    }
-
     pub fn seek_to_time(&mut self, time: DateTime<Utc>) {
         self.data.seek_to_time(time);
     }
 
-    pub fn set_zoom(&mut self, zoom: f32) {
-        self.data.set_zoom(zoom);
-    }
-
-    pub fn zoom_in(&mut self) {
-        self.data.zoom_in();
-    }
-
-    pub fn zoom_out(&mut self) {
-        self.data.zoom_out();
-    }
-
-    pub fn set_loop_region(&mut self, start: Option<f32>, end: Option<f32>) {
-        self.data.set_loop_region(start, end);
-    }
-
-    pub fn clear_loop_region(&mut self) {
-        self.data.clear_loop_region();
-    }
-
-    pub fn in_loop_region(&self) -> bool {
-        self.data.in_loop_region()
-    }
-
-    pub fn add_marker(&mut self, position: f32, label: &str, color: [f32; 4]) {
-        self.data.add_marker(position, label, color);
-    }
-
-    pub fn clear_markers(&mut self) {
-        self.data.clear_markers();
-    }
-
-    pub fn build_density(&mut self, timestamps: &[DateTime<Utc>], num_bins: usize) {
-        self.data.build_density(timestamps, num_bins);
+    pub fn build_density(&mut self, timestamps: &[DateTime<Utc>], num_bins: usize, mode: DensityMode) {
+        self.data.build_density(timestamps, num_bins, mode);
     }
 
     /// Set the playing state (for playback button display)
@@ -379,6 +513,7 @@ impl TimelineWidget {
 
     /// Render the timeline using the active variant
     pub fn render(&mut self, ui: &Ui) -> TimelineAction {
+        self.data.refresh_zoomed_density();
         match self.variant {
             TimelineVariant::Minimal => self.minimal.render(ui, &mut self.data),
             TimelineVariant::Classic => self.classic.render(ui, &mut self.data),
@@ -389,7 +524,6 @@ impl TimelineWidget {
 /// Timeline window wrapper
 pub struct TimelineWindow {
     timeline: TimelineWidget,
-    visible: bool,
 }
 
 impl Default for TimelineWindow {
@@ -402,7 +536,6 @@ impl TimelineWindow {
     pub fn new() -> Self {
         Self {
             timeline: TimelineWidget::new(),
-            visible: true,
         }
     }
 
@@ -410,19 +543,7 @@ impl TimelineWindow {
         &mut self.timeline
     }
 
-    pub fn set_visible(&mut self, visible: bool) {
-        self.visible = visible;
-    }
-
-    pub fn is_visible(&self) -> bool {
-        self.visible
-    }
-
     pub fn render(&mut self, ui: &Ui, is_open: &mut bool) -> TimelineAction {
-        if !self.visible {
-            return TimelineAction::None;
-        }
-
         let mut action = TimelineAction::None;
 
         ui.window("Timeline")
@@ -430,17 +551,131 @@ impl TimelineWindow {
             .position([10.0, 860.0], imgui::Condition::FirstUseEver)
             .opened(is_open)
             .build(|| {
+                let variants = TimelineVariant::all();
+                let mut variant_idx = variants.iter().position(|v| *v == self.timeline.variant()).unwrap_or(0);
+                let names: Vec<&str> = variants.iter().map(|v| v.name()).collect();
+                ui.set_next_item_width(100.0);
+                if ui.combo_simple_string("Style", &mut variant_idx, &names) {
+                    self.timeline.set_variant(variants[variant_idx]);
+                }
+                ui.same_line();
+
+                let data = self.timeline.data_mut();
+                let mut mode_idx = DensityMode::ALL.iter()
+                    .position(|m| *m == data.density_mode())
+                    .unwrap_or(0);
+                let labels: Vec<&str> = DensityMode::ALL.iter().map(|m| m.label()).collect();
+                ui.set_next_item_width(110.0);
+                if ui.combo_simple_string("Density", &mut mode_idx, &labels) {
+                    data.set_density_mode(DensityMode::ALL[mode_idx]);
+                }
+                if ui.is_item_hovered() {
+                    ui.tooltip_text("By frames gives each bar an equal share of the frames, so bursts around long idle gaps stay visible");
+                }
+                ui.same_line();
+                let mut bins = data.density_bins as i32;
+                ui.set_next_item_width(140.0);
+                if ui.slider_config("Bins", MIN_DENSITY_BINS as i32, MAX_DENSITY_BINS as i32).build(&mut bins) {
+                    data.set_density_bins(bins as usize);
+                }
+                if ui.is_item_hovered() {
+                    ui.tooltip_text("Bars across the timeline; when zoomed, the visible slice is re-binned to this many bars");
+                }
+                ui.same_line();
+                ui.checkbox("Bin info", &mut data.show_density_tooltip);
+                if ui.is_item_hovered() {
+                    ui.tooltip_text("Hover the density bars for each bin's time span and frame/error counts");
+                }
                 action = self.timeline.render(ui);
             });
 
         action
     }
+}
 
-    /// Render content without window wrapper - for embedding in workspace
-    pub fn render_content(&mut self, ui: &Ui, _width: f32, _height: f32) -> TimelineAction {
-        if !self.visible {
-            return TimelineAction::None;
-        }
-        self.timeline.render(ui)
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_zoomed_density_rebins_visible_range() {
+        let start = Utc.timestamp_opt(1_000, 0).unwrap();
+        // One frame per ms for 10s
+        let timestamps: Vec<_> = (0..10_000).map(|ms| start + chrono::Duration::milliseconds(ms)).collect();
+        let mut data = TimelineData::new();
+        data.build_density(&timestamps, 100, DensityMode::Time);
+        assert_eq!(data.density.len(), 100);
+        assert_eq!(data.density_for_view().1, (0.0, 1.0));
+
+        // Zoomed 10x: the same number of bins now covers the first second only
+        data.set_zoom(10.0);
+        data.refresh_zoomed_density();
+        let (bins, range) = data.density_for_view();
+        assert_eq!(range, (0.0, 0.1));
+        assert_eq!(bins.len(), 100);
+        assert!((995..=1001).contains(&bins.iter().sum::<u32>()));
+        assert_eq!(data.density.iter().sum::<u32>(), 10_000);
+
+        // Panning invalidates the zoomed bins until the next refresh
+        data.pan = 0.5;
+        assert_eq!(data.density_for_view().1, (0.0, 1.0));
+        data.refresh_zoomed_density();
+        let (from, to) = data.density_for_view().1;
+        assert_eq!(from, 0.5);
+        assert!((to - 0.6).abs() < 1e-6);
+
+        data.set_density_bins(50);
+        assert_eq!(data.density.len(), 50);
+        assert_eq!(data.density_for_view().0.len(), 50);
+    }
+
+    #[test]
+    fn test_density_bin_at() {
+        let start = Utc.timestamp_opt(1_000, 0).unwrap();
+        // 10 frames per second for 10s, then 100 frames in the last 100 ms
+        let mut timestamps: Vec<_> = (0..100).map(|i| start + chrono::Duration::milliseconds(i * 100)).collect();
+        timestamps.extend((0..100).map(|i| start + chrono::Duration::milliseconds(9_900 + i)));
+        let mut data = TimelineData::new();
+        data.build_density(&timestamps, 11, DensityMode::Time);
+        data.density_secondary = vec![0; 11];
+        data.density_secondary[3] = 2;
+
+        // Time bins split the 9.999s log into 10 widths
+        let bin = data.overview_bin_at(0.3).unwrap();
+        assert!((bin.start_secs - 2.9997).abs() < 1e-3 && (bin.end_secs - 3.9996).abs() < 1e-3);
+        assert_eq!(bin.frames, data.density[3] as usize);
+        assert_eq!(bin.errors, Some(2));
+        assert_eq!(data.overview_bin_at(1.0).unwrap().frames, *data.density.last().unwrap() as usize);
+
+        // Frame bins hold equal shares of the frames; error bins don't line up when zoomed
+        data.build_density(&timestamps, 4, DensityMode::Frames);
+        let last = data.overview_bin_at(0.9).unwrap();
+        assert_eq!(last.frames, 50);
+        assert!(last.start_secs > 9.9 && last.end_secs <= 10.0);
+        data.set_zoom(2.0);
+        data.refresh_zoomed_density();
+        assert_eq!(data.view_bin_at(0.0).unwrap().errors, None);
+
+        assert_eq!(TimelineData::new().view_bin_at(0.5), None);
+    }
+
+    #[test]
+    fn test_error_density_follows_overview_bins() {
+        let start = Utc.timestamp_opt(1_000, 0).unwrap();
+        let timestamps: Vec<_> = (0..100).map(|i| start + chrono::Duration::milliseconds(i * 100)).collect();
+        let errors = [timestamps[5], timestamps[6], timestamps[95]];
+        let mut data = TimelineData::new();
+        data.build_density(&timestamps, 10, DensityMode::Time);
+        data.set_error_timestamps(&errors);
+        assert_eq!(data.density_secondary.len(), 10);
+        assert_eq!(data.overview_bin_at(0.0).unwrap().errors, Some(2));
+        assert_eq!(data.density_secondary.iter().sum::<u32>(), 3);
+
+        // Rebinning keeps the error bins lined up
+        data.set_density_mode(DensityMode::Frames);
+        data.set_density_bins(4);
+        assert_eq!(data.density_secondary, vec![2, 0, 0, 1]);
+        assert_eq!(data.overview_bin_at(0.9).unwrap().errors, Some(1));
     }
 }
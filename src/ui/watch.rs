@@ -0,0 +1,334 @@
+//! Watch window: a numeric dashboard of a handful of decoded signals, each a big
+//! readout of its value at the playhead (or the newest live frame) with unit,
+//! min/max seen and the direction of its last change.
+
+use imgui::{Condition, Ui};
+use crate::core::{CanMessage, DbcFile};
+use crate::decode::router::{parse_signal_key, signal_key, SignalRouter};
+use crate::decode::SignalDecoder;
+use std::cmp::Ordering;
+
+const CARD_SIZE: [f32; 2] = [190.0, 115.0];
+const VALUE_FONT_SCALE: f32 = 2.2;
+/// Signals listed in the add picker at once
+const PICKER_LIMIT: usize = 50;
+
+/// One readout
+#[derive(Debug, Clone, PartialEq)]
+struct WatchedSignal {
+    /// Chart key "name@0xID@busN"
+    key: String,
+    unit: String,
+    value: Option<f64>,
+    /// (min, max) of the values seen since added or reset
+    seen: Option<(f64, f64)>,
+    /// Direction of the most recent change (Equal until the value first moves)
+    trend: Ordering,
+}
+
+impl WatchedSignal {
+    fn new(key: String, unit: String) -> Self {
+        Self { key, unit, value: None, seen: None, trend: Ordering::Equal }
+    }
+
+    fn observe(&mut self, value: f64) {
+        if let Some(change) = self.value.and_then(|previous| value.partial_cmp(&previous)) {
+            if change != Ordering::Equal {
+                self.trend = change;
+            }
+        }
+        self.value = Some(value);
+        self.seen = Some(match self.seen {
+            Some((lo, hi)) => (lo.min(value), hi.max(value)),
+            None => (value, value),
+        });
+    }
+
+    fn reset(&mut self) {
+        self.seen = self.value.map(|v| (v, v));
+        self.trend = Ordering::Equal;
+    }
+}
+
+/// What a card's buttons asked for
+enum CardAction {
+    MoveLeft,
+    MoveRight,
+    Remove,
+}
+
+/// Window of large current-value readouts for the watched signals
+pub struct WatchWindow {
+    watched: Vec<WatchedSignal>,
+    /// Routes for the watched keys, rebuilt after the list or the DBC changes
+    router: Option<SignalRouter>,
+    filter: String,
+    bus: i32,
+}
+
+impl WatchWindow {
+    pub fn new() -> Self {
+        Self {
+            watched: Vec::new(),
+            router: None,
+            filter: String::new(),
+            bus: 0,
+        }
+    }
+
+    /// Watched signal keys, in display order (for savestates)
+    pub fn watched_keys(&self) -> Vec<String> {
+        self.watched.iter().map(|w| w.key.clone()).collect()
+    }
+
+    /// Replace the watch list with saved keys
+    pub fn restore(&mut self, keys: &[String], dbc: &DbcFile) {
+        self.watched.clear();
+        for key in keys {
+            self.add(key, dbc);
+        }
+    }
+
+    fn add(&mut self, key: &str, dbc: &DbcFile) {
+        if self.watched.iter().any(|w| w.key == key) {
+            return;
+        }
+        self.watched.push(WatchedSignal::new(key.to_string(), signal_unit(dbc, key)));
+        self.router = None;
+    }
+
+    /// The DBC was replaced or edited: signals may have moved to other messages
+    pub fn dbc_changed(&mut self) {
+        self.router = None;
+    }
+
+    /// Forget values and min/max seen (a new log was loaded)
+    pub fn clear_values(&mut self) {
+        for watched in &mut self.watched {
+            watched.value = None;
+            watched.reset();
+        }
+    }
+
+    /// Decode the watched signals from `latest(id, bus)`, the newest frame of each message
+    /// at the playhead or on the live bus
+    pub fn update(&mut self, decoder: &SignalDecoder, dbc: &DbcFile, latest: impl Fn(u32, u8) -> Option<CanMessage>) {
+        if self.watched.is_empty() {
+            return;
+        }
+        if self.router.is_none() {
+            for watched in &mut self.watched {
+                watched.unit = signal_unit(dbc, &watched.key);
+            }
+        }
+        let router = self.router.get_or_insert_with(|| SignalRouter::new(dbc, self.watched.iter().map(|w| w.key.as_str())));
+        for (id, bus) in router.sources() {
+            let Some(frame) = latest(id, bus) else {
+                continue;
+            };
+            router.route(decoder, &frame, |key, value| {
+                if let Some(watched) = self.watched.iter_mut().find(|w| w.key == key) {
+                    watched.observe(value);
+                }
+            });
+        }
+    }
+
+    pub fn render(&mut self, ui: &Ui, dbc: &DbcFile, is_open: &mut bool) {
+        ui.window("Watch")
+            .size([620.0, 300.0], Condition::FirstUseEver)
+            .position([250.0, 150.0], Condition::FirstUseEver)
+            .opened(is_open)
+            .build(|| {
+                self.render_content(ui, dbc);
+            });
+    }
+
+    fn render_content(&mut self, ui: &Ui, dbc: &DbcFile) {
+        if ui.button("Add Signal...") {
+            ui.open_popup("watch_add");
+        }
+        ui.popup("watch_add", || self.render_picker(ui, dbc));
+        ui.same_line();
+        if ui.button("Reset Min/Max") {
+            for watched in &mut self.watched {
+                watched.reset();
+            }
+        }
+        ui.separator();
+
+        if self.watched.is_empty() {
+            ui.text_colored([0.5, 0.5, 0.5, 1.0], "Add signals to watch their current values");
+            return;
+        }
+
+        let mut action = None;
+        let last = self.watched.len() - 1;
+        let row_width = ui.content_region_avail()[0];
+        let spacing = ui.clone_style().item_spacing[0];
+        let mut x = 0.0;
+        for (idx, watched) in self.watched.iter().enumerate() {
+            if idx > 0 && x + CARD_SIZE[0] <= row_width {
+                ui.same_line();
+            } else {
+                x = 0.0;
+            }
+            x += CARD_SIZE[0] + spacing;
+            if let Some(a) = render_card(ui, watched, idx, idx > 0, idx < last) {
+                action = Some((idx, a));
+            }
+        }
+
+        match action {
+            Some((idx, CardAction::MoveLeft)) => self.watched.swap(idx, idx - 1),
+            Some((idx, CardAction::MoveRight)) => self.watched.swap(idx, idx + 1),
+            Some((idx, CardAction::Remove)) => {
+                self.watched.remove(idx);
+                self.router = None;
+            }
+            None => {}
+        }
+    }
+
+    fn render_picker(&mut self, ui: &Ui, dbc: &DbcFile) {
+        ui.set_next_item_width(200.0);
+        ui.input_text("##watch_filter", &mut self.filter).hint("Search signals").build();
+        ui.same_line();
+        ui.set_next_item_width(80.0);
+        if ui.input_int("Bus", &mut self.bus).build() {
+            self.bus = self.bus.clamp(0, u8::MAX as i32);
+        }
+
+        if dbc.messages.is_empty() {
+            ui.text_disabled("Load a DBC first");
+            return;
+        }
+        let filter = self.filter.to_lowercase();
+        let matches = dbc.messages.iter()
+            .flat_map(|msg| msg.signals.iter().map(move |signal| (msg, signal)))
+            .filter(|(msg, signal)| {
+                filter.is_empty()
+                    || signal.name.to_lowercase().contains(&filter)
+                    || msg.name.to_lowercase().contains(&filter)
+            })
+            .take(PICKER_LIMIT);
+        let mut picked = None;
+        for (msg, signal) in matches {
+            let key = signal_key(&signal.name, msg.id, self.bus as u8);
+            let watching = self.watched.iter().any(|w| w.key == key);
+            if ui.selectable_config(format!("{}  (0x{:03X} {})", signal.name, msg.id, msg.name))
+                .selected(watching)
+                .build()
+            {
+                picked = Some(key);
+            }
+        }
+        if let Some(key) = picked {
+            self.add(&key, dbc);
+            ui.close_current_popup();
+        }
+    }
+}
+
+impl Default for WatchWindow {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn signal_unit(dbc: &DbcFile, key: &str) -> String {
+    let (name, msg_id, _) = parse_signal_key(key);
+    dbc.messages.iter()
+        .filter(|m| msg_id.is_none_or(|id| m.id == id))
+        .find_map(|m| m.get_signal(name))
+        .and_then(|s| s.unit.clone())
+        .unwrap_or_default()
+}
+
+fn render_card(ui: &Ui, watched: &WatchedSignal, idx: usize, can_left: bool, can_right: bool) -> Option<CardAction> {
+    let mut action = None;
+    ui.child_window(format!("watch_card_{}", idx))
+        .size(CARD_SIZE)
+        .border(true)
+        .build(|| {
+            ui.text(&watched.key);
+            if ui.is_item_hovered() {
+                ui.tooltip_text(&watched.key);
+            }
+
+            let value = match watched.value {
+                Some(v) => format!("{:.3}", v),
+                None => "--".to_string(),
+            };
+            ui.set_window_font_scale(VALUE_FONT_SCALE);
+            ui.text(&value);
+            ui.set_window_font_scale(1.0);
+            ui.same_line();
+            let (arrow, color) = match watched.trend {
+                Ordering::Greater => ("^", [0.4, 0.9, 0.4, 1.0]),
+                Ordering::Less => ("v", [1.0, 0.5, 0.4, 1.0]),
+                Ordering::Equal => ("=", [0.6, 0.6, 0.6, 1.0]),
+            };
+            ui.text_colored(color, arrow);
+            ui.same_line();
+            ui.text_disabled(&watched.unit);
+
+            match watched.seen {
+                Some((lo, hi)) => ui.text_disabled(format!("min {:.3}  max {:.3}", lo, hi)),
+                None => ui.text_disabled("no frames yet"),
+            }
+
+            let _no_left = ui.begin_disabled(!can_left);
+            if ui.small_button("<") {
+                action = Some(CardAction::MoveLeft);
+            }
+            drop(_no_left);
+            ui.same_line();
+            let _no_right = ui.begin_disabled(!can_right);
+            if ui.small_button(">") {
+                action = Some(CardAction::MoveRight);
+            }
+            drop(_no_right);
+            ui.same_line();
+            if ui.small_button("Remove") {
+                action = Some(CardAction::Remove);
+            }
+        });
+    action
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::dbc::{DbcMessage, DbcSignal};
+
+    #[test]
+    fn test_watch_tracks_value_range_and_trend() {
+        let mut engine = DbcMessage::new(0x100, "Engine", 8);
+        engine.add_signal(DbcSignal::new("Rpm", 0, 8));
+        let mut dbc = DbcFile::new();
+        dbc.add_message(engine);
+        let mut decoder = SignalDecoder::new();
+        decoder.set_dbc(dbc.clone());
+
+        let mut window = WatchWindow::new();
+        window.restore(&["Rpm@0x100@bus1".to_string(), "Rpm@0x100@bus1".to_string()], &dbc);
+        assert_eq!(window.watched_keys(), vec!["Rpm@0x100@bus1"]);
+
+        // Only frames on the watched bus count
+        for byte in [40u8, 60, 50] {
+            window.update(&decoder, &dbc, |id, bus| {
+                (bus == 1).then(|| CanMessage::new(bus, id, vec![byte].into()))
+            });
+        }
+        let rpm = &window.watched[0];
+        assert_eq!((rpm.value, rpm.seen, rpm.trend), (Some(50.0), Some((40.0, 60.0)), Ordering::Less));
+
+        // An unchanged value keeps the trend of the last change
+        window.update(&decoder, &dbc, |id, bus| Some(CanMessage::new(bus, id, vec![50].into())));
+        assert_eq!(window.watched[0].trend, Ordering::Less);
+
+        window.clear_values();
+        assert_eq!((window.watched[0].value, window.watched[0].seen), (None, None));
+    }
+}
@@ -1,8 +1,71 @@
-use imgui::{Condition, StyleColor, Ui};
-use std::collections::HashMap;
+use imgui::{Condition, Key, StyleColor, Ui, WindowFocusedFlags};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::time::{Duration, Instant};
 use crate::core::CanMessage;
-use crate::core::dbc::DbcFile;
+use crate::core::byte_pattern::BytePattern;
+use crate::core::dbc::{CycleTiming, DbcFile};
+use crate::ui::colors::{series_color, ByteChangeColors, ColorPalette};
+
+/// Group name for rows that match no prefix or ID range
+const OTHER_GROUP: &str = "Other";
+
+/// Row ID of a bus's error frames: outside the 29-bit ID range, so it never meets a real message
+const ERROR_FRAME_ROW_ID: u32 = u32::MAX;
+
+const RTR_BADGE_COLOR: [f32; 4] = [0.85, 0.7, 0.25, 1.0];
+const ERROR_COLOR: [f32; 4] = [1.0, 0.35, 0.35, 1.0];
+
+/// Frames matching the byte search: row text, and the outline around the matched bytes
+pub const BYTE_MATCH_COLOR: [f32; 4] = [1.0, 0.85, 0.2, 1.0];
+
+/// "Find bytes" box: highlights (doesn't filter) frames whose data contains a
+/// byte pattern such as `?? FF ?? ??`
+#[derive(Default)]
+pub struct ByteSearch {
+    text: String,
+    pattern: Option<BytePattern>,
+    error: Option<String>,
+}
+
+impl ByteSearch {
+    pub fn render(&mut self, ui: &Ui) {
+        ui.text("Find bytes:");
+        ui.same_line();
+        ui.set_next_item_width(130.0);
+        if ui.input_text("##byte_search", &mut self.text).hint("?? FF ?? ??").build() {
+            self.update();
+        }
+        if ui.is_item_hovered() {
+            ui.tooltip_text("Highlight frames whose data contains these bytes at any offset; ? is a wildcard nibble");
+        }
+        if let Some(error) = &self.error {
+            ui.same_line();
+            ui.text_colored(ERROR_COLOR, error);
+        }
+    }
+
+    fn update(&mut self) {
+        (self.pattern, self.error) = if self.text.trim().is_empty() {
+            (None, None)
+        } else {
+            match BytePattern::parse(&self.text) {
+                Ok(pattern) => (Some(pattern), None),
+                Err(e) => (None, Some(e)),
+            }
+        };
+    }
+
+    /// Matched bytes of a frame's data (None without a valid pattern)
+    pub fn find(&self, data: &[u8]) -> Option<std::ops::Range<usize>> {
+        self.pattern.as_ref()?.find(data)
+    }
+
+    /// Whether `frame` carries data matching the pattern (never error or remote frames)
+    pub fn matches(&self, frame: &CanMessage) -> bool {
+        !frame.is_error && !frame.is_rtr && self.find(&frame.data).is_some()
+    }
+}
 
 /// Direction: RX (received) or TX (sent)
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
@@ -24,6 +87,8 @@ pub struct MessageState {
     pub freq: f32,
     pub last_timestamp: f64,
     pub last_update: Instant,
+    /// Most recent frame as received, for clipboard export
+    last_frame: Option<CanMessage>,
     // For frequency calculation
     freq_samples: Vec<f64>,
 }
@@ -34,22 +99,29 @@ impl MessageState {
             MessageDirection::Rx => "",
             MessageDirection::Tx => " (TX)",
         };
+        let name = if id == ERROR_FRAME_ROW_ID {
+            format!("Error frames{}", suffix)
+        } else {
+            format!("MSG_0x{:03X}{}", id, suffix)
+        };
         Self {
             id,
             bus,
             direction,
-            name: format!("MSG_0x{:03X}{}", id, suffix),
+            name,
             data: Vec::new(),
             byte_colors: Vec::new(),
             count: 0,
             freq: 0.0,
             last_timestamp: 0.0,
             last_update: Instant::now(),
+            last_frame: None,
             freq_samples: Vec::with_capacity(10),
         }
     }
 
-    pub fn update(&mut self, msg: &CanMessage, msg_name: Option<&str>) {
+    /// Fold in a frame; changed bytes are colored from `palette`
+    pub fn update(&mut self, msg: &CanMessage, msg_name: Option<&str>, palette: ColorPalette) {
         // Update name if provided (DBC names override default names)
         if let Some(name) = msg_name {
             if !name.is_empty() {
@@ -73,14 +145,15 @@ impl MessageState {
         // Update data and calculate colors
         let old_data = self.data.clone();
         self.data = msg.data.to_vec();
-        self.byte_colors = self.calculate_byte_colors(&old_data, &msg.data);
+        self.byte_colors = self.calculate_byte_colors(&old_data, &msg.data, palette.byte_change_colors());
 
         self.count += 1;
         self.last_timestamp = msg.timestamp_unix();
         self.last_update = Instant::now();
+        self.last_frame = Some(msg.clone());
     }
 
-    fn calculate_byte_colors(&self, old_data: &[u8], new_data: &[u8]) -> Vec<[f32; 4]> {
+    fn calculate_byte_colors(&self, old_data: &[u8], new_data: &[u8], changed: ByteChangeColors) -> Vec<[f32; 4]> {
         let mut colors = Vec::with_capacity(new_data.len());
 
         for (i, &new_byte) in new_data.iter().enumerate() {
@@ -99,13 +172,11 @@ impl MessageState {
 
                 if diff == 0xFF {
                     // All bits changed (toggle?)
-                    [0.9, 0.6, 0.2, 1.0] // Orange
+                    changed.toggled
                 } else if new_byte > old_byte {
-                    // Increasing
-                    [0.3, 0.7, 0.4, 1.0] // Green
+                    changed.increased
                 } else if new_byte < old_byte {
-                    // Decreasing
-                    [0.7, 0.4, 0.3, 1.0] // Red
+                    changed.decreased
                 } else {
                     // Mixed change
                     [0.5, 0.5, 0.2 + change_ratio * 0.5, 1.0] // Yellow-ish
@@ -137,11 +208,68 @@ impl MessageState {
     pub fn is_active(&self) -> bool {
         self.last_update.elapsed() < Duration::from_millis(500)
     }
+
+    /// The row collecting a bus's error frames
+    pub fn is_error_row(&self) -> bool {
+        self.id == ERROR_FRAME_ROW_ID
+    }
+
+    /// Whether the most recent frame was a remote request
+    pub fn is_rtr(&self) -> bool {
+        self.last_frame.as_ref().is_some_and(|f| f.is_rtr)
+    }
 }
 
 /// Key: (CAN ID, bus, direction)
 type MessageKey = (u32, u8, MessageDirection);
 
+/// How the live message list groups its rows
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum MessageGrouping {
+    /// Flat list
+    #[default]
+    None,
+    /// By DBC message name up to the first underscore (`PT_EngineData` -> `PT`)
+    NamePrefix,
+    /// By the user-defined ID ranges
+    IdRange,
+}
+
+impl MessageGrouping {
+    pub const ALL: [MessageGrouping; 3] = [MessageGrouping::None, MessageGrouping::NamePrefix, MessageGrouping::IdRange];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            MessageGrouping::None => "No grouping",
+            MessageGrouping::NamePrefix => "Name prefix",
+            MessageGrouping::IdRange => "ID ranges",
+        }
+    }
+}
+
+/// IDs `start..=end` shown together under `name`
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct IdRangeGroup {
+    pub name: String,
+    pub start: u32,
+    pub end: u32,
+}
+
+/// Message list grouping, persisted with the app settings
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct MessageGroupSettings {
+    pub mode: MessageGrouping,
+    /// Checked in order; the first range containing an ID wins
+    pub id_ranges: Vec<IdRangeGroup>,
+    /// Groups the user has collapsed
+    pub collapsed: BTreeSet<String>,
+}
+
+/// Color used for a bus number in the message list
+fn bus_color(palette: ColorPalette, bus: u8) -> [f32; 4] {
+    series_color(palette, bus as usize)
+}
+
 /// Window showing live CAN message state - one row per CAN ID + direction (Cabana style)
 pub struct MessageListWindow {
     /// Map of (CAN ID, bus, direction) to current state
@@ -154,11 +282,34 @@ pub struct MessageListWindow {
     live_mode: bool,
     /// Filter string
     filter: String,
+    /// Only show rows from this bus (None = all buses)
+    bus_filter: Option<u8>,
     /// Sort column
     sort_column: usize,
     sort_ascending: bool,
     /// DBC file for message names
     dbc_file: Option<DbcFile>,
+    grouping: MessageGroupSettings,
+    /// Rows pinned to the top of the list (Enter toggles)
+    pinned: HashSet<MessageKey>,
+    /// Scroll the selected row into view on the next render (after keyboard navigation)
+    scroll_to_selected: bool,
+    /// The row list had keyboard focus last frame (it takes Up/Down/Home/End for itself)
+    has_key_focus: bool,
+    byte_search: ByteSearch,
+    color_palette: ColorPalette,
+}
+
+/// Move through `order` from `current` by `step` rows, clamped to the ends.
+/// With nothing selected, Down picks the first row and Up the last.
+fn step_selection(order: &[MessageKey], current: Option<MessageKey>, step: isize) -> Option<MessageKey> {
+    let last = order.len().checked_sub(1)?;
+    let target = match current.and_then(|key| order.iter().position(|k| *k == key)) {
+        Some(i) => i.saturating_add_signed(step).min(last),
+        None if step < 0 => last,
+        None => 0,
+    };
+    order.get(target).copied()
 }
 
 impl MessageListWindow {
@@ -169,12 +320,37 @@ impl MessageListWindow {
             selected: None,
             live_mode: true,
             filter: String::new(),
+            bus_filter: None,
             sort_column: 0,
             sort_ascending: true,
             dbc_file: None,
+            grouping: MessageGroupSettings::default(),
+            pinned: HashSet::new(),
+            scroll_to_selected: false,
+            has_key_focus: false,
+            byte_search: ByteSearch::default(),
+            color_palette: ColorPalette::default(),
         }
     }
 
+    /// Palette for bus numbers and changed bytes (rows recolor as frames arrive)
+    pub fn set_color_palette(&mut self, palette: ColorPalette) {
+        self.color_palette = palette;
+    }
+
+    /// Whether the row list used the navigation keys last frame
+    pub fn has_key_focus(&self) -> bool {
+        self.has_key_focus
+    }
+
+    pub fn group_settings(&self) -> &MessageGroupSettings {
+        &self.grouping
+    }
+
+    pub fn set_group_settings(&mut self, grouping: MessageGroupSettings) {
+        self.grouping = grouping;
+    }
+
     pub fn set_messages(&mut self, messages: Vec<CanMessage>) {
         self.messages = messages;
     }
@@ -212,11 +388,14 @@ impl MessageListWindow {
     }
 
     fn update_message_with_direction(&mut self, msg: &CanMessage, direction: MessageDirection) {
-        let key = (msg.id, msg.bus, direction);
-        let state = self.states.entry(key).or_insert_with(|| MessageState::new(msg.id, msg.bus, direction));
+        // Error frames carry error classes, not message IDs: one row per bus holds them all
+        let id = if msg.is_error { ERROR_FRAME_ROW_ID } else { msg.id };
+        let key = (id, msg.bus, direction);
+        let state = self.states.entry(key).or_insert_with(|| MessageState::new(id, msg.bus, direction));
 
         // Get message name from DBC if available (RX only - TX keeps suffix)
         let msg_name = self.dbc_file.as_ref()
+            .filter(|_| !msg.is_error)
             .and_then(|dbc| dbc.get_message(msg.id))
             .map(|m| {
                 let base = m.name.as_str();
@@ -226,7 +405,7 @@ impl MessageListWindow {
                 }
             });
 
-        state.update(msg, msg_name.as_deref());
+        state.update(msg, msg_name.as_deref(), self.color_palette);
     }
 
     /// Clear all states
@@ -234,18 +413,45 @@ impl MessageListWindow {
         self.states.clear();
         self.messages.clear();
         self.selected = None;
+        self.pinned.clear();
     }
 
     pub fn selected_message(&self) -> Option<&MessageState> {
         self.selected.and_then(|key| self.states.get(&key))
     }
 
+    /// Most recent frame with `id` on any bus
+    pub fn latest_frame_for_id(&self, id: u32) -> Option<&CanMessage> {
+        self.states.values()
+            .filter(|state| state.id == id)
+            .filter_map(|state| state.last_frame.as_ref())
+            .max_by_key(|frame| frame.timestamp)
+    }
+
     /// Get latest state for a message by (id, bus) - prefers RX, used for bit visualizer
     pub fn get_state(&self, id: u32, bus: u8) -> Option<&MessageState> {
         self.states.get(&(id, bus, MessageDirection::Rx))
             .or_else(|| self.states.get(&(id, bus, MessageDirection::Tx)))
     }
 
+    /// DBC cycle time for a row and how its measured rate compares, once a rate is known
+    fn cycle_check(&self, state: &MessageState) -> Option<(u32, CycleTiming)> {
+        if state.freq <= 0.0 {
+            return None;
+        }
+        let def = self.dbc_file.as_ref()?.get_message(state.id)?;
+        let timing = def.check_cycle(1000.0 / state.freq as f64)?;
+        Some((def.cycle_time_ms?, timing))
+    }
+
+    /// Transmitting node for a message ID, if the DBC names one
+    fn transmitter_for(&self, id: u32) -> Option<&str> {
+        self.dbc_file.as_ref()
+            .and_then(|dbc| dbc.get_message(id))
+            .filter(|m| m.has_transmitter())
+            .map(|m| m.transmitter.as_str())
+    }
+
     /// Debug info
     pub fn debug_info(&self) -> (Option<(u32, u8)>, usize, usize) {
         (self.selected.map(|(id, bus, _)| (id, bus)), self.states.len(), self.messages.len())
@@ -289,6 +495,10 @@ impl MessageListWindow {
             .hint("ID or name...")
             .build();
 
+        self.render_bus_filter(ui);
+        self.render_grouping_controls(ui);
+        self.byte_search.render(ui);
+
         ui.separator();
 
         if self.live_mode {
@@ -298,15 +508,137 @@ impl MessageListWindow {
         }
     }
 
-    fn render_live_mode(&mut self, ui: &Ui, is_playing: bool) {
-        // Header
-        ui.text("ID   Bus   Dir  Name              Freq     Count   Data");
-        ui.separator();
+    /// Buses seen so far, ascending
+    fn buses(&self) -> Vec<u8> {
+        let mut buses: Vec<u8> = self.states.keys().map(|&(_, bus, _)| bus).collect();
+        buses.sort_unstable();
+        buses.dedup();
+        buses
+    }
+
+    /// Bus filter dropdown plus a color legend (only shown for multi-bus logs)
+    fn render_bus_filter(&mut self, ui: &Ui) {
+        let buses = self.buses();
+        if buses.len() < 2 && self.bus_filter.is_none() {
+            return;
+        }
+
+        let preview = match self.bus_filter {
+            Some(bus) => format!("Bus {}", bus),
+            None => "All buses".to_string(),
+        };
+        ui.set_next_item_width(100.0);
+        if let Some(_combo) = ui.begin_combo("##bus_filter", &preview) {
+            if ui.selectable_config("All buses").selected(self.bus_filter.is_none()).build() {
+                self.bus_filter = None;
+            }
+            for &bus in &buses {
+                let _color = ui.push_style_color(StyleColor::Text, bus_color(self.color_palette, bus));
+                if ui.selectable_config(format!("Bus {}", bus)).selected(self.bus_filter == Some(bus)).build() {
+                    self.bus_filter = Some(bus);
+                }
+            }
+        }
+
+        for &bus in &buses {
+            ui.same_line();
+            ui.text_colored(bus_color(self.color_palette, bus), format!("Bus {}", bus));
+        }
+    }
+
+    /// Grouping mode combo, plus the ID range editor when grouping by range
+    fn render_grouping_controls(&mut self, ui: &Ui) {
+        ui.same_line();
+        ui.set_next_item_width(110.0);
+        if let Some(_combo) = ui.begin_combo("##grouping", self.grouping.mode.label()) {
+            for mode in MessageGrouping::ALL {
+                if ui.selectable_config(mode.label()).selected(self.grouping.mode == mode).build() {
+                    self.grouping.mode = mode;
+                }
+            }
+        }
+        if self.grouping.mode != MessageGrouping::IdRange {
+            return;
+        }
+
+        ui.same_line();
+        if ui.small_button("Ranges...") {
+            ui.open_popup("id_range_groups");
+        }
+        if let Some(_popup) = ui.begin_popup("id_range_groups") {
+            ui.text("Group name / first ID / last ID (hex)");
+            let mut remove = None;
+            for (i, range) in self.grouping.id_ranges.iter_mut().enumerate() {
+                let _id = ui.push_id_usize(i);
+                ui.set_next_item_width(120.0);
+                ui.input_text("##name", &mut range.name).build();
+                ui.same_line();
+                ui.set_next_item_width(80.0);
+                ui.input_scalar("##start", &mut range.start).display_format("%X").chars_hexadecimal(true).build();
+                ui.same_line();
+                ui.set_next_item_width(80.0);
+                ui.input_scalar("##end", &mut range.end).display_format("%X").chars_hexadecimal(true).build();
+                ui.same_line();
+                if ui.small_button("x") {
+                    remove = Some(i);
+                }
+            }
+            if let Some(i) = remove {
+                self.grouping.id_ranges.remove(i);
+            }
+            if ui.small_button("+ Add range") {
+                let start = self.grouping.id_ranges.last().map_or(0, |r| r.end.saturating_add(1));
+                self.grouping.id_ranges.push(IdRangeGroup {
+                    name: format!("Group {}", self.grouping.id_ranges.len() + 1),
+                    start,
+                    end: start.saturating_add(0xFF),
+                });
+            }
+        }
+    }
+
+    /// Group a row belongs to under the current grouping mode
+    fn group_for(&self, key: &MessageKey) -> String {
+        let (id, _, _) = *key;
+        match self.grouping.mode {
+            MessageGrouping::None => String::new(),
+            MessageGrouping::NamePrefix => self.dbc_file.as_ref()
+                .and_then(|dbc| dbc.get_message(id))
+                .and_then(|m| m.name.split_once('_'))
+                .map(|(prefix, _)| prefix)
+                .filter(|prefix| !prefix.is_empty())
+                .unwrap_or(OTHER_GROUP)
+                .to_string(),
+            MessageGrouping::IdRange => self.grouping.id_ranges.iter()
+                .find(|r| (r.start..=r.end).contains(&id))
+                .map_or(OTHER_GROUP, |r| r.name.as_str())
+                .to_string(),
+        }
+    }
+
+    /// Split rows (already in display order) into named groups, "Other" last
+    fn grouped_keys(&self, keys: Vec<MessageKey>) -> Vec<(String, Vec<MessageKey>)> {
+        let mut groups: BTreeMap<String, Vec<MessageKey>> = BTreeMap::new();
+        for key in keys {
+            groups.entry(self.group_for(&key)).or_default().push(key);
+        }
+        let other = groups.remove(OTHER_GROUP);
+        let mut groups: Vec<_> = groups.into_iter().collect();
+        if let Some(other) = other {
+            groups.push((OTHER_GROUP.to_string(), other));
+        }
+        groups
+    }
 
-        // Collect and sort states
+    /// Row keys after the text/bus filters, in display order
+    fn visible_keys(&self, is_playing: bool) -> Vec<MessageKey> {
         let filter_lower = self.filter.to_lowercase();
         let mut sorted_keys: Vec<MessageKey> = self.states.keys().cloned().collect();
 
+        if let Some(bus) = self.bus_filter {
+            sorted_keys.retain(|&(_, key_bus, _)| key_bus == bus);
+        }
+
         // Apply filter
         if !filter_lower.is_empty() {
             sorted_keys.retain(|key| {
@@ -329,8 +661,8 @@ impl MessageListWindow {
             let state_a = self.states.get(&(id_a, bus_a, dir_a)).unwrap();
             let state_b = self.states.get(&(id_b, bus_b, dir_b)).unwrap();
             let cmp = match effective_sort_col {
-                0 => id_a.cmp(&id_b).then_with(|| dir_a.cmp(&dir_b)),
-                1 => bus_a.cmp(&bus_b).then_with(|| dir_a.cmp(&dir_b)),
+                0 => id_a.cmp(&id_b).then(bus_a.cmp(&bus_b)).then_with(|| dir_a.cmp(&dir_b)),
+                1 => bus_a.cmp(&bus_b).then(id_a.cmp(&id_b)).then_with(|| dir_a.cmp(&dir_b)),
                 2 => state_a.name.cmp(&state_b.name),
                 3 => state_a.freq.partial_cmp(&state_b.freq).unwrap_or(std::cmp::Ordering::Equal),
                 4 => state_a.count.cmp(&state_b.count),
@@ -338,56 +670,60 @@ impl MessageListWindow {
             };
             if self.sort_ascending { cmp } else { cmp.reverse() }
         });
+        // Pinned rows first, keeping the sort order within each part
+        sorted_keys.sort_by_key(|key| !self.pinned.contains(key));
 
-        // Render rows with two columns: ID|Bus|Dir|Name|Freq|Count | Data (colored bytes)
-        ui.columns(2, "msg_list_cols", false);
-        ui.set_column_width(0, 360.0);  // Wide enough for ID, Bus, Dir, Name (18), Freq (8), Count (6)
+        sorted_keys
+    }
 
-        for key in sorted_keys {
-            let (id, bus, dir) = key;
-            let state = self.states.get(&key).unwrap();
-            let is_selected = self.selected == Some(key);
+    fn render_live_mode(&mut self, ui: &Ui, is_playing: bool) {
+        // Header
+        ui.text("ID   Bus   Dir  Name              Freq     Count   Data");
+        ui.separator();
 
-            // TX rows: blue-tinted text to distinguish from RX
-            let dir_str = match dir {
-                MessageDirection::Rx => "RX",
-                MessageDirection::Tx => "TX",
-            };
-            let _tx_color = match dir {
-                MessageDirection::Rx => None,
-                MessageDirection::Tx => Some(ui.push_style_color(StyleColor::Text, [0.4, 0.7, 1.0, 1.0])),
+        let sorted_keys = self.visible_keys(is_playing);
+        let groups = (self.grouping.mode != MessageGrouping::None).then(|| self.grouped_keys(sorted_keys.clone()));
+
+        // Keyboard navigation walks the rows actually on screen (collapsed groups skipped).
+        // Only while this window has focus and no text box is being typed into.
+        self.has_key_focus = ui.is_window_focused_with_flags(WindowFocusedFlags::ROOT_AND_CHILD_WINDOWS) && !ui.io().want_text_input;
+        if self.has_key_focus {
+            let order: Vec<MessageKey> = match &groups {
+                None => sorted_keys.clone(),
+                Some(groups) => groups.iter()
+                    .filter(|(group, _)| !self.grouping.collapsed.contains(group))
+                    .flat_map(|(_, keys)| keys.iter().copied())
+                    .collect(),
             };
+            self.handle_list_keys(ui, &order);
+        }
 
-            // Column 0: ID, Bus, Dir, Name, Freq, Count
-            let name_padded = format!("{:<18}", &state.name[..state.name.len().min(18)]);
-            let row_label = format!("0x{:03X}  {}    {}  {}{:>8}  {:>6}",
-                id, bus, dir_str, name_padded, state.freq_str(), state.count);
-
-            // Stable ID + span full row: during rapid playback, (1) label must not change or
-            // ImGui loses the click, (2) full row must be clickable (including colored bytes).
-            let id_scope = ui.push_id(&format!("msg_{}_{}_{:?}", id, bus, dir));
-            let clicked = ui.selectable_config("##row")
-                .selected(is_selected)
-                .span_all_columns(true)
-                .build();
-            if clicked {
-                self.selected = Some(key);
-            }
-            // Draw display text over the selectable (text is non-interactive, can change every frame)
-            ui.same_line_with_spacing(0.0, 0.0);
-            ui.text(&row_label);
-            id_scope.pop();
+        // Render rows with two columns: ID|Bus|Dir|Name|Freq|Count | Data (colored bytes)
+        ui.columns(2, "msg_list_cols", false);
+        ui.set_column_width(0, 360.0);  // Wide enough for ID, Bus, Dir, Name (18), Freq (8), Count (6)
 
-            if ui.is_item_hovered() {
-                ui.tooltip(|| {
-                    ui.text(format!("Data: {}", state.hex_data()));
-                });
+        if let Some(groups) = groups {
+            for (group, keys) in groups {
+                let rate: f32 = keys.iter().filter_map(|key| self.states.get(key)).map(|s| s.freq).sum();
+                let label = format!("{} ({} IDs, {:.1} Hz)###group_{}", group, keys.len(), rate, group);
+                let node = ui.tree_node_config(&label)
+                    .opened(!self.grouping.collapsed.contains(&group), Condition::FirstUseEver)
+                    .push();
+                ui.next_column();
+                ui.next_column();
+                if node.is_some() {
+                    self.grouping.collapsed.remove(&group);
+                    for key in keys {
+                        self.render_live_row(ui, key);
+                    }
+                } else {
+                    self.grouping.collapsed.insert(group);
+                }
+            }
+        } else {
+            for key in sorted_keys {
+                self.render_live_row(ui, key);
             }
-
-            // Column 1: Colored bytes
-            ui.next_column();
-            self.render_colored_bytes(ui, state);
-            ui.next_column();
         }
 
         ui.columns(1, "", false);
@@ -399,46 +735,140 @@ impl MessageListWindow {
         }
     }
 
-    fn render_colored_bytes(&self, ui: &Ui, state: &MessageState) {
-        let draw_list = ui.get_window_draw_list();
-        let cursor = ui.cursor_screen_pos();
+    /// Up/Down move the selection, Home/End jump to the ends, Enter pins or unpins the selected row
+    fn handle_list_keys(&mut self, ui: &Ui, order: &[MessageKey]) {
+        let step = if ui.is_key_pressed(Key::DownArrow) {
+            Some(1)
+        } else if ui.is_key_pressed(Key::UpArrow) {
+            Some(-1)
+        } else if ui.is_key_pressed(Key::Home) {
+            Some(isize::MIN)
+        } else if ui.is_key_pressed(Key::End) {
+            Some(isize::MAX)
+        } else {
+            None
+        };
+        if let Some(key) = step.and_then(|step| step_selection(order, self.selected, step)) {
+            self.selected = Some(key);
+            self.scroll_to_selected = true;
+        }
 
-        let byte_width = 22.0;
-        let byte_height = 18.0;
-        let gap = 2.0;
+        if ui.is_key_pressed(Key::Enter) || ui.is_key_pressed(Key::KeypadEnter) {
+            if let Some(key) = self.selected {
+                if !self.pinned.remove(&key) {
+                    self.pinned.insert(key);
+                }
+                self.scroll_to_selected = true;
+            }
+        }
+    }
 
-        for (i, (&byte, &color)) in state.data.iter().zip(state.byte_colors.iter()).enumerate() {
-            // Add gap every 4 bytes
-            let gap_offset = (i / 4) as f32 * 4.0;
-
-            let x = cursor[0] + (i as f32 * byte_width) + gap_offset;
-            let y = cursor[1];
-
-            // Background color
-            draw_list.add_rect(
-                [x, y],
-                [x + byte_width - gap, y + byte_height],
-                color,
-            ).filled(true).rounding(2.0).build();
-
-            // Hex text
-            let hex = format!("{:02X}", byte);
-            let text_color = if color[0] + color[1] + color[2] > 1.5 {
-                [0.0, 0.0, 0.0, 1.0]
+    /// One message row: text in column 0, colored bytes in column 1
+    fn render_live_row(&mut self, ui: &Ui, key: MessageKey) {
+        let (id, bus, dir) = key;
+        let state = self.states.get(&key).unwrap();
+        let is_selected = self.selected == Some(key);
+
+        // TX rows: blue-tinted text to distinguish from RX
+        let dir_str = match (dir, self.pinned.contains(&key)) {
+            (MessageDirection::Rx, false) => "RX",
+            (MessageDirection::Rx, true) => "RX*",
+            (MessageDirection::Tx, false) => "TX",
+            (MessageDirection::Tx, true) => "TX*",
+        };
+        let _tx_color = match dir {
+            MessageDirection::Rx => None,
+            MessageDirection::Tx => Some(ui.push_style_color(StyleColor::Text, [0.4, 0.7, 1.0, 1.0])),
+        };
+
+        // Column 0: ID, Bus, Dir, Name, Freq, Count (bus number in its bus color)
+        let name_padded = format!("{:<18}", &state.name[..state.name.len().min(18)]);
+        let id_label = if state.is_error_row() { "ERR    ".to_string() } else { format!("0x{:03X}  ", id) };
+        let bus_label = format!("{:<5}", bus);
+        let row_label = format!("{:<4}{}{:>8}  {:>6}",
+            dir_str, name_padded, state.freq_str(), state.count);
+        let cycle = self.cycle_check(state);
+
+        // Stable ID + span full row: during rapid playback, (1) label must not change or
+        // ImGui loses the click, (2) full row must be clickable (including colored bytes).
+        let id_scope = ui.push_id(&format!("msg_{}_{}_{:?}", id, bus, dir));
+        let clicked = ui.selectable_config("##row")
+            .selected(is_selected)
+            .span_all_columns(true)
+            .build();
+        if clicked {
+            self.selected = Some(key);
+        }
+        if is_selected && self.scroll_to_selected {
+            ui.set_scroll_here_y();
+            self.scroll_to_selected = false;
+        }
+        if let Some(frame) = &state.last_frame {
+            frame_context_menu(ui, frame);
+        }
+        // Draw display text over the selectable (text is non-interactive, can change every frame)
+        ui.same_line_with_spacing(0.0, 0.0);
+        ui.group(|| {
+            if state.is_error_row() {
+                ui.text_colored(ERROR_COLOR, &id_label);
             } else {
-                [1.0, 1.0, 1.0, 1.0]
-            };
-            draw_list.add_text([x + 3.0, y + 2.0], text_color, hex);
+                ui.text(&id_label);
+            }
+            ui.same_line_with_spacing(0.0, 0.0);
+            ui.text_colored(bus_color(self.color_palette, bus), &bus_label);
+            ui.same_line_with_spacing(0.0, 0.0);
+            match cycle {
+                _ if state.is_error_row() => ui.text_colored(ERROR_COLOR, &row_label),
+                // Off-spec rate: orange row text
+                Some((_, CycleTiming::Slow | CycleTiming::Fast)) => ui.text_colored([1.0, 0.6, 0.2, 1.0], &row_label),
+                _ => ui.text(&row_label),
+            }
+        });
+        id_scope.pop();
+
+        if ui.is_item_hovered() {
+            ui.tooltip(|| {
+                match &state.last_frame {
+                    Some(frame) if frame.is_error => {
+                        ui.text(format!("Last error class: 0x{:08X}", frame.id));
+                        if !frame.data.is_empty() {
+                            ui.text(format!("Error data: {}", frame.hex_data()));
+                        }
+                    }
+                    Some(frame) if frame.is_rtr => ui.text(format!("Remote request, DLC {}", frame.data.len())),
+                    _ => ui.text(format!("Data: {}", state.hex_data())),
+                }
+                if let Some(tx) = self.transmitter_for(id) {
+                    ui.text(format!("Transmitter: {}", tx));
+                }
+                if let Some((expected, timing)) = cycle {
+                    ui.text(format!("Cycle time: {} ms, observed {:.1} ms ({})", expected, 1000.0 / state.freq, timing.label()));
+                }
+                ui.text_disabled("Up/Down to select, Enter to pin to the top (*)");
+            });
         }
 
-        // Reserve space
-        let total_width = (state.data.len() as f32 * byte_width) + ((state.data.len() / 4) as f32 * 4.0);
-        ui.dummy([total_width.max(100.0), byte_height]);
+        // Column 1: Colored bytes (remote requests have none: a badge with the requested length)
+        ui.next_column();
+        if state.is_error_row() {
+            render_badge(ui, "ERR", ERROR_COLOR);
+        } else if state.is_rtr() {
+            render_badge(ui, &format!("RTR  DLC {}", state.data.len()), RTR_BADGE_COLOR);
+        } else {
+            render_colored_bytes(ui, state, self.byte_search.find(&state.data));
+        }
+        ui.next_column();
     }
 
     fn render_message_details(&self, ui: &Ui, state: &MessageState) {
         ui.text(format!("Message: {} (0x{:03X})", state.name, state.id));
+        if let Some(tx) = self.transmitter_for(state.id) {
+            ui.text(format!("Transmitter: {}", tx));
+        }
         ui.text(format!("Frequency: {}", state.freq_str()));
+        if let Some((expected, timing)) = self.cycle_check(state) {
+            ui.text(format!("Cycle time: {} ms ({:.1} Hz expected, {})", expected, 1000.0 / expected as f64, timing.label()));
+        }
         ui.text(format!("Count: {}", state.count));
 
         ui.separator();
@@ -462,24 +892,98 @@ impl MessageListWindow {
             for i in clipper.display_start()..clipper.display_end() {
                 let i = i as usize;
                 if let Some(msg) = self.messages.get(i) {
-                    let label = format!(
-                        "{} | 0x{:03X} [Bus {}] | {}",
-                        msg.timestamp.format("%H:%M:%S%.3f"),
-                        msg.id,
-                        msg.bus,
-                        msg.hex_data()
-                    );
+                    let payload = if msg.is_rtr { format!("RTR DLC {}", msg.data.len()) } else { msg.hex_data() };
+                    let label = if msg.is_error {
+                        format!("{} | ERR   [Bus {}] | class 0x{:08X} {}", msg.timestamp.format("%H:%M:%S%.3f"), msg.bus, msg.id, payload)
+                    } else {
+                        format!("{} | 0x{:03X} [Bus {}] | {}", msg.timestamp.format("%H:%M:%S%.3f"), msg.id, msg.bus, payload)
+                    };
 
+                    let _id = ui.push_id_usize(i);
+                    let row_color = if msg.is_error {
+                        Some(ERROR_COLOR)
+                    } else {
+                        self.byte_search.matches(msg).then_some(BYTE_MATCH_COLOR)
+                    };
+                    let _row_color = row_color.map(|color| ui.push_style_color(StyleColor::Text, color));
                     if ui.selectable(&label) {
                         eprintln!("MessageList[History]: CLICKED id=0x{:03X}, bus={}", msg.id, msg.bus);
-                        self.selected = Some((msg.id, msg.bus, MessageDirection::Rx));
+                        let id = if msg.is_error { ERROR_FRAME_ROW_ID } else { msg.id };
+                        self.selected = Some((id, msg.bus, MessageDirection::Rx));
                     }
+                    frame_context_menu(ui, msg);
                 }
             }
         }
     }
 }
 
+/// Row of hex bytes, each tinted by how it changed in the last update (see `MessageState::update`),
+/// with the `highlight` bytes (a byte search match) outlined
+pub fn render_colored_bytes(ui: &Ui, state: &MessageState, highlight: Option<std::ops::Range<usize>>) {
+    let draw_list = ui.get_window_draw_list();
+    let cursor = ui.cursor_screen_pos();
+
+    let byte_width = 22.0;
+    let byte_height = 18.0;
+    let gap = 2.0;
+
+    for (i, (&byte, &color)) in state.data.iter().zip(state.byte_colors.iter()).enumerate() {
+        // Add gap every 4 bytes
+        let gap_offset = (i / 4) as f32 * 4.0;
+
+        let x = cursor[0] + (i as f32 * byte_width) + gap_offset;
+        let y = cursor[1];
+
+        // Background color
+        draw_list.add_rect(
+            [x, y],
+            [x + byte_width - gap, y + byte_height],
+            color,
+        ).filled(true).rounding(2.0).build();
+
+        // Hex text
+        let hex = format!("{:02X}", byte);
+        let text_color = if color[0] + color[1] + color[2] > 1.5 {
+            [0.0, 0.0, 0.0, 1.0]
+        } else {
+            [1.0, 1.0, 1.0, 1.0]
+        };
+        draw_list.add_text([x + 3.0, y + 2.0], text_color, hex);
+
+        if highlight.as_ref().is_some_and(|range| range.contains(&i)) {
+            draw_list.add_rect([x, y], [x + byte_width - gap, y + byte_height], BYTE_MATCH_COLOR)
+                .rounding(2.0).thickness(2.0).build();
+        }
+    }
+
+    // Reserve space
+    let total_width = (state.data.len() as f32 * byte_width) + ((state.data.len() / 4) as f32 * 4.0);
+    ui.dummy([total_width.max(100.0), byte_height]);
+}
+
+/// Small filled label in place of a row's data bytes
+fn render_badge(ui: &Ui, text: &str, color: [f32; 4]) {
+    let cursor = ui.cursor_screen_pos();
+    let size = ui.calc_text_size(text);
+    let end = [cursor[0] + size[0] + 8.0, cursor[1] + 18.0];
+    ui.get_window_draw_list().add_rect(cursor, end, color).filled(true).rounding(2.0).build();
+    ui.get_window_draw_list().add_text([cursor[0] + 4.0, cursor[1] + 2.0], [0.0, 0.0, 0.0, 1.0], text);
+    ui.dummy([end[0] - cursor[0], 18.0]);
+}
+
+/// Right-click menu on the last item with clipboard exports of `frame`
+fn frame_context_menu(ui: &Ui, frame: &CanMessage) {
+    if let Some(_popup) = ui.begin_popup_context_item() {
+        if ui.menu_item("Copy frame as hex") {
+            ui.set_clipboard_text(frame.hex_data());
+        }
+        if ui.menu_item("Copy as candump line") {
+            ui.set_clipboard_text(frame.to_candump_line());
+        }
+    }
+}
+
 /// Window for editing DBC file definitions
 pub struct DbcEditorWindow {
     dbc_file: DbcFile,
@@ -585,3 +1089,111 @@ impl DbcEditorWindow {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_id_on_two_buses_gives_two_rows() {
+        let mut list = MessageListWindow::new();
+        list.update_message(&CanMessage::new(0, 0x123, vec![1, 2].into()));
+        list.update_message(&CanMessage::new(1, 0x123, vec![3, 4].into()));
+        list.update_message(&CanMessage::new(1, 0x123, vec![5, 6].into()));
+
+        let rows = list.visible_keys(false);
+        assert_eq!(rows, vec![(0x123, 0, MessageDirection::Rx), (0x123, 1, MessageDirection::Rx)]);
+        assert_eq!(list.get_state(0x123, 0).unwrap().data, vec![1, 2]);
+        assert_eq!(list.get_state(0x123, 1).unwrap().count, 2);
+        assert_eq!(list.buses(), vec![0, 1]);
+
+        list.bus_filter = Some(1);
+        assert_eq!(list.visible_keys(false), vec![(0x123, 1, MessageDirection::Rx)]);
+    }
+
+    #[test]
+    fn test_error_frames_get_their_own_row() {
+        let mut list = MessageListWindow::new();
+        list.update_message(&CanMessage::new(0, 0x004, vec![1].into()));
+        list.update_message(&CanMessage::new_error(0, 0x004, vec![0, 4].into()));
+        list.update_message(&CanMessage::new_error(0, 0x010, vec![].into()));
+        list.update_message(&CanMessage::new_rtr(0, 0x7E0, 3));
+
+        let rows = list.visible_keys(false);
+        assert_eq!(rows, vec![
+            (0x004, 0, MessageDirection::Rx),
+            (0x7E0, 0, MessageDirection::Rx),
+            (ERROR_FRAME_ROW_ID, 0, MessageDirection::Rx),
+        ]);
+        // The data frame with the same number as an error class is untouched
+        assert_eq!(list.get_state(0x004, 0).unwrap().count, 1);
+        let errors = list.get_state(ERROR_FRAME_ROW_ID, 0).unwrap();
+        assert!(errors.is_error_row() && !errors.is_rtr());
+        assert_eq!((errors.count, errors.name.as_str()), (2, "Error frames"));
+        assert!(list.get_state(0x7E0, 0).unwrap().is_rtr());
+    }
+
+    #[test]
+    fn test_byte_search_skips_error_and_remote_frames() {
+        let mut search = ByteSearch::default();
+        assert!(!search.matches(&CanMessage::new(0, 0x100, vec![0xFF].into())));
+
+        search.text = "?? FF".to_string();
+        search.update();
+        assert!(search.matches(&CanMessage::new(0, 0x100, vec![0x01, 0x02, 0xFF].into())));
+        assert!(!search.matches(&CanMessage::new(0, 0x100, vec![0xFF, 0x02].into())));
+        assert!(!search.matches(&CanMessage::new_error(0, 0x004, vec![0x00, 0xFF].into())));
+
+        search.text = "XY".to_string();
+        search.update();
+        assert!(search.error.is_some() && search.pattern.is_none());
+    }
+
+    #[test]
+    fn test_grouping_by_name_prefix_and_id_range() {
+        let mut dbc = DbcFile::new();
+        dbc.add_message(crate::core::dbc::DbcMessage::new(0x100, "PT_Engine", 8));
+        dbc.add_message(crate::core::dbc::DbcMessage::new(0x101, "PT_Gearbox", 8));
+        dbc.add_message(crate::core::dbc::DbcMessage::new(0x400, "BODY_Doors", 8));
+        let mut list = MessageListWindow::new();
+        list.set_dbc(dbc);
+        for id in [0x100, 0x101, 0x400, 0x7FF] {
+            list.update_message(&CanMessage::new(0, id, vec![0].into()));
+        }
+        let keys = list.visible_keys(false);
+        let names = |groups: &[(String, Vec<MessageKey>)]| -> Vec<(String, usize)> {
+            groups.iter().map(|(name, keys)| (name.clone(), keys.len())).collect()
+        };
+
+        list.grouping.mode = MessageGrouping::NamePrefix;
+        let groups = list.grouped_keys(keys.clone());
+        assert_eq!(names(&groups), vec![("BODY".to_string(), 1), ("PT".to_string(), 2), ("Other".to_string(), 1)]);
+
+        list.grouping.mode = MessageGrouping::IdRange;
+        list.grouping.id_ranges = vec![IdRangeGroup { name: "Powertrain".to_string(), start: 0x100, end: 0x1FF }];
+        let groups = list.grouped_keys(keys);
+        assert_eq!(names(&groups), vec![("Powertrain".to_string(), 2), ("Other".to_string(), 2)]);
+        assert_eq!(groups[0].1[0].0, 0x100);
+    }
+
+    #[test]
+    fn test_keyboard_selection_and_pinning() {
+        let mut list = MessageListWindow::new();
+        for id in [0x300, 0x100, 0x200] {
+            list.update_message(&CanMessage::new(0, id, vec![0].into()));
+        }
+        let key = |id| (id, 0, MessageDirection::Rx);
+        let order = list.visible_keys(false);
+        assert_eq!(order, vec![key(0x100), key(0x200), key(0x300)]);
+
+        assert_eq!(step_selection(&order, None, 1), Some(key(0x100)));
+        assert_eq!(step_selection(&order, None, -1), Some(key(0x300)));
+        assert_eq!(step_selection(&order, Some(key(0x100)), 1), Some(key(0x200)));
+        assert_eq!(step_selection(&order, Some(key(0x100)), -1), Some(key(0x100)));
+        assert_eq!(step_selection(&order, Some(key(0x200)), isize::MAX), Some(key(0x300)));
+        assert_eq!(step_selection(&[], None, 1), None);
+
+        list.pinned.insert(key(0x300));
+        assert_eq!(list.visible_keys(false), vec![key(0x300), key(0x100), key(0x200)]);
+    }
+}
@@ -0,0 +1,157 @@
+//! Named workspaces: a docking layout plus the set of open windows, saved under
+//! a name ("Reverse Engineering", "Live Monitoring") and switched between from
+//! View > Workspace. Presets live next to the settings in the config dir.
+
+use imgui::Ui;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+/// One saved workspace
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Workspace {
+    pub name: String,
+    /// ImGui layout INI content
+    pub layout_ini: String,
+    /// Names of the windows open in this workspace
+    #[serde(default)]
+    pub open_windows: Vec<String>,
+}
+
+/// What the user picked in the Workspace menu
+#[derive(Debug, Clone, PartialEq)]
+pub enum WorkspaceAction {
+    None,
+    /// Switch to this workspace
+    Apply(Workspace),
+    /// Save the current layout and windows under this name (replacing a preset of the same name)
+    SaveCurrent(String),
+    Delete(String),
+}
+
+/// Saved workspace presets, persisted as JSON in the config dir
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct WorkspaceStore {
+    workspaces: Vec<Workspace>,
+    /// Workspace last applied or saved
+    #[serde(default)]
+    active: Option<String>,
+    #[serde(skip)]
+    name_input: String,
+}
+
+impl WorkspaceStore {
+    fn config_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|p| p.join("can-viz").join("workspaces.json"))
+    }
+
+    pub fn load() -> Self {
+        Self::config_path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) {
+        if let Some(path) = Self::config_path() {
+            if let Some(parent) = path.parent() {
+                let _ = fs::create_dir_all(parent);
+            }
+            if let Ok(json) = serde_json::to_string_pretty(self) {
+                let _ = fs::write(&path, json);
+            }
+        }
+    }
+
+    /// Add a workspace, replacing any existing one with the same name, and make it active
+    pub fn upsert(&mut self, workspace: Workspace) {
+        self.active = Some(workspace.name.clone());
+        match self.workspaces.iter_mut().find(|w| w.name == workspace.name) {
+            Some(existing) => *existing = workspace,
+            None => self.workspaces.push(workspace),
+        }
+    }
+
+    pub fn remove(&mut self, name: &str) {
+        self.workspaces.retain(|w| w.name != name);
+        if self.active.as_deref() == Some(name) {
+            self.active = None;
+        }
+    }
+
+    pub fn set_active(&mut self, name: &str) {
+        self.active = Some(name.to_string());
+    }
+
+    /// Contents of the View > Workspace submenu
+    pub fn render_menu(&mut self, ui: &Ui) -> WorkspaceAction {
+        let mut action = WorkspaceAction::None;
+
+        if self.workspaces.is_empty() {
+            ui.text_disabled("No saved workspaces");
+        }
+        for workspace in &self.workspaces {
+            let active = self.active.as_deref() == Some(workspace.name.as_str());
+            if ui.menu_item_config(&workspace.name).selected(active).build() {
+                action = WorkspaceAction::Apply(workspace.clone());
+            }
+        }
+
+        ui.separator();
+        ui.set_next_item_width(160.0);
+        let entered = ui.input_text("##workspace_name", &mut self.name_input)
+            .hint("Workspace name")
+            .enter_returns_true(true)
+            .build();
+        ui.same_line();
+        let name = self.name_input.trim();
+        let _no_name = ui.begin_disabled(name.is_empty());
+        if (ui.button("Save Current") || entered) && !name.is_empty() {
+            action = WorkspaceAction::SaveCurrent(name.to_string());
+            self.name_input.clear();
+        }
+        drop(_no_name);
+
+        if !self.workspaces.is_empty() {
+            ui.menu("Delete", || {
+                for workspace in &self.workspaces {
+                    if ui.menu_item(&workspace.name) {
+                        action = WorkspaceAction::Delete(workspace.name.clone());
+                    }
+                }
+            });
+        }
+
+        action
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_workspace_upsert_and_remove() {
+        let workspace = |name: &str, layout: &str| Workspace {
+            name: name.to_string(),
+            layout_ini: layout.to_string(),
+            open_windows: vec!["Charts".to_string()],
+        };
+        let mut store = WorkspaceStore::default();
+        store.upsert(workspace("Reverse Engineering", "[Window][A]"));
+        store.upsert(workspace("Live Monitoring", "[Window][B]"));
+        store.upsert(workspace("Reverse Engineering", "[Window][C]"));
+
+        assert_eq!(store.workspaces.len(), 2);
+        assert_eq!(store.workspaces[0].layout_ini, "[Window][C]");
+        assert_eq!(store.active.as_deref(), Some("Reverse Engineering"));
+
+        let json = serde_json::to_string(&store).unwrap();
+        let mut restored: WorkspaceStore = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.workspaces, store.workspaces);
+
+        restored.remove("Reverse Engineering");
+        assert_eq!(restored.workspaces.len(), 1);
+        assert_eq!(restored.active, None);
+    }
+}